@@ -0,0 +1,213 @@
+//! Angles written in degrees.
+//!
+//! With [`crate::Options::angle_conversions`], the deserializer reads `deg(90)` and
+//! `!degrees 90` into radians, but a plain `f64` in radians serializes as
+//! `1.5707963267948966`. Wrap angle fields in [`Radians`] to write them back in
+//! degrees when they are a "nice" number of degrees, as chosen by
+//! [`crate::ser::options::SerializerOptions::angle_format`]. Other values are written
+//! as plain radians and read back unchanged. A value written in degrees reads back as its
+//! rounded degree value, which with the default [`AngleFormat`] is at most `1e-9` degrees
+//! off; a larger [`AngleFormat::tolerance`] or [`AngleFormat::precision`] trades that
+//! exactness for shorter output.
+//!
+//! ```rust
+//! use serde::{Deserialize, Serialize};
+//! use serde_saphyr::Radians;
+//!
+//! #[derive(Debug, Deserialize, PartialEq, Serialize)]
+//! struct Joint {
+//!     lower: Radians<f64>,
+//!     upper: Radians<f64>,
+//! }
+//!
+//! let joint = Joint {
+//!     lower: Radians(-std::f64::consts::FRAC_PI_2),
+//!     upper: Radians(0.3),
+//! };
+//! let yaml = serde_saphyr::to_string(&joint).unwrap();
+//! assert_eq!(yaml, "lower: deg(-90)\nupper: 0.3\n");
+//!
+//! let options = serde_saphyr::options! { angle_conversions: true };
+//! let back: Joint = serde_saphyr::from_str_with_options(&yaml, options).unwrap();
+//! assert_eq!(back, joint);
+//! ```
+
+use serde_core::de::{Deserialize, Deserializer};
+use serde_core::ser::{Serialize, Serializer};
+
+/// Newtype-struct name the serializer recognizes for [`Radians`].
+pub(crate) const NAME_RADIANS: &str = "__yaml_radians";
+
+/// An angle in radians, written in degrees when it is a "nice" number of degrees.
+///
+/// `T` is `f64` or `f32`; `f32` values are converted to degrees in `f32` precision.
+/// The degree form is chosen by [`AngleFormat`]. Serializers other than this crate's
+/// see a plain float. Deserialization is transparent, so reading `deg(...)` or
+/// `!degrees` values needs [`crate::Options::angle_conversions`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+pub struct Radians<T>(pub T);
+
+impl<T: Serialize> Serialize for Radians<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_newtype_struct(NAME_RADIANS, &self.0)
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Radians<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        T::deserialize(deserializer).map(Radians)
+    }
+}
+
+/// How a nice [`Radians`] value is written.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum AngleStyle {
+    /// `deg(90)` (default).
+    #[default]
+    DegFunction,
+    /// `!degrees 90`.
+    DegreesTag,
+    /// Always plain radians, as if [`Radians`] were not used.
+    Radians,
+}
+
+/// Serializer settings for [`Radians`] values.
+///
+/// A value is written in degrees if its degree value is within `tolerance` of a
+/// number with at most `precision` decimal places; that number is what gets written.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AngleFormat {
+    /// Form of the degree output.
+    pub style: AngleStyle,
+    /// Largest difference in degrees between a value and its rounded form that still
+    /// counts as nice, and so the largest error of a written value when it is read back.
+    /// Must be finite and not negative. Default: `1e-9`.
+    pub tolerance: f64,
+    /// Decimal places of the rounded degree value, at most [`AngleFormat::MAX_PRECISION`].
+    /// Default: 0, so only whole degrees are nice.
+    pub precision: u8,
+}
+
+impl AngleFormat {
+    /// Largest supported [`AngleFormat::precision`].
+    pub const MAX_PRECISION: u8 = 9;
+
+    /// Default tolerance and precision with the given style.
+    #[must_use]
+    pub fn new(style: AngleStyle) -> Self {
+        Self {
+            style,
+            ..Self::default()
+        }
+    }
+
+    /// Set [`AngleFormat::tolerance`].
+    #[must_use]
+    pub fn with_tolerance(mut self, tolerance: f64) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Set [`AngleFormat::precision`].
+    #[must_use]
+    pub fn with_precision(mut self, precision: u8) -> Self {
+        self.precision = precision;
+        self
+    }
+
+    #[cfg(feature = "serialize")]
+    pub(crate) fn validate(&self) -> Result<(), String> {
+        if !(self.tolerance.is_finite() && self.tolerance >= 0.0) {
+            return Err(format!(
+                "angle_format tolerance must be finite and not negative, got {}",
+                self.tolerance
+            ));
+        }
+        if self.precision > Self::MAX_PRECISION {
+            return Err(format!(
+                "angle_format precision must be at most {}, got {}",
+                Self::MAX_PRECISION,
+                self.precision
+            ));
+        }
+        Ok(())
+    }
+
+    /// The degree form of an angle of `degrees`, or `None` if it should be written as radians.
+    #[cfg(feature = "serialize")]
+    pub(crate) fn degrees_text(&self, degrees: f64) -> Option<String> {
+        if self.style == AngleStyle::Radians || !degrees.is_finite() {
+            return None;
+        }
+        let scale = 10f64.powi(i32::from(self.precision));
+        let rounded = (degrees * scale).round() / scale;
+        if (degrees - rounded).abs() > self.tolerance {
+            return None;
+        }
+
+        let mut number = format!("{rounded:.*}", usize::from(self.precision));
+        if number.contains('.') {
+            number.truncate(number.trim_end_matches('0').trim_end_matches('.').len());
+        }
+        if number == "-0" {
+            number.remove(0);
+        }
+        Some(match self.style {
+            AngleStyle::DegreesTag => format!("!degrees {number}"),
+            _ => format!("deg({number})"),
+        })
+    }
+}
+
+impl Default for AngleFormat {
+    fn default() -> Self {
+        Self {
+            style: AngleStyle::DegFunction,
+            tolerance: 1e-9,
+            precision: 0,
+        }
+    }
+}
+
+#[cfg(all(test, feature = "serialize"))]
+mod tests {
+    use super::{AngleFormat, AngleStyle};
+    use rstest::rstest;
+
+    #[rstest]
+    #[case::right_angle(90.0, 0, Some("deg(90)"))]
+    #[case::negative(-180.0, 0, Some("deg(-180)"))]
+    #[case::zero(0.0, 0, Some("deg(0)"))]
+    #[case::negative_zero(-0.0, 0, Some("deg(0)"))]
+    #[case::not_whole(17.2, 0, None)]
+    #[case::one_decimal(12.5, 1, Some("deg(12.5)"))]
+    #[case::trailing_zeros_trimmed(45.0, 3, Some("deg(45)"))]
+    #[case::too_fine(12.25, 1, None)]
+    #[case::radian_rounding(core::f64::consts::FRAC_PI_4.to_degrees(), 0, Some("deg(45)"))]
+    #[case::non_finite(f64::NAN, 0, None)]
+    fn deg_function(#[case] degrees: f64, #[case] precision: u8, #[case] expected: Option<&str>) {
+        let format = AngleFormat::default().with_precision(precision);
+        assert_eq!(format.degrees_text(degrees).as_deref(), expected);
+    }
+
+    #[test]
+    fn tolerance_decides_what_is_nice() {
+        assert_eq!(AngleFormat::default().degrees_text(90.0004), None);
+        let loose = AngleFormat::new(AngleStyle::DegreesTag).with_tolerance(1e-3);
+        assert_eq!(loose.degrees_text(90.0004).as_deref(), Some("!degrees 90"));
+        assert_eq!(
+            AngleFormat::new(AngleStyle::Radians).degrees_text(180.0),
+            None
+        );
+    }
+
+    #[rstest]
+    #[case(AngleFormat::default().with_tolerance(-1.0))]
+    #[case(AngleFormat::default().with_tolerance(f64::INFINITY))]
+    #[case(AngleFormat::default().with_precision(AngleFormat::MAX_PRECISION + 1))]
+    fn invalid_settings_are_rejected(#[case] format: AngleFormat) {
+        assert!(format.validate().is_err());
+    }
+}
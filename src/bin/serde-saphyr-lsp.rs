@@ -0,0 +1,26 @@
+#![forbid(unsafe_code)]
+
+//! Language server checking that `*.yaml` and `*.yml` files are well-formed YAML.
+//!
+//! Type-aware checking needs the Rust types of the documents; build your own binary
+//! around [`serde_saphyr::lsp::Server`] and register them there.
+
+use std::process::exit;
+
+use serde_core::de::IgnoredAny;
+use serde_saphyr::lsp::Server;
+
+fn main() {
+    let server = Server::new()
+        .register_with("**/*.yaml", check)
+        .register_with("**/*.yml", check);
+    if let Err(err) = server.run_stdio() {
+        eprintln!("serde-saphyr-lsp: {err}");
+        exit(1);
+    }
+}
+
+/// Accept any number of documents of any shape.
+fn check(yaml: &str, options: serde_saphyr::Options) -> Result<(), serde_saphyr::Error> {
+    serde_saphyr::from_multiple_with_options::<IgnoredAny>(yaml, options).map(drop)
+}
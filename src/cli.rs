@@ -5,19 +5,265 @@ use std::rc::Rc;
 use serde_core::de::IgnoredAny;
 
 use crate::de::budget::{BudgetBreach, BudgetReport};
-use crate::{Error, from_str_with_options};
+use crate::diff;
+use crate::lint::{self, LintConfig, LintLevel};
+use crate::{Error, error_codes, from_str_with_options};
 
 fn usage() -> &'static str {
     "Usage: serde-saphyr [--plain] [--include <path>] <path>\n\
+       serde-saphyr explain [<code>]\n\
+       serde-saphyr lint [--config <file>] <path>\n\
+       serde-saphyr diff [--include <path>] <old> <new>\n\
 \n\
 Reads the YAML file at <path> and prints a budget summary.\n\
 It can also be used as a YAML validator.\n\
 \n\
+`explain <code>` describes an error code such as SY0101 and how to fix it;\n\
+`explain` alone lists all codes.\n\
+\n\
+`lint <path>` checks the YAML file at <path> for style issues. `--config <file>`\n\
+reads yamllint-style rule settings; the exit status is 3 if any error-level\n\
+issue is found.\n\
+\n\
+`diff <old> <new>` lists semantic differences between two YAML files by key path,\n\
+ignoring formatting and comments; aliases, merge keys and includes are resolved.\n\
+\n\
 Options:\n\
   --plain           Disable miette formatting and print errors in plain text\n\
   --include <path>  Configure parser to allow file inclusion from <path> directory"
 }
 
+fn explain<Stdout, Stderr>(code: Option<&str>, stdout: &mut Stdout, stderr: &mut Stderr) -> i32
+where
+    Stdout: std::io::Write,
+    Stderr: std::io::Write,
+{
+    let Some(code) = code else {
+        for info in error_codes::ERROR_CODES {
+            let _ = writeln!(stdout, "{} {}: {}", info.code, info.name, info.summary);
+        }
+        return 0;
+    };
+
+    match error_codes::explain(code) {
+        Some(info) => {
+            let _ = writeln!(
+                stdout,
+                "{} {}: {}\n\n{}",
+                info.code, info.name, info.summary, info.explanation
+            );
+            0
+        }
+        None => {
+            let _ = writeln!(
+                stderr,
+                "Unknown error code: {code}\n\nRun `serde-saphyr explain` to list all codes."
+            );
+            1
+        }
+    }
+}
+
+fn lint<I, S, Stdout, Stderr>(args: I, stdout: &mut Stdout, stderr: &mut Stderr) -> i32
+where
+    I: Iterator<Item = S>,
+    S: AsRef<str>,
+    Stdout: std::io::Write,
+    Stderr: std::io::Write,
+{
+    let mut config_path: Option<String> = None;
+    let mut path: Option<String> = None;
+
+    let mut args = args;
+    while let Some(arg) = args.next() {
+        let arg = arg.as_ref();
+        match arg {
+            "--config" => {
+                config_path = match args.next() {
+                    Some(file) if !file.as_ref().starts_with('-') => Some(file.as_ref().to_owned()),
+                    _ => {
+                        let _ = writeln!(stderr, "Missing file for --config\n\n{}", usage());
+                        return 1;
+                    }
+                };
+            }
+            _ if arg.starts_with('-') => {
+                let _ = writeln!(stderr, "Unknown option: {arg}\n\n{}", usage());
+                return 1;
+            }
+            _ => {
+                if path.is_some() {
+                    let _ = writeln!(stderr, "Unexpected extra argument: {arg}\n\n{}", usage());
+                    return 1;
+                }
+                path = Some(arg.to_owned());
+            }
+        }
+    }
+
+    let Some(path) = path else {
+        let _ = writeln!(stderr, "{}", usage());
+        return 1;
+    };
+
+    let render_options = crate::render_options! {
+        error_codes: true,
+    };
+
+    let config = match config_path {
+        None => LintConfig::default(),
+        Some(config_path) => {
+            let text = match std::fs::read_to_string(&config_path) {
+                Ok(text) => text,
+                Err(err) => {
+                    let _ = writeln!(stderr, "Failed to read {config_path}: {err}");
+                    return 2;
+                }
+            };
+            match LintConfig::from_yaml(&text) {
+                Ok(config) => config,
+                Err(err) => {
+                    let rendered = err.render_with_options(render_options);
+                    let _ = writeln!(stderr, "{config_path} invalid:\n{rendered}");
+                    return 2;
+                }
+            }
+        }
+    };
+
+    let content = match std::fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(err) => {
+            let _ = writeln!(stderr, "Failed to read {path}: {err}");
+            return 2;
+        }
+    };
+
+    let issues = match lint::check(&content, &config) {
+        Ok(issues) => issues,
+        Err(err) => {
+            let rendered = err.render_with_options(render_options);
+            let _ = writeln!(stderr, "{path} invalid:\n{rendered}");
+            return 3;
+        }
+    };
+
+    for issue in &issues {
+        let rendered = issue.render_with_source(&content, &path, render_options);
+        let _ = writeln!(stdout, "{rendered}");
+    }
+
+    let errors = issues
+        .iter()
+        .filter(|issue| issue.level == LintLevel::Error)
+        .count();
+    let _ = writeln!(
+        stdout,
+        "{path}: {} issue(s), {errors} error(s)",
+        issues.len()
+    );
+    if errors > 0 { 3 } else { 0 }
+}
+
+fn diff<I, S, Stdout, Stderr>(args: I, stdout: &mut Stdout, stderr: &mut Stderr) -> i32
+where
+    I: Iterator<Item = S>,
+    S: AsRef<str>,
+    Stdout: std::io::Write,
+    Stderr: std::io::Write,
+{
+    let mut include_path: Option<String> = None;
+    let mut paths: Vec<String> = Vec::new();
+
+    let mut args = args;
+    while let Some(arg) = args.next() {
+        let arg = arg.as_ref();
+        match arg {
+            "--include" => {
+                include_path = match args.next() {
+                    Some(path) if !path.as_ref().starts_with('-') => Some(path.as_ref().to_owned()),
+                    _ => {
+                        let _ = writeln!(stderr, "Missing path for --include\n\n{}", usage());
+                        return 1;
+                    }
+                };
+            }
+            _ if arg.starts_with('-') => {
+                let _ = writeln!(stderr, "Unknown option: {arg}\n\n{}", usage());
+                return 1;
+            }
+            _ => {
+                if paths.len() == 2 {
+                    let _ = writeln!(stderr, "Unexpected extra argument: {arg}\n\n{}", usage());
+                    return 1;
+                }
+                paths.push(arg.to_owned());
+            }
+        }
+    }
+
+    let [old_path, new_path] = paths.as_slice() else {
+        let _ = writeln!(stderr, "{}", usage());
+        return 1;
+    };
+
+    let mut options = crate::options! {
+        with_snippet: true,
+    };
+    if let Some(path) = include_path {
+        options = match options.with_filesystem_root(&path) {
+            Ok(options) => options,
+            Err(err) => {
+                let _ = writeln!(stderr, "Failed to configure include root {path}: {err}");
+                return 2;
+            }
+        };
+    }
+
+    let mut documents = Vec::with_capacity(2);
+    for path in [old_path, new_path] {
+        let content = match std::fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(err) => {
+                let _ = writeln!(stderr, "Failed to read {path}: {err}");
+                return 2;
+            }
+        };
+        match diff::Document::from_str_with_options(&content, options.clone()) {
+            Ok(document) => documents.push(document),
+            Err(err) => {
+                let rendered = err.render_with_options(crate::render_options! {
+                    error_codes: true,
+                });
+                let _ = writeln!(stderr, "{path} invalid:\n{rendered}");
+                return 3;
+            }
+        }
+    }
+
+    let changes = documents[0].diff(&documents[1]);
+    if changes.is_empty() {
+        let _ = writeln!(stdout, "No differences");
+    }
+    for change in &changes {
+        let _ = writeln!(stdout, "{change}");
+        let sides = [
+            (old_path, change.old_location),
+            (new_path, change.new_location),
+        ];
+        let located: Vec<String> = sides
+            .into_iter()
+            .filter_map(|(path, location)| {
+                location.map(|location| format!("{path}:{}:{}", location.line(), location.column()))
+            })
+            .collect();
+        if !located.is_empty() {
+            let _ = writeln!(stdout, "  {}", located.join(" -> "));
+        }
+    }
+    0
+}
+
 fn format_budget_report(report: &BudgetReport) -> String {
     let mut out = String::new();
 
@@ -111,7 +357,30 @@ where
     let mut path: Option<String> = None;
     let mut include_path: Option<String> = None;
 
-    let mut args = args.into_iter();
+    let mut args = args.into_iter().peekable();
+    if args.peek().is_some_and(|arg| arg.as_ref() == "explain") {
+        args.next();
+        let code = args.next();
+        if let Some(extra) = args.next() {
+            let _ = writeln!(
+                stderr,
+                "Unexpected extra argument: {}\n\n{}",
+                extra.as_ref(),
+                usage()
+            );
+            return 1;
+        }
+        return explain(code.as_ref().map(AsRef::as_ref), stdout, stderr);
+    }
+    if args.peek().is_some_and(|arg| arg.as_ref() == "lint") {
+        args.next();
+        return lint(args, stdout, stderr);
+    }
+    if args.peek().is_some_and(|arg| arg.as_ref() == "diff") {
+        args.next();
+        return diff(args, stdout, stderr);
+    }
+
     while let Some(arg) = args.next() {
         let arg = arg.as_ref();
         match arg {
@@ -199,8 +468,13 @@ where
     }
 
     if let Err(err) = result {
+        let render_options = crate::render_options! {
+            error_codes: true,
+        };
+
         if plain {
-            let _ = writeln!(stderr, "{path} invalid:\n{err}");
+            let rendered = err.render_with_options(render_options);
+            let _ = writeln!(stderr, "{path} invalid:\n{rendered}");
             return 3;
         }
 
@@ -214,7 +488,8 @@ where
 
         #[cfg(not(feature = "miette"))]
         {
-            let _ = writeln!(stderr, "{path} invalid:\n{err}");
+            let rendered = err.render_with_options(render_options);
+            let _ = writeln!(stderr, "{path} invalid:\n{rendered}");
             return 3;
         }
     }
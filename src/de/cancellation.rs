@@ -0,0 +1,102 @@
+//! Deadlines and cooperative cancellation for parsing.
+//!
+//! [`crate::Budget`] bounds how much work a document may cause, but not how long it may
+//! take: a slow reader, an expensive include resolver or a large document that stays
+//! within budget can still hold a request for a long time. Set
+//! [`crate::Options::with_deadline`] or [`crate::Options::with_cancellation`] to stop
+//! parsing with [`crate::Error::Interrupted`] instead.
+//!
+//! Both are checked before every parser event, before every replayed alias event and
+//! around include resolution. A read that blocks inside the reader, or a single very
+//! long scalar, is only interrupted once it returns.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Instant;
+
+/// Why parsing was interrupted.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde_derived_types",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+pub enum Interruption {
+    /// The deadline set with [`crate::Options::with_deadline`] passed.
+    DeadlineExceeded,
+    /// [`CancellationToken::cancel`] was called.
+    Cancelled,
+}
+
+/// A flag that stops a running parse when set.
+///
+/// Clones share the flag, and the token can be cancelled from another thread while
+/// the parse runs.
+///
+/// ```rust
+/// use serde_saphyr::{CancellationToken, Error, Interruption};
+///
+/// let token = CancellationToken::new();
+/// let options = serde_saphyr::options! {}.with_cancellation(&token);
+/// token.cancel();
+///
+/// let err = serde_saphyr::from_str_with_options::<Vec<u8>>("[1, 2]", options).unwrap_err();
+/// assert!(matches!(
+///     err.without_snippet(),
+///     Error::Interrupted { reason: Interruption::Cancelled, .. }
+/// ));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancellationToken {
+    /// Create a token that is not cancelled.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Request cancellation of every parse using this token.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    /// True once [`CancellationToken::cancel`] has been called.
+    #[must_use]
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+}
+
+/// The deadline and token of one parse, checked as it makes progress.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct InterruptCheck {
+    deadline: Option<Instant>,
+    token: Option<CancellationToken>,
+}
+
+impl InterruptCheck {
+    /// `None` if neither a deadline nor a token is set, so callers can skip checking.
+    pub(crate) fn new(deadline: Option<Instant>, token: Option<CancellationToken>) -> Option<Self> {
+        (deadline.is_some() || token.is_some()).then_some(Self { deadline, token })
+    }
+
+    pub(crate) fn check(&self) -> Result<(), Interruption> {
+        if self
+            .token
+            .as_ref()
+            .is_some_and(CancellationToken::is_cancelled)
+        {
+            return Err(Interruption::Cancelled);
+        }
+        if self
+            .deadline
+            .is_some_and(|deadline| Instant::now() >= deadline)
+        {
+            return Err(Interruption::DeadlineExceeded);
+        }
+        Ok(())
+    }
+}
@@ -1,6 +1,10 @@
 use super::options::{DuplicateKeyPolicy, MergeKeyPolicy, Options};
 use super::{Error, Location};
 use crate::budget::BudgetBreach;
+use crate::parse_scalars::{maybe_not_string, scalar_is_nullish, scalar_is_nullish_for_option};
+use crate::schema::{self, Schema};
+use crate::tags::SfTag;
+use granit_parser::ScalarStyle;
 
 /// Small immutable runtime configuration that `YamlDeserializer` needs.
 #[derive(Copy, Clone)]
@@ -9,12 +13,16 @@ pub(crate) struct Cfg {
     pub(crate) dup_policy: DuplicateKeyPolicy,
     /// Policy for YAML merge keys (`<<`).
     pub(crate) merge_keys: MergeKeyPolicy,
+    /// Configured schema; a `%YAML 1.1` document may override it, see [`Cfg::schema`].
+    pub(crate) schema: Option<Schema>,
     /// If true, accept legacy octal numbers that start with `0`.
     pub(crate) legacy_octal_numbers: bool,
     /// If true, only accept exact literals `true`/`false` as booleans.
     pub(crate) strict_booleans: bool,
     /// If true, ROS-compliant angle resolver is enabled
     pub(crate) angle_conversions: bool,
+    /// If true, physical unit functions and suffixes are converted to SI base units
+    pub(crate) unit_conversions: bool,
     /// Ignore !!binary for string
     pub(crate) ignore_binary_tag_for_string: bool,
     /// Do not take into String type that looks like number or boolean (require quoting)
@@ -27,6 +35,9 @@ pub(crate) struct Cfg {
     pub(crate) max_depth: Option<usize>,
     /// Current container depth for the recursive Serde deserializer.
     pub(crate) depth: usize,
+    /// `!degrees` / `!radians` tag of the enclosing sequence or mapping, applied to its
+    /// untagged float items and values (`!degrees [90, 0, 0]`, `!degrees {roll: 90}`).
+    pub(crate) item_angle_tag: SfTag,
 }
 
 impl Cfg {
@@ -35,14 +46,17 @@ impl Cfg {
         Self {
             dup_policy: options.duplicate_keys,
             merge_keys: options.merge_keys,
+            schema: options.schema,
             legacy_octal_numbers: options.legacy_octal_numbers,
             strict_booleans: options.strict_booleans,
             angle_conversions: options.angle_conversions,
+            unit_conversions: options.unit_conversions,
             ignore_binary_tag_for_string: options.ignore_binary_tag_for_string,
             no_schema: options.no_schema,
             reject_non_finite_typeless_float: options.reject_non_finite_typeless_float,
             max_depth: options.budget.as_ref().map(|budget| budget.max_depth),
             depth: 0,
+            item_angle_tag: SfTag::None,
         }
     }
 
@@ -54,6 +68,64 @@ impl Cfg {
             return Err(crate::de_error::budget_error(BudgetBreach::Depth { depth })
                 .with_location(location));
         }
-        Ok(Self { depth, ..self })
+        Ok(Self {
+            depth,
+            item_angle_tag: SfTag::None,
+            ..self
+        })
+    }
+
+    /// Tag to use for a float scalar: its own, or the angle tag of the enclosing collection.
+    #[inline]
+    pub(crate) fn float_tag(&self, tag: SfTag) -> SfTag {
+        if tag == SfTag::None {
+            self.item_angle_tag
+        } else {
+            tag
+        }
+    }
+
+    /// Schema for the current document, or `None` for the flag-controlled resolution.
+    #[inline]
+    pub(crate) fn schema(&self) -> Option<Schema> {
+        schema::document_schema(self.schema)
+    }
+
+    /// True if integer targets read a leading-zero literal such as `012` as octal.
+    pub(crate) fn reads_legacy_octal(&self) -> bool {
+        match self.schema() {
+            Some(schema) => schema == Schema::Yaml11,
+            None => self.legacy_octal_numbers,
+        }
+    }
+
+    /// True if a scalar is null for non-`Option` targets.
+    pub(crate) fn is_null(&self, value: &str, style: &ScalarStyle) -> bool {
+        match self.schema() {
+            Some(schema) => matches!(style, ScalarStyle::Plain) && schema.is_null(value),
+            None => scalar_is_nullish(value, style),
+        }
+    }
+
+    /// True if a scalar is `None` for `Option<T>`: an empty unquoted scalar counts like
+    /// an empty plain one.
+    pub(crate) fn is_null_for_option(&self, value: &str, style: &ScalarStyle) -> bool {
+        match self.schema() {
+            Some(schema) => match style {
+                ScalarStyle::Plain => schema.is_null(value),
+                ScalarStyle::SingleQuoted | ScalarStyle::DoubleQuoted => false,
+                _ => value.is_empty() && schema.is_null(value),
+            },
+            None => scalar_is_nullish_for_option(value, style),
+        }
+    }
+
+    /// True if an unquoted string would be read as another type, which `no_schema`
+    /// rejects.
+    pub(crate) fn looks_like_non_string(&self, value: &str, style: &ScalarStyle) -> bool {
+        match self.schema() {
+            Some(schema) => matches!(style, ScalarStyle::Plain) && !schema.is_string(value),
+            None => maybe_not_string(value, style, self.strict_booleans),
+        }
     }
 }
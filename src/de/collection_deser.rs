@@ -0,0 +1,352 @@
+//! Internal support for the YAML 1.1 collection types `!!set`, `!!omap` and `!!pairs`.
+//!
+//! A `!!set` is a mapping whose values are all null; it is read as a sequence of its
+//! keys. `!!omap` and `!!pairs` are sequences of single-pair mappings; they are read
+//! either as a mapping or as a sequence of `(key, value)` pairs. Keys of an `!!omap`
+//! must be unique.
+
+use std::collections::HashSet;
+use std::marker::PhantomData;
+
+use serde_core::de::{self, Visitor};
+
+use super::cfg::Cfg;
+use super::deserializer::skip_one_node_from_events;
+use super::events::{Ev, Events, ReplayContext, ReplayEvents, eof_with_loc};
+use super::key_nodes::{KeyFingerprint, capture_node};
+use super::tags::SfTag;
+use super::{Error, Location};
+use crate::Deserializer;
+
+/// Read a `!!set` mapping as a sequence of its keys.
+pub(super) fn deserialize_yaml_set<'de, V>(
+    de: Deserializer<'de, '_>,
+    visitor: V,
+) -> Result<V::Value, Error>
+where
+    V: Visitor<'de>,
+{
+    let cfg = open_container(de.ev, de.cfg)?;
+    let value = visitor.visit_seq(SetAccess {
+        ev: &mut *de.ev,
+        cfg,
+    })?;
+    drain_container(de.ev)?;
+    Ok(value)
+}
+
+/// Read an `!!omap` or `!!pairs` sequence as a mapping (`as_map`) or as a sequence of pairs.
+pub(super) fn deserialize_yaml_pairs<'de, V>(
+    de: Deserializer<'de, '_>,
+    tag: SfTag,
+    as_map: bool,
+    visitor: V,
+) -> Result<V::Value, Error>
+where
+    V: Visitor<'de>,
+{
+    let cfg = open_container(de.ev, de.cfg)?;
+    let mut access = PairsAccess {
+        ev: de.ev,
+        cfg,
+        tag: if tag == SfTag::Omap {
+            "!!omap"
+        } else {
+            "!!pairs"
+        },
+        seen: (tag == SfTag::Omap).then(HashSet::new),
+    };
+    let value = if as_map {
+        visitor.visit_map(&mut access)?
+    } else {
+        visitor.visit_seq(&mut access)?
+    };
+    drain_container(access.ev)?;
+    Ok(value)
+}
+
+/// Charge the container against the depth budget and consume its start event.
+fn open_container(ev: &mut dyn Events<'_>, cfg: Cfg) -> Result<Cfg, Error> {
+    let location = match ev.peek()? {
+        Some(ev) => ev.location(),
+        None => ev.last_location(),
+    };
+    let cfg = cfg.enter_container(location)?;
+    let _ = ev.next()?;
+    Ok(cfg)
+}
+
+/// Skip whatever the visitor left unread and consume the container end.
+fn drain_container(ev: &mut dyn Events<'_>) -> Result<(), Error> {
+    loop {
+        match ev.peek()? {
+            Some(Ev::SeqEnd { .. } | Ev::MapEnd { .. }) => {
+                let _ = ev.next()?;
+                return Ok(());
+            }
+            Some(_) => skip_one_node_from_events(ev)?,
+            None => return Err(eof_with_loc(ev)),
+        }
+    }
+}
+
+fn replay_node<'de, T>(
+    ev: &dyn Events<'de>,
+    cfg: Cfg,
+    events: Vec<Ev<'de>>,
+    seed: T,
+) -> Result<T::Value, Error>
+where
+    T: de::DeserializeSeed<'de>,
+{
+    let mut replay = ReplayEvents::new(events, ReplayContext::of(ev));
+    seed.deserialize(Deserializer::new(&mut replay, cfg))
+}
+
+/// `SeqAccess` over the keys of a `!!set`, rejecting any non-null value.
+struct SetAccess<'de, 'e> {
+    ev: &'e mut dyn Events<'de>,
+    cfg: Cfg,
+}
+
+impl<'de> de::SeqAccess<'de> for SetAccess<'de, '_> {
+    type Error = Error;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Error>
+    where
+        T: de::DeserializeSeed<'de>,
+    {
+        match self.ev.peek()? {
+            Some(Ev::MapEnd { .. }) => return Ok(None),
+            Some(_) => {}
+            None => return Err(eof_with_loc(self.ev)),
+        }
+        let element = seed.deserialize(Deserializer::new(self.ev, self.cfg))?;
+
+        let (is_null, location) = match self.ev.peek()? {
+            Some(Ev::Scalar {
+                value,
+                tag,
+                style,
+                location,
+                ..
+            }) => (
+                *tag == SfTag::Null || (*tag == SfTag::None && self.cfg.is_null(value, style)),
+                *location,
+            ),
+            Some(other) => (false, other.location()),
+            None => return Err(eof_with_loc(self.ev)),
+        };
+        if !is_null {
+            return Err(Error::SetValueNotNull { location });
+        }
+        let _ = self.ev.next()?;
+        Ok(Some(element))
+    }
+}
+
+/// Access over the single-pair mappings of an `!!omap` or `!!pairs` sequence.
+struct PairsAccess<'de, 'e> {
+    ev: &'e mut dyn Events<'de>,
+    cfg: Cfg,
+    tag: &'static str,
+    /// Keys seen so far; `Some` only for `!!omap`, which requires unique keys.
+    seen: Option<HashSet<KeyFingerprint<'de>>>,
+}
+
+impl<'de> PairsAccess<'de, '_> {
+    fn invalid_entry(&self, location: Location) -> Error {
+        Error::InvalidPairsEntry {
+            tag: self.tag,
+            location,
+        }
+    }
+
+    /// Enter the next entry mapping and capture its key, or return `None` at the end.
+    fn open_entry(&mut self) -> Result<Option<Vec<Ev<'de>>>, Error> {
+        let location = match self.ev.peek()? {
+            Some(Ev::SeqEnd { .. }) => return Ok(None),
+            Some(Ev::MapStart { .. }) => None,
+            Some(other) => Some(other.location()),
+            None => return Err(eof_with_loc(self.ev)),
+        };
+        if let Some(location) = location {
+            return Err(self.invalid_entry(location));
+        }
+        let entry = self
+            .ev
+            .next()?
+            .map_or(Location::UNKNOWN, |ev| ev.location());
+        if matches!(self.ev.peek()?, Some(Ev::MapEnd { .. })) {
+            return Err(self.invalid_entry(entry));
+        }
+
+        let mut key = capture_node(self.ev)?;
+        if let Some(seen) = self.seen.as_mut() {
+            let fingerprint = key.take_fingerprint();
+            if seen.contains(&fingerprint) {
+                return Err(Error::DuplicateOmapKey {
+                    key: fingerprint.stringy_scalar_value().map(str::to_owned),
+                    location: key.location(),
+                });
+            }
+            seen.insert(fingerprint);
+        }
+        Ok(Some(key.take_events()))
+    }
+
+    /// Read the entry value from the live stream and leave its mapping.
+    fn entry_value<T>(&mut self, seed: T) -> Result<T::Value, Error>
+    where
+        T: de::DeserializeSeed<'de>,
+    {
+        let value = seed.deserialize(Deserializer::new(self.ev, self.cfg))?;
+        match self.ev.next()? {
+            Some(Ev::MapEnd { .. }) => Ok(value),
+            Some(other) => Err(self.invalid_entry(other.location())),
+            None => Err(eof_with_loc(self.ev)),
+        }
+    }
+}
+
+impl<'de> de::MapAccess<'de> for &mut PairsAccess<'de, '_> {
+    type Error = Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Error>
+    where
+        K: de::DeserializeSeed<'de>,
+    {
+        match self.open_entry()? {
+            Some(key) => replay_node(self.ev, self.cfg, key, seed).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Error>
+    where
+        V: de::DeserializeSeed<'de>,
+    {
+        self.entry_value(seed)
+    }
+}
+
+impl<'de> de::SeqAccess<'de> for &mut PairsAccess<'de, '_> {
+    type Error = Error;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Error>
+    where
+        T: de::DeserializeSeed<'de>,
+    {
+        let Some(key) = self.open_entry()? else {
+            return Ok(None);
+        };
+        let mut pair = Pair {
+            access: self,
+            key: Some(key),
+            value_done: false,
+        };
+        let element = seed.deserialize(&mut pair)?;
+        if !pair.value_done {
+            // The element type did not read the value; skip it and leave the entry.
+            pair.value(PhantomData::<de::IgnoredAny>)?;
+        }
+        Ok(Some(element))
+    }
+}
+
+/// One `!!omap`/`!!pairs` entry: a two-element sequence for tuples, otherwise a
+/// single-entry mapping.
+struct Pair<'a, 'de, 'e> {
+    access: &'a mut PairsAccess<'de, 'e>,
+    key: Option<Vec<Ev<'de>>>,
+    value_done: bool,
+}
+
+impl<'de> Pair<'_, 'de, '_> {
+    fn key<T>(&mut self, seed: T) -> Result<Option<T::Value>, Error>
+    where
+        T: de::DeserializeSeed<'de>,
+    {
+        match self.key.take() {
+            Some(key) => replay_node(self.access.ev, self.access.cfg, key, seed).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    fn value<T>(&mut self, seed: T) -> Result<T::Value, Error>
+    where
+        T: de::DeserializeSeed<'de>,
+    {
+        self.value_done = true;
+        self.access.entry_value(seed)
+    }
+}
+
+impl<'de> de::Deserializer<'de> for &mut Pair<'_, 'de, '_> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_map(self)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_seq(self)
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(
+        self,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_seq(self)
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_seq(self)
+    }
+
+    serde_core::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf
+        option unit unit_struct newtype_struct map struct enum identifier ignored_any
+    }
+}
+
+impl<'de> de::SeqAccess<'de> for &mut Pair<'_, 'de, '_> {
+    type Error = Error;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Error>
+    where
+        T: de::DeserializeSeed<'de>,
+    {
+        if self.key.is_some() {
+            self.key(seed)
+        } else if !self.value_done {
+            self.value(seed).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+impl<'de> de::MapAccess<'de> for &mut Pair<'_, 'de, '_> {
+    type Error = Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Error>
+    where
+        K: de::DeserializeSeed<'de>,
+    {
+        self.key(seed)
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Error>
+    where
+        V: de::DeserializeSeed<'de>,
+    {
+        self.value(seed)
+    }
+}
@@ -1,4 +1,6 @@
 use std::collections::{HashSet, VecDeque};
+#[cfg(feature = "properties")]
+use std::rc::Rc;
 use std::{borrow::Cow, fmt};
 
 use granit_parser::ScalarStyle;
@@ -6,9 +8,12 @@ use serde_core::de::{self, Deserializer as _, IntoDeserializer, Visitor};
 
 use super::base64::decode_base64_yaml;
 use super::cfg::Cfg;
+use super::collection_deser;
 use super::commented_deser;
 use super::error::{Error, MissingFieldLocationGuard, TransformReason};
-use super::events::{Ev, Events, ReplayEvents, attach_alias_locations_if_missing, eof_with_loc};
+use super::events::{
+    Ev, Events, ReplayContext, ReplayEvents, attach_alias_locations_if_missing, eof_with_loc,
+};
 use super::key_nodes::{
     KeyFingerprint, KeyNode, PendingEntry, apply_duplicate_key_policy_to_entries, capture_node,
     capture_simple_tagged_node_as_map_events, is_empty_mapping_key_fingerprint, is_merge_key,
@@ -17,24 +22,24 @@ use super::key_nodes::{
     validate_no_merge_keys_in_node_events,
 };
 use super::options::{DuplicateKeyPolicy, MergeKeyPolicy};
-#[cfg(any(feature = "garde", feature = "validator"))]
 use super::path_map::PathRecorder;
 #[cfg(feature = "properties")]
-use super::properties::interpolate_compose_style;
+use super::properties::{Interpolation, interpolate};
 use super::properties_redaction::{
     ScalarRedactionCtx, ScalarRedactionGuard, with_interp_redaction_scope,
 };
+use super::raw_deser;
 use super::spanned_deser;
 use super::tags::SfTag;
+use super::warnings::{self, WarningKind, WarningPathGuard};
 use crate::anchor_store::{self, AnchorKind};
 use crate::location::Location;
-#[cfg(any(feature = "garde", feature = "validator"))]
 use crate::location::Locations;
 use crate::parse_scalars::{
-    leading_zero_decimal, maybe_not_string, parse_int_signed, parse_int_unsigned,
-    parse_yaml11_bool, parse_yaml12_float, scalar_is_nullish, scalar_is_nullish_for_option,
-    try_parse_float_incl_overflow,
+    leading_zero_decimal, parse_int_signed, parse_int_unsigned, parse_yaml11_bool,
+    parse_yaml12_float, scalar_is_nullish, try_parse_float_incl_overflow,
 };
+use crate::schema::{IntLiteral, Resolved, Schema};
 
 struct TupleLenExpected {
     len: usize,
@@ -115,7 +120,7 @@ where
     }
 }
 
-fn skip_one_node_from_events(ev: &mut dyn Events<'_>) -> Result<(), Error> {
+pub(super) fn skip_one_node_from_events(ev: &mut dyn Events<'_>) -> Result<(), Error> {
     let mut depth;
     match ev.next()? {
         Some(Ev::Scalar { .. }) => return Ok(()),
@@ -157,6 +162,13 @@ fn drain_remaining_sequence(ev: &mut dyn Events<'_>) -> Result<(), Error> {
     }
 }
 
+/// Report a key that replaces an earlier value under `DuplicateKeyPolicy::LastWins`.
+fn warn_duplicate_override(fingerprint: &KeyFingerprint<'_>, location: Location) {
+    warnings::warn(location, || WarningKind::DuplicateKeyOverride {
+        key: fingerprint.stringy_scalar_value().map(str::to_owned),
+    });
+}
+
 /// Whether `V` is Serde's private visitor for buffering an arbitrary value as
 /// `Content`, as used by `#[serde(untagged)]` and `#[serde(flatten)]`.
 ///
@@ -233,7 +245,6 @@ pub struct YamlDeserializer<'de, 'e> {
     /// Comments that appeared above the value node itself.
     pub(super) pending_value_comments: Vec<Cow<'de, str>>,
 
-    #[cfg(any(feature = "garde", feature = "validator"))]
     pub(super) garde: Option<&'e mut PathRecorder>,
 }
 
@@ -398,7 +409,6 @@ impl<'de, 'e> YamlDeserializer<'de, 'e> {
             pending_value_separator_comments: Vec::new(),
             pending_value_comments: Vec::new(),
 
-            #[cfg(any(feature = "garde", feature = "validator"))]
             garde: None,
         }
     }
@@ -419,7 +429,7 @@ impl<'de, 'e> YamlDeserializer<'de, 'e> {
 
         #[cfg(feature = "properties")]
         {
-            self.ev.property_map().is_some()
+            self.ev.property_map().is_some() || self.ev.ros_substitutions().is_some()
         }
     }
 
@@ -440,7 +450,6 @@ impl<'de, 'e> YamlDeserializer<'de, 'e> {
             .and_then(|view| view.redaction_ctx()))
     }
 
-    #[cfg(any(feature = "garde", feature = "validator"))]
     pub(crate) fn new_with_path_recorder(
         ev: &'e mut dyn Events<'de>,
         cfg: Cfg,
@@ -503,6 +512,134 @@ impl<'de, 'e> YamlDeserializer<'de, 'e> {
         }
     }
 
+    /// Report a plain scalar like `0755` that is read as a string, but that YAML 1.1
+    /// would read as an octal number.
+    fn warn_if_octal_string(&self, view: &ScalarView<'_>) {
+        if !self.in_key
+            && !view.interpolated
+            && view.style == ScalarStyle::Plain
+            && view.tag != SfTag::String
+        {
+            warnings::warn_legacy_octal(&view.effective, view.location, false);
+        }
+    }
+
+    /// Parse a signed integer target with the document's schema, or with the
+    /// flag-controlled rules when none is selected.
+    fn parse_signed<T: TryFrom<i128>>(
+        &self,
+        s: &str,
+        ty: &'static str,
+        location: Location,
+    ) -> Result<T, Error> {
+        match self.cfg.schema() {
+            Some(schema) => schema
+                .for_typed()
+                .parse_int(s.trim())
+                .and_then(IntLiteral::to_i128)
+                .and_then(|v| T::try_from(v).ok())
+                .ok_or(Error::InvalidScalar { ty, location }),
+            None => parse_int_signed(s, ty, location, self.cfg.legacy_octal_numbers),
+        }
+    }
+
+    /// Unsigned counterpart of [`Self::parse_signed`].
+    fn parse_unsigned<T: TryFrom<u128>>(
+        &self,
+        s: &str,
+        ty: &'static str,
+        location: Location,
+    ) -> Result<T, Error> {
+        match self.cfg.schema() {
+            Some(schema) => schema
+                .for_typed()
+                .parse_int(s.trim())
+                .and_then(IntLiteral::to_u128)
+                .and_then(|v| T::try_from(v).ok())
+                .ok_or(Error::InvalidScalar { ty, location }),
+            None => parse_int_unsigned(s, ty, location, self.cfg.legacy_octal_numbers),
+        }
+    }
+
+    /// Parse a float target with the document's schema. `None` means the default rules
+    /// apply: no schema is selected, or angle or unit conversions own the float syntax.
+    fn parse_schema_float(&self, s: &str, location: Location) -> Option<Result<f64, Error>> {
+        let schema = self
+            .cfg
+            .schema()
+            .filter(|_| !self.cfg.angle_conversions && !self.cfg.unit_conversions)?;
+        Some(
+            schema
+                .parse_typed_float(s.trim())
+                .ok_or(Error::InvalidScalar {
+                    ty: "floating point",
+                    location,
+                }),
+        )
+    }
+
+    /// `deserialize_any` for an untagged plain scalar under a selected schema.
+    fn deserialize_any_with_schema<V: Visitor<'de>>(
+        mut self,
+        schema: Schema,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        let view = self.take_scalar_view()?;
+        match schema.resolve(&view.effective) {
+            Resolved::Null => visitor.visit_unit(),
+            Resolved::Bool(b) => visitor.visit_bool(b),
+            // Like the default rules, integers beyond 64 bits are read as floats.
+            Resolved::Int(int) if int.negative => {
+                match int.to_i128().and_then(|v| i64::try_from(v).ok()) {
+                    Some(v) => visitor.visit_i64(v),
+                    None => visitor.visit_f64(int.to_f64()),
+                }
+            }
+            Resolved::Int(int) => match u64::try_from(int.magnitude) {
+                Ok(v) => visitor.visit_u64(v),
+                Err(_) => visitor.visit_f64(int.to_f64()),
+            },
+            Resolved::Float(v) => {
+                self.visit_typeless_float(v, view.raw.into_owned(), view.location, visitor)
+            }
+            Resolved::Str => match view.effective {
+                Cow::Borrowed(b) => visitor.visit_borrowed_str(b),
+                Cow::Owned(s) => visitor.visit_string(s),
+            },
+        }
+    }
+
+    /// Visit a float read by `deserialize_any`.
+    ///
+    /// Typeless consumers such as serde_json::Value cannot represent non-finite floats.
+    /// By default, reject these scalars. When rejection is disabled, return a canonical
+    /// string so these values do not become null or fail later.
+    fn visit_typeless_float<V: Visitor<'de>>(
+        &self,
+        v: f64,
+        raw: String,
+        location: Location,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        if v.is_finite() {
+            return visitor.visit_f64(v);
+        }
+        if self.cfg.reject_non_finite_typeless_float {
+            return Err(Error::NonFiniteFloat {
+                value: raw,
+                location,
+            });
+        }
+        let canon = if v.is_nan() {
+            ".nan"
+        } else if v.is_sign_negative() {
+            "-.inf"
+        } else {
+            ".inf"
+        };
+        visitor.visit_string(canon.to_string())
+    }
+
     /// Read a scalar as `String`, decoding `!!binary` into UTF-8 text if needed.
     ///
     /// Errors if the tag is incompatible with strings or if the binary payload
@@ -611,13 +748,16 @@ impl<'de, 'e> YamlDeserializer<'de, 'e> {
 
         #[cfg(feature = "properties")]
         {
-            let Some(vars) = self.ev.property_map() else {
-                return Ok(value);
+            let ctx = Interpolation {
+                vars: self.ev.property_map().map(Rc::as_ref),
+                syntax: self.ev.property_syntax(),
+                ros: self.ev.ros_substitutions().map(Rc::as_ref),
             };
-            let vars = vars.as_ref();
-            let syntax = self.ev.property_syntax();
+            if ctx.vars.is_none() && ctx.ros.is_none() {
+                return Ok(value);
+            }
 
-            match interpolate_compose_style(value, vars, syntax) {
+            match interpolate(value, ctx) {
                 Ok(value) => Ok(value),
                 Err(crate::properties::PropertyError::Unresolved(name)) => {
                     Err(Error::UnresolvedProperty { name, location })
@@ -639,6 +779,18 @@ impl<'de, 'e> YamlDeserializer<'de, 'e> {
                         location,
                     })
                 }
+                Err(crate::properties::PropertyError::UnresolvedSubstitution(substitution)) => {
+                    Err(Error::UnresolvedSubstitution {
+                        substitution,
+                        location,
+                    })
+                }
+                Err(crate::properties::PropertyError::InvalidSubstitution(substitution)) => {
+                    Err(Error::InvalidSubstitution {
+                        substitution,
+                        location,
+                    })
+                }
             }
         }
     }
@@ -700,13 +852,7 @@ impl<'de> de::Deserializer<'de> for YamlDeserializer<'de, '_> {
         if is_serde_content_buffer::<V>()
             && let Some(events) = capture_simple_tagged_node_as_map_events(self.ev)?
         {
-            let mut replay = ReplayEvents::new(
-                events,
-                #[cfg(feature = "properties")]
-                self.ev.property_map().cloned(),
-                #[cfg(feature = "properties")]
-                self.ev.property_syntax(),
-            );
+            let mut replay = ReplayEvents::new(events, ReplayContext::of(self.ev));
             return YamlDeserializer::new(&mut replay, self.cfg).deserialize_map(visitor);
         }
 
@@ -717,6 +863,12 @@ impl<'de> de::Deserializer<'de> for YamlDeserializer<'de, '_> {
                 return visitor.visit_unit();
             }
             let is_plain = matches!(style, ScalarStyle::Plain);
+            if is_plain
+                && tag == SfTag::None
+                && let Some(schema) = self.cfg.schema()
+            {
+                return self.deserialize_any_with_schema(schema, visitor);
+            }
             // Treat all YAML null-like scalars (null, ~, empty) as null when typeless.
             if scalar_is_nullish(&value, &style) {
                 let _ = self.ev.next()?; // consume
@@ -764,6 +916,7 @@ impl<'de> de::Deserializer<'de> for YamlDeserializer<'de, '_> {
                 }
                 // otherwise not a bool in strict mode; continue to numbers/float/string
             } else if let Ok(b) = parse_yaml11_bool(&effective) {
+                warnings::warn_yaml11_bool(&effective, b, location);
                 return visitor.visit_bool(b);
             }
 
@@ -774,12 +927,14 @@ impl<'de> de::Deserializer<'de> for YamlDeserializer<'de, '_> {
                     && let Ok(v) =
                         parse_int_signed::<i64>(t, "i64", location, self.cfg.legacy_octal_numbers)
                 {
+                    warnings::warn_legacy_octal(t, location, true);
                     return visitor.visit_i64(v);
                 }
             } else {
                 if let Ok(v) =
                     parse_int_unsigned::<u64>(t, "u64", location, self.cfg.legacy_octal_numbers)
                 {
+                    warnings::warn_legacy_octal(t, location, true);
                     return visitor.visit_u64(v);
                 }
             }
@@ -791,31 +946,15 @@ impl<'de> de::Deserializer<'de> for YamlDeserializer<'de, '_> {
                 location,
                 view.tag,
                 self.cfg.angle_conversions,
+                self.cfg.unit_conversions,
             ) {
-                // Typeless consumers such as serde_json::Value cannot represent non-finite
-                // floats. By default, reject these scalars. When rejection is disabled,
-                // deserialize_any returns a canonical string so these values do not become
-                // null or fail later.
-                if v.is_finite() {
-                    return visitor.visit_f64(v);
-                }
-                if self.cfg.reject_non_finite_typeless_float {
-                    return Err(Error::NonFiniteFloat {
-                        value: raw,
-                        location,
-                    });
-                }
-                let canon = if v.is_nan() {
-                    ".nan".to_string()
-                } else if v.is_sign_negative() {
-                    "-.inf".to_string()
-                } else {
-                    ".inf".to_string()
-                };
-                return visitor.visit_string(canon);
+                return self.visit_typeless_float(v, raw, location, visitor);
             }
 
             // Fallback: treat as string as-is.
+            if view.style == ScalarStyle::Plain {
+                warnings::warn_legacy_octal(&effective, location, false);
+            }
             return visitor.visit_string(effective);
         }
 
@@ -854,7 +993,15 @@ impl<'de> de::Deserializer<'de> for YamlDeserializer<'de, '_> {
         validate_core_scalar_tag(tag, SfTag::Bool, "boolean", location)?;
         let s = s.as_ref();
         let t = s.trim();
-        let b: bool = if self.cfg.strict_booleans {
+        let b: bool = if let Some(schema) = self.cfg.schema() {
+            schema
+                .for_typed()
+                .parse_bool(t)
+                .ok_or(Error::InvalidScalar {
+                    ty: "boolean",
+                    location,
+                })?
+        } else if self.cfg.strict_booleans {
             if t.eq_ignore_ascii_case("true") {
                 true
             } else if t.eq_ignore_ascii_case("false") {
@@ -863,10 +1010,12 @@ impl<'de> de::Deserializer<'de> for YamlDeserializer<'de, '_> {
                 return Err(Error::InvalidBooleanStrict { location });
             }
         } else {
-            parse_yaml11_bool(s).map_err(|_| Error::InvalidScalar {
+            let b = parse_yaml11_bool(s).map_err(|_| Error::InvalidScalar {
                 ty: "boolean",
                 location,
-            })?
+            })?;
+            warnings::warn_yaml11_bool(s, b, location);
+            b
         };
         visitor.visit_bool(b)
     }
@@ -875,36 +1024,40 @@ impl<'de> de::Deserializer<'de> for YamlDeserializer<'de, '_> {
     fn deserialize_i8<V: Visitor<'de>>(mut self, visitor: V) -> Result<V::Value, Self::Error> {
         let (s, tag, location) = self.take_scalar_cow_event()?;
         validate_core_scalar_tag(tag, SfTag::Int, "i8", location)?;
-        let v: i8 = parse_int_signed(s.as_ref(), "i8", location, self.cfg.legacy_octal_numbers)?;
+        let v: i8 = self.parse_signed(s.as_ref(), "i8", location)?;
+        warnings::warn_legacy_octal(s.as_ref(), location, self.cfg.reads_legacy_octal());
         visitor.visit_i8(v)
     }
     /// Parse a signed 16-bit integer.
     fn deserialize_i16<V: Visitor<'de>>(mut self, visitor: V) -> Result<V::Value, Self::Error> {
         let (s, tag, location) = self.take_scalar_cow_event()?;
         validate_core_scalar_tag(tag, SfTag::Int, "i16", location)?;
-        let v: i16 = parse_int_signed(s.as_ref(), "i16", location, self.cfg.legacy_octal_numbers)?;
+        let v: i16 = self.parse_signed(s.as_ref(), "i16", location)?;
+        warnings::warn_legacy_octal(s.as_ref(), location, self.cfg.reads_legacy_octal());
         visitor.visit_i16(v)
     }
     /// Parse a signed 32-bit integer.
     fn deserialize_i32<V: Visitor<'de>>(mut self, visitor: V) -> Result<V::Value, Self::Error> {
         let (s, tag, location) = self.take_scalar_cow_event()?;
         validate_core_scalar_tag(tag, SfTag::Int, "i32", location)?;
-        let v: i32 = parse_int_signed(s.as_ref(), "i32", location, self.cfg.legacy_octal_numbers)?;
+        let v: i32 = self.parse_signed(s.as_ref(), "i32", location)?;
+        warnings::warn_legacy_octal(s.as_ref(), location, self.cfg.reads_legacy_octal());
         visitor.visit_i32(v)
     }
     /// Parse a signed 64-bit integer.
     fn deserialize_i64<V: Visitor<'de>>(mut self, visitor: V) -> Result<V::Value, Self::Error> {
         let (s, tag, location) = self.take_scalar_cow_event()?;
         validate_core_scalar_tag(tag, SfTag::Int, "i64", location)?;
-        let v: i64 = parse_int_signed(s.as_ref(), "i64", location, self.cfg.legacy_octal_numbers)?;
+        let v: i64 = self.parse_signed(s.as_ref(), "i64", location)?;
+        warnings::warn_legacy_octal(s.as_ref(), location, self.cfg.reads_legacy_octal());
         visitor.visit_i64(v)
     }
     /// Parse a signed 128-bit integer.
     fn deserialize_i128<V: Visitor<'de>>(mut self, visitor: V) -> Result<V::Value, Self::Error> {
         let (s, tag, location) = self.take_scalar_cow_event()?;
         validate_core_scalar_tag(tag, SfTag::Int, "i128", location)?;
-        let v: i128 =
-            parse_int_signed(s.as_ref(), "i128", location, self.cfg.legacy_octal_numbers)?;
+        let v: i128 = self.parse_signed(s.as_ref(), "i128", location)?;
+        warnings::warn_legacy_octal(s.as_ref(), location, self.cfg.reads_legacy_octal());
         visitor.visit_i128(v)
     }
 
@@ -912,52 +1065,82 @@ impl<'de> de::Deserializer<'de> for YamlDeserializer<'de, '_> {
     fn deserialize_u8<V: Visitor<'de>>(mut self, visitor: V) -> Result<V::Value, Self::Error> {
         let (s, tag, location) = self.take_scalar_cow_event()?;
         validate_core_scalar_tag(tag, SfTag::Int, "u8", location)?;
-        let v: u8 = parse_int_unsigned(s.as_ref(), "u8", location, self.cfg.legacy_octal_numbers)?;
+        let v: u8 = self.parse_unsigned(s.as_ref(), "u8", location)?;
+        warnings::warn_legacy_octal(s.as_ref(), location, self.cfg.reads_legacy_octal());
         visitor.visit_u8(v)
     }
     /// Parse an unsigned 16-bit integer.
     fn deserialize_u16<V: Visitor<'de>>(mut self, visitor: V) -> Result<V::Value, Self::Error> {
         let (s, tag, location) = self.take_scalar_cow_event()?;
         validate_core_scalar_tag(tag, SfTag::Int, "u16", location)?;
-        let v: u16 =
-            parse_int_unsigned(s.as_ref(), "u16", location, self.cfg.legacy_octal_numbers)?;
+        let v: u16 = self.parse_unsigned(s.as_ref(), "u16", location)?;
+        warnings::warn_legacy_octal(s.as_ref(), location, self.cfg.reads_legacy_octal());
         visitor.visit_u16(v)
     }
     /// Parse an unsigned 32-bit integer.
     fn deserialize_u32<V: Visitor<'de>>(mut self, visitor: V) -> Result<V::Value, Self::Error> {
         let (s, tag, location) = self.take_scalar_cow_event()?;
         validate_core_scalar_tag(tag, SfTag::Int, "u32", location)?;
-        let v: u32 =
-            parse_int_unsigned(s.as_ref(), "u32", location, self.cfg.legacy_octal_numbers)?;
+        let v: u32 = self.parse_unsigned(s.as_ref(), "u32", location)?;
+        warnings::warn_legacy_octal(s.as_ref(), location, self.cfg.reads_legacy_octal());
         visitor.visit_u32(v)
     }
     /// Parse an unsigned 64-bit integer.
     fn deserialize_u64<V: Visitor<'de>>(mut self, visitor: V) -> Result<V::Value, Self::Error> {
         let (s, tag, location) = self.take_scalar_cow_event()?;
         validate_core_scalar_tag(tag, SfTag::Int, "u64", location)?;
-        let v: u64 =
-            parse_int_unsigned(s.as_ref(), "u64", location, self.cfg.legacy_octal_numbers)?;
+        let v: u64 = self.parse_unsigned(s.as_ref(), "u64", location)?;
+        warnings::warn_legacy_octal(s.as_ref(), location, self.cfg.reads_legacy_octal());
         visitor.visit_u64(v)
     }
     /// Parse an unsigned 128-bit integer.
     fn deserialize_u128<V: Visitor<'de>>(mut self, visitor: V) -> Result<V::Value, Self::Error> {
         let (s, tag, location) = self.take_scalar_cow_event()?;
         validate_core_scalar_tag(tag, SfTag::Int, "u128", location)?;
-        let v: u128 =
-            parse_int_unsigned(s.as_ref(), "u128", location, self.cfg.legacy_octal_numbers)?;
+        let v: u128 = self.parse_unsigned(s.as_ref(), "u128", location)?;
+        warnings::warn_legacy_octal(s.as_ref(), location, self.cfg.reads_legacy_octal());
         visitor.visit_u128(v)
     }
 
     /// Parse a 32-bit float (supports YAML 1.2 `+.inf`, `-.inf`, `.nan`).
     fn deserialize_f32<V: Visitor<'de>>(mut self, visitor: V) -> Result<V::Value, Self::Error> {
         let (s, tag, location) = self.take_scalar_cow_event()?;
-        let v: f32 = parse_yaml12_float(s.as_ref(), location, tag, self.cfg.angle_conversions)?;
+        let v: f32 = match self.parse_schema_float(s.as_ref(), location) {
+            Some(v) => {
+                let v = v?;
+                #[allow(clippy::cast_possible_truncation)]
+                let narrowed = v as f32;
+                if narrowed.is_infinite() && v.is_finite() {
+                    return Err(Error::InvalidScalar {
+                        ty: "floating point",
+                        location,
+                    });
+                }
+                narrowed
+            }
+            None => parse_yaml12_float(
+                s.as_ref(),
+                location,
+                self.cfg.float_tag(tag),
+                self.cfg.angle_conversions,
+                self.cfg.unit_conversions,
+            )?,
+        };
         visitor.visit_f32(v)
     }
     /// Parse a 64-bit float (supports YAML 1.2 `+.inf`, `-.inf`, `.nan`).
     fn deserialize_f64<V: Visitor<'de>>(mut self, visitor: V) -> Result<V::Value, Self::Error> {
         let (s, tag, location) = self.take_scalar_cow_event()?;
-        let v: f64 = parse_yaml12_float(s.as_ref(), location, tag, self.cfg.angle_conversions)?;
+        let v: f64 = match self.parse_schema_float(s.as_ref(), location) {
+            Some(v) => v?,
+            None => parse_yaml12_float(
+                s.as_ref(),
+                location,
+                self.cfg.float_tag(tag),
+                self.cfg.angle_conversions,
+                self.cfg.unit_conversions,
+            )?,
+        };
         visitor.visit_f64(v)
     }
 
@@ -974,11 +1157,11 @@ impl<'de> de::Deserializer<'de> for YamlDeserializer<'de, '_> {
             && view.tag != SfTag::String
         {
             // Reject YAML null for char (allow quoted values like "null").
-            if view.tag == SfTag::Null || scalar_is_nullish(&view.effective, &view.style) {
+            if view.tag == SfTag::Null || self.cfg.is_null(&view.effective, &view.style) {
                 let (_value, _tag, location) = self.take_scalar_event()?;
                 return Err(Error::InvalidCharNull { location });
             } else if self.cfg.no_schema
-                && maybe_not_string(&view.effective, &view.style, self.cfg.strict_booleans)
+                && self.cfg.looks_like_non_string(&view.effective, &view.style)
             {
                 // Require quoting for ambiguous plain scalars in no_schema mode.
                 let view = self.take_scalar_view()?;
@@ -1013,14 +1196,14 @@ impl<'de> de::Deserializer<'de> for YamlDeserializer<'de, '_> {
                 // Check for null - not valid for string deserialization. A deliberately
                 // substituted ${...} that resolved to "" stays a string, not null.
                 if (view.tag == SfTag::Null
-                    || (!view.interpolated && scalar_is_nullish(&view.effective, &view.style)))
+                    || (!view.interpolated && self.cfg.is_null(&view.effective, &view.style)))
                     && view.tag != SfTag::String
                 {
                     let loc = view.location;
                     let _ = self.ev.next()?;
                     return Err(Error::NullIntoString { location: loc });
                 } else if self.cfg.no_schema
-                    && maybe_not_string(&view.effective, &view.style, self.cfg.strict_booleans)
+                    && self.cfg.looks_like_non_string(&view.effective, &view.style)
                     && view.tag != SfTag::String
                 {
                     let view = self.take_scalar_view()?;
@@ -1053,6 +1236,7 @@ impl<'de> de::Deserializer<'de> for YamlDeserializer<'de, '_> {
         };
 
         let location = view.location;
+        self.warn_if_octal_string(&view);
         let redaction_ctx = view.redaction_ctx();
         let cannot_borrow_reason = if view.interpolated {
             TransformReason::VariableInterpolation
@@ -1097,14 +1281,14 @@ impl<'de> de::Deserializer<'de> for YamlDeserializer<'de, '_> {
             // If explicitly tagged as null, or plain null-like, this is not a valid String.
             // A ${...} that the user deliberately substituted to "" is a legitimate string.
             if (view.tag == SfTag::Null
-                || (!view.interpolated && scalar_is_nullish(&view.effective, &view.style)))
+                || (!view.interpolated && self.cfg.is_null(&view.effective, &view.style)))
                 && view.tag != SfTag::String
             {
                 // Consume the scalar to anchor the error at the correct location.
                 let (_value, _tag, location) = self.take_scalar_event()?;
                 return Err(Error::NullIntoString { location });
             } else if self.cfg.no_schema
-                && maybe_not_string(&view.effective, &view.style, self.cfg.strict_booleans)
+                && self.cfg.looks_like_non_string(&view.effective, &view.style)
                 && view.tag != SfTag::String
             {
                 // Consume the scalar to anchor the error at the correct location.
@@ -1128,6 +1312,7 @@ impl<'de> de::Deserializer<'de> for YamlDeserializer<'de, '_> {
             return visitor.visit_string(self.take_string_scalar()?);
         };
 
+        self.warn_if_octal_string(&view);
         let redaction_ctx = view.redaction_ctx();
         let view = self.take_peeked_scalar_view(view)?;
         match view.effective {
@@ -1235,7 +1420,7 @@ impl<'de> de::Deserializer<'de> for YamlDeserializer<'de, '_> {
         }
 
         if let Some((value, tag, style, _location)) = self.peek_effective_scalar()?
-            && (tag == SfTag::Null || scalar_is_nullish_for_option(&value, &style))
+            && (tag == SfTag::Null || self.cfg.is_null_for_option(&value, &style))
         {
             let _ = self.ev.next()?; // consume the scalar
             return visitor.visit_none();
@@ -1326,6 +1511,7 @@ impl<'de> de::Deserializer<'de> for YamlDeserializer<'de, '_> {
             // Internal wrapper types use `__yaml_*` names (see `__yaml_rc_anchor`, etc.).
             "__yaml_spanned" => spanned_deser::deserialize_yaml_spanned(self, visitor),
             "__yaml_commented" => commented_deser::deserialize_yaml_commented(self, visitor),
+            "__yaml_raw" => raw_deser::deserialize_yaml_raw(self, visitor),
             "__yaml_rc_anchor" => {
                 let anchor = self.peek_anchor_id()?;
                 anchor_store::with_anchor_context(AnchorKind::Rc, anchor, || {
@@ -1438,6 +1624,19 @@ impl<'de> de::Deserializer<'de> for YamlDeserializer<'de, '_> {
                 return visitor.visit_seq(ByteSeq { data, idx: 0 });
             }
         }
+        match self.ev.peek()? {
+            Some(Ev::MapStart {
+                tag: SfTag::Set, ..
+            }) => return collection_deser::deserialize_yaml_set(self, visitor),
+            Some(Ev::SeqStart {
+                tag: tag @ (SfTag::Omap | SfTag::Pairs),
+                ..
+            }) => {
+                let tag = *tag;
+                return collection_deser::deserialize_yaml_pairs(self, tag, false, visitor);
+            }
+            _ => {}
+        }
         // Comments passed in from the parent value slot belong to the first item
         // of the sequence. If this sequence is reached through a nested alias,
         // alias replay exposes the anchored sequence start here, so the same
@@ -1448,7 +1647,15 @@ impl<'de> de::Deserializer<'de> for YamlDeserializer<'de, '_> {
             Some(ev) => ev.location(),
             None => self.ev.last_location(),
         };
-        let child_cfg = self.cfg.enter_container(seq_location)?;
+        let item_angle_tag = match self.ev.peek()? {
+            Some(Ev::SeqStart {
+                tag: tag @ (SfTag::Degrees | SfTag::Radians),
+                ..
+            }) => *tag,
+            _ => SfTag::None,
+        };
+        let mut child_cfg = self.cfg.enter_container(seq_location)?;
+        child_cfg.item_angle_tag = item_angle_tag;
         self.expect_seq_start()?;
         /// Streaming `SeqAccess` over the underlying `Events`.
         struct SA<'de, 'e> {
@@ -1456,9 +1663,7 @@ impl<'de> de::Deserializer<'de> for YamlDeserializer<'de, '_> {
             cfg: Cfg,
             pending_first_element_comments: Vec<Cow<'de, str>>,
 
-            #[cfg(any(feature = "garde", feature = "validator"))]
             garde: Option<&'e mut PathRecorder>,
-            #[cfg(any(feature = "garde", feature = "validator"))]
             idx: usize,
         }
         impl<'de> de::SeqAccess<'de> for SA<'de, '_> {
@@ -1489,14 +1694,16 @@ impl<'de> de::Deserializer<'de> for YamlDeserializer<'de, '_> {
                 let value_separator_comments = self
                     .ev
                     .take_separator_comments_before_sequence_item_value()?;
+                let idx = self.idx;
+                self.idx += 1;
+                let _warning_path = WarningPathGuard::index(idx, reference_location);
 
-                #[cfg(any(feature = "garde", feature = "validator"))]
                 {
                     if let Some(garde_ref) = self.garde.as_mut() {
                         let recorder: &mut PathRecorder = garde_ref;
 
                         let prev = recorder.current.take();
-                        let now = prev.clone().join(self.idx);
+                        let now = prev.clone().join(idx);
                         recorder.current = now.clone();
                         recorder.map.insert(
                             now,
@@ -1522,7 +1729,6 @@ impl<'de> de::Deserializer<'de> for YamlDeserializer<'de, '_> {
                             });
 
                         recorder.current = prev;
-                        self.idx += 1;
                         return res;
                     }
                 }
@@ -1539,7 +1745,6 @@ impl<'de> de::Deserializer<'de> for YamlDeserializer<'de, '_> {
             }
         }
 
-        #[cfg(any(feature = "garde", feature = "validator"))]
         let garde = self.garde;
 
         let result = visitor.visit_seq(SA {
@@ -1547,9 +1752,7 @@ impl<'de> de::Deserializer<'de> for YamlDeserializer<'de, '_> {
             cfg: child_cfg,
             pending_first_element_comments: seq_start_comments,
 
-            #[cfg(any(feature = "garde", feature = "validator"))]
             garde,
-            #[cfg(any(feature = "garde", feature = "validator"))]
             idx: 0,
         })?;
         drain_remaining_sequence(self.ev)?;
@@ -1618,6 +1821,14 @@ impl<'de> de::Deserializer<'de> for YamlDeserializer<'de, '_> {
             }
             return visitor.visit_map(EmptyMap { location });
         }
+        if let Some(Ev::SeqStart {
+            tag: tag @ (SfTag::Omap | SfTag::Pairs),
+            ..
+        }) = self.ev.peek()?
+        {
+            let tag = *tag;
+            return collection_deser::deserialize_yaml_pairs(self, tag, true, visitor);
+        }
         // Same-line separator comments on the parent field belong to this mapping node.
         // `Commented<Container>` consumes those comments before this point; a plain map
         // must not reattach them to the first child key.
@@ -1632,14 +1843,21 @@ impl<'de> de::Deserializer<'de> for YamlDeserializer<'de, '_> {
             Some(ev) => ev.location(),
             None => self.ev.last_location(),
         };
-        let child_cfg = self.cfg.enter_container(map_location)?;
+        let item_angle_tag = match self.ev.peek()? {
+            Some(Ev::MapStart {
+                tag: tag @ (SfTag::Degrees | SfTag::Radians),
+                ..
+            }) => *tag,
+            _ => SfTag::None,
+        };
+        let mut child_cfg = self.cfg.enter_container(map_location)?;
+        child_cfg.item_angle_tag = item_angle_tag;
         self.expect_map_start()?;
 
         // Ensure "missing field" errors (which have no natural span) get attributed to the
         // current container.
         let _missing_field_guard = MissingFieldLocationGuard::new(self.ev.reference_location());
 
-        #[cfg(any(feature = "garde", feature = "validator"))]
         if let Some(recorder) = self.garde.as_mut() {
             // Record the container itself, not just its leaf scalars, so that missing-field
             // errors can fall back to a parent structure.
@@ -1733,6 +1951,14 @@ impl<'de> de::Deserializer<'de> for YamlDeserializer<'de, '_> {
                     let fingerprint = entry.key.fingerprint().into_owned();
                     if seen.insert(fingerprint) {
                         merge_entries.push(entry);
+                    } else {
+                        warnings::warn(entry.reference_location, || WarningKind::MergeOverride {
+                            key: entry
+                                .key
+                                .fingerprint()
+                                .stringy_scalar_value()
+                                .map(str::to_owned),
+                        });
                     }
                 }
             }
@@ -1752,10 +1978,9 @@ impl<'de> de::Deserializer<'de> for YamlDeserializer<'de, '_> {
             // a useful span even though they are raised outside of this deserializer’s call stack.
             fallback_guard: Option<MissingFieldLocationGuard>,
 
-            #[cfg(any(feature = "garde", feature = "validator"))]
             garde: Option<&'e mut PathRecorder>,
-            #[cfg(any(feature = "garde", feature = "validator"))]
             pending_path_segment: Option<String>,
+            pending_key_location: Location,
 
             // For duplicate-key detection for arbitrary keys.
             seen: HashSet<KeyFingerprint<'de>>,
@@ -1828,13 +2053,7 @@ impl<'de> de::Deserializer<'de> for YamlDeserializer<'de, '_> {
             where
                 K: de::DeserializeSeed<'de2>,
             {
-                let mut replay = ReplayEvents::new(
-                    events,
-                    #[cfg(feature = "properties")]
-                    self.ev.property_map().cloned(),
-                    #[cfg(feature = "properties")]
-                    self.ev.property_syntax(),
-                );
+                let mut replay = ReplayEvents::new(events, ReplayContext::of(self.ev));
 
                 // Get location from replay events for error reporting.
                 let location = replay.reference_location();
@@ -1849,7 +2068,6 @@ impl<'de> de::Deserializer<'de> for YamlDeserializer<'de, '_> {
                     pending_value_separator_comments: Vec::new(),
                     pending_value_comments: Vec::new(),
 
-                    #[cfg(any(feature = "garde", feature = "validator"))]
                     garde: None,
                 };
                 seed.deserialize(de).map_err(|e| {
@@ -1909,6 +2127,9 @@ impl<'de> de::Deserializer<'de> for YamlDeserializer<'de, '_> {
                         let is_duplicate = self.seen.contains(&fingerprint);
                         if self.flushing_merges {
                             if is_duplicate {
+                                warnings::warn(reference_location, || WarningKind::MergeOverride {
+                                    key: fingerprint.stringy_scalar_value().map(str::to_owned),
+                                });
                                 self.validate_skipped_node(&value)?;
                                 continue;
                             }
@@ -1928,7 +2149,11 @@ impl<'de> de::Deserializer<'de> for YamlDeserializer<'de, '_> {
                                         continue;
                                     }
                                 }
-                                DuplicateKeyPolicy::LastWins => {}
+                                DuplicateKeyPolicy::LastWins => {
+                                    if is_duplicate {
+                                        warn_duplicate_override(&fingerprint, location);
+                                    }
+                                }
                             }
                         }
 
@@ -1948,11 +2173,13 @@ impl<'de> de::Deserializer<'de> for YamlDeserializer<'de, '_> {
                                     anchor,
                                     tag,
                                     raw_tag,
+                                    tag_prefix_len,
                                     location,
                                 }) => Ev::MapStart {
                                     anchor: *anchor,
                                     tag: *tag,
                                     raw_tag: raw_tag.clone(),
+                                    tag_prefix_len: *tag_prefix_len,
                                     location: *location,
                                 },
                                 Some(other) => other.clone(),
@@ -1999,10 +2226,10 @@ impl<'de> de::Deserializer<'de> for YamlDeserializer<'de, '_> {
                         self.pending_value_comments = value_comments;
                         self.pending_value = Some((value_events, reference_location));
 
-                        #[cfg(any(feature = "garde", feature = "validator"))]
                         {
                             self.pending_path_segment =
                                 fingerprint.stringy_scalar_value().map(|s| s.to_owned());
+                            self.pending_key_location = location;
                         }
 
                         self.seen.insert(fingerprint);
@@ -2090,7 +2317,14 @@ impl<'de> de::Deserializer<'de> for YamlDeserializer<'de, '_> {
                                         continue;
                                     }
                                 }
-                                DuplicateKeyPolicy::LastWins => {}
+                                DuplicateKeyPolicy::LastWins => {
+                                    if is_duplicate {
+                                        warn_duplicate_override(
+                                            fingerprint.as_ref(),
+                                            key_node.location(),
+                                        );
+                                    }
+                                }
                             }
 
                             // Decide whether we need the slow recorded path (only for the tricky
@@ -2152,10 +2386,10 @@ impl<'de> de::Deserializer<'de> for YamlDeserializer<'de, '_> {
                             self.pending_field_comments = key_comments;
                             self.pending_value = None; // value will be read live
 
-                            #[cfg(any(feature = "garde", feature = "validator"))]
                             {
                                 self.pending_path_segment =
                                     fingerprint.stringy_scalar_value().map(|s| s.to_owned());
+                                self.pending_key_location = location;
                             }
 
                             self.seen.insert(fingerprint);
@@ -2178,8 +2412,9 @@ impl<'de> de::Deserializer<'de> for YamlDeserializer<'de, '_> {
                 }
                 self.have_key = false;
 
-                #[cfg(any(feature = "garde", feature = "validator"))]
                 let pending_segment = self.pending_path_segment.take();
+                let _warning_path =
+                    WarningPathGuard::key(pending_segment.as_deref(), self.pending_key_location);
 
                 let field_comments = std::mem::take(&mut self.pending_field_comments);
                 let mut value_separator_comments =
@@ -2191,10 +2426,7 @@ impl<'de> de::Deserializer<'de> for YamlDeserializer<'de, '_> {
                     let mut replay = ReplayEvents::with_reference(
                         events,
                         reference_location,
-                        #[cfg(feature = "properties")]
-                        self.ev.property_map().cloned(),
-                        #[cfg(feature = "properties")]
-                        self.ev.property_syntax(),
+                        ReplayContext::of(self.ev),
                     );
 
                     // Definition-site location: where the node is defined in the YAML.
@@ -2204,7 +2436,6 @@ impl<'de> de::Deserializer<'de> for YamlDeserializer<'de, '_> {
                         None => replay.last_location(),
                     };
 
-                    #[cfg(any(feature = "garde", feature = "validator"))]
                     {
                         if let (Some(seg), Some(garde_ref)) = (pending_segment, self.garde.as_mut())
                         {
@@ -2265,7 +2496,6 @@ impl<'de> de::Deserializer<'de> for YamlDeserializer<'de, '_> {
 
                     let reference_location = self.ev.reference_location();
 
-                    #[cfg(any(feature = "garde", feature = "validator"))]
                     {
                         if let (Some(seg), Some(garde_ref)) = (pending_segment, self.garde.as_mut())
                         {
@@ -2330,7 +2560,6 @@ impl<'de> de::Deserializer<'de> for YamlDeserializer<'de, '_> {
                 (VecDeque::new(), map_start_comments, false)
             };
 
-        #[cfg(any(feature = "garde", feature = "validator"))]
         let garde = self.garde;
 
         visitor.visit_map(MA {
@@ -2340,10 +2569,9 @@ impl<'de> de::Deserializer<'de> for YamlDeserializer<'de, '_> {
 
             fallback_guard: None,
 
-            #[cfg(any(feature = "garde", feature = "validator"))]
             garde,
-            #[cfg(any(feature = "garde", feature = "validator"))]
             pending_path_segment: None,
+            pending_key_location: Location::UNKNOWN,
 
             seen: HashSet::with_capacity(8),
             pending,
@@ -2403,10 +2631,11 @@ impl<'de> de::Deserializer<'de> for YamlDeserializer<'de, '_> {
                 style,
                 value: _,
                 raw_tag,
+                tag_prefix_len,
                 location,
                 ..
             }) => {
-                if let Some(tag_name) = simple_tagged_enum_name(&raw_tag, &tag) {
+                if let Some(tag_name) = simple_tagged_enum_name(&raw_tag, tag_prefix_len, &tag) {
                     tagged_enum = Some((tag_name, location));
                 }
                 let Some(view) = self.peek_scalar_view()? else {
@@ -2414,7 +2643,7 @@ impl<'de> de::Deserializer<'de> for YamlDeserializer<'de, '_> {
                 };
                 if self.cfg.no_schema
                     && tag != SfTag::String
-                    && maybe_not_string(&view.effective, &style, self.cfg.strict_booleans)
+                    && self.cfg.looks_like_non_string(&view.effective, &style)
                 {
                     let view = self.take_scalar_view()?;
                     return Err(Self::quoting_required_for_scalar(&view));
@@ -2445,6 +2674,7 @@ impl<'de> de::Deserializer<'de> for YamlDeserializer<'de, '_> {
                                     value,
                                     tag: SfTag::None,
                                     raw_tag: None,
+                                    tag_prefix_len: 0,
                                     style,
                                     location,
                                     anchor,
@@ -2466,6 +2696,7 @@ impl<'de> de::Deserializer<'de> for YamlDeserializer<'de, '_> {
             Some(Ev::MapStart {
                 tag,
                 raw_tag,
+                tag_prefix_len,
                 location,
                 ..
             }) => {
@@ -2474,10 +2705,11 @@ impl<'de> de::Deserializer<'de> for YamlDeserializer<'de, '_> {
                 // a renamed `map` variant. Resolved core forms (`!!map`, verbatim tags,
                 // and directive-expanded handles) have a different `raw_tag` and remain
                 // YAML type annotations.
-                let tag_name = simple_tagged_enum_name(&raw_tag, &tag).or_else(|| {
-                    (tag == SfTag::Map && raw_tag.as_deref() == Some("!map"))
-                        .then(|| "map".to_owned())
-                });
+                let tag_name =
+                    simple_tagged_enum_name(&raw_tag, tag_prefix_len, &tag).or_else(|| {
+                        (tag == SfTag::Map && raw_tag.as_deref() == Some("!map"))
+                            .then(|| "map".to_owned())
+                    });
                 if let Some(tag_name) = tag_name
                     && variants.contains(&tag_name.as_str())
                 {
@@ -2502,7 +2734,7 @@ impl<'de> de::Deserializer<'de> for YamlDeserializer<'de, '_> {
                 if let Some(view) = key_de.peek_scalar_view()? {
                     if self.cfg.no_schema
                         && view.tag != SfTag::String
-                        && maybe_not_string(&view.raw, &view.style, self.cfg.strict_booleans)
+                        && self.cfg.looks_like_non_string(&view.raw, &view.style)
                     {
                         let view = key_de.take_scalar_view()?;
                         return Err(Self::quoting_required_for_scalar(&view));
@@ -2523,10 +2755,11 @@ impl<'de> de::Deserializer<'de> for YamlDeserializer<'de, '_> {
             Some(Ev::SeqStart {
                 tag,
                 raw_tag,
+                tag_prefix_len,
                 location,
                 ..
             }) => {
-                if let Some(tag_name) = simple_tagged_enum_name(&raw_tag, &tag)
+                if let Some(tag_name) = simple_tagged_enum_name(&raw_tag, tag_prefix_len, &tag)
                     && variants.contains(&tag_name.as_str())
                 {
                     // Consume the SeqStart, collect all events until SeqEnd, replay as untagged sequence
@@ -2544,6 +2777,7 @@ impl<'de> de::Deserializer<'de> for YamlDeserializer<'de, '_> {
                             anchor,
                             tag: SfTag::None,
                             raw_tag: None,
+                            tag_prefix_len: 0,
                             location,
                         });
                     }
@@ -2564,13 +2798,8 @@ impl<'de> de::Deserializer<'de> for YamlDeserializer<'de, '_> {
                             None => return Err(eof_with_loc(self.ev)),
                         }
                     }
-                    let replay = Box::new(ReplayEvents::new(
-                        replay_events,
-                        #[cfg(feature = "properties")]
-                        self.ev.property_map().cloned(),
-                        #[cfg(feature = "properties")]
-                        self.ev.property_syntax(),
-                    ));
+                    let replay =
+                        Box::new(ReplayEvents::new(replay_events, ReplayContext::of(self.ev)));
                     return visitor.visit_enum(TaggedEA {
                         replay,
                         cfg: self.cfg,
@@ -2834,13 +3063,7 @@ impl<'de> de::Deserializer<'de> for YamlDeserializer<'de, '_> {
                 tagged_payload: false,
             },
             Mode::TaggedNewtype(variant, replay_buf) => {
-                let replay = Box::new(ReplayEvents::new(
-                    replay_buf,
-                    #[cfg(feature = "properties")]
-                    self.ev.property_map().cloned(),
-                    #[cfg(feature = "properties")]
-                    self.ev.property_syntax(),
-                ));
+                let replay = Box::new(ReplayEvents::new(replay_buf, ReplayContext::of(self.ev)));
                 // We need to use a replay source for the payload
                 return visitor.visit_enum(TaggedEA {
                     replay,
@@ -2864,18 +3087,18 @@ impl<'de> de::Deserializer<'de> for YamlDeserializer<'de, '_> {
     /// requires `serde::de::IgnoredAny` at the call site.
     fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
         // Delegate to `any`—callers that truly want to ignore should request `IgnoredAny`.
-        self.deserialize_any(visitor)
+        warnings::ignoring(|| self.deserialize_any(visitor))
     }
 }
 
 #[cfg(all(test, feature = "properties"))]
 mod tests {
-    use super::super::options::{Options, PropertySyntax};
+    use super::super::options::Options;
     use super::*;
 
     #[test]
     fn effective_scalar_value_without_property_map_returns_original_scalar() {
-        let mut events = ReplayEvents::new(Vec::new(), None, PropertySyntax::Braced);
+        let mut events = ReplayEvents::new(Vec::new(), ReplayContext::default());
         let de = YamlDeserializer::new(&mut events, Cfg::from_options(&Options::default()));
 
         let value = de
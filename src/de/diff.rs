@@ -0,0 +1,612 @@
+//! Semantic diff between two YAML documents.
+//!
+//! Both inputs are read the same way [`crate::from_str_with_options`] reads them, so aliases,
+//! merge keys and `!include`s are resolved and formatting, quoting and comments do not matter.
+//! Differences are reported by key path, each with its [`Location`] in both inputs:
+//!
+//! ```rust
+//! use serde_saphyr::diff::{self, ChangeKind};
+//!
+//! let old = "name: demo\nport: 80\ndefaults: &d {retries: 3}\nservice: *d\n";
+//! let new = "# reformatted\nname: 'demo'\nport: 8080\nservice: {retries: 3}\n";
+//!
+//! let changes = diff::diff(old, new)?;
+//! assert_eq!(changes.len(), 2);
+//! assert_eq!(changes[0].kind, ChangeKind::Changed);
+//! assert_eq!(changes[0].to_string(), "changed port: 80 -> 8080");
+//! assert_eq!(changes[0].old_location.unwrap().line(), 2);
+//! assert_eq!(changes[0].new_location.unwrap().line(), 3);
+//! assert_eq!(changes[1].to_string(), "removed defaults: {retries: 3}");
+//! # Ok::<(), serde_saphyr::Error>(())
+//! ```
+//!
+//! Sequence items are matched by value, so inserting an item reports one addition rather than
+//! a change of every following item.
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use serde_core::de::{self, Deserialize, Deserializer, MapAccess, SeqAccess, Visitor};
+
+use super::path_map::PathKey;
+use super::source_map::{SourceMap, from_str_with_options_and_source_map};
+use crate::de::{Error, Options};
+use crate::location::Location;
+
+/// Sequences with more item pairs than this are compared by index instead of by value.
+const MAX_MATCHED_PAIRS: usize = 1 << 22;
+
+/// What happened to the value at a [`Change::path`].
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeKind {
+    /// The value exists only in the new document.
+    Added,
+    /// The value exists only in the old document.
+    Removed,
+    /// The value exists in both documents but differs.
+    Changed,
+    /// The value is equal in both documents but moved within its mapping or sequence.
+    Reordered {
+        /// Position in the old mapping or sequence.
+        old_index: usize,
+        /// Position in the new mapping or sequence.
+        new_index: usize,
+    },
+}
+
+/// One semantic difference found by [`diff`].
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Change {
+    /// What changed.
+    pub kind: ChangeKind,
+    /// Path of the value in the new document, or in the old one for removals.
+    pub path: PathKey,
+    /// Old value in compact flow style, unless [`ChangeKind::Added`] or
+    /// [`ChangeKind::Reordered`].
+    pub old_value: Option<String>,
+    /// New value in compact flow style, unless [`ChangeKind::Removed`] or
+    /// [`ChangeKind::Reordered`].
+    pub new_value: Option<String>,
+    /// Location of the value in the old document. For additions, the location of the
+    /// mapping or sequence it was added to.
+    pub old_location: Option<Location>,
+    /// Location of the value in the new document. For removals, the location of the
+    /// mapping or sequence it was removed from.
+    pub new_location: Option<Location>,
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let old = self.old_value.as_deref().unwrap_or_default();
+        let new = self.new_value.as_deref().unwrap_or_default();
+        match self.kind {
+            ChangeKind::Added => write!(f, "added {}: {new}", self.path),
+            ChangeKind::Removed => write!(f, "removed {}: {old}", self.path),
+            ChangeKind::Changed => write!(f, "changed {}: {old} -> {new}", self.path),
+            ChangeKind::Reordered {
+                old_index,
+                new_index,
+            } => write!(
+                f,
+                "reordered {}: position {old_index} -> {new_index}",
+                self.path
+            ),
+        }
+    }
+}
+
+/// A parsed YAML document that can be compared with another one.
+#[derive(Debug, Clone)]
+pub struct Document {
+    root: Node,
+    source_map: SourceMap,
+}
+
+/// Parses a single YAML document with default [`Options`].
+impl std::str::FromStr for Document {
+    type Err = Error;
+
+    fn from_str(input: &str) -> Result<Self, Error> {
+        Self::from_str_with_options(input, Options::default())
+    }
+}
+
+impl Document {
+    /// Parse a single YAML document. `options` control merge keys, includes, budgets
+    /// and the other settings used by [`crate::from_str_with_options`].
+    pub fn from_str_with_options(input: &str, options: Options) -> Result<Self, Error> {
+        let (root, source_map) = from_str_with_options_and_source_map(input, options)?;
+        Ok(Self { root, source_map })
+    }
+
+    /// Differences from this (old) document to `new`.
+    #[must_use]
+    pub fn diff(&self, new: &Document) -> Vec<Change> {
+        let mut differ = Differ {
+            old: &self.source_map,
+            new: &new.source_map,
+            changes: Vec::new(),
+        };
+        differ.node(&self.root, &new.root, &PathKey::new(), &PathKey::new());
+        differ.changes
+    }
+}
+
+/// Differences from the `old` YAML document to the `new` one.
+pub fn diff(old: &str, new: &str) -> Result<Vec<Change>, Error> {
+    diff_with_options(old, new, Options::default())
+}
+
+/// Like [`diff`], reading both documents with `options`.
+pub fn diff_with_options(old: &str, new: &str, options: Options) -> Result<Vec<Change>, Error> {
+    let old = Document::from_str_with_options(old, options.clone())?;
+    let new = Document::from_str_with_options(new, options)?;
+    Ok(old.diff(&new))
+}
+
+/// A deserialized value with mapping keys in document order.
+#[derive(Debug, Clone)]
+enum Node {
+    Null,
+    Bool(bool),
+    Int(i128),
+    Float(f64),
+    String(String),
+    Bytes(Vec<u8>),
+    Sequence(Vec<Node>),
+    Mapping(Vec<(String, Node)>),
+}
+
+impl Node {
+    fn get(&self, key: &str) -> Option<&Node> {
+        match self {
+            Node::Mapping(entries) => entries.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+}
+
+/// Equal values; mappings compare regardless of key order and `.nan` equals itself.
+impl PartialEq for Node {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Node::Null, Node::Null) => true,
+            (Node::Bool(a), Node::Bool(b)) => a == b,
+            (Node::Int(a), Node::Int(b)) => a == b,
+            (Node::Float(a), Node::Float(b)) => a == b || (a.is_nan() && b.is_nan()),
+            (Node::String(a), Node::String(b)) => a == b,
+            (Node::Bytes(a), Node::Bytes(b)) => a == b,
+            (Node::Sequence(a), Node::Sequence(b)) => a == b,
+            (Node::Mapping(a), Node::Mapping(b)) => {
+                a.len() == b.len() && a.iter().all(|(key, value)| other.get(key) == Some(value))
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Compact flow-style rendering, such as `{host: a, ports: [80, 443]}`.
+impl fmt::Display for Node {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Node::Null => f.write_str("null"),
+            Node::Bool(v) => write!(f, "{v}"),
+            Node::Int(v) => write!(f, "{v}"),
+            Node::Float(v) if v.is_nan() => f.write_str(".nan"),
+            Node::Float(v) if v.is_infinite() => {
+                f.write_str(if *v > 0.0 { ".inf" } else { "-.inf" })
+            }
+            Node::Float(v) => write!(f, "{v:?}"),
+            Node::String(v) => write_str(f, v),
+            Node::Bytes(v) => write!(f, "!!binary <{} bytes>", v.len()),
+            Node::Sequence(items) => {
+                f.write_str("[")?;
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "{item}")?;
+                }
+                f.write_str("]")
+            }
+            Node::Mapping(entries) => {
+                f.write_str("{")?;
+                for (i, (key, value)) in entries.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write_str(f, key)?;
+                    write!(f, ": {value}")?;
+                }
+                f.write_str("}")
+            }
+        }
+    }
+}
+
+/// Write a string plain when it reads back as the same string, double-quoted otherwise.
+fn write_str(f: &mut fmt::Formatter<'_>, value: &str) -> fmt::Result {
+    let plain = value
+        .chars()
+        .next()
+        .is_some_and(|first| !"-?:,[]{}#&*!|>'\"%@`".contains(first) && !first.is_whitespace())
+        && !value.ends_with(char::is_whitespace)
+        && !value.contains([':', '#', ',', '[', ']', '{', '}'])
+        && !value.chars().any(char::is_control)
+        && crate::Schema::Core.is_string(value);
+    if plain {
+        f.write_str(value)
+    } else {
+        write!(f, "{value:?}")
+    }
+}
+
+impl<'de> Deserialize<'de> for Node {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct NodeVisitor;
+
+        impl<'de> Visitor<'de> for NodeVisitor {
+            type Value = Node;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("any YAML value")
+            }
+
+            fn visit_unit<E: de::Error>(self) -> Result<Node, E> {
+                Ok(Node::Null)
+            }
+
+            fn visit_none<E: de::Error>(self) -> Result<Node, E> {
+                Ok(Node::Null)
+            }
+
+            fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<Node, D::Error> {
+                Node::deserialize(deserializer)
+            }
+
+            fn visit_newtype_struct<D: Deserializer<'de>>(
+                self,
+                deserializer: D,
+            ) -> Result<Node, D::Error> {
+                Node::deserialize(deserializer)
+            }
+
+            fn visit_bool<E: de::Error>(self, v: bool) -> Result<Node, E> {
+                Ok(Node::Bool(v))
+            }
+
+            fn visit_i64<E: de::Error>(self, v: i64) -> Result<Node, E> {
+                Ok(Node::Int(v.into()))
+            }
+
+            fn visit_u64<E: de::Error>(self, v: u64) -> Result<Node, E> {
+                Ok(Node::Int(v.into()))
+            }
+
+            fn visit_i128<E: de::Error>(self, v: i128) -> Result<Node, E> {
+                Ok(Node::Int(v))
+            }
+
+            fn visit_u128<E: de::Error>(self, v: u128) -> Result<Node, E> {
+                Ok(i128::try_from(v).map_or(Node::Float(v as f64), Node::Int))
+            }
+
+            fn visit_f64<E: de::Error>(self, v: f64) -> Result<Node, E> {
+                Ok(Node::Float(v))
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<Node, E> {
+                Ok(Node::String(v.to_owned()))
+            }
+
+            fn visit_string<E: de::Error>(self, v: String) -> Result<Node, E> {
+                Ok(Node::String(v))
+            }
+
+            fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Node, E> {
+                Ok(Node::Bytes(v.to_vec()))
+            }
+
+            fn visit_byte_buf<E: de::Error>(self, v: Vec<u8>) -> Result<Node, E> {
+                Ok(Node::Bytes(v))
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Node, A::Error> {
+                let mut items = Vec::new();
+                while let Some(item) = seq.next_element()? {
+                    items.push(item);
+                }
+                Ok(Node::Sequence(items))
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Node, A::Error> {
+                let mut entries = Vec::new();
+                while let Some(KeyText(key)) = map.next_key()? {
+                    entries.push((key, map.next_value()?));
+                }
+                Ok(Node::Mapping(entries))
+            }
+        }
+
+        deserializer.deserialize_any(NodeVisitor)
+    }
+}
+
+/// A scalar mapping key, as written in the document. This is also the spelling used by
+/// the [`SourceMap`] paths.
+struct KeyText(String);
+
+impl<'de> Deserialize<'de> for KeyText {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct KeyVisitor;
+
+        impl Visitor<'_> for KeyVisitor {
+            type Value = KeyText;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a scalar mapping key")
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<KeyText, E> {
+                Ok(KeyText(v.to_owned()))
+            }
+
+            fn visit_string<E: de::Error>(self, v: String) -> Result<KeyText, E> {
+                Ok(KeyText(v))
+            }
+        }
+
+        deserializer.deserialize_str(KeyVisitor)
+    }
+}
+
+struct Differ<'a> {
+    old: &'a SourceMap,
+    new: &'a SourceMap,
+    changes: Vec<Change>,
+}
+
+impl Differ<'_> {
+    fn push(
+        &mut self,
+        kind: ChangeKind,
+        old: Option<(&PathKey, &Node)>,
+        new: Option<(&PathKey, &Node)>,
+        parent: (&PathKey, &PathKey),
+    ) {
+        let old_location = old.map_or(parent.0, |(path, _)| path);
+        let new_location = new.map_or(parent.1, |(path, _)| path);
+        let values = !matches!(kind, ChangeKind::Reordered { .. });
+        self.changes.push(Change {
+            kind,
+            path: new
+                .or(old)
+                .map(|(path, _)| path.clone())
+                .unwrap_or_default(),
+            old_value: old.filter(|_| values).map(|(_, node)| node.to_string()),
+            new_value: new.filter(|_| values).map(|(_, node)| node.to_string()),
+            old_location: location(self.old, old_location),
+            new_location: location(self.new, new_location),
+        });
+    }
+
+    fn node(&mut self, old: &Node, new: &Node, old_path: &PathKey, new_path: &PathKey) {
+        match (old, new) {
+            (Node::Mapping(old_entries), Node::Mapping(new_entries)) => {
+                self.mapping(old_entries, new_entries, old_path, new_path);
+            }
+            (Node::Sequence(old_items), Node::Sequence(new_items)) => {
+                self.sequence(old_items, new_items, old_path, new_path);
+            }
+            _ if old == new => {}
+            _ => self.push(
+                ChangeKind::Changed,
+                Some((old_path, old)),
+                Some((new_path, new)),
+                (old_path, new_path),
+            ),
+        }
+    }
+
+    fn mapping(
+        &mut self,
+        old: &[(String, Node)],
+        new: &[(String, Node)],
+        old_path: &PathKey,
+        new_path: &PathKey,
+    ) {
+        let parents = (old_path, new_path);
+        let old_index: HashMap<&str, usize> = old
+            .iter()
+            .enumerate()
+            .map(|(i, (key, _))| (key.as_str(), i))
+            .collect();
+        let new_keys: HashSet<&str> = new.iter().map(|(key, _)| key.as_str()).collect();
+
+        // Keys present in both mappings, as (old index, new index) in new order.
+        let common: Vec<(usize, usize)> = new
+            .iter()
+            .enumerate()
+            .filter_map(|(j, (key, _))| old_index.get(key.as_str()).map(|&i| (i, j)))
+            .collect();
+        let old_order: Vec<usize> = common.iter().map(|(i, _)| *i).collect();
+        let mut in_place = vec![false; common.len()];
+        for position in longest_increasing_subsequence(&old_order) {
+            in_place[position] = true;
+        }
+
+        for (n, (i, j)) in common.iter().copied().enumerate() {
+            let (key, old_value) = &old[i];
+            let new_value = &new[j].1;
+            let old_child = old_path.clone().join_key(key.as_str());
+            let new_child = new_path.clone().join_key(key.as_str());
+            if !in_place[n] {
+                self.push(
+                    ChangeKind::Reordered {
+                        old_index: i,
+                        new_index: j,
+                    },
+                    Some((&old_child, old_value)),
+                    Some((&new_child, new_value)),
+                    parents,
+                );
+            }
+            self.node(old_value, new_value, &old_child, &new_child);
+        }
+
+        for (key, value) in new {
+            if !old_index.contains_key(key.as_str()) {
+                let child = new_path.clone().join_key(key.as_str());
+                self.push(ChangeKind::Added, None, Some((&child, value)), parents);
+            }
+        }
+        for (key, value) in old {
+            if !new_keys.contains(key.as_str()) {
+                let child = old_path.clone().join_key(key.as_str());
+                self.push(ChangeKind::Removed, Some((&child, value)), None, parents);
+            }
+        }
+    }
+
+    fn sequence(&mut self, old: &[Node], new: &[Node], old_path: &PathKey, new_path: &PathKey) {
+        let parents = (old_path, new_path);
+
+        // Items equal in both sequences and in the same relative order stay in place.
+        let by_value = old.len().saturating_mul(new.len()) <= MAX_MATCHED_PAIRS;
+        let pairs = if by_value {
+            longest_common_subsequence(old, new, |a, b| a == b)
+        } else {
+            Vec::new()
+        };
+        let mut old_free = vec![true; old.len()];
+        let mut new_free = vec![true; new.len()];
+        for &(i, j) in &pairs {
+            old_free[i] = false;
+            new_free[j] = false;
+        }
+
+        let mut pairs: Vec<(usize, usize, bool)> =
+            pairs.into_iter().map(|(i, j)| (i, j, false)).collect();
+
+        // Equal items elsewhere in the sequence have moved, unless they kept their index.
+        // Above the limit this search is skipped too, so items are compared by index.
+        if by_value {
+            for j in 0..new.len() {
+                if !new_free[j] {
+                    continue;
+                }
+                if let Some(i) = (0..old.len()).find(|&i| old_free[i] && old[i] == new[j]) {
+                    old_free[i] = false;
+                    new_free[j] = false;
+                    pairs.push((i, j, i != j));
+                }
+            }
+        }
+
+        // The remaining items are changed in place, pairing them in order.
+        let old_rest: Vec<usize> = (0..old.len()).filter(|&i| old_free[i]).collect();
+        let new_rest: Vec<usize> = (0..new.len()).filter(|&j| new_free[j]).collect();
+        pairs.extend(old_rest.iter().zip(&new_rest).map(|(&i, &j)| (i, j, false)));
+        pairs.sort_unstable_by_key(|&(_, j, _)| j);
+
+        for (i, j, moved) in pairs {
+            let old_child = old_path.clone().join_index(i);
+            let new_child = new_path.clone().join_index(j);
+            if moved {
+                self.push(
+                    ChangeKind::Reordered {
+                        old_index: i,
+                        new_index: j,
+                    },
+                    Some((&old_child, &old[i])),
+                    Some((&new_child, &new[j])),
+                    parents,
+                );
+            }
+            self.node(&old[i], &new[j], &old_child, &new_child);
+        }
+
+        let paired = old_rest.len().min(new_rest.len());
+        for &j in &new_rest[paired..] {
+            let child = new_path.clone().join_index(j);
+            self.push(ChangeKind::Added, None, Some((&child, &new[j])), parents);
+        }
+        for &i in &old_rest[paired..] {
+            let child = old_path.clone().join_index(i);
+            self.push(ChangeKind::Removed, Some((&child, &old[i])), None, parents);
+        }
+    }
+}
+
+fn location(source_map: &SourceMap, path: &PathKey) -> Option<Location> {
+    source_map
+        .get(path)
+        .map(|locations| locations.reference_location)
+        .filter(|location| *location != Location::UNKNOWN)
+}
+
+/// Positions of a longest strictly increasing subsequence of `values`, in order.
+///
+/// Patience sorting: `tails[len]` is the position of the smallest value ending an increasing
+/// run of `len + 1` values, so each value costs one binary search.
+fn longest_increasing_subsequence(values: &[usize]) -> Vec<usize> {
+    let mut tails: Vec<usize> = Vec::new();
+    let mut previous = vec![usize::MAX; values.len()];
+    for (position, &value) in values.iter().enumerate() {
+        let len = tails.partition_point(|&tail| values[tail] < value);
+        if len > 0 {
+            previous[position] = tails[len - 1];
+        }
+        if len == tails.len() {
+            tails.push(position);
+        } else {
+            tails[len] = position;
+        }
+    }
+
+    let mut positions = Vec::with_capacity(tails.len());
+    let mut position = tails.last().copied().unwrap_or(usize::MAX);
+    while position != usize::MAX {
+        positions.push(position);
+        position = previous[position];
+    }
+    positions.reverse();
+    positions
+}
+
+/// Index pairs of a longest common subsequence of `a` and `b`, in order.
+fn longest_common_subsequence<T>(
+    a: &[T],
+    b: &[T],
+    eq: impl Fn(&T, &T) -> bool,
+) -> Vec<(usize, usize)> {
+    let width = b.len() + 1;
+    // lengths[i * width + j]: LCS length of a[i..] and b[j..].
+    let mut lengths = vec![0usize; (a.len() + 1) * width];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            lengths[i * width + j] = if eq(&a[i], &b[j]) {
+                lengths[(i + 1) * width + j + 1] + 1
+            } else {
+                lengths[(i + 1) * width + j].max(lengths[i * width + j + 1])
+            };
+        }
+    }
+
+    let mut pairs = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() && j < b.len() {
+        if eq(&a[i], &b[j]) {
+            pairs.push((i, j));
+            i += 1;
+            j += 1;
+        } else if lengths[(i + 1) * width + j] > lengths[i * width + j + 1] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    pairs
+}
@@ -1,5 +1,6 @@
 use crate::Location;
 use crate::budget::BudgetBreach;
+use crate::cancellation::Interruption;
 use crate::de_snippet::{
     fmt_snippet_window_offset_or_fallback, snippet_window_frame_prefix_offset,
 };
@@ -15,7 +16,6 @@ use crate::properties_redaction::{
     redact_custom_message, redact_dynamic_identifier, redact_dynamic_value,
 };
 use crate::tags::SfTag;
-#[cfg(any(feature = "garde", feature = "validator"))]
 use crate::{
     localizer::ExternalMessage,
     path_map::{PathKey, PathMap, format_path_with_resolved_leaf},
@@ -27,7 +27,7 @@ use std::borrow::Cow;
 use std::cell::Cell;
 use std::fmt;
 
-#[cfg(all(feature = "properties", any(feature = "garde", feature = "validator")))]
+#[cfg(feature = "properties")]
 use crate::properties_redaction::{redact_with_ctxs, with_interp_redaction};
 
 #[cfg(feature = "validator")]
@@ -98,6 +98,14 @@ pub trait MessageFormatter {
     /// The returned string should NOT include location suffixes like
     /// `"at line X, column Y"`; those are added by the renderer.
     fn format_message<'a>(&self, err: &'a Error) -> Cow<'a, str>;
+
+    /// Return the message text for a non-fatal [`crate::Warning`].
+    ///
+    /// Like [`MessageFormatter::format_message`], the text should not include a location
+    /// suffix. The default is [`crate::Warning::default_message`].
+    fn format_warning<'a>(&self, warning: &'a crate::Warning) -> Cow<'a, str> {
+        crate::warnings::default_warning_message(warning)
+    }
 }
 
 /// User-facing message formatter.
@@ -124,6 +132,38 @@ pub enum SnippetMode {
     Off,
 }
 
+/// Output format of rendered errors and warnings.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RenderTarget {
+    /// Plain text without escape sequences.
+    #[default]
+    Plain,
+    /// Text with ANSI color escape sequences, for terminals.
+    ///
+    /// Falls back to [`RenderTarget::Plain`] when the `NO_COLOR` environment variable is set
+    /// to a non-empty value (see <https://no-color.org>).
+    Ansi,
+    /// An HTML fragment: a `<pre class="serde-saphyr-diagnostic">` element with the source
+    /// text escaped and snippet parts wrapped in `<span>`s with classes such as `error`,
+    /// `warning`, `note`, `line-num` and `emphasis` for styling.
+    Html,
+}
+
+impl RenderTarget {
+    /// The target actually used for rendering, after honoring `NO_COLOR`.
+    pub(crate) fn resolve(self) -> Self {
+        match self {
+            RenderTarget::Ansi
+                if std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty()) =>
+            {
+                RenderTarget::Plain
+            }
+            other => other,
+        }
+    }
+}
+
 /// Options for deferred error rendering.
 ///
 /// Prefer constructing this via the [`render_options!`](crate::render_options!) macro
@@ -153,6 +193,11 @@ pub struct RenderOptions<'a> {
     pub formatter: &'a dyn MessageFormatter,
     /// Snippet rendering mode.
     pub snippets: SnippetMode,
+    /// Prefix each error message with its stable [`Error::code`], as in `[SY0101] duplicate ...`.
+    /// Warnings have no codes and are not affected.
+    pub error_codes: bool,
+    /// Output format: plain text, ANSI-colored text or HTML.
+    pub target: RenderTarget,
 }
 
 impl Default for RenderOptions<'_> {
@@ -172,12 +217,16 @@ impl<'a> RenderOptions<'a> {
     ///
     /// Defaults:
     /// - `snippets`: [`SnippetMode::Auto`]
+    /// - `error_codes`: `false`
+    /// - `target`: [`RenderTarget::Plain`]
     #[inline]
     #[must_use]
     pub fn new(formatter: &'a dyn MessageFormatter) -> Self {
         Self {
             formatter,
             snippets: SnippetMode::Auto,
+            error_codes: false,
+            target: RenderTarget::Plain,
         }
     }
 }
@@ -337,7 +386,6 @@ fn push_regions_for_locations(
     }
 }
 
-#[cfg(any(feature = "garde", feature = "validator"))]
 fn push_validation_issue_regions(
     regions: &mut Vec<CroppedRegion>,
     issues: &[ValidationIssue],
@@ -366,7 +414,6 @@ fn collect_snippet_regions(
 
     // Validation errors may contain multiple independent issue locations; pre-crop
     // one region per issue so we can later pick the region that covers the issue.
-    #[cfg(any(feature = "garde", feature = "validator"))]
     if let Error::ValidationError {
         issues, locations, ..
     } = inner
@@ -395,8 +442,7 @@ fn collect_snippet_regions(
     regions
 }
 
-#[cfg(any(feature = "garde", feature = "validator"))]
-/// A structured issue reported by a validation library.
+/// A structured issue reported by a validation library or a custom validation hook.
 ///
 /// Use [`ValidationIssue::new`] to construct synthetic issues when testing custom
 /// formatters or localizers.
@@ -413,25 +459,28 @@ pub struct ValidationIssue {
     pub params: Vec<(String, String)>,
 }
 
-#[cfg(any(feature = "garde", feature = "validator"))]
 #[non_exhaustive]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ValidationSource {
     Garde,
     Validator,
+    /// Issues reported through [`crate::ValidationContext`] by a custom validation hook.
+    Custom,
+    /// Keys skipped by the target type under [`crate::UnknownKeyPolicy::Error`].
+    UnknownKeys,
 }
 
-#[cfg(any(feature = "garde", feature = "validator"))]
 impl ValidationSource {
     pub(crate) fn external_message_source(self) -> ExternalMessageSource {
         match self {
             ValidationSource::Garde => ExternalMessageSource::Garde,
             ValidationSource::Validator => ExternalMessageSource::Validator,
+            ValidationSource::Custom => ExternalMessageSource::Custom,
+            ValidationSource::UnknownKeys => ExternalMessageSource::UnknownKeys,
         }
     }
 }
 
-#[cfg(any(feature = "garde", feature = "validator"))]
 impl ValidationIssue {
     /// Construct a validation issue without a message or structured parameters.
     #[must_use]
@@ -497,7 +546,7 @@ impl ValidationIssue {
     }
 }
 
-#[cfg(all(feature = "properties", any(feature = "garde", feature = "validator")))]
+#[cfg(feature = "properties")]
 fn replace_known_effectives(
     mut text: String,
     ctxs: &[crate::properties_redaction::ScalarRedactionCtx],
@@ -518,7 +567,7 @@ fn replace_known_effectives(
     text
 }
 
-#[cfg(all(feature = "properties", any(feature = "garde", feature = "validator")))]
+#[cfg(feature = "properties")]
 pub(crate) fn redact_issue(mut issue: ValidationIssue) -> ValidationIssue {
     with_interp_redaction(|pairs| {
         if pairs.is_empty() {
@@ -540,10 +589,7 @@ pub(crate) fn redact_issue(mut issue: ValidationIssue) -> ValidationIssue {
     })
 }
 
-#[cfg(all(
-    not(feature = "properties"),
-    any(feature = "garde", feature = "validator")
-))]
+#[cfg(not(feature = "properties"))]
 pub(crate) fn redact_issue(issue: ValidationIssue) -> ValidationIssue {
     issue
 }
@@ -902,6 +948,32 @@ pub enum Error {
         location: Location,
     },
 
+    /// An `!!omap` lists the same key twice.
+    ///
+    /// When the duplicate key can be rendered as a string-like scalar, `key` is provided.
+    DuplicateOmapKey {
+        key: Option<String>,
+        location: Location,
+    },
+
+    /// A `!!set` entry has a value other than null.
+    SetValueNotNull {
+        location: Location,
+    },
+
+    /// An entry of an `!!omap` or `!!pairs` sequence is not a mapping with exactly one key.
+    InvalidPairsEntry {
+        /// The collection tag, `!!omap` or `!!pairs`.
+        tag: &'static str,
+        location: Location,
+    },
+
+    /// A `RawYaml` field was read from input whose source text is not available,
+    /// such as a reader, an included file or a buffered value.
+    RawYamlUnavailable {
+        location: Location,
+    },
+
     /// Tagged enum name does not match the target enum.
     TaggedEnumMismatch {
         tagged: String,
@@ -983,11 +1055,31 @@ pub enum Error {
         message: String,
         location: Location,
     },
+    /// A `$(...)` ROS substitution had no value: its resolver is not installed or
+    /// returned `None`.
+    UnresolvedSubstitution {
+        /// The substitution as it appeared in the YAML source.
+        substitution: String,
+        location: Location,
+    },
+    /// A `$(...)` ROS substitution was unknown, had the wrong arguments, or its
+    /// expression did not evaluate.
+    InvalidSubstitution {
+        /// The substitution as it appeared in the YAML source.
+        substitution: String,
+        location: Location,
+    },
     /// A YAML budget limit was exceeded.
     Budget {
         breach: BudgetBreach,
         location: Location,
     },
+    /// Parsing stopped because the deadline passed or the cancellation token was
+    /// cancelled. `location` is where parsing had got to.
+    Interrupted {
+        reason: Interruption,
+        location: Location,
+    },
     /// Unexpected I/O error. This may happen only when deserializing from a reader.
     IOError {
         cause: std::io::Error,
@@ -1031,7 +1123,6 @@ pub enum Error {
     },
 
     /// Validation failure.
-    #[cfg(any(feature = "garde", feature = "validator"))]
     ValidationError {
         source: ValidationSource,
         issues: Vec<ValidationIssue>,
@@ -1039,7 +1130,6 @@ pub enum Error {
     },
 
     /// Validation failures (multiple, if multiple validations fail)
-    #[cfg(any(feature = "garde", feature = "validator"))]
     ValidationErrors {
         source: ValidationSource,
         errors: Vec<Error>,
@@ -1088,7 +1178,6 @@ impl Error {
         }
     }
 
-    #[cfg(feature = "include")]
     #[cold]
     #[inline(never)]
     pub(crate) fn with_additional_snippet_named(
@@ -1213,15 +1302,12 @@ impl Error {
     /// Render this error using a custom message formatter.
     #[must_use]
     pub fn render_with_formatter(&self, formatter: &dyn MessageFormatter) -> String {
-        self.render_with_options(RenderOptions {
-            formatter,
-            snippets: SnippetMode::Auto,
-        })
+        self.render_with_options(RenderOptions::new(formatter))
     }
 
     /// Render this error using the provided options.
     #[must_use]
-    pub fn render_with_options(&self, options: RenderOptions<'_>) -> String {
+    pub fn render_with_options(&self, mut options: RenderOptions<'_>) -> String {
         struct RenderDisplay<'a> {
             err: &'a Error,
             options: RenderOptions<'a>,
@@ -1233,7 +1319,20 @@ impl Error {
             }
         }
 
-        RenderDisplay { err: self, options }.to_string()
+        options.target = options.target.resolve();
+        let html_safe = HtmlSafeFormatter(options.formatter);
+        let options = match options.target {
+            RenderTarget::Html => RenderOptions {
+                formatter: &html_safe,
+                ..options
+            },
+            _ => options,
+        };
+        let rendered = RenderDisplay { err: self, options }.to_string();
+        match options.target {
+            RenderTarget::Html => crate::de_snippet::to_html(&rendered),
+            _ => rendered,
+        }
     }
 
     /// Construct a `Message` error with no known location.
@@ -1275,7 +1374,7 @@ impl Error {
         // that may be used for attack)
         let location = Location::UNKNOWN;
         let value = if !interpolated
-            && (parse_yaml12_float::<f64>(value, location, SfTag::None, false).is_ok()
+            && (parse_yaml12_float::<f64>(value, location, SfTag::None, false, false).is_ok()
                 || parse_int_signed::<i128>(value, "i128", location, false).is_ok()
                 || parse_yaml11_bool(value).is_ok()
                 || scalar_is_nullish(value, &ScalarStyle::Plain))
@@ -1327,7 +1426,6 @@ impl Error {
         }
     }
 
-    #[cfg(any(feature = "garde", feature = "validator"))]
     pub(crate) fn validation_error(
         source: ValidationSource,
         issues: Vec<ValidationIssue>,
@@ -1340,12 +1438,10 @@ impl Error {
         }
     }
 
-    #[cfg(any(feature = "garde", feature = "validator"))]
     pub(crate) fn validation_errors(source: ValidationSource, errors: Vec<Error>) -> Self {
         Error::ValidationErrors { source, errors }
     }
 
-    #[cfg(any(feature = "garde", feature = "validator"))]
     pub(crate) fn is_validation_error(&self) -> bool {
         matches!(self, Error::ValidationError { .. })
     }
@@ -1433,6 +1529,10 @@ impl Error {
             | Error::SerdeMissingField { location, .. }
             | Error::UnexpectedContainerEndWhileReadingKeyNode { location }
             | Error::DuplicateMappingKey { location, .. }
+            | Error::DuplicateOmapKey { location, .. }
+            | Error::SetValueNotNull { location }
+            | Error::InvalidPairsEntry { location, .. }
+            | Error::RawYamlUnavailable { location }
             | Error::TaggedEnumMismatch { location, .. }
             | Error::SerdeVariantId { location, .. }
             | Error::ExpectedMappingEndAfterEnumVariantValue { location }
@@ -1441,6 +1541,8 @@ impl Error {
             | Error::InvalidPropertyName { location, .. }
             | Error::PropertyRequiredButUnset { location, .. }
             | Error::PropertyRequiredButEmpty { location, .. }
+            | Error::UnresolvedSubstitution { location, .. }
+            | Error::InvalidSubstitution { location, .. }
             | Error::ContainerEndMismatch { location, .. }
             | Error::UnknownAnchor { location, .. }
             | Error::CyclicInclude { location, .. }
@@ -1448,6 +1550,7 @@ impl Error {
             | Error::ResolverError { location, .. }
             | Error::QuotingRequired { location, .. }
             | Error::Budget { location, .. }
+            | Error::Interrupted { location, .. }
             | Error::CannotBorrowTransformedString { location, .. }
             | Error::IndentationError { location, .. } => {
                 *location = set_location;
@@ -1461,11 +1564,9 @@ impl Error {
                 let inner = *std::mem::replace(error, Box::new(Error::eof()));
                 **error = inner.with_location(set_location);
             }
-            #[cfg(any(feature = "garde", feature = "validator"))]
             Error::ValidationError { .. } => {
                 // Validation errors carry their own per-path locations.
             }
-            #[cfg(any(feature = "garde", feature = "validator"))]
             Error::ValidationErrors { .. } => {
                 // Aggregate validation errors carry their own per-entry locations.
             }
@@ -1482,7 +1583,6 @@ impl Error {
     /// - Callers that want to surface precise positions to users.
     #[must_use]
     pub fn location(&self) -> Option<Location> {
-        #[cfg(any(feature = "garde", feature = "validator"))]
         if let Error::ValidationErrors { errors, .. } = self {
             // Preserve aggregate behavior: use the first child that has a location,
             // rather than requiring the first child itself to have one.
@@ -1491,6 +1591,133 @@ impl Error {
 
         self.locations().and_then(Locations::primary_location)
     }
+
+    /// For an unknown field or variant, the expected name that the input most likely
+    /// misspells.
+    ///
+    /// Case and the separators `_`, `-` and space are ignored, so `maxReplicas` suggests
+    /// `max_replicas`; otherwise the closest name by edit distance is chosen if it is
+    /// close enough. Returns `None` for other errors or when no name is close.
+    ///
+    /// ```rust
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Debug, Deserialize)]
+    /// #[serde(deny_unknown_fields)]
+    /// struct Config {
+    ///     name: String,
+    /// }
+    ///
+    /// let err = serde_saphyr::from_str::<Config>("nmae: x\n").unwrap_err();
+    /// assert_eq!(err.suggestion(), Some("name"));
+    /// assert!(err.to_string().contains("did you mean `name`?"));
+    /// ```
+    #[must_use]
+    pub fn suggestion(&self) -> Option<&'static str> {
+        match self {
+            Error::WithSnippet { error, .. } => error.suggestion(),
+            Error::SerdeUnknownField {
+                field: name,
+                expected,
+                ..
+            }
+            | Error::SerdeUnknownVariant {
+                variant: name,
+                expected,
+                ..
+            } => crate::de::suggest::closest_name(name, expected),
+            _ => None,
+        }
+    }
+
+    /// Stable code of this error, such as `SY0101` for a duplicate mapping key.
+    ///
+    /// Codes do not depend on the message language and never change meaning; see
+    /// [`error_codes`](crate::error_codes) for the full list and long-form explanations.
+    ///
+    /// ```rust
+    /// let err = serde_saphyr::from_str::<u8>("300").unwrap_err();
+    /// assert_eq!(err.code(), "SY0301");
+    /// ```
+    #[must_use]
+    pub fn code(&self) -> &'static str {
+        match self {
+            Error::WithSnippet { error, .. } => error.code(),
+            Error::Message { .. } => "SY0001",
+            Error::InvalidOptions { .. } => "SY0002",
+            Error::ExternalMessage { .. } => "SY0003",
+            Error::HookError { .. } => "SY0004",
+            Error::IOError { .. } => "SY0005",
+            Error::DuplicateMappingKey { .. } => "SY0101",
+            Error::DuplicateOmapKey { .. } => "SY0102",
+            Error::SetValueNotNull { .. } => "SY0103",
+            Error::InvalidPairsEntry { .. } => "SY0104",
+            Error::MergeValueNotMapOrSeqOfMaps { .. } => "SY0105",
+            Error::MergeKeyNotAllowed { .. } => "SY0106",
+            Error::UnexpectedContainerEndWhileReadingKeyNode { .. } => "SY0107",
+            Error::ValueRequestedBeforeKey { .. } => "SY0108",
+            Error::Eof { .. } => "SY0201",
+            Error::MultipleDocuments { .. } => "SY0202",
+            Error::Unexpected { .. } => "SY0203",
+            Error::UnexpectedSequenceEnd { .. } => "SY0204",
+            Error::UnexpectedMappingEnd { .. } => "SY0205",
+            Error::ContainerEndMismatch { .. } => "SY0206",
+            Error::UnexpectedContainerEndWhileSkippingNode { .. } => "SY0207",
+            Error::FoldedBlockScalarMustIndentContent { .. } => "SY0208",
+            Error::IndentationError { .. } => "SY0209",
+            Error::InvalidUtf8Input => "SY0210",
+            Error::InvalidScalar { .. } => "SY0301",
+            Error::NonFiniteFloat { .. } => "SY0302",
+            Error::InvalidBooleanStrict { .. } => "SY0303",
+            Error::InvalidCharNull { .. } => "SY0304",
+            Error::InvalidCharNotSingleScalar { .. } => "SY0305",
+            Error::NullIntoString { .. } => "SY0306",
+            Error::InvalidBinaryBase64 { .. } => "SY0307",
+            Error::BinaryNotUtf8 { .. } => "SY0308",
+            Error::BytesNotSupportedMissingBinaryTag { .. } => "SY0309",
+            Error::TaggedScalarCannotDeserializeIntoString { .. } => "SY0310",
+            Error::CannotBorrowTransformedString { .. } => "SY0311",
+            Error::QuotingRequired { .. } => "SY0312",
+            Error::RawYamlUnavailable { .. } => "SY0313",
+            Error::SerdeInvalidType { .. } => "SY0401",
+            Error::SerdeInvalidValue { .. } => "SY0402",
+            Error::SerdeUnknownVariant { .. } => "SY0403",
+            Error::SerdeUnknownField { .. } => "SY0404",
+            Error::SerdeMissingField { .. } => "SY0405",
+            Error::SerdeVariantId { .. } => "SY0406",
+            Error::UnexpectedValueForUnit { .. } => "SY0407",
+            Error::ExpectedEmptyMappingForUnitStruct { .. } => "SY0408",
+            Error::ExpectedStringKeyForExternallyTaggedEnum { .. } => "SY0409",
+            Error::ExternallyTaggedEnumExpectedScalarOrMapping { .. } => "SY0410",
+            Error::UnexpectedValueForUnitEnumVariant { .. } => "SY0411",
+            Error::ExpectedMappingEndAfterEnumVariantValue { .. } => "SY0412",
+            Error::TaggedEnumMismatch { .. } => "SY0413",
+            Error::UnknownAnchor { .. } => "SY0501",
+            Error::AliasError { .. } => "SY0502",
+            Error::RecursiveReferencesRequireWeakTypes { .. } => "SY0503",
+            Error::AliasReplayCounterOverflow { .. } => "SY0504",
+            Error::AliasReplayLimitExceeded { .. } => "SY0505",
+            Error::AliasExpansionLimitExceeded { .. } => "SY0506",
+            Error::AliasReplayStackDepthExceeded { .. } => "SY0507",
+            Error::Budget { .. } => "SY0601",
+            Error::Interrupted { .. } => "SY0602",
+            Error::CyclicInclude { .. } => "SY0701",
+            Error::UnsupportedIncludeForm { .. } => "SY0702",
+            Error::ResolverError { .. } => "SY0703",
+            Error::UnresolvedProperty { .. } => "SY0801",
+            Error::InvalidPropertyName { .. } => "SY0802",
+            Error::PropertyRequiredButUnset { .. } => "SY0803",
+            Error::PropertyRequiredButEmpty { .. } => "SY0804",
+            Error::UnresolvedSubstitution { .. } => "SY0805",
+            Error::InvalidSubstitution { .. } => "SY0806",
+            Error::ValidationError { .. } => "SY0901",
+            Error::ValidationErrors { .. } => "SY0902",
+            Error::InternalSeedReusedForMapKey { .. } => "SY9901",
+            Error::InternalDepthUnderflow { .. } => "SY9902",
+            Error::InternalRecursionStackEmpty { .. } => "SY9903",
+        }
+    }
+
     /// Return a pair of locations associated with this error.
     ///
     /// - For syntax and other errors that carry a single [`Location`], this returns two
@@ -1546,6 +1773,10 @@ impl Error {
             | Error::SerdeMissingField { location, .. }
             | Error::UnexpectedContainerEndWhileReadingKeyNode { location }
             | Error::DuplicateMappingKey { location, .. }
+            | Error::DuplicateOmapKey { location, .. }
+            | Error::SetValueNotNull { location }
+            | Error::InvalidPairsEntry { location, .. }
+            | Error::RawYamlUnavailable { location }
             | Error::TaggedEnumMismatch { location, .. }
             | Error::SerdeVariantId { location, .. }
             | Error::ExpectedMappingEndAfterEnumVariantValue { location }
@@ -1554,6 +1785,8 @@ impl Error {
             | Error::InvalidPropertyName { location, .. }
             | Error::PropertyRequiredButUnset { location, .. }
             | Error::PropertyRequiredButEmpty { location, .. }
+            | Error::UnresolvedSubstitution { location, .. }
+            | Error::InvalidSubstitution { location, .. }
             | Error::ContainerEndMismatch { location, .. }
             | Error::UnknownAnchor { location, .. }
             | Error::CyclicInclude { location, .. }
@@ -1561,20 +1794,19 @@ impl Error {
             | Error::ResolverError { location, .. }
             | Error::QuotingRequired { location, .. }
             | Error::Budget { location, .. }
+            | Error::Interrupted { location, .. }
             | Error::CannotBorrowTransformedString { location, .. }
             | Error::IndentationError { location, .. } => Locations::same(location),
             Error::InvalidUtf8Input => None,
             Error::IOError { .. } => None,
             Error::AliasError { locations, .. } => Some(*locations),
             Error::WithSnippet { error, .. } => error.locations(),
-            #[cfg(any(feature = "garde", feature = "validator"))]
             Error::ValidationError {
                 issues, locations, ..
             } => issues
                 .first()
                 .and_then(|issue| locations.search_with_ancestor_fallback(&issue.path))
                 .map(|(locs, _)| locs),
-            #[cfg(any(feature = "garde", feature = "validator"))]
             Error::ValidationErrors { errors, .. } => errors.first().and_then(Error::locations),
         }
     }
@@ -1653,7 +1885,6 @@ fn fmt_error_plain_with_formatter(
     // Validation errors embed per-issue locations in their formatted message (potentially
     // multiple distinct locations). Do not attach a single top-level location suffix here,
     // or we'd duplicate location wording.
-    #[cfg(any(feature = "garde", feature = "validator"))]
     if matches!(err, Error::ValidationError { .. }) {
         return write!(f, "{msg}");
     }
@@ -1664,7 +1895,6 @@ fn fmt_error_plain_with_formatter(
         write!(f, "{msg}")?;
     }
 
-    #[cfg(any(feature = "garde", feature = "validator"))]
     if let Error::ValidationErrors { errors, .. } = err {
         for err in errors {
             writeln!(f)?;
@@ -1725,19 +1955,73 @@ fn writeln_anchor_intro(
     }
 }
 
+/// Formatter adapter used by [`RenderOptions::error_codes`].
+struct CodedFormatter<'a>(&'a dyn MessageFormatter);
+
+impl MessageFormatter for CodedFormatter<'_> {
+    fn localizer(&self) -> &dyn Localizer {
+        self.0.localizer()
+    }
+
+    fn format_message<'a>(&self, err: &'a Error) -> Cow<'a, str> {
+        let msg = self.0.format_message(err);
+        if msg.is_empty() {
+            return msg;
+        }
+        Cow::Owned(format!("[{}] {msg}", err.code()))
+    }
+
+    fn format_warning<'a>(&self, warning: &'a crate::Warning) -> Cow<'a, str> {
+        self.0.format_warning(warning)
+    }
+}
+
+/// Formatter adapter used by [`RenderTarget::Html`], so that messages quoting user text cannot
+/// inject markup.
+struct HtmlSafeFormatter<'a>(&'a dyn MessageFormatter);
+
+impl MessageFormatter for HtmlSafeFormatter<'_> {
+    fn localizer(&self) -> &dyn Localizer {
+        self.0.localizer()
+    }
+
+    fn format_message<'a>(&self, err: &'a Error) -> Cow<'a, str> {
+        neutralize_owned(self.0.format_message(err))
+    }
+
+    fn format_warning<'a>(&self, warning: &'a crate::Warning) -> Cow<'a, str> {
+        neutralize_owned(self.0.format_warning(warning))
+    }
+}
+
+fn neutralize_owned(msg: Cow<'_, str>) -> Cow<'_, str> {
+    match crate::de_snippet::neutralize_escapes(&msg) {
+        Cow::Owned(safe) => Cow::Owned(safe),
+        Cow::Borrowed(_) => msg,
+    }
+}
+
 fn fmt_error_rendered(
     f: &mut fmt::Formatter<'_>,
     err: &Error,
     options: RenderOptions<'_>,
 ) -> fmt::Result {
+    // Plain text carries the code as a message prefix; snippets show it in their title.
+    let coded = CodedFormatter(options.formatter);
+    let plain: &dyn MessageFormatter = if options.error_codes {
+        &coded
+    } else {
+        options.formatter
+    };
+    let code = options.error_codes.then(|| err.code());
+
     if options.snippets == SnippetMode::Off {
-        return fmt_error_plain_with_formatter(f, err, options.formatter);
+        return fmt_error_plain_with_formatter(f, err, plain);
     }
 
     match err {
-        #[cfg(any(feature = "garde", feature = "validator"))]
         Error::ValidationErrors { errors, .. } => {
-            let msg = options.formatter.format_message(err);
+            let msg = plain.format_message(err);
             if !msg.is_empty() {
                 writeln!(f, "{msg}")?;
             }
@@ -1760,16 +2044,15 @@ fn fmt_error_rendered(
         } => {
             if *crop_radius == 0 {
                 // Treat as "snippet disabled".
-                return fmt_error_plain_with_formatter(f, error, options.formatter);
+                return fmt_error_plain_with_formatter(f, error, plain);
             }
 
             if regions.is_empty() {
-                return fmt_error_plain_with_formatter(f, error, options.formatter);
+                return fmt_error_plain_with_formatter(f, error, plain);
             }
 
             // Validation errors have custom snippet formatting (paths, alias context, and
             // messages without location duplication).
-            #[cfg(any(feature = "garde", feature = "validator"))]
             if let Error::ValidationError {
                 source,
                 issues,
@@ -1784,11 +2067,11 @@ fn fmt_error_rendered(
                     locations,
                     regions,
                     *crop_radius,
+                    options.target,
                 );
             }
-            #[cfg(any(feature = "garde", feature = "validator"))]
             if let Error::ValidationErrors { errors, .. } = error.as_ref() {
-                let msg = options.formatter.format_message(error);
+                let msg = plain.format_message(error);
                 if !msg.is_empty() {
                     writeln!(f, "{msg}")?;
                 }
@@ -1799,13 +2082,7 @@ fn fmt_error_rendered(
                         writeln!(f)?;
                     }
                     first = false;
-                    fmt_error_with_snippets_offset(
-                        f,
-                        err,
-                        regions,
-                        *crop_radius,
-                        options.formatter,
-                    )?;
+                    fmt_error_with_snippets_offset(f, err, regions, *crop_radius, options)?;
                 }
                 return Ok(());
             }
@@ -1813,16 +2090,16 @@ fn fmt_error_rendered(
             // Render a snippet from the cropped source window. If anything is missing,
             // fall back to the plain nested error.
             let Some(location) = error.location() else {
-                return fmt_error_plain_with_formatter(f, error, options.formatter);
+                return fmt_error_plain_with_formatter(f, error, plain);
             };
             if location == Location::UNKNOWN {
-                return fmt_error_plain_with_formatter(f, error, options.formatter);
+                return fmt_error_plain_with_formatter(f, error, plain);
             }
 
             let l10n = options.formatter.localizer();
 
             let Some(region) = pick_cropped_region(regions, &location) else {
-                return fmt_error_plain_with_formatter(f, error, options.formatter);
+                return fmt_error_plain_with_formatter(f, error, plain);
             };
 
             // Dual-location rendering: show both the reference and the definition window.
@@ -1857,7 +2134,9 @@ fn fmt_error_rendered(
                     used_region.source_name.as_str(),
                     *crop_radius,
                 )
-                .with_offset(used_region.start_line);
+                .with_offset(used_region.start_line)
+                .with_target(options.target)
+                .with_code(code);
                 ctx.fmt_or_fallback_with_label(
                     f,
                     Level::ERROR,
@@ -1887,7 +2166,9 @@ fn fmt_error_rendered(
                     region.source_name.as_str(),
                     *crop_radius,
                 )
-                .with_offset(region.start_line);
+                .with_offset(region.start_line)
+                .with_target(options.target)
+                .with_code(code);
                 ctx.fmt_or_fallback(f, Level::ERROR, l10n, msg.as_ref(), &location)?;
 
                 for extra_region in regions {
@@ -1901,13 +2182,14 @@ fn fmt_error_rendered(
                         extra_region.source_name.as_str(),
                         *crop_radius,
                     )
-                    .with_offset(extra_region.start_line);
+                    .with_offset(extra_region.start_line)
+                    .with_target(options.target);
                     extra_ctx.fmt_or_fallback(f, Level::NOTE, l10n, "", &extra_region.location)?;
                 }
                 Ok(())
             }
         }
-        _ => fmt_error_plain_with_formatter(f, err, options.formatter),
+        _ => fmt_error_plain_with_formatter(f, err, plain),
     }
 }
 
@@ -1923,7 +2205,7 @@ impl fmt::Debug for Error {
     }
 }
 
-#[cfg(any(feature = "garde", feature = "validator"))]
+#[allow(clippy::too_many_arguments)]
 fn fmt_validation_error_with_snippets_offset(
     f: &mut fmt::Formatter<'_>,
     l10n: &dyn Localizer,
@@ -1932,6 +2214,7 @@ fn fmt_validation_error_with_snippets_offset(
     locations: &PathMap,
     regions: &[CroppedRegion],
     crop_radius: usize,
+    target: RenderTarget,
 ) -> fmt::Result {
     let mut first = true;
     for issue in issues {
@@ -1971,7 +2254,8 @@ fn fmt_validation_error_with_snippets_offset(
                         label.as_ref(),
                         crop_radius,
                     )
-                    .with_offset(region.start_line);
+                    .with_offset(region.start_line)
+                    .with_target(target);
                     ctx.fmt_or_fallback(f, Level::ERROR, l10n, &base_msg, &r)?;
                 } else {
                     fmt_with_location(f, l10n, &base_msg, &r)?;
@@ -1986,7 +2270,8 @@ fn fmt_validation_error_with_snippets_offset(
                         label.as_ref(),
                         crop_radius,
                     )
-                    .with_offset(region.start_line);
+                    .with_offset(region.start_line)
+                    .with_target(target);
                     ctx.fmt_or_fallback(f, Level::ERROR, l10n, &base_msg, &d)?;
                 } else {
                     fmt_with_location(f, l10n, &base_msg, &d)?;
@@ -2002,7 +2287,8 @@ fn fmt_validation_error_with_snippets_offset(
                         region.source_name.as_str(),
                         crop_radius,
                     )
-                    .with_offset(region.start_line);
+                    .with_offset(region.start_line)
+                    .with_target(target);
                     ctx.fmt_or_fallback_with_label(
                         f,
                         Level::ERROR,
@@ -2045,31 +2331,37 @@ fn fmt_validation_error_with_snippets_offset(
                 extra_region.source_name.as_str(),
                 crop_radius,
             )
-            .with_offset(extra_region.start_line);
+            .with_offset(extra_region.start_line)
+            .with_target(target);
             extra_ctx.fmt_or_fallback(f, Level::NOTE, l10n, "", &extra_region.location)?;
         }
     }
     Ok(())
 }
 
-#[cfg(any(feature = "garde", feature = "validator"))]
 fn fmt_error_with_snippets_offset(
     f: &mut fmt::Formatter<'_>,
     err: &Error,
     regions: &[CroppedRegion],
     crop_radius: usize,
-    formatter: &dyn MessageFormatter,
+    options: RenderOptions<'_>,
 ) -> fmt::Result {
+    let formatter = options.formatter;
+    let coded = CodedFormatter(formatter);
+    let plain: &dyn MessageFormatter = if options.error_codes {
+        &coded
+    } else {
+        formatter
+    };
     if crop_radius == 0 {
-        return fmt_error_plain_with_formatter(f, err, formatter);
+        return fmt_error_plain_with_formatter(f, err, plain);
     }
 
     // Keep existing snippet output if the nested error is already wrapped.
     if let Error::WithSnippet { .. } = err {
-        return fmt_error_rendered(f, err, RenderOptions::new(formatter));
+        return fmt_error_rendered(f, err, options);
     }
 
-    #[cfg(any(feature = "garde", feature = "validator"))]
     if let Error::ValidationError {
         source,
         issues,
@@ -2084,26 +2376,30 @@ fn fmt_error_with_snippets_offset(
             locations,
             regions,
             crop_radius,
+            options.target,
         );
     }
 
-    let msg = formatter.format_message(err);
-    let Some(location) = err.location() else {
-        return write!(f, "{msg}");
+    let Some(location) = err
+        .location()
+        .filter(|location| *location != Location::UNKNOWN)
+    else {
+        return write!(f, "{}", plain.format_message(err));
     };
-    if location == Location::UNKNOWN {
-        return write!(f, "{msg}");
-    }
 
     let Some(region) = pick_cropped_region(regions, &location) else {
+        let msg = plain.format_message(err);
         return fmt_with_location(f, formatter.localizer(), msg.as_ref(), &location);
     };
+    let msg = formatter.format_message(err);
     let ctx = crate::de_snippet::Snippet::new(
         region.text.as_str(),
         region.source_name.as_str(),
         crop_radius,
     )
-    .with_offset(region.start_line);
+    .with_offset(region.start_line)
+    .with_target(options.target)
+    .with_code(options.error_codes.then(|| err.code()));
     ctx.fmt_or_fallback(
         f,
         Level::ERROR,
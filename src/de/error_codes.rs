@@ -0,0 +1,686 @@
+//! Stable error codes.
+//!
+//! Every [`Error`](crate::Error) variant has a stable code such as `SY0101`, returned by
+//! [`Error::code`](crate::Error::code). Codes never change meaning and are never reused, so
+//! they can be searched for, matched in scripts and looked up with [`explain`] (or
+//! `serde-saphyr explain <code>` on the command line) even when the message text is
+//! translated.
+//!
+//! Codes are grouped by the hundreds digit:
+//!
+//! | Range    | Group                                  |
+//! |----------|----------------------------------------|
+//! | `SY00xx` | general and external errors            |
+//! | `SY01xx` | keys and mappings                      |
+//! | `SY02xx` | document structure                     |
+//! | `SY03xx` | scalars                                |
+//! | `SY04xx` | Rust types and serde                   |
+//! | `SY05xx` | anchors and aliases                    |
+//! | `SY06xx` | budgets and interruption               |
+//! | `SY07xx` | `!include`                             |
+//! | `SY08xx` | properties and substitutions           |
+//! | `SY09xx` | validation                             |
+//! | `SY99xx` | internal errors (please report them)   |
+//!
+//! ```rust
+//! let err = serde_saphyr::from_str::<std::collections::HashMap<String, u8>>("a: 1\na: 2\n")
+//!     .unwrap_err();
+//! assert_eq!(err.code(), "SY0101");
+//!
+//! let info = serde_saphyr::error_codes::explain("sy0101").unwrap();
+//! assert_eq!(info.name, "DuplicateMappingKey");
+//! ```
+
+/// Description of one stable error code.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ErrorCodeInfo {
+    /// The code, such as `SY0101`.
+    pub code: &'static str,
+    /// Name of the [`Error`](crate::Error) variant with this code.
+    pub name: &'static str,
+    /// One-line summary.
+    pub summary: &'static str,
+    /// Long-form description with the usual causes and fixes.
+    pub explanation: &'static str,
+}
+
+const fn info(
+    code: &'static str,
+    name: &'static str,
+    summary: &'static str,
+    explanation: &'static str,
+) -> ErrorCodeInfo {
+    ErrorCodeInfo {
+        code,
+        name,
+        summary,
+        explanation,
+    }
+}
+
+/// All error codes, in code order.
+pub static ERROR_CODES: &[ErrorCodeInfo] = &[
+    info(
+        "SY0001",
+        "Message",
+        "free-form error message",
+        "A free-form error, usually raised by a custom `Deserialize` implementation through \
+         `serde::de::Error::custom`. The message itself describes the problem; check the \
+         implementation of the type being deserialized at the reported location.",
+    ),
+    info(
+        "SY0002",
+        "InvalidOptions",
+        "invalid deserializer options",
+        "The options passed to the deserializer are inconsistent, for example a limit of zero \
+         or two settings that exclude each other. This is a programming error rather than a \
+         problem with the YAML input. Fix: correct the `Options` value; the message names the \
+         offending setting.",
+    ),
+    info(
+        "SY0003",
+        "ExternalMessage",
+        "error reported by the YAML parser or a validator",
+        "The text comes from the underlying YAML parser or from a validator. Parser errors mean \
+         the input is not well-formed YAML: look for wrong indentation, a missing `:` after a \
+         key, an unclosed quote or bracket, or a tab used for indentation near the reported \
+         location.",
+    ),
+    info(
+        "SY0004",
+        "HookError",
+        "extension hook failed",
+        "A hook that parses extensions beyond standard YAML, such as the robotics dialect's \
+         angle and unit expressions, rejected the value. Fix: correct the expression at the \
+         reported location, or disable the extension if the value should be read literally.",
+    ),
+    info(
+        "SY0005",
+        "IOError",
+        "I/O error while reading input",
+        "Reading from the `std::io::Read` source failed. This is not a YAML problem; the cause \
+         is reported by the operating system. Fix: check that the file or stream is readable \
+         and not truncated.",
+    ),
+    info(
+        "SY0101",
+        "DuplicateMappingKey",
+        "duplicate key in a mapping",
+        "The same key appears twice in one mapping. YAML requires keys to be unique, and by \
+         default serde-saphyr rejects duplicates instead of silently keeping one of the values. \
+         Fix: remove or rename one of the keys. If the input legitimately repeats keys, set \
+         `Options::duplicate_keys` to `FirstWins` or `LastWins`.",
+    ),
+    info(
+        "SY0102",
+        "DuplicateOmapKey",
+        "duplicate key in an `!!omap`",
+        "An ordered map (`!!omap`) lists the same key twice. Unlike `!!pairs`, an omap requires \
+         unique keys. Fix: remove the duplicate entry, or tag the sequence `!!pairs` if \
+         repeated keys are intended.",
+    ),
+    info(
+        "SY0103",
+        "SetValueNotNull",
+        "`!!set` entry has a value",
+        "Entries of a `!!set` are keys only; their values must be null. Fix: write the entry as \
+         `? item` or `item:` with nothing after the colon, or drop the `!!set` tag if the \
+         values are needed.",
+    ),
+    info(
+        "SY0104",
+        "InvalidPairsEntry",
+        "`!!omap` or `!!pairs` entry is not a single key-value pair",
+        "Every item of an `!!omap` or `!!pairs` sequence must be a mapping with exactly one \
+         key, such as `- name: value`. Fix: split items with several keys into separate \
+         sequence items, and replace scalars with `key: value` pairs.",
+    ),
+    info(
+        "SY0105",
+        "MergeValueNotMapOrSeqOfMaps",
+        "merge value is not a mapping or a sequence of mappings",
+        "The value of a merge key (`<<`) must be a mapping, usually an alias such as \
+         `<<: *defaults`, or a sequence of mappings such as `<<: [*a, *b]`. Fix: make the \
+         anchored value a mapping, or quote `\"<<\"` if it is meant to be an ordinary key.",
+    ),
+    info(
+        "SY0106",
+        "MergeKeyNotAllowed",
+        "merge keys are disabled",
+        "The input uses a merge key (`<<`), but merge keys are disabled by \
+         `Options::merge_keys`. Fix: expand the merged values by hand, quote `\"<<\"` if it is \
+         an ordinary key, or enable merge keys.",
+    ),
+    info(
+        "SY0107",
+        "UnexpectedContainerEndWhileReadingKeyNode",
+        "container ended while reading a key",
+        "A sequence or mapping ended where a mapping key was expected, which means the \
+         structure of the input does not match what was being read. Fix: check the \
+         indentation and brackets around the reported location.",
+    ),
+    info(
+        "SY0108",
+        "ValueRequestedBeforeKey",
+        "value requested before its key",
+        "A `Deserialize` implementation asked for a map value before reading its key. This is \
+         a bug in the implementation being deserialized, not in the YAML input.",
+    ),
+    info(
+        "SY0201",
+        "Eof",
+        "unexpected end of input",
+        "The input ended while a value was still expected, for example an empty document read \
+         into a non-optional type, or a file cut short. Fix: complete the document, or \
+         deserialize into `Option<T>` if an empty document is acceptable.",
+    ),
+    info(
+        "SY0202",
+        "MultipleDocuments",
+        "more than one document where one was expected",
+        "Single-document entry points such as `from_str` found a second `---` document. Fix: \
+         remove the extra documents, or use `from_multiple` / `read` to process every \
+         document in the stream.",
+    ),
+    info(
+        "SY0203",
+        "Unexpected",
+        "unexpected kind of node",
+        "Something other than the expected node was found, such as a scalar where a sequence \
+         was expected. Fix: compare the shape of the YAML at the reported location with the \
+         Rust type; a missing `-` or wrong indentation is the usual cause.",
+    ),
+    info(
+        "SY0204",
+        "UnexpectedSequenceEnd",
+        "sequence ended unexpectedly",
+        "A sequence ended where more items or a different node were expected, for example a \
+         tuple or fixed-size array with too few items. Fix: add the missing items or change \
+         the target type.",
+    ),
+    info(
+        "SY0205",
+        "UnexpectedMappingEnd",
+        "mapping ended unexpectedly",
+        "A mapping ended where more entries or a different node were expected. Fix: check \
+         that the mapping is complete and correctly indented.",
+    ),
+    info(
+        "SY0206",
+        "ContainerEndMismatch",
+        "end of a sequence or mapping without a start",
+        "The parser reported the end of a sequence or mapping that was never opened, or \
+         closed a different kind of container than the one that was open. Fix: check the \
+         brackets and braces of flow collections near the reported location.",
+    ),
+    info(
+        "SY0207",
+        "UnexpectedContainerEndWhileSkippingNode",
+        "container ended while skipping a node",
+        "While skipping an ignored value, a container ended earlier than expected. This \
+         points to malformed structure in the input. Fix: check the indentation and brackets \
+         around the reported location.",
+    ),
+    info(
+        "SY0208",
+        "FoldedBlockScalarMustIndentContent",
+        "folded block scalar content is not indented",
+        "The content of a folded block scalar (`>`) must be indented more than its parent \
+         key. Fix: indent the lines after `>`, or use an explicit indentation indicator such \
+         as `>2`.",
+    ),
+    info(
+        "SY0209",
+        "IndentationError",
+        "indentation does not meet the configured requirement",
+        "`Options::require_indent` asks for a particular indentation style, such as a fixed \
+         width or an even number of spaces, and the input does not follow it. Fix: reindent \
+         the reported line, or relax the requirement.",
+    ),
+    info(
+        "SY0210",
+        "InvalidUtf8Input",
+        "input is not valid UTF-8",
+        "YAML input must be text. The bytes given to the parser are not valid UTF-8, for \
+         example a file saved in Latin-1 or UTF-16, or a binary file. Fix: convert the file \
+         to UTF-8.",
+    ),
+    info(
+        "SY0301",
+        "InvalidScalar",
+        "scalar cannot be parsed as the requested type",
+        "The scalar text is not a valid value of the target type, for example `12x` for an \
+         integer or `300` for a `u8`. Fix: correct the value, or change the field type.",
+    ),
+    info(
+        "SY0302",
+        "NonFiniteFloat",
+        "non-finite float in a typeless position",
+        "A value such as `.nan`, `.inf` or `1e999` was read into a typeless target like \
+         `serde_json::Value` while `Options::reject_non_finite_typeless_float` is set. Fix: \
+         use a finite number, quote the value to keep it as a string, or disable the option.",
+    ),
+    info(
+        "SY0303",
+        "InvalidBooleanStrict",
+        "invalid boolean in strict mode",
+        "With strict booleans only `true` and `false` are accepted. YAML 1.1 spellings such \
+         as `yes`, `on` or `True` are rejected. Fix: write `true` or `false`.",
+    ),
+    info(
+        "SY0304",
+        "InvalidCharNull",
+        "null read into `char`",
+        "A null value (`~`, `null` or nothing) was read into a `char`. Fix: provide a \
+         character, or use `Option<char>`.",
+    ),
+    info(
+        "SY0305",
+        "InvalidCharNotSingleScalar",
+        "`char` value is not a single character",
+        "A `char` field needs exactly one Unicode scalar value. Fix: shorten the value to one \
+         character, or change the field to `String`.",
+    ),
+    info(
+        "SY0306",
+        "NullIntoString",
+        "null read into a string",
+        "A null value was read into a `String` field. An empty plain scalar, `~` and `null` \
+         are all null in YAML. Fix: quote the value (`\"\"` or `\"null\"`), or use \
+         `Option<String>`.",
+    ),
+    info(
+        "SY0307",
+        "InvalidBinaryBase64",
+        "`!!binary` scalar is not valid base64",
+        "A scalar tagged `!!binary` could not be decoded as base64. Fix: check for stray \
+         characters or wrong padding in the encoded data.",
+    ),
+    info(
+        "SY0308",
+        "BinaryNotUtf8",
+        "`!!binary` value is not UTF-8 text",
+        "A `!!binary` scalar decoded correctly, but the bytes are not valid UTF-8 and the \
+         target is a string. Fix: read the value into `Vec<u8>` or `serde_bytes::ByteBuf`.",
+    ),
+    info(
+        "SY0309",
+        "BytesNotSupportedMissingBinaryTag",
+        "bytes require a `!!binary` scalar",
+        "Byte buffers are only read from scalars tagged `!!binary`. Fix: base64-encode the \
+         data and tag it `!!binary`, or read the value as a string.",
+    ),
+    info(
+        "SY0310",
+        "TaggedScalarCannotDeserializeIntoString",
+        "tagged scalar cannot be read as a string",
+        "A scalar carries an explicit tag, such as `!!int`, that conflicts with the string \
+         target. Fix: remove the tag, or use `!!str`.",
+    ),
+    info(
+        "SY0311",
+        "CannotBorrowTransformedString",
+        "string cannot be borrowed from the input",
+        "The target type borrows `&str` from the input, but the value was changed while \
+         parsing, by escape sequences, line folding or multi-line normalization, so there is \
+         no contiguous input text to borrow. Fix: use `String` or `Cow<str>`.",
+    ),
+    info(
+        "SY0312",
+        "QuotingRequired",
+        "string value must be quoted",
+        "With `Options::no_schema` set, a plain scalar that would otherwise read as a number or \
+         boolean is not accepted into a string field. Fix: quote the value, for example \
+         `version: \"1.10\"`.",
+    ),
+    info(
+        "SY0313",
+        "RawYamlUnavailable",
+        "source text of a `RawYaml` value is unavailable",
+        "A `RawYaml` field keeps the original text of its value, which is only possible when \
+         deserializing from a string or slice. Values read from a reader, an included file or \
+         a buffered replay have no source text. Fix: read the input into a string first, or \
+         use `Value` instead of `RawYaml`.",
+    ),
+    info(
+        "SY0401",
+        "SerdeInvalidType",
+        "value has the wrong type",
+        "The YAML node has a different type than the Rust field, such as a string for a \
+         number or a sequence for a struct. Fix: change the value, or change the field type.",
+    ),
+    info(
+        "SY0402",
+        "SerdeInvalidValue",
+        "value is of the right type but not accepted",
+        "The value has the expected type, but the target rejected it, for example a negative \
+         number for an unsigned field. Fix: use a value within the accepted range.",
+    ),
+    info(
+        "SY0403",
+        "SerdeUnknownVariant",
+        "unknown enum variant",
+        "The value does not name any variant of the target enum. The message lists the \
+         accepted variants and may suggest the closest one. Fix: correct the spelling; \
+         variant names are case-sensitive and follow any `#[serde(rename_all)]` attribute.",
+    ),
+    info(
+        "SY0404",
+        "SerdeUnknownField",
+        "unknown field",
+        "The mapping has a key that the target struct does not declare, and the struct uses \
+         `#[serde(deny_unknown_fields)]`. Fix: correct a misspelt key (a suggestion is given \
+         when one is close), or remove the key.",
+    ),
+    info(
+        "SY0405",
+        "SerdeMissingField",
+        "missing required field",
+        "A required field of the target struct is absent. Fix: add the key, or give the field \
+         a default with `#[serde(default)]` or an `Option` type.",
+    ),
+    info(
+        "SY0406",
+        "SerdeVariantId",
+        "enum variant name could not be read",
+        "The enum variant identifier could not be deserialized, usually because it is not a \
+         string. Fix: write the variant as a plain string such as `Circle` or `Circle: {...}`.",
+    ),
+    info(
+        "SY0407",
+        "UnexpectedValueForUnit",
+        "unexpected value for `()`",
+        "The unit type `()` accepts only null. Fix: leave the value empty or write `null`.",
+    ),
+    info(
+        "SY0408",
+        "ExpectedEmptyMappingForUnitStruct",
+        "unit struct expects an empty mapping",
+        "A unit struct accepts null or an empty mapping `{}`. Fix: remove the contents of the \
+         value.",
+    ),
+    info(
+        "SY0409",
+        "ExpectedStringKeyForExternallyTaggedEnum",
+        "externally tagged enum needs a string key",
+        "An externally tagged enum is written as `Variant: value`. The key must be a string \
+         naming the variant. Fix: replace the key with the variant name.",
+    ),
+    info(
+        "SY0410",
+        "ExternallyTaggedEnumExpectedScalarOrMapping",
+        "externally tagged enum needs a scalar or a mapping",
+        "An externally tagged enum is written either as a plain variant name (`Variant`) or as \
+         a single-entry mapping (`Variant: value`). A sequence was found instead. Fix: use \
+         one of the two forms.",
+    ),
+    info(
+        "SY0411",
+        "UnexpectedValueForUnitEnumVariant",
+        "unit variant given a value",
+        "The variant carries no data, but a value was given for it, as in `Off: 3`. Fix: \
+         write the variant name alone.",
+    ),
+    info(
+        "SY0412",
+        "ExpectedMappingEndAfterEnumVariantValue",
+        "extra entries after an enum variant",
+        "An externally tagged enum is a mapping with exactly one entry. More entries followed \
+         the variant. Fix: remove the extra keys, or move them inside the variant's value.",
+    ),
+    info(
+        "SY0413",
+        "TaggedEnumMismatch",
+        "enum tag names a different enum",
+        "A YAML tag such as `!Shape::Circle` names an enum other than the target type. Fix: \
+         correct the tag, or remove it and name the variant only.",
+    ),
+    info(
+        "SY0501",
+        "UnknownAnchor",
+        "alias refers to an unknown anchor",
+        "An alias (`*name`) refers to an anchor (`&name`) that is not defined earlier in the \
+         same document. Fix: define the anchor before its first use, and check the spelling; \
+         anchors do not carry over between documents.",
+    ),
+    info(
+        "SY0502",
+        "AliasError",
+        "error in a value reached through an alias",
+        "The value that an alias expands to could not be deserialized at the place where the \
+         alias is used. The report shows both the use and the anchor definition. Fix: change \
+         the anchored value, or stop sharing it where a different type is needed.",
+    ),
+    info(
+        "SY0503",
+        "RecursiveReferencesRequireWeakTypes",
+        "recursive reference needs a weak type",
+        "An alias refers to a node that contains it, forming a cycle. Cycles can only be read \
+         into the crate's recursion-aware types. Fix: break the cycle in the YAML, or use the \
+         recursive and weak reference types for the fields involved.",
+    ),
+    info(
+        "SY0504",
+        "AliasReplayCounterOverflow",
+        "alias replay counter overflowed",
+        "The number of replayed events overflowed its counter. This only happens with \
+         deliberately expanding inputs (a \"billion laughs\" document). Fix: reduce the use \
+         of nested aliases.",
+    ),
+    info(
+        "SY0505",
+        "AliasReplayLimitExceeded",
+        "too many events replayed through aliases",
+        "Expanding aliases produced more events than the configured limit. This protects \
+         against documents that grow exponentially through nested aliases. Fix: reduce \
+         aliasing, or raise the alias limits in `Options` if the input is trusted.",
+    ),
+    info(
+        "SY0506",
+        "AliasExpansionLimitExceeded",
+        "one anchor expanded too many times",
+        "A single anchor was expanded more often than the configured limit allows. Fix: \
+         reduce the number of aliases to that anchor, or raise the limit if the input is \
+         trusted.",
+    ),
+    info(
+        "SY0507",
+        "AliasReplayStackDepthExceeded",
+        "aliases nested too deeply",
+        "Aliases refer to anchors that themselves contain aliases, deeper than the configured \
+         limit. Fix: flatten the chain of anchors, or raise the limit if the input is trusted.",
+    ),
+    info(
+        "SY0601",
+        "Budget",
+        "document exceeds a budget limit",
+        "The document is larger or more complex than the configured `Budget` allows: too many \
+         events, nodes, anchors or documents, nesting too deep, or scalars too large. The \
+         message names the limit. Fix: simplify the input, or raise that limit if the input \
+         is trusted.",
+    ),
+    info(
+        "SY0602",
+        "Interrupted",
+        "parsing stopped by deadline or cancellation",
+        "Parsing was stopped because the configured deadline passed or the cancellation \
+         token was cancelled. The location shows how far parsing had got. Fix: allow more \
+         time, or investigate why the input is slow to parse.",
+    ),
+    info(
+        "SY0701",
+        "CyclicInclude",
+        "`!include` cycle",
+        "A file includes itself, directly or through other files. Fix: remove the `!include` \
+         that closes the cycle.",
+    ),
+    info(
+        "SY0702",
+        "UnsupportedIncludeForm",
+        "unsupported `!include` form",
+        "`!include` only supports the scalar form `!include path/to/file.yaml`. Fix: rewrite \
+         sequence or mapping forms as one scalar include per value.",
+    ),
+    info(
+        "SY0703",
+        "ResolverError",
+        "include could not be resolved",
+        "The include resolver could not provide the requested file: it may not exist, lie \
+         outside the allowed root, or be unreadable. Fix: check the path, which is relative \
+         to the including file, and the configured include root.",
+    ),
+    info(
+        "SY0801",
+        "UnresolvedProperty",
+        "`${NAME}` property is not set",
+        "A `${NAME}` reference names a property that is not in the configured property map. \
+         Fix: provide the property, or give a default with `${NAME:-default}`.",
+    ),
+    info(
+        "SY0802",
+        "InvalidPropertyName",
+        "invalid property name",
+        "A `${...}` reference contains a name that is not a valid property name. Fix: use \
+         letters, digits and underscores, or escape the `$` as `$$` if no substitution is \
+         intended.",
+    ),
+    info(
+        "SY0803",
+        "PropertyRequiredButUnset",
+        "required property is not set",
+        "A `${NAME?message}` or `${NAME:?message}` reference requires the property, and it is \
+         not set. The message after `?` explains what is needed. Fix: set the property.",
+    ),
+    info(
+        "SY0804",
+        "PropertyRequiredButEmpty",
+        "required property is empty",
+        "A `${NAME:?message}` reference requires a non-empty value, and the property is set \
+         but empty. Fix: give the property a value.",
+    ),
+    info(
+        "SY0805",
+        "UnresolvedSubstitution",
+        "`$(...)` substitution has no value",
+        "A ROS launch-style `$(...)` substitution could not be resolved: no resolver is \
+         installed for it, or the resolver returned nothing. Fix: install a resolver for the \
+         substitution, or provide the missing argument or environment variable.",
+    ),
+    info(
+        "SY0806",
+        "InvalidSubstitution",
+        "invalid `$(...)` substitution",
+        "A `$(...)` substitution is unknown, has the wrong number of arguments, or its \
+         expression did not evaluate. Fix: check the substitution name and arguments.",
+    ),
+    info(
+        "SY0901",
+        "ValidationError",
+        "validation failed",
+        "The document was read successfully, but a validator rejected a value. The message \
+         names the field and the rule. Fix: change the value to satisfy the rule.",
+    ),
+    info(
+        "SY0902",
+        "ValidationErrors",
+        "several validations failed",
+        "Several values failed validation. Each failure is reported with its own code and \
+         location. Fix: address each of them.",
+    ),
+    info(
+        "SY9901",
+        "InternalSeedReusedForMapKey",
+        "internal error: seed reused for a map key",
+        "An internal consistency check failed. This is a bug in serde-saphyr or in a custom \
+         `Deserialize` implementation; please report it with the input that triggers it.",
+    ),
+    info(
+        "SY9902",
+        "InternalDepthUnderflow",
+        "internal error: depth underflow",
+        "An internal consistency check failed. This is a bug in serde-saphyr; please report \
+         it with the input that triggers it.",
+    ),
+    info(
+        "SY9903",
+        "InternalRecursionStackEmpty",
+        "internal error: recursion stack empty",
+        "An internal consistency check failed. This is a bug in serde-saphyr; please report \
+         it with the input that triggers it.",
+    ),
+];
+
+/// Look up an error code, ignoring ASCII case.
+///
+/// Returns `None` if the code is unknown.
+#[must_use]
+pub fn explain(code: &str) -> Option<&'static ErrorCodeInfo> {
+    let code = code.trim();
+    ERROR_CODES
+        .iter()
+        .find(|info| info.code.eq_ignore_ascii_case(code))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Location;
+    use rstest::rstest;
+
+    #[test]
+    fn codes_are_sorted_unique_and_well_formed() {
+        for pair in ERROR_CODES.windows(2) {
+            assert!(
+                pair[0].code < pair[1].code,
+                "{} / {}",
+                pair[0].code,
+                pair[1].code
+            );
+        }
+        for info in ERROR_CODES {
+            assert_eq!(info.code.len(), 6, "{}", info.code);
+            assert!(info.code.starts_with("SY"), "{}", info.code);
+            assert!(info.code[2..].bytes().all(|b| b.is_ascii_digit()));
+        }
+    }
+
+    #[test]
+    fn every_code_of_error_is_in_the_catalog() {
+        let source = include_str!("error.rs");
+        let start = source.find("pub fn code(&self)").unwrap();
+        let body = &source[start..];
+        let body = &body[..body.find("\n    }\n").unwrap()];
+
+        let mut used: Vec<&str> = body
+            .split('"')
+            .filter(|part| part.starts_with("SY"))
+            .collect();
+        used.sort_unstable();
+        let catalog: Vec<&str> = ERROR_CODES.iter().map(|info| info.code).collect();
+        assert_eq!(used, catalog);
+    }
+
+    #[rstest]
+    #[case(crate::Error::Eof { location: Location::UNKNOWN }, "Eof")]
+    #[case(crate::Error::InvalidUtf8Input, "InvalidUtf8Input")]
+    #[case(
+        crate::Error::DuplicateMappingKey { key: None, location: Location::UNKNOWN },
+        "DuplicateMappingKey"
+    )]
+    fn code_matches_variant_name(#[case] err: crate::Error, #[case] name: &str) {
+        assert_eq!(explain(err.code()).unwrap().name, name);
+        let wrapped = crate::Error::WithSnippet {
+            regions: Vec::new(),
+            crop_radius: 0,
+            error: Box::new(err),
+        };
+        assert_eq!(explain(wrapped.code()).unwrap().name, name);
+    }
+
+    #[test]
+    fn explain_ignores_case_and_whitespace() {
+        assert_eq!(explain(" sy0201 ").unwrap().name, "Eof");
+        assert!(explain("SY0000").is_none());
+    }
+}
@@ -10,6 +10,8 @@ use granit_parser::ScalarStyle;
 use super::error::Error;
 #[cfg(feature = "properties")]
 use super::options::PropertySyntax;
+#[cfg(feature = "properties")]
+use super::ros_substitutions::RosSubstitutions;
 use super::tags::SfTag;
 use crate::location::{Location, Locations};
 
@@ -67,6 +69,9 @@ pub(crate) enum Ev<'a> {
         value: Cow<'a, str>,
         tag: SfTag,
         raw_tag: Option<Cow<'a, str>>,
+        /// Length of the `%TAG` directive prefix at the start of `raw_tag` (`tag:example.com,2026:`
+        /// for `!k8s!Deployment`), or 0 if the tag was not written with a directive handle.
+        tag_prefix_len: usize,
         style: ScalarStyle,
         /// Numeric anchor id (0 if none) attached to this scalar node.
         anchor: usize,
@@ -77,6 +82,9 @@ pub(crate) enum Ev<'a> {
         anchor: usize,
         tag: SfTag,
         raw_tag: Option<Cow<'a, str>>,
+        /// Length of the `%TAG` directive prefix at the start of `raw_tag` (`tag:example.com,2026:`
+        /// for `!k8s!Deployment`), or 0 if the tag was not written with a directive handle.
+        tag_prefix_len: usize,
         location: Location,
     },
     /// End of a sequence.
@@ -86,6 +94,9 @@ pub(crate) enum Ev<'a> {
         anchor: usize,
         tag: SfTag,
         raw_tag: Option<Cow<'a, str>>,
+        /// Length of the `%TAG` directive prefix at the start of `raw_tag` (`tag:example.com,2026:`
+        /// for `!k8s!Deployment`), or 0 if the tag was not written with a directive handle.
+        tag_prefix_len: usize,
         location: Location,
     },
     /// End of a mapping.
@@ -126,11 +137,27 @@ impl Ev<'_> {
     /// Clear the YAML tag attached to a scalar or container-start event.
     pub(super) fn strip_node_tag(&mut self) -> bool {
         match self {
-            Ev::Scalar { tag, raw_tag, .. }
-            | Ev::SeqStart { tag, raw_tag, .. }
-            | Ev::MapStart { tag, raw_tag, .. } => {
+            Ev::Scalar {
+                tag,
+                raw_tag,
+                tag_prefix_len,
+                ..
+            }
+            | Ev::SeqStart {
+                tag,
+                raw_tag,
+                tag_prefix_len,
+                ..
+            }
+            | Ev::MapStart {
+                tag,
+                raw_tag,
+                tag_prefix_len,
+                ..
+            } => {
                 *tag = SfTag::None;
                 *raw_tag = None;
+                *tag_prefix_len = 0;
                 true
             }
             Ev::SeqEnd { .. } | Ev::MapEnd { .. } | Ev::Taken { .. } => false,
@@ -257,6 +284,10 @@ pub(crate) trait Events<'de> {
 
     #[cfg(feature = "properties")]
     fn property_syntax(&self) -> PropertySyntax;
+
+    /// Return the resolvers used for `$(...)` substitutions, if configured.
+    #[cfg(feature = "properties")]
+    fn ros_substitutions(&self) -> Option<&Rc<RosSubstitutions>>;
 }
 
 #[cold]
@@ -285,12 +316,39 @@ pub(super) struct ReplayEvents<'a> {
     ///   different nested nodes should create nested replay sources (which we do during
     ///   recursive merge expansion).
     ref_override: Option<Location>,
+    /// Scalar resolution inputs of the source the events were captured from.
+    #[cfg(feature = "properties")]
+    ctx: ReplayContext,
+}
 
+/// Scalar resolution inputs that a [`ReplayEvents`] shares with the source its events were
+/// captured from, so replayed scalars resolve like live ones.
+#[derive(Clone, Default)]
+pub(super) struct ReplayContext {
     #[cfg(feature = "properties")]
     property_map: Option<Rc<HashMap<String, String>>>,
 
     #[cfg(feature = "properties")]
     property_syntax: PropertySyntax,
+
+    #[cfg(feature = "properties")]
+    ros_substitutions: Option<Rc<RosSubstitutions>>,
+}
+
+impl ReplayContext {
+    /// The context of `events`, for replaying events captured from it.
+    pub(super) fn of<'e>(events: &(impl Events<'e> + ?Sized)) -> Self {
+        #[cfg(not(feature = "properties"))]
+        let _ = events;
+        Self {
+            #[cfg(feature = "properties")]
+            property_map: events.property_map().cloned(),
+            #[cfg(feature = "properties")]
+            property_syntax: events.property_syntax(),
+            #[cfg(feature = "properties")]
+            ros_substitutions: events.ros_substitutions().cloned(),
+        }
+    }
 }
 
 impl<'a> ReplayEvents<'a> {
@@ -298,22 +356,19 @@ impl<'a> ReplayEvents<'a> {
     ///
     /// Arguments:
     /// - `buf`: previously captured events.
+    /// - `ctx`: resolution inputs of the source `buf` was captured from.
     ///
     /// Called by:
     /// - Merge expansion and recorded key/value deserialization.
-    pub(super) fn new(
-        buf: Vec<Ev<'a>>,
-        #[cfg(feature = "properties")] property_map: Option<Rc<HashMap<String, String>>>,
-        #[cfg(feature = "properties")] property_syntax: PropertySyntax,
-    ) -> Self {
+    pub(super) fn new(buf: Vec<Ev<'a>>, ctx: ReplayContext) -> Self {
+        #[cfg(not(feature = "properties"))]
+        let _ = ctx;
         Self {
             buf,
             idx: 0,
             ref_override: None,
             #[cfg(feature = "properties")]
-            property_map,
-            #[cfg(feature = "properties")]
-            property_syntax,
+            ctx,
         }
     }
 
@@ -332,17 +387,16 @@ impl<'a> ReplayEvents<'a> {
     pub(super) fn with_reference(
         buf: Vec<Ev<'a>>,
         reference: Location,
-        #[cfg(feature = "properties")] property_map: Option<Rc<HashMap<String, String>>>,
-        #[cfg(feature = "properties")] property_syntax: PropertySyntax,
+        ctx: ReplayContext,
     ) -> Self {
+        #[cfg(not(feature = "properties"))]
+        let _ = ctx;
         Self {
             buf,
             idx: 0,
             ref_override: Some(reference),
             #[cfg(feature = "properties")]
-            property_map,
-            #[cfg(feature = "properties")]
-            property_syntax,
+            ctx,
         }
     }
 }
@@ -394,11 +448,16 @@ impl<'a> Events<'a> for ReplayEvents<'a> {
 
     #[cfg(feature = "properties")]
     fn property_map(&self) -> Option<&Rc<HashMap<String, String>>> {
-        self.property_map.as_ref()
+        self.ctx.property_map.as_ref()
     }
 
     #[cfg(feature = "properties")]
     fn property_syntax(&self) -> PropertySyntax {
-        self.property_syntax
+        self.ctx.property_syntax
+    }
+
+    #[cfg(feature = "properties")]
+    fn ros_substitutions(&self) -> Option<&Rc<RosSubstitutions>> {
+        self.ctx.ros_substitutions.as_ref()
     }
 }
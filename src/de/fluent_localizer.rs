@@ -0,0 +1,854 @@
+//! Bundled translations and a [Fluent](https://projectfluent.org/) based localizer.
+//!
+//! This module is feature-gated behind the `fluent` feature.
+//!
+//! [`FluentLocalizer`] is both a [`Localizer`] and a [`MessageFormatter`], so it translates
+//! error messages as well as location suffixes, snippet labels, validation messages, budget
+//! breaches and warnings. Catalogs for English, German, French, Spanish and Japanese are
+//! bundled, see [`FluentLocalizer::BUNDLED_LANGUAGES`]. [`FluentLocalizer::with_ftl`] adds or
+//! replaces messages from a user-provided `.ftl` catalog, and [`FluentLocalizer::from_ftl`]
+//! builds a localizer for any other language; [`bundled_catalog`] returns a bundled catalog
+//! to start from. Messages missing from a catalog fall back to the English wording of
+//! [`UserMessageFormatter`](crate::UserMessageFormatter).
+//!
+//! Values embedded in messages, such as keys, type names, the expectations reported by Serde
+//! and text produced by dependencies, are not translated.
+//!
+//! ```rust
+//! use serde_saphyr::FluentLocalizer;
+//!
+//! let localizer = FluentLocalizer::bundled("de-CH").unwrap();
+//! let err = serde_saphyr::from_str::<String>("").unwrap_err();
+//! assert_eq!(
+//!     err.render_with_formatter(&localizer),
+//!     "unerwartetes Dateiende in Zeile 1, Spalte 1"
+//! );
+//!
+//! let localizer = localizer
+//!     .with_ftl("error-eof = Die Datei ist unvollständig")
+//!     .unwrap();
+//! assert_eq!(
+//!     err.render_with_formatter(&localizer),
+//!     "Die Datei ist unvollständig in Zeile 1, Spalte 1"
+//! );
+//! ```
+
+use std::borrow::Cow;
+use std::fmt;
+
+use fluent_bundle::{FluentArgs, FluentBundle, FluentResource};
+use unic_langid::LanguageIdentifier;
+
+use crate::Location;
+use crate::budget::BudgetBreach;
+use crate::cancellation::Interruption;
+use crate::de_error::{Error, MessageFormatter};
+use crate::indentation::RequireIndent;
+use crate::localizer::{DEFAULT_ENGLISH_LOCALIZER, Localizer};
+use crate::message_formatters::user_format_message;
+use crate::warnings::{Warning, WarningKind, default_warning_message};
+
+const CATALOGS: [(&str, &str); 5] = [
+    ("en", include_str!("locales/en.ftl")),
+    ("de", include_str!("locales/de.ftl")),
+    ("fr", include_str!("locales/fr.ftl")),
+    ("es", include_str!("locales/es.ftl")),
+    ("ja", include_str!("locales/ja.ftl")),
+];
+
+/// The source of the bundled catalog for `language`, a language code such as `"de"`.
+///
+/// The English catalog lists every message id, with comments on where each is used.
+#[must_use]
+pub fn bundled_catalog(language: &str) -> Option<&'static str> {
+    CATALOGS
+        .iter()
+        .find(|(code, _)| *code == language)
+        .map(|(_, source)| *source)
+}
+
+/// Why a catalog could not be loaded.
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CatalogError {
+    /// The language is not a valid BCP 47 language tag.
+    InvalidLanguage(String),
+    /// No catalog is bundled for the language.
+    UnsupportedLanguage(String),
+    /// The `.ftl` source has syntax errors, one entry per error.
+    Syntax(Vec<String>),
+}
+
+impl fmt::Display for CatalogError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CatalogError::InvalidLanguage(language) => {
+                write!(f, "invalid language tag `{language}`")
+            }
+            CatalogError::UnsupportedLanguage(language) => {
+                write!(f, "no catalog is bundled for `{language}`")
+            }
+            CatalogError::Syntax(errors) => {
+                write!(f, "invalid Fluent catalog: {}", errors.join("; "))
+            }
+        }
+    }
+}
+
+impl std::error::Error for CatalogError {}
+
+/// A [`Localizer`] and [`MessageFormatter`] backed by Fluent catalogs.
+///
+/// Use it wherever a formatter is accepted, for example
+/// [`Error::render_with_formatter`] or [`crate::RenderOptions`].
+pub struct FluentLocalizer {
+    language: LanguageIdentifier,
+    bundle: FluentBundle<FluentResource>,
+}
+
+impl FluentLocalizer {
+    /// Languages with a bundled catalog.
+    pub const BUNDLED_LANGUAGES: &'static [&'static str] = &["en", "de", "fr", "es", "ja"];
+
+    /// Use the bundled catalog for `language`.
+    ///
+    /// Regional variants use the catalog of their language, so `"fr-CA"` gets French.
+    pub fn bundled(language: &str) -> Result<Self, CatalogError> {
+        let id = parse_language(language)?;
+        let source = bundled_catalog(id.language.as_str())
+            .ok_or_else(|| CatalogError::UnsupportedLanguage(language.to_owned()))?;
+        Self::with_language(id).with_ftl(source)
+    }
+
+    /// Use the bundled catalog for the first of `languages` that has one, in order of
+    /// preference, or the built-in English wording if none has.
+    ///
+    /// Invalid language tags are skipped, so this can take the entries of an
+    /// `Accept-Language` header as they are.
+    #[must_use]
+    pub fn negotiate<'a>(languages: impl IntoIterator<Item = &'a str>) -> Self {
+        languages
+            .into_iter()
+            .find_map(|language| Self::bundled(language).ok())
+            .unwrap_or_else(|| Self::with_language("en".parse().unwrap_or_default()))
+    }
+
+    /// Use only the catalog in `ftl` for `language`, a language without a bundled catalog
+    /// or a replacement for one.
+    pub fn from_ftl(language: &str, ftl: &str) -> Result<Self, CatalogError> {
+        Self::with_language(parse_language(language)?).with_ftl(ftl)
+    }
+
+    /// Add the messages of the catalog in `ftl`, replacing messages with the same id.
+    pub fn with_ftl(mut self, ftl: &str) -> Result<Self, CatalogError> {
+        let resource = FluentResource::try_new(ftl.to_owned()).map_err(|(_, errors)| {
+            CatalogError::Syntax(
+                errors
+                    .iter()
+                    .map(|error| {
+                        let line = ftl[..error.pos.start.min(ftl.len())].lines().count().max(1);
+                        format!("line {line}: {error}")
+                    })
+                    .collect(),
+            )
+        })?;
+        self.bundle.add_resource_overriding(resource);
+        Ok(self)
+    }
+
+    /// The language of this localizer, such as `de-CH`.
+    #[must_use]
+    pub fn language(&self) -> String {
+        self.language.to_string()
+    }
+
+    fn with_language(language: LanguageIdentifier) -> Self {
+        let mut bundle = FluentBundle::new(vec![language.clone()]);
+        // Isolation marks would end up in plain-text output.
+        bundle.set_use_isolating(false);
+        Self { language, bundle }
+    }
+
+    /// Format message `id`, or `None` if the catalog lacks it or it did not format cleanly.
+    fn message(&self, id: &str, args: Option<&FluentArgs>) -> Option<String> {
+        let pattern = self.bundle.get_message(id)?.value()?;
+        let mut errors = Vec::new();
+        let text = self.bundle.format_pattern(pattern, args, &mut errors);
+        errors.is_empty().then(|| text.into_owned())
+    }
+
+    fn located(&self, id: &str, loc: Location, mut args: FluentArgs<'_>) -> Option<String> {
+        args.set("line", loc.line());
+        args.set("column", loc.column());
+        self.message(id, Some(&args))
+    }
+
+    fn error_message(&self, err: &Error) -> Option<String> {
+        let mut args = FluentArgs::new();
+        let id = match err {
+            Error::Eof { .. } => "error-eof",
+            Error::MultipleDocuments { .. } => "error-multiple-documents",
+            Error::Unexpected { expected, .. } => {
+                args.set("expected", *expected);
+                "error-unexpected"
+            }
+            Error::MergeValueNotMapOrSeqOfMaps { .. } => "error-merge-value-not-map-or-seq-of-maps",
+            Error::MergeKeyNotAllowed { .. } => "error-merge-key-not-allowed",
+            Error::InvalidBinaryBase64 { .. } => "error-invalid-binary-base64",
+            Error::BinaryNotUtf8 { .. } => "error-binary-not-utf8",
+            Error::TaggedScalarCannotDeserializeIntoString { .. } => {
+                "error-tagged-scalar-into-string"
+            }
+            Error::UnexpectedSequenceEnd { .. } => "error-unexpected-sequence-end",
+            Error::UnexpectedMappingEnd { .. } => "error-unexpected-mapping-end",
+            Error::InvalidBooleanStrict { .. } => "error-invalid-boolean-strict",
+            Error::InvalidCharNull { .. } | Error::NullIntoString { .. } => {
+                "error-null-not-allowed"
+            }
+            Error::InvalidCharNotSingleScalar { .. } => "error-invalid-char-not-single-scalar",
+            Error::BytesNotSupportedMissingBinaryTag { .. } => "error-bytes-missing-binary-tag",
+            Error::UnexpectedValueForUnit { .. } => "error-unexpected-value-for-unit",
+            Error::ExpectedEmptyMappingForUnitStruct { .. } => {
+                "error-expected-empty-mapping-for-unit-struct"
+            }
+            Error::UnexpectedContainerEndWhileSkippingNode { .. } => {
+                "error-unexpected-container-end"
+            }
+            Error::UnexpectedContainerEndWhileReadingKeyNode { .. } => {
+                "error-unexpected-container-end-reading-key"
+            }
+            Error::InternalSeedReusedForMapKey { .. } => "error-internal-seed-reused-for-map-key",
+            Error::ValueRequestedBeforeKey { .. } => "error-value-requested-before-key",
+            Error::ExpectedStringKeyForExternallyTaggedEnum { .. } => {
+                "error-expected-string-key-for-enum"
+            }
+            Error::ExternallyTaggedEnumExpectedScalarOrMapping { .. } => {
+                "error-enum-expected-scalar-or-mapping"
+            }
+            Error::UnexpectedValueForUnitEnumVariant { .. } => {
+                "error-unexpected-value-for-unit-variant"
+            }
+            Error::ExpectedMappingEndAfterEnumVariantValue { .. } => {
+                "error-expected-mapping-end-after-variant"
+            }
+            Error::InvalidUtf8Input => "error-invalid-utf8-input",
+            Error::AliasReplayCounterOverflow { .. } => "error-too-complex",
+            Error::AliasReplayLimitExceeded {
+                total_replayed_events,
+                max_total_replayed_events,
+                ..
+            } => {
+                args.set("total", *total_replayed_events);
+                args.set("max", *max_total_replayed_events);
+                "error-alias-replay-limit"
+            }
+            Error::AliasExpansionLimitExceeded {
+                anchor_id,
+                expansions,
+                max_expansions_per_anchor,
+                ..
+            } => {
+                args.set("anchor", *anchor_id);
+                args.set("expansions", *expansions);
+                args.set("max", *max_expansions_per_anchor);
+                "error-alias-expansion-limit"
+            }
+            Error::AliasReplayStackDepthExceeded {
+                depth, max_depth, ..
+            } => {
+                args.set("depth", *depth);
+                args.set("max", *max_depth);
+                "error-alias-stack-depth"
+            }
+            Error::FoldedBlockScalarMustIndentContent { .. } => "error-folded-block-scalar-indent",
+            Error::InternalDepthUnderflow { .. } => "error-internal-depth-underflow",
+            Error::InternalRecursionStackEmpty { .. } => "error-internal-recursion-stack-empty",
+            Error::RecursiveReferencesRequireWeakTypes { .. } => "error-recursive-reference",
+            Error::InvalidScalar { ty, .. } => {
+                args.set("type", *ty);
+                "error-invalid-scalar"
+            }
+            Error::NonFiniteFloat { value, .. } => {
+                args.set("value", value.as_str());
+                "error-non-finite-float"
+            }
+            Error::SerdeInvalidType {
+                unexpected,
+                expected,
+                ..
+            } => {
+                args.set("unexpected", unexpected.as_str());
+                args.set("expected", expected.as_str());
+                "error-invalid-type"
+            }
+            Error::SerdeInvalidValue {
+                unexpected,
+                expected,
+                ..
+            } => {
+                args.set("unexpected", unexpected.as_str());
+                args.set("expected", expected.as_str());
+                "error-invalid-value"
+            }
+            Error::SerdeUnknownVariant {
+                variant, expected, ..
+            } => {
+                args.set("variant", variant.as_str());
+                args.set("expected", expected.join(", "));
+                "error-unknown-variant"
+            }
+            Error::SerdeUnknownField {
+                field, expected, ..
+            } => {
+                args.set("field", field.as_str());
+                args.set("expected", expected.join(", "));
+                "error-unknown-field"
+            }
+            Error::SerdeMissingField { field, .. } => {
+                args.set("field", *field);
+                "error-missing-field"
+            }
+            Error::DuplicateMappingKey { key: Some(key), .. } => {
+                args.set("key", key.as_str());
+                "error-duplicate-key"
+            }
+            Error::DuplicateMappingKey { key: None, .. } => "error-duplicate-key-unnamed",
+            Error::DuplicateOmapKey { .. } => "error-duplicate-omap-key",
+            Error::SetValueNotNull { .. } => "error-set-value-not-null",
+            Error::InvalidPairsEntry { .. } => "error-invalid-pairs-entry",
+            Error::RawYamlUnavailable { .. } => "error-raw-yaml-unavailable",
+            Error::TaggedEnumMismatch { tagged, target, .. } => {
+                args.set("tagged", tagged.as_str());
+                args.set("target", *target);
+                "error-tagged-enum-mismatch"
+            }
+            Error::ContainerEndMismatch { .. } => "error-container-end-mismatch",
+            Error::UnknownAnchor { .. } => "error-unknown-anchor",
+            Error::CyclicInclude { .. } => "error-cyclic-include",
+            Error::UnsupportedIncludeForm { .. } => "error-unsupported-include-form",
+            Error::ResolverError { .. } => "error-include-failed",
+            Error::UnresolvedProperty { name, .. } => {
+                args.set("name", name.as_str());
+                "error-unresolved-property"
+            }
+            Error::InvalidPropertyName { name, .. } => {
+                args.set("name", name.as_str());
+                "error-invalid-property-name"
+            }
+            Error::PropertyRequiredButUnset { name, message, .. } => {
+                args.set("name", name.as_str());
+                args.set("message", message.as_str());
+                if message.is_empty() {
+                    "error-property-unset"
+                } else {
+                    "error-property-unset-message"
+                }
+            }
+            Error::PropertyRequiredButEmpty { name, message, .. } => {
+                args.set("name", name.as_str());
+                args.set("message", message.as_str());
+                if message.is_empty() {
+                    "error-property-empty"
+                } else {
+                    "error-property-empty-message"
+                }
+            }
+            Error::UnresolvedSubstitution { substitution, .. } => {
+                args.set("substitution", substitution.as_str());
+                "error-unresolved-substitution"
+            }
+            Error::InvalidSubstitution { substitution, .. } => {
+                args.set("substitution", substitution.as_str());
+                "error-invalid-substitution"
+            }
+            Error::Budget { breach, .. } => {
+                args.set("breach", self.budget_breach(breach)?);
+                "error-budget"
+            }
+            Error::Interrupted { reason, .. } => match reason {
+                Interruption::DeadlineExceeded => "error-deadline-exceeded",
+                Interruption::Cancelled => "error-cancelled",
+            },
+            Error::IOError { cause } => {
+                args.set("cause", cause.to_string());
+                "error-io"
+            }
+            Error::QuotingRequired { .. } => "error-quoting-required",
+            Error::CannotBorrowTransformedString { .. } => "error-cannot-borrow",
+            Error::IndentationError {
+                required, actual, ..
+            } => {
+                args.set("required", self.indent_requirement(required)?);
+                args.set("actual", *actual);
+                "error-indentation"
+            }
+            Error::ValidationErrors { errors, .. } => {
+                args.set("count", errors.len());
+                "validation-failed"
+            }
+            // Text from Serde, dependencies or hooks, or composed from the `Localizer` hooks.
+            Error::Message { .. }
+            | Error::InvalidOptions { .. }
+            | Error::ExternalMessage { .. }
+            | Error::SerdeVariantId { .. }
+            | Error::AliasError { .. }
+            | Error::HookError { .. }
+            | Error::WithSnippet { .. }
+            | Error::ValidationError { .. } => return None,
+        };
+        self.message(id, Some(&args))
+    }
+
+    fn budget_breach(&self, breach: &BudgetBreach) -> Option<String> {
+        let mut args = FluentArgs::new();
+        let id = match breach {
+            BudgetBreach::Events { events } => {
+                args.set("count", *events);
+                "budget-events"
+            }
+            BudgetBreach::Aliases { aliases } => {
+                args.set("count", *aliases);
+                "budget-aliases"
+            }
+            BudgetBreach::Anchors { anchors } => {
+                args.set("count", *anchors);
+                "budget-anchors"
+            }
+            BudgetBreach::Depth { depth } => {
+                args.set("count", *depth);
+                "budget-depth"
+            }
+            BudgetBreach::InclusionDepth { depth } => {
+                args.set("count", *depth);
+                "budget-inclusion-depth"
+            }
+            BudgetBreach::Documents { documents } => {
+                args.set("count", *documents);
+                "budget-documents"
+            }
+            BudgetBreach::Nodes { nodes } => {
+                args.set("count", *nodes);
+                "budget-nodes"
+            }
+            BudgetBreach::ScalarBytes { total_scalar_bytes } => {
+                args.set("count", *total_scalar_bytes);
+                "budget-scalar-bytes"
+            }
+            BudgetBreach::CommentBytes {
+                total_comment_bytes,
+            } => {
+                args.set("count", *total_comment_bytes);
+                "budget-comment-bytes"
+            }
+            BudgetBreach::MergeKeys { merge_keys } => {
+                args.set("count", *merge_keys);
+                "budget-merge-keys"
+            }
+            BudgetBreach::AliasAnchorRatio { aliases, anchors } => {
+                args.set("aliases", *aliases);
+                args.set("anchors", *anchors);
+                "budget-alias-anchor-ratio"
+            }
+            BudgetBreach::SequenceUnbalanced => "budget-unbalanced",
+            BudgetBreach::InputBytes { input_bytes } => {
+                args.set("count", *input_bytes);
+                "budget-input-bytes"
+            }
+        };
+        self.message(id, Some(&args))
+    }
+
+    fn indent_requirement(&self, required: &RequireIndent) -> Option<String> {
+        let mut args = FluentArgs::new();
+        let id = match required {
+            RequireIndent::Unchecked => "indent-unchecked",
+            RequireIndent::Divisible(width) | RequireIndent::Uniform(Some(width)) => {
+                args.set("width", *width);
+                if matches!(required, RequireIndent::Divisible(_)) {
+                    "indent-divisible"
+                } else {
+                    "indent-uniform-width"
+                }
+            }
+            RequireIndent::Even => "indent-even",
+            RequireIndent::Uniform(None) => "indent-uniform",
+        };
+        self.message(id, Some(&args))
+    }
+}
+
+impl fmt::Debug for FluentLocalizer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FluentLocalizer")
+            .field("language", &self.language())
+            .finish_non_exhaustive()
+    }
+}
+
+impl Localizer for FluentLocalizer {
+    fn attach_location<'a>(&self, base: Cow<'a, str>, loc: Location) -> Cow<'a, str> {
+        if loc == Location::UNKNOWN {
+            return base;
+        }
+        let mut args = FluentArgs::new();
+        args.set("message", base.as_ref());
+        match self.located("location-suffix", loc, args) {
+            Some(text) => Cow::Owned(text),
+            None => DEFAULT_ENGLISH_LOCALIZER.attach_location(base, loc),
+        }
+    }
+
+    fn did_you_mean(&self, suggestion: &str) -> String {
+        let mut args = FluentArgs::new();
+        args.set("suggestion", suggestion);
+        self.message("did-you-mean", Some(&args))
+            .unwrap_or_else(|| DEFAULT_ENGLISH_LOCALIZER.did_you_mean(suggestion))
+    }
+
+    fn attach_suggestion<'a>(&self, base: Cow<'a, str>, suggestion: &str) -> Cow<'a, str> {
+        let mut args = FluentArgs::new();
+        args.set("message", base.as_ref());
+        args.set("hint", self.did_you_mean(suggestion));
+        match self.message("suggestion-suffix", Some(&args)) {
+            Some(text) => Cow::Owned(text),
+            None => Cow::Owned(format!("{base}; {}", self.did_you_mean(suggestion))),
+        }
+    }
+
+    fn root_path_label(&self) -> Cow<'static, str> {
+        self.message("root-path", None)
+            .map_or_else(|| DEFAULT_ENGLISH_LOCALIZER.root_path_label(), Cow::Owned)
+    }
+
+    fn alias_defined_at(&self, defined: Location) -> String {
+        match self.located("alias-defined-at", defined, FluentArgs::new()) {
+            Some(text) => format!(" ({text})"),
+            None => DEFAULT_ENGLISH_LOCALIZER.alias_defined_at(defined),
+        }
+    }
+
+    fn validation_issue_line(
+        &self,
+        resolved_path: &str,
+        entry: &str,
+        loc: Option<Location>,
+    ) -> String {
+        let mut args = FluentArgs::new();
+        args.set("path", resolved_path);
+        args.set("entry", entry);
+        let Some(base) = self.message("validation-issue", Some(&args)) else {
+            return DEFAULT_ENGLISH_LOCALIZER.validation_issue_line(resolved_path, entry, loc);
+        };
+        match loc {
+            Some(l) if l != Location::UNKNOWN => {
+                self.attach_location(Cow::Owned(base), l).into_owned()
+            }
+            _ => base,
+        }
+    }
+
+    fn defined(&self) -> Cow<'static, str> {
+        self.message("label-defined", None)
+            .map_or_else(|| DEFAULT_ENGLISH_LOCALIZER.defined(), Cow::Owned)
+    }
+
+    fn defined_here(&self) -> Cow<'static, str> {
+        self.message("label-defined-here", None)
+            .map_or_else(|| DEFAULT_ENGLISH_LOCALIZER.defined_here(), Cow::Owned)
+    }
+
+    fn value_used_here(&self) -> Cow<'static, str> {
+        self.message("label-value-used-here", None)
+            .map_or_else(|| DEFAULT_ENGLISH_LOCALIZER.value_used_here(), Cow::Owned)
+    }
+
+    fn defined_window(&self) -> Cow<'static, str> {
+        self.message("label-defined-window", None)
+            .map_or_else(|| DEFAULT_ENGLISH_LOCALIZER.defined_window(), Cow::Owned)
+    }
+
+    fn validation_base_message(&self, entry: &str, resolved_path: &str) -> String {
+        let mut args = FluentArgs::new();
+        args.set("entry", entry);
+        args.set("path", resolved_path);
+        self.message("validation-base", Some(&args))
+            .unwrap_or_else(|| {
+                DEFAULT_ENGLISH_LOCALIZER.validation_base_message(entry, resolved_path)
+            })
+    }
+
+    fn invalid_here(&self, base: &str) -> String {
+        let mut args = FluentArgs::new();
+        args.set("message", base);
+        self.message("validation-invalid-here", Some(&args))
+            .unwrap_or_else(|| DEFAULT_ENGLISH_LOCALIZER.invalid_here(base))
+    }
+
+    fn value_comes_from_the_anchor(&self, def: Location) -> String {
+        match self.located("value-from-anchor", def, FluentArgs::new()) {
+            Some(text) => format!("  | {text}"),
+            None => DEFAULT_ENGLISH_LOCALIZER.value_comes_from_the_anchor(def),
+        }
+    }
+
+    fn snippet_location_prefix(&self, loc: Location) -> String {
+        if loc == Location::UNKNOWN {
+            return String::new();
+        }
+        self.located("snippet-location", loc, FluentArgs::new())
+            .unwrap_or_else(|| DEFAULT_ENGLISH_LOCALIZER.snippet_location_prefix(loc))
+    }
+}
+
+impl MessageFormatter for FluentLocalizer {
+    fn localizer(&self) -> &dyn Localizer {
+        self
+    }
+
+    fn format_message<'a>(&self, err: &'a Error) -> Cow<'a, str> {
+        if let Error::WithSnippet { error, .. } = err {
+            return self.format_message(error);
+        }
+        let Some(text) = self.error_message(err) else {
+            return user_format_message(self, err);
+        };
+        match err.suggestion() {
+            Some(suggestion) => self.attach_suggestion(Cow::Owned(text), suggestion),
+            None => Cow::Owned(text),
+        }
+    }
+
+    fn format_warning<'a>(&self, warning: &'a Warning) -> Cow<'a, str> {
+        let mut args = FluentArgs::new();
+        let id = match &warning.kind {
+            WarningKind::Yaml11Boolean {
+                value,
+                interpreted_as,
+            } => {
+                args.set("value", value.as_str());
+                args.set("boolean", interpreted_as.to_string());
+                "warning-yaml11-boolean"
+            }
+            WarningKind::LegacyOctal {
+                value,
+                read_as_octal,
+            } => {
+                args.set("value", value.as_str());
+                args.set(
+                    "digits",
+                    value.trim_start_matches(['+', '-']).trim_start_matches('0'),
+                );
+                if *read_as_octal {
+                    "warning-legacy-octal"
+                } else {
+                    "warning-legacy-octal-string"
+                }
+            }
+            WarningKind::IgnoredKey { key } => {
+                args.set("key", key.as_str());
+                "warning-ignored-key"
+            }
+            WarningKind::DuplicateKeyOverride { key: Some(key) } => {
+                args.set("key", key.as_str());
+                "warning-duplicate-key"
+            }
+            WarningKind::DuplicateKeyOverride { key: None } => "warning-duplicate-key-unnamed",
+            WarningKind::MergeOverride { key: Some(key) } => {
+                args.set("key", key.as_str());
+                "warning-merge-override"
+            }
+            WarningKind::MergeOverride { key: None } => "warning-merge-override-unnamed",
+        };
+        self.message(id, Some(&args))
+            .map_or_else(|| default_warning_message(warning), Cow::Owned)
+    }
+}
+
+fn parse_language(language: &str) -> Result<LanguageIdentifier, CatalogError> {
+    language
+        .parse()
+        .map_err(|_| CatalogError::InvalidLanguage(language.to_owned()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CATALOGS, CatalogError, FluentLocalizer, bundled_catalog};
+    use crate::Location;
+    use crate::budget::BudgetBreach;
+    use crate::de_error::{Error, MessageFormatter, UserMessageFormatter};
+    use crate::localizer::Localizer;
+    use crate::path_map::PathKey;
+    use rstest::rstest;
+    use std::collections::BTreeSet;
+
+    fn message_ids(source: &str) -> BTreeSet<&str> {
+        source
+            .lines()
+            .filter_map(|line| line.split_once(" ="))
+            .map(|(id, _)| id)
+            .filter(|id| !id.starts_with(['#', ' ']))
+            .collect()
+    }
+
+    #[test]
+    fn bundled_catalogs_define_the_same_messages() {
+        let reference = message_ids(CATALOGS[0].1);
+        assert!(reference.len() > 100);
+        for (language, source) in CATALOGS {
+            assert_eq!(message_ids(source), reference, "catalog `{language}`");
+            FluentLocalizer::bundled(language).unwrap();
+        }
+    }
+
+    fn samples() -> Vec<Error> {
+        let location = Location::new(3, 7);
+        vec![
+            Error::Eof { location },
+            Error::InvalidBooleanStrict { location },
+            Error::SerdeMissingField {
+                field: "name",
+                location,
+            },
+            Error::DuplicateMappingKey {
+                key: Some("port".to_owned()),
+                location,
+            },
+            Error::Budget {
+                breach: BudgetBreach::Depth { depth: 65 },
+                location,
+            },
+            Error::IndentationError {
+                required: crate::indentation::RequireIndent::Even,
+                actual: 3,
+                location,
+            },
+            Error::ValidationErrors {
+                source: crate::ValidationSource::Custom,
+                errors: vec![Error::Eof { location }],
+            },
+        ]
+    }
+
+    #[rstest]
+    #[case("de")]
+    #[case("fr")]
+    #[case("es")]
+    #[case("ja")]
+    fn bundled_languages_translate(#[case] language: &str) {
+        let localizer = FluentLocalizer::bundled(language).unwrap();
+        for err in samples() {
+            let english = UserMessageFormatter.format_message(&err).into_owned();
+            assert_ne!(
+                localizer.format_message(&err),
+                english,
+                "{language}: {english}"
+            );
+        }
+    }
+
+    #[test]
+    fn english_catalog_matches_user_formatter() {
+        let localizer = FluentLocalizer::bundled("en").unwrap();
+        for err in samples()
+            .into_iter()
+            .filter(|err| !matches!(err, Error::Budget { .. } | Error::ValidationErrors { .. }))
+        {
+            assert_eq!(
+                localizer.format_message(&err),
+                UserMessageFormatter.format_message(&err)
+            );
+        }
+    }
+
+    #[test]
+    fn plurals_and_numbers() {
+        let localizer = FluentLocalizer::bundled("de").unwrap();
+        let err = |count| Error::ValidationErrors {
+            source: crate::ValidationSource::Custom,
+            errors: (0..count).map(|_| Error::InvalidUtf8Input).collect(),
+        };
+        assert_eq!(
+            localizer.format_message(&err(1)),
+            "Validierung für 1 Dokument fehlgeschlagen"
+        );
+        assert_eq!(
+            localizer.format_message(&err(2)),
+            "Validierung für 2 Dokumente fehlgeschlagen"
+        );
+        assert_eq!(
+            localizer.attach_location("x".into(), Location::new(12, 3)),
+            "x in Zeile 12, Spalte 3"
+        );
+    }
+
+    #[test]
+    fn missing_messages_fall_back_to_english() {
+        let localizer = FluentLocalizer::from_ftl("nl", "error-eof = onverwacht einde").unwrap();
+        let eof = Error::Eof {
+            location: Location::UNKNOWN,
+        };
+        assert_eq!(localizer.format_message(&eof), "onverwacht einde");
+        let strict = Error::InvalidBooleanStrict {
+            location: Location::UNKNOWN,
+        };
+        assert_eq!(
+            localizer.format_message(&strict),
+            UserMessageFormatter.format_message(&strict)
+        );
+        assert_eq!(
+            localizer.attach_location("x".into(), Location::new(1, 2)),
+            "x at line 1, column 2"
+        );
+        let warning = crate::Warning {
+            kind: crate::WarningKind::IgnoredKey {
+                key: "k".to_owned(),
+            },
+            location: Location::UNKNOWN,
+            path: PathKey::new(),
+        };
+        assert_eq!(
+            localizer.format_warning(&warning),
+            warning.default_message()
+        );
+    }
+
+    #[test]
+    fn unknown_variables_fall_back_to_english() {
+        let localizer =
+            FluentLocalizer::from_ftl("en", "error-missing-field = missing { $name }").unwrap();
+        let err = Error::SerdeMissingField {
+            field: "id",
+            location: Location::UNKNOWN,
+        };
+        assert_eq!(localizer.format_message(&err), "missing field `id`");
+    }
+
+    #[rstest]
+    #[case::regional("pt-BR", "pt-BR")]
+    #[case::invalid("not a tag", "not a tag")]
+    fn bundled_rejects(#[case] language: &str, #[case] reported: &str) {
+        let err = FluentLocalizer::bundled(language).unwrap_err();
+        assert!(err.to_string().contains(reported), "{err}");
+    }
+
+    #[test]
+    fn negotiate_picks_the_first_bundled_language() {
+        assert_eq!(
+            FluentLocalizer::negotiate(["pt-BR", "??", "fr-CA", "de"]).language(),
+            "fr-CA"
+        );
+        assert_eq!(FluentLocalizer::negotiate(["pt"]).language(), "en");
+        assert_eq!(bundled_catalog("pt"), None);
+    }
+
+    #[test]
+    fn syntax_errors_report_lines() {
+        let err = FluentLocalizer::from_ftl("en", "ok = fine\n\nbroken = { $x\n").unwrap_err();
+        match err {
+            CatalogError::Syntax(errors) => {
+                assert!(errors[0].starts_with("line 3: "), "{errors:?}");
+            }
+            other => panic!("unexpected error: {other:?}"),
+        }
+    }
+}
@@ -42,6 +42,8 @@ pub struct ParserStack<'input> {
     next_source_id: u32,
     active_source_ids: Vec<u32>,
     pub(crate) resolved_sources: HashMap<u32, RecordedSource>,
+    source_registry: Option<crate::SourceRegistry>,
+    interrupt: Option<crate::cancellation::InterruptCheck>,
 }
 impl<'input> ParserStack<'input> {
     #[cfg(test)]
@@ -66,6 +68,8 @@ impl<'input> ParserStack<'input> {
             next_source_id: 1,
             active_source_ids: Vec::new(),
             resolved_sources: HashMap::new(),
+            source_registry: None,
+            interrupt: None,
         }
     }
     pub fn set_resolver(
@@ -103,8 +107,51 @@ impl<'input> ParserStack<'input> {
             text: snippet.map(|snippet| Rc::clone(&snippet.text)),
             include_location,
         };
+        if let Some(registry) = &self.source_registry {
+            // `resolve` marks the include as active before pushing its parser.
+            let resolved_id = self
+                .active_ids
+                .last()
+                .filter(|(depth, _)| *depth == self.inner.stack().len() + 1)
+                .map(|(_, id)| id.as_str());
+            registry.record(
+                source_id,
+                &recorded.name,
+                resolved_id,
+                recorded.text.clone(),
+                parent_source_id,
+                include_location,
+            );
+        }
         self.resolved_sources.insert(source_id, recorded);
     }
+    /// Record every source of this stack, including those already pushed, in `registry`.
+    pub(crate) fn set_source_registry(&mut self, registry: crate::SourceRegistry) {
+        registry.clear();
+        for (id, source) in &self.resolved_sources {
+            registry.record(
+                *id,
+                &source.name,
+                None,
+                source.text.clone(),
+                source.parent_source_id,
+                source.include_location,
+            );
+        }
+        self.source_registry = Some(registry);
+    }
+    /// Check `interrupt` before and after calling the include resolver.
+    pub(crate) fn set_interrupt_check(&mut self, interrupt: crate::cancellation::InterruptCheck) {
+        self.interrupt = Some(interrupt);
+    }
+    fn check_interrupt(&self, location: crate::Location) -> Result<(), crate::de_error::Error> {
+        match &self.interrupt {
+            Some(interrupt) => interrupt
+                .check()
+                .map_err(|reason| crate::de_error::Error::Interrupted { reason, location }),
+            None => Ok(()),
+        }
+    }
     pub(crate) fn push_str_parser_with_snippet(
         &mut self,
         parser: Parser<'input, StrInput<'input>>,
@@ -163,6 +210,7 @@ impl<'input> ParserStack<'input> {
         include_str: &str,
         location: crate::Location,
     ) -> Result<(), crate::de_error::Error> {
+        self.check_interrupt(location)?;
         let Some(resolver) = &mut self.include_resolver else {
             return Err(
                 crate::de_error::Error::msg("No include resolver set for parser stack.")
@@ -197,7 +245,10 @@ impl<'input> ParserStack<'input> {
                 .map(|limit| limit.saturating_sub(self.reader_bytes_read.get())),
             location,
         };
-        let resolved = match resolver(request) {
+        let resolved = resolver(request);
+        // The resolver may have spent the remaining time reading the include.
+        self.check_interrupt(location)?;
+        let resolved = match resolved {
             Ok(r) => r,
             Err(e) => {
                 let stack = self.inner.stack().into_iter().collect();
@@ -1,24 +1,22 @@
 use std::borrow::Cow;
-#[cfg(feature = "properties")]
-use std::collections::HashMap;
 use std::collections::HashSet;
 use std::mem;
-#[cfg(feature = "properties")]
-use std::rc::Rc;
 
 use granit_parser::ScalarStyle;
 
 use super::error::Error;
-use super::events::{Ev, Events, ReplayEvents};
-#[cfg(feature = "properties")]
-use super::options::PropertySyntax;
+use super::events::{Ev, Events, ReplayContext, ReplayEvents};
 use super::options::{DuplicateKeyPolicy, MergeKeyPolicy};
 use super::tags::SfTag;
+use super::warnings::{self, WarningKind};
 use crate::location::Location;
 use crate::parse_scalars::scalar_is_nullish;
 
+/// Enum variant name selected by a tag: `Widget` for `!Widget`, or the part after the
+/// `%TAG` prefix for a tag written with a directive handle (`!k8s!Deployment`).
 pub(super) fn simple_tagged_enum_name(
     raw_tag: &Option<Cow<'_, str>>,
+    tag_prefix_len: usize,
     tag: &SfTag,
 ) -> Option<String> {
     if !matches!(tag, SfTag::Other) {
@@ -26,12 +24,13 @@ pub(super) fn simple_tagged_enum_name(
     }
 
     let raw = raw_tag.as_deref()?;
-    let mut candidate =
-        if let Some(inner) = raw.strip_prefix("!<").and_then(|s| s.strip_suffix('>')) {
-            inner
-        } else {
-            raw
-        };
+    let mut candidate = if tag_prefix_len > 0 {
+        raw.get(tag_prefix_len..)?
+    } else if let Some(inner) = raw.strip_prefix("!<").and_then(|s| s.strip_suffix('>')) {
+        inner
+    } else {
+        raw
+    };
 
     if let Some(stripped) = candidate.strip_prefix("tag:yaml.org,2002:") {
         candidate = stripped;
@@ -382,6 +381,7 @@ pub(super) fn capture_node<'a>(ev: &mut dyn Events<'a>) -> Result<KeyNode<'a>, E
             value,
             tag,
             raw_tag,
+            tag_prefix_len,
             style,
             anchor,
             location,
@@ -390,6 +390,7 @@ pub(super) fn capture_node<'a>(ev: &mut dyn Events<'a>) -> Result<KeyNode<'a>, E
                 value,
                 tag,
                 raw_tag,
+                tag_prefix_len,
                 style,
                 anchor,
                 location,
@@ -403,6 +404,7 @@ pub(super) fn capture_node<'a>(ev: &mut dyn Events<'a>) -> Result<KeyNode<'a>, E
             anchor,
             tag,
             raw_tag,
+            tag_prefix_len,
             location,
         } => {
             let fingerprint_tag = canonical_node_key_tag(tag, &raw_tag, SfTag::Seq);
@@ -410,6 +412,7 @@ pub(super) fn capture_node<'a>(ev: &mut dyn Events<'a>) -> Result<KeyNode<'a>, E
                 anchor,
                 tag,
                 raw_tag,
+                tag_prefix_len,
                 location,
             }];
             let mut elements = Vec::new();
@@ -447,6 +450,7 @@ pub(super) fn capture_node<'a>(ev: &mut dyn Events<'a>) -> Result<KeyNode<'a>, E
             anchor,
             tag,
             raw_tag,
+            tag_prefix_len,
             location,
         } => {
             let fingerprint_tag = canonical_node_key_tag(tag, &raw_tag, SfTag::Map);
@@ -454,6 +458,7 @@ pub(super) fn capture_node<'a>(ev: &mut dyn Events<'a>) -> Result<KeyNode<'a>, E
                 anchor,
                 tag,
                 raw_tag,
+                tag_prefix_len,
                 location,
             }];
             let mut entries = Vec::new();
@@ -504,21 +509,24 @@ pub(super) fn simple_tagged_node_name(event: &Ev<'_>) -> Option<(String, Locatio
         Ev::Scalar {
             tag,
             raw_tag,
+            tag_prefix_len,
             location,
             ..
         }
         | Ev::SeqStart {
             tag,
             raw_tag,
+            tag_prefix_len,
             location,
             ..
         }
         | Ev::MapStart {
             tag,
             raw_tag,
+            tag_prefix_len,
             location,
             ..
-        } => simple_tagged_enum_name(raw_tag, tag).map(|name| (name, *location)),
+        } => simple_tagged_enum_name(raw_tag, *tag_prefix_len, tag).map(|name| (name, *location)),
         _ => None,
     }
 }
@@ -551,12 +559,14 @@ pub(super) fn externally_tagged_payload_as_map_events(
         anchor: 0,
         tag: SfTag::None,
         raw_tag: None,
+        tag_prefix_len: 0,
         location: tag_location,
     });
     events.push(Ev::Scalar {
         value: Cow::Owned(variant),
         tag: SfTag::String,
         raw_tag: None,
+        tag_prefix_len: 0,
         style: ScalarStyle::Plain,
         anchor: 0,
         location: tag_location,
@@ -687,6 +697,18 @@ pub(super) fn apply_duplicate_key_policy_to_entries(
 ) -> Result<Vec<PendingEntry<'_>>, Error> {
     let last_wins = matches!(duplicate_keys, DuplicateKeyPolicy::LastWins);
     if last_wins {
+        if warnings::is_collecting() {
+            // Report each repeated key at the occurrence that replaces the earlier one.
+            let mut seen = HashSet::with_capacity(entries.len());
+            for entry in &entries {
+                let fingerprint = entry.key.fingerprint();
+                if !seen.insert(fingerprint.clone().into_owned()) {
+                    warnings::warn(entry.key.location(), || WarningKind::DuplicateKeyOverride {
+                        key: fingerprint.stringy_scalar_value().map(str::to_owned),
+                    });
+                }
+            }
+        }
         entries.reverse();
     }
 
@@ -734,23 +756,15 @@ pub(super) fn apply_duplicate_key_policy_to_entries(
 ///
 /// Called by:
 /// - Mapping deserialization when encountering `<<: value`.
-pub(super) fn pending_entries_from_events(
-    events: Vec<Ev<'_>>,
+pub(super) fn pending_entries_from_events<'a>(
+    events: Vec<Ev<'a>>,
     location: Location,
     reference_location: Location,
     merge_keys: MergeKeyPolicy,
     duplicate_keys: DuplicateKeyPolicy,
-    #[cfg(feature = "properties")] property_map: Option<Rc<HashMap<String, String>>>,
-    #[cfg(feature = "properties")] property_syntax: PropertySyntax,
-) -> Result<Vec<PendingEntry<'_>>, Error> {
-    let mut replay = ReplayEvents::with_reference(
-        events,
-        reference_location,
-        #[cfg(feature = "properties")]
-        property_map.clone(),
-        #[cfg(feature = "properties")]
-        property_syntax,
-    );
+    ctx: &ReplayContext,
+) -> Result<Vec<PendingEntry<'a>>, Error> {
+    let mut replay = ReplayEvents::with_reference(events, reference_location, ctx.clone());
     match replay.peek()? {
         Some(Ev::Scalar { value, style, .. }) if scalar_is_nullish(value.as_ref(), style) => {
             Ok(Vec::new())
@@ -783,10 +797,7 @@ pub(super) fn pending_entries_from_events(
                             element_ref_loc,
                             merge_keys,
                             duplicate_keys,
-                            #[cfg(feature = "properties")]
-                            property_map.clone(),
-                            #[cfg(feature = "properties")]
-                            property_syntax,
+                            ctx,
                         )?); // recursive
                     }
                     None => {
@@ -826,10 +837,7 @@ pub(super) fn pending_entries_from_live_events<'a>(
     merge_keys: MergeKeyPolicy,
     duplicate_keys: DuplicateKeyPolicy,
 ) -> Result<Vec<PendingEntry<'a>>, Error> {
-    #[cfg(feature = "properties")]
-    let property_map = ev.property_map().map(Rc::clone);
-    #[cfg(feature = "properties")]
-    let property_syntax = ev.property_syntax();
+    let ctx = &ReplayContext::of(ev);
     match ev.peek()? {
         Some(Ev::Scalar { value, style, .. }) if scalar_is_nullish(value.as_ref(), style) => {
             let _ = ev.next()?;
@@ -846,10 +854,7 @@ pub(super) fn pending_entries_from_live_events<'a>(
                 merge_reference_location,
                 merge_keys,
                 duplicate_keys,
-                #[cfg(feature = "properties")]
-                property_map,
-                #[cfg(feature = "properties")]
-                property_syntax,
+                ctx,
             )
         }
         Some(Ev::SeqStart { .. }) => {
@@ -871,10 +876,7 @@ pub(super) fn pending_entries_from_live_events<'a>(
                             element_ref_loc,
                             merge_keys,
                             duplicate_keys,
-                            #[cfg(feature = "properties")]
-                            property_map.clone(),
-                            #[cfg(feature = "properties")]
-                            property_syntax,
+                            ctx,
                         )?);
                     }
                     None => return Err(Error::eof().with_location(ev.last_location())),
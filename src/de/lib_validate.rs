@@ -11,7 +11,7 @@ use crate::de_error::collect_validator_issues;
 use crate::de_error::redact_issue;
 use crate::live_events::LiveEvents;
 use crate::parse_scalars::scalar_document_is_empty_or_null;
-use crate::path_map::PathMap;
+use crate::path_map::{PathKey, PathMap};
 use serde_core::de::DeserializeOwned;
 use std::io::Read;
 
@@ -20,9 +20,8 @@ use garde::Validate;
 #[cfg(feature = "validator")]
 use validator::Validate as ValidatorValidate;
 
-use crate::de_error::ValidationSource;
+use crate::de_error::{ValidationIssue, ValidationSource};
 
-#[cfg(any(feature = "garde", feature = "validator"))]
 fn synthesized_null_error_should_be_eof(error: &Error) -> bool {
     !error.is_validation_error()
 }
@@ -51,8 +50,56 @@ fn validator_validation_error(errors: &validator::ValidationErrors, locations: &
     )
 }
 
-/// Deserialize a single YAML document with configurable [`Options`], and also
-/// return a map from validation paths to source [`Location`]s.
+/// Issues reported by a custom validation hook for one document.
+///
+/// Passed to the closure of [`from_str_with_validator`] and its siblings. Paths use the
+/// key spelling of the YAML document (see [`crate::path_key!`]); serde-saphyr resolves
+/// them to source locations and renders the issues like those of the `garde` and
+/// `validator` integrations, including snippets, localization and redaction.
+#[derive(Debug, Default)]
+pub struct ValidationContext {
+    issues: Vec<ValidationIssue>,
+}
+
+impl ValidationContext {
+    /// Report that the value at `path` is invalid. `code` is a short identifier passed
+    /// to [`crate::Localizer::override_external_message`]; `message` is displayed.
+    pub fn report(&mut self, path: PathKey, code: impl Into<String>, message: impl Into<String>) {
+        self.issues
+            .push(ValidationIssue::new(path, code).with_message(message));
+    }
+
+    /// Report a fully constructed issue, for example one carrying structured parameters.
+    pub fn push(&mut self, issue: ValidationIssue) {
+        self.issues.push(issue);
+    }
+
+    /// Whether any issue was reported so far.
+    #[must_use]
+    pub fn has_issues(&self) -> bool {
+        !self.issues.is_empty()
+    }
+}
+
+/// Run a custom validation hook and turn its reported issues into a validation error.
+fn run_custom_validator<T, F>(validate: F, value: &T, locations: &PathMap) -> Result<(), Error>
+where
+    F: FnOnce(&T, &mut ValidationContext),
+{
+    let mut ctx = ValidationContext::default();
+    validate(value, &mut ctx);
+    if ctx.issues.is_empty() {
+        return Ok(());
+    }
+    Err(Error::validation_error(
+        ValidationSource::Custom,
+        ctx.issues.into_iter().map(redact_issue).collect(),
+        locations.clone(),
+    ))
+}
+
+/// Deserialize a single YAML document with configurable [`Options`] and check it
+/// against the map from validation paths to source [`Location`]s.
 fn from_str_with_options_and_path_recorder_validated<T, F>(
     input: &str,
     options: Options,
@@ -62,33 +109,12 @@ where
     T: DeserializeOwned,
     F: FnOnce(&T, &PathMap) -> Result<(), Error>,
 {
-    let input = normalize_str_input(input);
-    let snippet_ctx = StrSnippetContext::new(input, options.with_snippet, options.crop_radius);
-    let cfg = crate::de::Cfg::from_options(&options);
-    let mut src = LiveEvents::from_str(input, options);
-    let mut recorder = crate::path_map::PathRecorder::new();
-    let wrap_err = |e, src: &LiveEvents<'_>| snippet_ctx.attach_snippet(e, src);
-
-    let value = run_with_document_scope(
-        &mut src,
-        |src| {
-            let value = crate::de::with_root_redaction(
-                crate::de::YamlDeserializer::new_with_path_recorder(src, cfg, &mut recorder),
-                |de| T::deserialize(de),
-            )?;
-            validate(&value, &recorder.map)?;
-            Ok(value)
-        },
-        wrap_err,
+    let (value, _) = crate::de::source_map::from_str_with_path_recorder(
+        input,
+        options,
+        validate,
         synthesized_null_error_should_be_eof,
     )?;
-
-    enforce_single_document_and_finish(
-        &mut src,
-        "use from_multiple or from_multiple_with_options",
-        wrap_err,
-    )?;
-
     Ok(value)
 }
 
@@ -651,3 +677,106 @@ where
             .map_err(|errors| validator_validation_error(&errors, locs))
     })
 }
+
+/// Deserialize a single YAML document from a YAML string and check it with a custom
+/// validation hook.
+///
+/// The hook reports problems through [`ValidationContext`]; if it reports any, the
+/// returned error lists them with snippets pointing at the offending values, and if a
+/// value comes from an anchor, serde-saphyr will also tell where it is defined.
+///
+/// ```rust
+/// use serde::Deserialize;
+/// use serde_saphyr::path_key;
+///
+/// #[derive(Debug, Deserialize)]
+/// struct Range { min: u32, max: u32 }
+///
+/// let err = serde_saphyr::from_str_with_validator("min: 5\nmax: 3\n", |r: &Range, ctx| {
+///     if r.min > r.max {
+///         ctx.report(path_key!["max"], "range", "max must not be below min");
+///     }
+/// })
+/// .unwrap_err();
+/// assert!(err.to_string().contains("max must not be below min"));
+/// ```
+pub fn from_str_with_validator<T, F>(input: &str, validate: F) -> Result<T, Error>
+where
+    T: DeserializeOwned,
+    F: FnOnce(&T, &mut ValidationContext),
+{
+    from_str_with_options_and_validator(input, Options::default(), validate)
+}
+
+/// Deserialize a single YAML document with configurable [`Options`] and check it with a
+/// custom validation hook. See [`from_str_with_validator`].
+pub fn from_str_with_options_and_validator<T, F>(
+    input: &str,
+    options: Options,
+    validate: F,
+) -> Result<T, Error>
+where
+    T: DeserializeOwned,
+    F: FnOnce(&T, &mut ValidationContext),
+{
+    from_str_with_options_and_path_recorder_validated::<T, _>(input, options, |value, locs| {
+        run_custom_validator(validate, value, locs)
+    })
+}
+
+/// Deserialize multiple YAML documents with configurable [`Options`] and check each with a
+/// custom validation hook. Issues of all documents are collected into one error.
+pub fn from_multiple_with_options_and_validator<T, F>(
+    input: &str,
+    options: Options,
+    validate: F,
+) -> Result<Vec<T>, Error>
+where
+    T: DeserializeOwned,
+    F: Fn(&T, &mut ValidationContext),
+{
+    from_multiple_with_options_validated(input, options, ValidationSource::Custom, |value, locs| {
+        run_custom_validator(&validate, value, locs)
+    })
+}
+
+/// Deserialize a single YAML document from a reader with options and check it with a
+/// custom validation hook.
+/// Snippets are attached on a best-effort basis for streamed root input, are available for
+/// included text sources, and may be unavailable for included reader sources.
+pub fn from_reader_with_options_and_validator<R, T, F>(
+    reader: R,
+    options: Options,
+    validate: F,
+) -> Result<T, Error>
+where
+    R: Read,
+    T: DeserializeOwned,
+    F: FnOnce(&T, &mut ValidationContext),
+{
+    from_reader_with_options_validated(
+        reader,
+        options,
+        |value, locs| run_custom_validator(validate, value, locs),
+        "use read_with_options_and_validator to obtain the iterator",
+    )
+}
+
+/// Create an iterator over YAML documents from a reader, checking each with a custom
+/// validation hook.
+/// Root streamed input gets snippets on a best-effort basis; included text sources retain full
+/// snippets, while included reader sources may not have snippet text available.
+pub fn read_with_options_and_validator<'a, R, T, F>(
+    reader: &'a mut R,
+    options: Options,
+    validate: F,
+) -> impl Iterator<Item = Result<T, Error>> + 'a
+where
+    R: Read + 'a,
+    T: DeserializeOwned + 'a,
+    F: Fn(&T, &mut ValidationContext) + 'a,
+{
+    read_with_options_validated(reader, options, move |value, locs| {
+        run_custom_validator(&validate, value, locs)
+    })
+}
@@ -0,0 +1,1215 @@
+//! Style checks for YAML files, in the spirit of yamllint.
+//!
+//! [`check`] runs the rules enabled in a [`LintConfig`] over the parser event stream and
+//! the source text and returns [`LintIssue`]s with [`Location`]s, which render with the same
+//! snippets as deserialization errors. Rules and their options use yamllint names, so a
+//! configuration can be written in YAML and loaded with [`LintConfig::from_yaml`]:
+//!
+//! ```yaml
+//! rules:
+//!   line-length:
+//!     max: 120
+//!   key-ordering: enable
+//!   truthy:
+//!     level: error
+//!     allowed-values: ["true", "false", "on", "off"]
+//! ```
+//!
+//! Rules not mentioned keep their defaults; see [`LintConfig::default`].
+//!
+//! ```rust
+//! use serde_saphyr::lint::{self, LintConfig};
+//!
+//! let issues = lint::check("---\nenabled: yes \n", &LintConfig::default())?;
+//! let rules: Vec<&str> = issues.iter().map(|issue| issue.rule).collect();
+//! assert_eq!(rules, ["truthy", "trailing-spaces"]);
+//! # Ok::<(), serde_saphyr::Error>(())
+//! ```
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use annotate_snippets::Level;
+use granit_parser::{Event, Parser, ScalarStyle, Span as ParserSpan, StructureStyle};
+use serde_core::de::{self, Deserialize, Deserializer, MapAccess, SeqAccess, Visitor};
+
+use crate::de_error::{RenderOptions, RenderTarget, SnippetMode};
+use crate::location::location_from_span;
+use crate::{Error, Location, Schema, Span};
+
+/// Same default as [`crate::Options::crop_radius`].
+const DEFAULT_CROP_RADIUS: usize = 64;
+
+/// Spellings checked by the `truthy` rule, as in yamllint. `y` and `n` are YAML 1.1
+/// booleans too, but are left alone: they are common keys (`x`, `y`, `z` coordinates).
+const TRUTHY_VALUES: &[&str] = &[
+    "YES", "Yes", "yes", "NO", "No", "no", "TRUE", "True", "true", "FALSE", "False", "false", "ON",
+    "On", "on", "OFF", "Off", "off",
+];
+
+/// Names of all rules, as used in configuration files and [`LintIssue::rule`].
+pub const RULE_NAMES: &[&str] = &[
+    "line-length",
+    "trailing-spaces",
+    "truthy",
+    "key-ordering",
+    "document-start",
+    "comments",
+    "empty-values",
+    "quoted-strings",
+    "anchors",
+];
+
+/// How serious a [`LintIssue`] is.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LintLevel {
+    /// Worth fixing, but not a reason to reject the file.
+    Warning,
+    /// The file should be rejected.
+    #[default]
+    Error,
+}
+
+/// `line-length`: lines must not be longer than `max` characters.
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineLength {
+    /// Level of reported issues.
+    pub level: LintLevel,
+    /// Longest allowed line, in characters.
+    pub max: usize,
+    /// Allow longer lines that hold a single word, such as a URL, after the indentation
+    /// and an optional `- ` or `# `.
+    pub allow_non_breakable_words: bool,
+}
+
+impl Default for LineLength {
+    fn default() -> Self {
+        Self {
+            level: LintLevel::Error,
+            max: 80,
+            allow_non_breakable_words: true,
+        }
+    }
+}
+
+/// `truthy`: plain `yes`, `no`, `true`, `false`, `on` and `off` in any of their YAML 1.1
+/// case variants (`Yes`, `OFF`, `True`, ...) must be spelled as one of `allowed_values`.
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Truthy {
+    /// Level of reported issues.
+    pub level: LintLevel,
+    /// Accepted spellings.
+    pub allowed_values: Vec<String>,
+    /// Also check mapping keys.
+    pub check_keys: bool,
+}
+
+impl Default for Truthy {
+    fn default() -> Self {
+        Self {
+            level: LintLevel::Warning,
+            allowed_values: vec!["true".to_owned(), "false".to_owned()],
+            check_keys: true,
+        }
+    }
+}
+
+/// `document-start`: documents must (or must not) begin with an explicit `---`.
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DocumentStart {
+    /// Level of reported issues.
+    pub level: LintLevel,
+    /// Require the marker if `true`, forbid it if `false`.
+    pub present: bool,
+}
+
+impl Default for DocumentStart {
+    fn default() -> Self {
+        Self {
+            level: LintLevel::Warning,
+            present: true,
+        }
+    }
+}
+
+/// `comments`: spacing around `#`.
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Comments {
+    /// Level of reported issues.
+    pub level: LintLevel,
+    /// Require a space after `#`. Shebang lines and `###` banners are accepted.
+    pub require_starting_space: bool,
+    /// Minimal number of spaces between content and a comment on the same line.
+    pub min_spaces_from_content: usize,
+}
+
+impl Default for Comments {
+    fn default() -> Self {
+        Self {
+            level: LintLevel::Warning,
+            require_starting_space: true,
+            min_spaces_from_content: 2,
+        }
+    }
+}
+
+/// `empty-values`: mapping values must not be left empty (implicit `null`).
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EmptyValues {
+    /// Level of reported issues.
+    pub level: LintLevel,
+    /// Check block mappings.
+    pub forbid_in_block_mappings: bool,
+    /// Check flow mappings.
+    pub forbid_in_flow_mappings: bool,
+}
+
+impl Default for EmptyValues {
+    fn default() -> Self {
+        Self {
+            level: LintLevel::Error,
+            forbid_in_block_mappings: true,
+            forbid_in_flow_mappings: true,
+        }
+    }
+}
+
+/// Quote character accepted by [`QuotedStrings`].
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum QuoteType {
+    /// Single or double quotes.
+    #[default]
+    Any,
+    /// Single quotes only.
+    Single,
+    /// Double quotes only.
+    Double,
+}
+
+/// When [`QuotedStrings`] requires quotes.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum QuoteRequirement {
+    /// Every string value must be quoted.
+    #[default]
+    Always,
+    /// Quotes are optional, but must be of the configured type when used.
+    Never,
+    /// Quotes are only allowed where a plain scalar would be read differently.
+    OnlyWhenNeeded,
+}
+
+/// `quoted-strings`: quoting of string values. Keys and block scalars are not checked.
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuotedStrings {
+    /// Level of reported issues.
+    pub level: LintLevel,
+    /// Accepted quote character.
+    pub quote_type: QuoteType,
+    /// Where quotes are required.
+    pub required: QuoteRequirement,
+}
+
+impl Default for QuotedStrings {
+    fn default() -> Self {
+        Self {
+            level: LintLevel::Error,
+            quote_type: QuoteType::Any,
+            required: QuoteRequirement::Always,
+        }
+    }
+}
+
+/// Enabled rules and their settings. A `None` field disables the rule.
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LintConfig {
+    /// `line-length`.
+    pub line_length: Option<LineLength>,
+    /// `trailing-spaces`: lines must not end with spaces or tabs.
+    pub trailing_spaces: Option<LintLevel>,
+    /// `truthy`.
+    pub truthy: Option<Truthy>,
+    /// `key-ordering`: keys of each mapping must be in ascending order.
+    pub key_ordering: Option<LintLevel>,
+    /// `document-start`.
+    pub document_start: Option<DocumentStart>,
+    /// `comments`.
+    pub comments: Option<Comments>,
+    /// `empty-values`.
+    pub empty_values: Option<EmptyValues>,
+    /// `quoted-strings`.
+    pub quoted_strings: Option<QuotedStrings>,
+    /// `anchors`: every anchor must be used by an alias.
+    pub anchors: Option<LintLevel>,
+}
+
+impl Default for LintConfig {
+    /// The yamllint `default` configuration: `line-length` (80), `trailing-spaces`,
+    /// `truthy`, `document-start`, `comments` and `anchors` are enabled; `key-ordering`,
+    /// `empty-values` and `quoted-strings` are disabled.
+    fn default() -> Self {
+        Self {
+            line_length: Some(LineLength::default()),
+            trailing_spaces: Some(LintLevel::Error),
+            truthy: Some(Truthy::default()),
+            key_ordering: None,
+            document_start: Some(DocumentStart::default()),
+            comments: Some(Comments::default()),
+            empty_values: None,
+            quoted_strings: None,
+            anchors: Some(LintLevel::Warning),
+        }
+    }
+}
+
+impl LintConfig {
+    /// A configuration with every rule disabled.
+    #[must_use]
+    pub fn none() -> Self {
+        Self {
+            line_length: None,
+            trailing_spaces: None,
+            truthy: None,
+            key_ordering: None,
+            document_start: None,
+            comments: None,
+            empty_values: None,
+            quoted_strings: None,
+            anchors: None,
+        }
+    }
+
+    /// Read a configuration file. Its `rules` mapping adjusts [`LintConfig::default`].
+    ///
+    /// Each rule is set to `enable`, `disable` or a mapping of options, which enables the
+    /// rule too. Every rule accepts `level: warning` or `level: error`.
+    ///
+    /// ```rust
+    /// use serde_saphyr::lint::LintConfig;
+    ///
+    /// let config = LintConfig::from_yaml("rules:\n  line-length:\n    max: 120\n")?;
+    /// assert_eq!(config.line_length.unwrap().max, 120);
+    ///
+    /// let err = LintConfig::from_yaml("rules:\n  line-lenght: disable\n").unwrap_err();
+    /// assert!(err.to_string().contains("line-length"));
+    /// # Ok::<(), serde_saphyr::Error>(())
+    /// ```
+    pub fn from_yaml(yaml: &str) -> Result<Self, Error> {
+        crate::from_str(yaml)
+    }
+}
+
+impl<'de> Deserialize<'de> for LintConfig {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct ConfigVisitor;
+
+        impl<'de> Visitor<'de> for ConfigVisitor {
+            type Value = LintConfig;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a lint configuration mapping")
+            }
+
+            fn visit_unit<E: de::Error>(self) -> Result<LintConfig, E> {
+                Ok(LintConfig::default())
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<LintConfig, A::Error> {
+                let mut config = LintConfig::default();
+                while let Some(key) = map.next_key::<String>()? {
+                    match key.as_str() {
+                        "rules" => map.next_value_seed(RulesSeed(&mut config))?,
+                        other => return Err(de::Error::unknown_field(other, &["rules"])),
+                    }
+                }
+                Ok(config)
+            }
+        }
+
+        deserializer.deserialize_any(ConfigVisitor)
+    }
+}
+
+/// Applies the `rules` mapping to a configuration.
+struct RulesSeed<'a>(&'a mut LintConfig);
+
+impl<'de> de::DeserializeSeed<'de> for RulesSeed<'_> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de> Visitor<'de> for RulesSeed<'_> {
+    type Value = ();
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a mapping of rule names to settings")
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<(), E> {
+        Ok(())
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<(), A::Error> {
+        let config = self.0;
+        while let Some(name) = map.next_key::<String>()? {
+            let setting: RuleSetting = map.next_value()?;
+            let applied = match name.as_str() {
+                "line-length" => apply(&mut config.line_length, setting),
+                "trailing-spaces" => apply(&mut config.trailing_spaces, setting),
+                "truthy" => apply(&mut config.truthy, setting),
+                "key-ordering" => apply(&mut config.key_ordering, setting),
+                "document-start" => apply(&mut config.document_start, setting),
+                "comments" => apply(&mut config.comments, setting),
+                "empty-values" => apply(&mut config.empty_values, setting),
+                "quoted-strings" => apply(&mut config.quoted_strings, setting),
+                "anchors" => apply(&mut config.anchors, setting),
+                other => return Err(de::Error::unknown_variant(other, RULE_NAMES)),
+            };
+            applied.map_err(|msg| de::Error::custom(format!("rule `{name}`: {msg}")))?;
+        }
+        Ok(())
+    }
+}
+
+/// Value of one rule in a configuration file.
+enum RuleSetting {
+    Enable,
+    Disable,
+    Options(Vec<(String, OptionValue)>),
+}
+
+impl<'de> Deserialize<'de> for RuleSetting {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct SettingVisitor;
+
+        impl<'de> Visitor<'de> for SettingVisitor {
+            type Value = RuleSetting;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("`enable`, `disable` or a mapping of rule options")
+            }
+
+            fn visit_bool<E: de::Error>(self, v: bool) -> Result<RuleSetting, E> {
+                Ok(if v {
+                    RuleSetting::Enable
+                } else {
+                    RuleSetting::Disable
+                })
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<RuleSetting, E> {
+                match v {
+                    "enable" => Ok(RuleSetting::Enable),
+                    "disable" => Ok(RuleSetting::Disable),
+                    _ => Err(de::Error::invalid_value(de::Unexpected::Str(v), &self)),
+                }
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<RuleSetting, A::Error> {
+                let mut options = Vec::new();
+                while let Some(key) = map.next_key::<String>()? {
+                    options.push((key, map.next_value()?));
+                }
+                Ok(RuleSetting::Options(options))
+            }
+        }
+
+        deserializer.deserialize_any(SettingVisitor)
+    }
+}
+
+/// Value of one rule option: a scalar (kept as text) or a list of scalars.
+enum OptionValue {
+    Scalar(String),
+    List(Vec<String>),
+}
+
+impl<'de> Deserialize<'de> for OptionValue {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct OptionVisitor;
+
+        impl<'de> Visitor<'de> for OptionVisitor {
+            type Value = OptionValue;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a scalar or a list of scalars")
+            }
+
+            fn visit_bool<E: de::Error>(self, v: bool) -> Result<OptionValue, E> {
+                Ok(OptionValue::Scalar(v.to_string()))
+            }
+
+            fn visit_i64<E: de::Error>(self, v: i64) -> Result<OptionValue, E> {
+                Ok(OptionValue::Scalar(v.to_string()))
+            }
+
+            fn visit_u64<E: de::Error>(self, v: u64) -> Result<OptionValue, E> {
+                Ok(OptionValue::Scalar(v.to_string()))
+            }
+
+            fn visit_f64<E: de::Error>(self, v: f64) -> Result<OptionValue, E> {
+                Ok(OptionValue::Scalar(v.to_string()))
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<OptionValue, E> {
+                Ok(OptionValue::Scalar(v.to_owned()))
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<OptionValue, A::Error> {
+                let mut items = Vec::new();
+                while let Some(item) = seq.next_element::<OptionValue>()? {
+                    match item {
+                        OptionValue::Scalar(item) => items.push(item),
+                        OptionValue::List(_) => {
+                            return Err(de::Error::custom("nested lists are not allowed"));
+                        }
+                    }
+                }
+                Ok(OptionValue::List(items))
+            }
+        }
+
+        deserializer.deserialize_any(OptionVisitor)
+    }
+}
+
+impl OptionValue {
+    fn scalar(&self) -> Result<&str, String> {
+        match self {
+            OptionValue::Scalar(value) => Ok(value),
+            OptionValue::List(_) => Err("expected a scalar, found a list".to_owned()),
+        }
+    }
+
+    fn parse<T: std::str::FromStr>(&self, what: &str) -> Result<T, String> {
+        let value = self.scalar()?;
+        value
+            .parse()
+            .map_err(|_| format!("expected {what}, found `{value}`"))
+    }
+
+    fn level(&self) -> Result<LintLevel, String> {
+        match self.scalar()? {
+            "warning" => Ok(LintLevel::Warning),
+            "error" => Ok(LintLevel::Error),
+            other => Err(format!("expected `warning` or `error`, found `{other}`")),
+        }
+    }
+}
+
+/// Settings of one rule that can be changed from a configuration file.
+trait RuleOptions: Default {
+    fn set(&mut self, key: &str, value: &OptionValue) -> Result<(), String>;
+}
+
+fn apply<R: RuleOptions>(slot: &mut Option<R>, setting: RuleSetting) -> Result<(), String> {
+    match setting {
+        RuleSetting::Disable => *slot = None,
+        RuleSetting::Enable => {
+            slot.get_or_insert_with(R::default);
+        }
+        RuleSetting::Options(options) => {
+            let rule = slot.get_or_insert_with(R::default);
+            for (key, value) in &options {
+                rule.set(key, value)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn unknown_option(key: &str) -> Result<(), String> {
+    Err(format!("unknown option `{key}`"))
+}
+
+impl RuleOptions for LintLevel {
+    fn set(&mut self, key: &str, value: &OptionValue) -> Result<(), String> {
+        match key {
+            "level" => *self = value.level()?,
+            _ => return unknown_option(key),
+        }
+        Ok(())
+    }
+}
+
+impl RuleOptions for LineLength {
+    fn set(&mut self, key: &str, value: &OptionValue) -> Result<(), String> {
+        match key {
+            "level" => self.level = value.level()?,
+            "max" => self.max = value.parse("a line length")?,
+            "allow-non-breakable-words" => {
+                self.allow_non_breakable_words = value.parse("`true` or `false`")?;
+            }
+            _ => return unknown_option(key),
+        }
+        Ok(())
+    }
+}
+
+impl RuleOptions for Truthy {
+    fn set(&mut self, key: &str, value: &OptionValue) -> Result<(), String> {
+        match key {
+            "level" => self.level = value.level()?,
+            "allowed-values" => match value {
+                OptionValue::List(values) => self.allowed_values.clone_from(values),
+                OptionValue::Scalar(value) => self.allowed_values = vec![value.clone()],
+            },
+            "check-keys" => self.check_keys = value.parse("`true` or `false`")?,
+            _ => return unknown_option(key),
+        }
+        Ok(())
+    }
+}
+
+impl RuleOptions for DocumentStart {
+    fn set(&mut self, key: &str, value: &OptionValue) -> Result<(), String> {
+        match key {
+            "level" => self.level = value.level()?,
+            "present" => self.present = value.parse("`true` or `false`")?,
+            _ => return unknown_option(key),
+        }
+        Ok(())
+    }
+}
+
+impl RuleOptions for Comments {
+    fn set(&mut self, key: &str, value: &OptionValue) -> Result<(), String> {
+        match key {
+            "level" => self.level = value.level()?,
+            "require-starting-space" => {
+                self.require_starting_space = value.parse("`true` or `false`")?;
+            }
+            "min-spaces-from-content" => {
+                self.min_spaces_from_content = value.parse("a number of spaces")?;
+            }
+            _ => return unknown_option(key),
+        }
+        Ok(())
+    }
+}
+
+impl RuleOptions for EmptyValues {
+    fn set(&mut self, key: &str, value: &OptionValue) -> Result<(), String> {
+        match key {
+            "level" => self.level = value.level()?,
+            "forbid-in-block-mappings" => {
+                self.forbid_in_block_mappings = value.parse("`true` or `false`")?;
+            }
+            "forbid-in-flow-mappings" => {
+                self.forbid_in_flow_mappings = value.parse("`true` or `false`")?;
+            }
+            _ => return unknown_option(key),
+        }
+        Ok(())
+    }
+}
+
+impl RuleOptions for QuotedStrings {
+    fn set(&mut self, key: &str, value: &OptionValue) -> Result<(), String> {
+        match key {
+            "level" => self.level = value.level()?,
+            "quote-type" => {
+                self.quote_type = match value.scalar()? {
+                    "any" => QuoteType::Any,
+                    "single" => QuoteType::Single,
+                    "double" => QuoteType::Double,
+                    other => {
+                        return Err(format!(
+                            "expected `any`, `single` or `double`, found `{other}`"
+                        ));
+                    }
+                };
+            }
+            "required" => {
+                self.required = match value.scalar()? {
+                    "true" => QuoteRequirement::Always,
+                    "false" => QuoteRequirement::Never,
+                    "only-when-needed" => QuoteRequirement::OnlyWhenNeeded,
+                    other => {
+                        return Err(format!(
+                            "expected `true`, `false` or `only-when-needed`, found `{other}`"
+                        ));
+                    }
+                };
+            }
+            _ => return unknown_option(key),
+        }
+        Ok(())
+    }
+}
+
+/// One finding of [`check`].
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LintIssue {
+    /// Name of the rule that reported the issue, one of [`RULE_NAMES`].
+    pub rule: &'static str,
+    /// Level configured for the rule.
+    pub level: LintLevel,
+    /// What is wrong.
+    pub message: String,
+    /// Where the issue is.
+    pub location: Location,
+}
+
+impl LintIssue {
+    /// Render this issue, message, rule and location, without a snippet.
+    #[must_use]
+    pub fn render_with_options(&self, options: RenderOptions<'_>) -> String {
+        let msg = format!("{} ({})", self.message, self.rule);
+        let rendered = options
+            .formatter
+            .localizer()
+            .attach_location(msg.into(), self.location)
+            .into_owned();
+        match options.target {
+            // Nothing here is markup yet, so every ESC comes from user text.
+            RenderTarget::Html => {
+                crate::de_snippet::to_html(&crate::de_snippet::neutralize_escapes(&rendered))
+            }
+            _ => rendered,
+        }
+    }
+
+    /// Render this issue with a snippet of `source`, the YAML text that was checked.
+    ///
+    /// `source_name` is shown in the snippet header, for example a file path.
+    #[must_use]
+    pub fn render_with_source(
+        &self,
+        source: &str,
+        source_name: &str,
+        mut options: RenderOptions<'_>,
+    ) -> String {
+        if options.snippets == SnippetMode::Off {
+            return self.render_with_options(options);
+        }
+
+        struct SnippetDisplay<'a> {
+            issue: &'a LintIssue,
+            source: &'a str,
+            source_name: &'a str,
+            options: RenderOptions<'a>,
+        }
+
+        impl fmt::Display for SnippetDisplay<'_> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                let level = match self.issue.level {
+                    LintLevel::Warning => Level::WARNING,
+                    LintLevel::Error => Level::ERROR,
+                };
+                let msg = format!("{} ({})", self.issue.message, self.issue.rule);
+                crate::de_snippet::Snippet::new(self.source, self.source_name, DEFAULT_CROP_RADIUS)
+                    .with_target(self.options.target)
+                    .fmt_or_fallback(
+                        f,
+                        level,
+                        self.options.formatter.localizer(),
+                        &msg,
+                        &self.issue.location,
+                    )
+            }
+        }
+
+        options.target = options.target.resolve();
+        let rendered = SnippetDisplay {
+            issue: self,
+            source,
+            source_name,
+            options,
+        }
+        .to_string();
+        match options.target {
+            RenderTarget::Html => crate::de_snippet::to_html(&rendered),
+            _ => rendered,
+        }
+    }
+}
+
+impl fmt::Display for LintIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.render_with_options(RenderOptions::default()))
+    }
+}
+
+/// Check `source` against the rules enabled in `config`.
+///
+/// Issues are sorted by location. Fails only if `source` is not valid YAML.
+pub fn check(source: &str, config: &LintConfig) -> Result<Vec<LintIssue>, Error> {
+    let lines = SourceLines::new(source);
+    let mut issues = Vec::new();
+    check_lines(source, &lines, config, &mut issues);
+    check_events(source, &lines, config, &mut issues)
+        .map_err(|err| err.with_snippet(source, DEFAULT_CROP_RADIUS))?;
+    issues.sort_by_key(|issue| (issue.location.line(), issue.location.column()));
+    Ok(issues)
+}
+
+/// Byte and character offsets of line starts, for building [`Location`]s.
+struct SourceLines<'a> {
+    source: &'a str,
+    /// `(byte offset, char offset)` of the start of each line.
+    starts: Vec<(usize, usize)>,
+}
+
+impl<'a> SourceLines<'a> {
+    fn new(source: &'a str) -> Self {
+        let mut starts = vec![(0, 0)];
+        let mut chars = 0;
+        for (byte, ch) in source.char_indices() {
+            chars += 1;
+            if ch == '\n' {
+                starts.push((byte + 1, chars));
+            }
+        }
+        Self { source, starts }
+    }
+
+    /// Location of `len` characters starting at byte offset `byte`.
+    fn location(&self, byte: usize, len: usize) -> Location {
+        let line = self.starts.partition_point(|(start, _)| *start <= byte) - 1;
+        let (line_byte, line_char) = self.starts[line];
+        let column = self.source[line_byte..byte].chars().count();
+        Location::new(line + 1, column + 1)
+            .with_span(Span::new((line_char + column) as u64, len as u64))
+    }
+}
+
+fn check_lines(
+    source: &str,
+    lines: &SourceLines<'_>,
+    config: &LintConfig,
+    out: &mut Vec<LintIssue>,
+) {
+    let mut offset = 0;
+    for raw in source.split_inclusive('\n') {
+        let line = raw.trim_end_matches(['\n', '\r']);
+
+        if let Some(rule) = &config.line_length {
+            let length = line.chars().count();
+            if length > rule.max && !(rule.allow_non_breakable_words && is_single_word(line)) {
+                let start = line
+                    .char_indices()
+                    .nth(rule.max)
+                    .map_or(line.len(), |(byte, _)| byte);
+                out.push(LintIssue {
+                    rule: "line-length",
+                    level: rule.level,
+                    message: format!("line too long ({length} > {} characters)", rule.max),
+                    location: lines.location(offset + start, length - rule.max),
+                });
+            }
+        }
+
+        if let Some(level) = config.trailing_spaces {
+            let trimmed = line.trim_end_matches([' ', '\t']);
+            if trimmed.len() < line.len() {
+                out.push(LintIssue {
+                    rule: "trailing-spaces",
+                    level,
+                    message: "trailing spaces".to_owned(),
+                    location: lines.location(offset + trimmed.len(), line.len() - trimmed.len()),
+                });
+            }
+        }
+
+        offset += raw.len();
+    }
+}
+
+/// Whether `line` is one word after indentation and an optional `- ` or `# ` prefix.
+fn is_single_word(line: &str) -> bool {
+    let rest = line.trim_start();
+    let rest = rest
+        .strip_prefix("- ")
+        .or_else(|| rest.strip_prefix('#'))
+        .unwrap_or(rest)
+        .trim_start();
+    !rest.is_empty() && !rest.contains([' ', '\t'])
+}
+
+/// Position of the node being read within its parent collection.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Role {
+    Key,
+    Value,
+    Item,
+    Root,
+}
+
+enum Frame {
+    Mapping {
+        flow: bool,
+        expect_key: bool,
+        last_key: Option<String>,
+    },
+    Sequence,
+}
+
+fn check_events(
+    source: &str,
+    lines: &SourceLines<'_>,
+    config: &LintConfig,
+    out: &mut Vec<LintIssue>,
+) -> Result<(), Error> {
+    let parser = Parser::new_from_str(source);
+    let mut stack: Vec<Frame> = Vec::new();
+    // Anchor id -> location of the anchored node, until an alias uses it.
+    let mut unused_anchors: BTreeMap<usize, Location> = BTreeMap::new();
+
+    for item in parser {
+        let (event, span) = item.map_err(Error::from_scan_error)?;
+        let location = location_from_span(&span);
+
+        // Role of a node event, advancing the parent mapping between keys and values.
+        let role = match &event {
+            Event::Scalar(..)
+            | Event::Alias(..)
+            | Event::SequenceStart(..)
+            | Event::MappingStart(..) => match stack.last_mut() {
+                Some(Frame::Mapping { expect_key, .. }) => {
+                    let role = if *expect_key { Role::Key } else { Role::Value };
+                    *expect_key = !*expect_key;
+                    role
+                }
+                Some(Frame::Sequence) => Role::Item,
+                None => Role::Root,
+            },
+            _ => Role::Root,
+        };
+
+        if let Some(anchor) = event_anchor(&event)
+            && config.anchors.is_some()
+        {
+            unused_anchors.insert(anchor, anchor_location(source, lines, &span, location));
+        }
+
+        match &event {
+            Event::DocumentStart(explicit, _) => {
+                if let Some(rule) = &config.document_start {
+                    if rule.present && !explicit {
+                        out.push(LintIssue {
+                            rule: "document-start",
+                            level: rule.level,
+                            message: "missing document start \"---\"".to_owned(),
+                            location,
+                        });
+                    } else if !rule.present && *explicit {
+                        out.push(LintIssue {
+                            rule: "document-start",
+                            level: rule.level,
+                            message: "found forbidden document start \"---\"".to_owned(),
+                            location,
+                        });
+                    }
+                }
+            }
+            Event::Alias(id) => {
+                unused_anchors.remove(id);
+            }
+            Event::Comment(text, _) => {
+                if let Some(rule) = &config.comments {
+                    check_comment(source, lines, rule, text, &span, out);
+                }
+            }
+            Event::Scalar(value, style, _, tag) => {
+                let is_key = role == Role::Key;
+                let untagged = tag.is_none();
+
+                if is_key
+                    && let Some(level) = config.key_ordering
+                    && let Some(Frame::Mapping { last_key, .. }) = stack.last_mut()
+                {
+                    if last_key
+                        .as_deref()
+                        .is_some_and(|last| value.as_ref() < last)
+                    {
+                        out.push(LintIssue {
+                            rule: "key-ordering",
+                            level,
+                            message: format!("wrong ordering of key \"{value}\" in mapping"),
+                            location,
+                        });
+                    }
+                    *last_key = Some(value.to_string());
+                }
+
+                if let Some(rule) = &config.truthy
+                    && *style == ScalarStyle::Plain
+                    && untagged
+                    && (rule.check_keys || !is_key)
+                    && TRUTHY_VALUES.contains(&value.as_ref())
+                    && !rule.allowed_values.iter().any(|allowed| allowed == value)
+                {
+                    out.push(LintIssue {
+                        rule: "truthy",
+                        level: rule.level,
+                        message: format!(
+                            "truthy value should be one of [{}]",
+                            rule.allowed_values.join(", ")
+                        ),
+                        location,
+                    });
+                }
+
+                if let Some(rule) = &config.empty_values
+                    && role == Role::Value
+                    && *style == ScalarStyle::Plain
+                    && untagged
+                    // Empty nodes are reported as `~` with an empty span.
+                    && span.is_empty()
+                    && let Some(Frame::Mapping { flow, .. }) = stack.last()
+                    && (if *flow {
+                        rule.forbid_in_flow_mappings
+                    } else {
+                        rule.forbid_in_block_mappings
+                    })
+                {
+                    let kind = if *flow { "flow" } else { "block" };
+                    out.push(LintIssue {
+                        rule: "empty-values",
+                        level: rule.level,
+                        message: format!("empty value in {kind} mapping"),
+                        location,
+                    });
+                }
+
+                if let Some(rule) = &config.quoted_strings
+                    && !is_key
+                    && untagged
+                    && let Some(message) = check_quotes(rule, value, *style)
+                {
+                    out.push(LintIssue {
+                        rule: "quoted-strings",
+                        level: rule.level,
+                        message,
+                        location,
+                    });
+                }
+            }
+            Event::SequenceStart(..) => stack.push(Frame::Sequence),
+            Event::MappingStart(style, ..) => stack.push(Frame::Mapping {
+                flow: *style == StructureStyle::Flow,
+                expect_key: true,
+                last_key: None,
+            }),
+            Event::SequenceEnd | Event::MappingEnd => {
+                stack.pop();
+            }
+            _ => {}
+        }
+    }
+
+    if let Some(level) = config.anchors {
+        for location in unused_anchors.into_values() {
+            out.push(LintIssue {
+                rule: "anchors",
+                level,
+                message: "anchor is never used".to_owned(),
+                location,
+            });
+        }
+    }
+    Ok(())
+}
+
+fn event_anchor(event: &Event<'_>) -> Option<usize> {
+    match event {
+        Event::Scalar(_, _, anchor, _)
+        | Event::SequenceStart(_, anchor, _)
+        | Event::MappingStart(_, anchor, _) => Some(*anchor).filter(|anchor| *anchor != 0),
+        _ => None,
+    }
+}
+
+/// Location of the `&name` token of an anchored node, or of the node if it is not found.
+fn anchor_location(
+    source: &str,
+    lines: &SourceLines<'_>,
+    span: &ParserSpan,
+    node: Location,
+) -> Location {
+    let Some(end) = span.start.byte_offset() else {
+        return node;
+    };
+    let Some(start) = source.get(..end).and_then(|before| before.rfind('&')) else {
+        return node;
+    };
+    let name_len = source[start + 1..]
+        .find(|ch: char| ch.is_whitespace() || ",[]{}".contains(ch))
+        .unwrap_or(source.len() - start - 1);
+    lines.location(start, source[start..=start + name_len].chars().count())
+}
+
+fn check_comment(
+    source: &str,
+    lines: &SourceLines<'_>,
+    rule: &Comments,
+    text: &str,
+    span: &ParserSpan,
+    out: &mut Vec<LintIssue>,
+) {
+    let Some(hash) = span.start.byte_offset() else {
+        return;
+    };
+
+    if rule.require_starting_space {
+        let shebang = hash == 0 && text.starts_with('!');
+        // `###` banners: the space is required after the last `#`.
+        let body = text.trim_start_matches('#');
+        if !body.is_empty() && !body.starts_with([' ', '\t']) && !shebang {
+            out.push(LintIssue {
+                rule: "comments",
+                level: rule.level,
+                message: "missing starting space in comment".to_owned(),
+                location: lines.location(hash, 1),
+            });
+        }
+    }
+
+    let line_start = source[..hash].rfind('\n').map_or(0, |i| i + 1);
+    let before = &source[line_start..hash];
+    let content = before.trim_end_matches([' ', '\t']);
+    if !content.trim_start().is_empty() {
+        let spaces = before.len() - content.len();
+        if spaces < rule.min_spaces_from_content {
+            out.push(LintIssue {
+                rule: "comments",
+                level: rule.level,
+                message: "too few spaces before comment".to_owned(),
+                location: lines.location(hash, 1),
+            });
+        }
+    }
+}
+
+fn check_quotes(rule: &QuotedStrings, value: &str, style: ScalarStyle) -> Option<String> {
+    let quote = match style {
+        ScalarStyle::SingleQuoted => "single",
+        ScalarStyle::DoubleQuoted => "double",
+        ScalarStyle::Plain => {
+            // Only plain scalars read as strings need quotes.
+            let needs = rule.required == QuoteRequirement::Always
+                && !value.is_empty()
+                && Schema::Yaml11.is_string(value);
+            return needs.then(|| match rule.quote_type {
+                QuoteType::Any => "string value is not quoted".to_owned(),
+                QuoteType::Single => "string value is not quoted with single quotes".to_owned(),
+                QuoteType::Double => "string value is not quoted with double quotes".to_owned(),
+            });
+        }
+        _ => return None,
+    };
+
+    let wrong_type = matches!(
+        (rule.quote_type, style),
+        (QuoteType::Single, ScalarStyle::DoubleQuoted)
+            | (QuoteType::Double, ScalarStyle::SingleQuoted)
+    );
+    if wrong_type {
+        let expected = if rule.quote_type == QuoteType::Single {
+            "single"
+        } else {
+            "double"
+        };
+        return Some(format!("string value is not quoted with {expected} quotes"));
+    }
+
+    (rule.required == QuoteRequirement::OnlyWhenNeeded && plain_would_read_the_same(value))
+        .then(|| format!("string value is redundantly quoted with {quote} quotes"))
+}
+
+/// Whether `value`, written as a plain scalar, would be read back as the same string.
+fn plain_would_read_the_same(value: &str) -> bool {
+    let Some(first) = value.chars().next() else {
+        return false;
+    };
+    Schema::Yaml11.is_string(value)
+        && !"-?:,[]{}#&*!|>'\"%@`".contains(first)
+        && !value.starts_with(char::is_whitespace)
+        && !value.ends_with(char::is_whitespace)
+        && !value.ends_with(':')
+        && !value.contains(": ")
+        && !value.contains(" #")
+        && !value.chars().any(char::is_control)
+        && !value.starts_with("---")
+        && !value.starts_with("...")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules(source: &str, config: &LintConfig) -> Vec<(&'static str, u64, u64)> {
+        check(source, config)
+            .unwrap()
+            .into_iter()
+            .map(|issue| (issue.rule, issue.location.line(), issue.location.column()))
+            .collect()
+    }
+
+    #[test]
+    fn key_ordering_checks_each_mapping_separately() {
+        let config = LintConfig {
+            key_ordering: Some(LintLevel::Error),
+            ..LintConfig::none()
+        };
+        let yaml = "b: 1\na:\n  y: 1\n  x: [1]\nc: {q: 1, p: 2}\n";
+        assert_eq!(
+            rules(yaml, &config),
+            [
+                ("key-ordering", 2, 1),
+                ("key-ordering", 4, 3),
+                ("key-ordering", 5, 11)
+            ]
+        );
+    }
+
+    #[test]
+    fn comments_need_space_after_hash_and_before_it() {
+        let config = LintConfig {
+            comments: Some(Comments::default()),
+            ..LintConfig::none()
+        };
+        let yaml = "#!/usr/bin/env yaml\n#bad\n### ok\na: 1 # too close\nb: 2  # fine\n";
+        assert_eq!(
+            rules(yaml, &config),
+            [("comments", 2, 1), ("comments", 4, 6)]
+        );
+    }
+
+    #[test]
+    fn unused_anchors_point_at_the_anchor() {
+        let config = LintConfig {
+            anchors: Some(LintLevel::Warning),
+            ..LintConfig::none()
+        };
+        let yaml = "a: &used 1\nb: &unused\n  c: 2\nd: *used\n";
+        assert_eq!(rules(yaml, &config), [("anchors", 2, 4)]);
+    }
+
+    #[test]
+    fn redundant_quotes_are_detected() {
+        let rule = QuotedStrings {
+            required: QuoteRequirement::OnlyWhenNeeded,
+            ..QuotedStrings::default()
+        };
+        assert!(check_quotes(&rule, "plain", ScalarStyle::SingleQuoted).is_some());
+        assert!(check_quotes(&rule, "yes", ScalarStyle::SingleQuoted).is_none());
+        assert!(check_quotes(&rule, "a: b", ScalarStyle::DoubleQuoted).is_none());
+        assert!(check_quotes(&rule, "12", ScalarStyle::DoubleQuoted).is_none());
+        assert!(check_quotes(&rule, "plain", ScalarStyle::Plain).is_none());
+    }
+}
@@ -22,6 +22,7 @@
 //! injection is exhausted.
 
 use crate::budget::{BudgetEnforcer, EnforcingPolicy};
+use crate::cancellation::InterruptCheck;
 
 #[cfg(not(feature = "include"))]
 use crate::buffered_input::ReaderInput;
@@ -29,14 +30,18 @@ use crate::buffered_input::ReaderInput;
 use crate::buffered_input::buffered_input_from_reader_with_limit;
 #[cfg(feature = "properties")]
 use crate::de::PropertySyntax;
-use crate::de::{AliasLimits, Error, Ev, Events, Location, Options};
+#[cfg(feature = "properties")]
+use crate::de::ros_substitutions::RosSubstitutions;
+use crate::de::{AliasLimits, Error, Ev, Events, Location, Options, UnknownKeyPolicy};
 use crate::de_error::budget_error;
 #[cfg(feature = "include")]
 use crate::include::create_parser_from_reader_input;
 use crate::include::{BaseParser, create_parser_from_str};
 use crate::location::location_from_span;
 use crate::options::BudgetReportCallback;
-use crate::tags::SfTag;
+use crate::source_registry::SourceRegistry;
+use crate::tags::{SfTag, directive_prefix_len};
+use crate::warnings::Warnings;
 use granit_parser::{Event, Placement, ScalarStyle, ScanError, Span, StructureStyle};
 
 #[cfg(not(feature = "include"))]
@@ -136,6 +141,39 @@ impl<'input> GranitParser<'input> {
         }
     }
 
+    /// Record the sources of this parse in `registry`, replacing what it held before.
+    fn set_source_registry(&mut self, registry: SourceRegistry, text: Option<&str>) {
+        #[cfg(feature = "include")]
+        {
+            let _ = text;
+            match self {
+                GranitParser::StringParser(parser) => parser.set_source_registry(registry),
+                GranitParser::StreamParser(parser) => parser.set_source_registry(registry),
+            }
+        }
+        #[cfg(not(feature = "include"))]
+        {
+            registry.clear();
+            registry.record(
+                crate::location::ROOT_SOURCE_ID,
+                "<input>",
+                None,
+                text.map(std::rc::Rc::from),
+                None,
+                Location::UNKNOWN,
+            );
+        }
+    }
+
+    /// Check `interrupt` around include resolution.
+    #[cfg(feature = "include")]
+    fn set_interrupt_check(&mut self, interrupt: InterruptCheck) {
+        match self {
+            GranitParser::StringParser(parser) => parser.set_interrupt_check(interrupt),
+            GranitParser::StreamParser(parser) => parser.set_interrupt_check(interrupt),
+        }
+    }
+
     #[cfg(feature = "include")]
     fn recorded_source_chain(&self, source_id: u32) -> Vec<&crate::include_stack::RecordedSource> {
         match self {
@@ -198,6 +236,12 @@ pub(crate) struct LiveEvents<'a> {
     budget_report: Option<fn(&crate::budget::BudgetReport)>,
     /// Optional reporter (new API)
     budget_report_cb: Option<BudgetReportCallback>,
+    /// Collector for non-fatal findings, activated per document.
+    warnings: Option<Warnings>,
+    /// Whether keys skipped by the target type fail the document.
+    unknown_keys: UnknownKeyPolicy,
+    /// Deadline and cancellation token, checked before each event.
+    interrupt: Option<InterruptCheck>,
     /// Location of the last yielded event (for better error reporting).
     last_location: Location,
     /// Location of the last event actually consumed by `next`.
@@ -215,10 +259,15 @@ pub(crate) struct LiveEvents<'a> {
     property_map: Option<Rc<HashMap<String, String>>>,
     #[cfg(feature = "properties")]
     property_syntax: PropertySyntax,
+    /// Resolvers for `$(...)` substitutions.
+    #[cfg(feature = "properties")]
+    ros_substitutions: Option<Rc<RosSubstitutions>>,
     /// Per-anchor replay expansion counters, indexed by anchor id (dense ids).
     per_anchor_expansions: Vec<usize>,
     /// Indicates whether a `DocumentEnd` was seen for the last parsed document.
     seen_doc_end: bool,
+    /// Version from the `%YAML` directive of the current document, if it has one.
+    document_version: Option<(u32, u32)>,
 
     /// Invalid options are reported through the same stream error channel as parse errors.
     pending_error: Option<Error>,
@@ -279,6 +328,8 @@ impl<'a> LiveEvents<'a> {
         let budget = options.budget.take();
         let budget_report = options.budget_report.take();
         let budget_report_cb = options.budget_report_cb.take();
+        let warnings = options.warnings.take();
+        let unknown_keys = options.unknown_keys;
         let alias_limits = options.alias_limits;
         let merge_keys = options.merge_keys;
         let pending_error = options.validate().err();
@@ -287,6 +338,8 @@ impl<'a> LiveEvents<'a> {
         let property_map = options.property_map.clone();
         #[cfg(feature = "properties")]
         let property_syntax = options.property_syntax;
+        #[cfg(feature = "properties")]
+        let ros_substitutions = options.ros_substitutions.clone();
         #[cfg(feature = "include")]
         let resolver = crate::resolver_from_options(&options);
 
@@ -309,10 +362,19 @@ impl<'a> LiveEvents<'a> {
         );
         #[cfg(not(feature = "include"))]
         let parser = granit_parser::Parser::with_options(input, parser_options);
+        let mut parser = GranitParser::StreamParser(parser);
+        if let Some(registry) = options.source_registry.take() {
+            parser.set_source_registry(registry, None);
+        }
+        let interrupt = InterruptCheck::new(options.deadline.take(), options.cancellation.take());
+        #[cfg(feature = "include")]
+        if let Some(interrupt) = &interrupt {
+            parser.set_interrupt_check(interrupt.clone());
+        }
         Self {
             produced_any_in_doc: false,
             synthesized_null_emitted: false,
-            parser: GranitParser::StreamParser(parser),
+            parser,
             input: None, // Reader-based input cannot support zero-copy borrowing
             look: None,
             look_leading_comments: Vec::new(),
@@ -326,7 +388,10 @@ impl<'a> LiveEvents<'a> {
 
             budget_report,
             budget_report_cb,
+            warnings,
+            unknown_keys,
 
+            interrupt,
             last_location: Location::UNKNOWN,
             last_consumed_event_location: Location::UNKNOWN,
             last_consumed_event_kind: None,
@@ -337,8 +402,11 @@ impl<'a> LiveEvents<'a> {
             property_map,
             #[cfg(feature = "properties")]
             property_syntax,
+            #[cfg(feature = "properties")]
+            ros_substitutions,
             per_anchor_expansions: Vec::new(),
             seen_doc_end: false,
+            document_version: None,
 
             pending_error,
 
@@ -364,6 +432,8 @@ impl<'a> LiveEvents<'a> {
         let budget = options.budget.take();
         let budget_report = options.budget_report.take();
         let budget_report_cb = options.budget_report_cb.take();
+        let warnings = options.warnings.take();
+        let unknown_keys = options.unknown_keys;
         let alias_limits = options.alias_limits;
         let merge_keys = options.merge_keys;
         let pending_error = options.validate().err();
@@ -372,6 +442,8 @@ impl<'a> LiveEvents<'a> {
         let property_map = options.property_map.clone();
         #[cfg(feature = "properties")]
         let property_syntax = options.property_syntax;
+        #[cfg(feature = "properties")]
+        let ros_substitutions = options.ros_substitutions.clone();
         #[cfg(feature = "include")]
         let resolver = crate::resolver_from_options(&options);
 
@@ -392,10 +464,19 @@ impl<'a> LiveEvents<'a> {
         );
         #[cfg(not(feature = "include"))]
         let parser = create_parser_from_str(input, parser_options);
+        let mut parser = GranitParser::StringParser(parser);
+        if let Some(registry) = options.source_registry.take() {
+            parser.set_source_registry(registry, Some(input));
+        }
+        let interrupt = InterruptCheck::new(options.deadline.take(), options.cancellation.take());
+        #[cfg(feature = "include")]
+        if let Some(interrupt) = &interrupt {
+            parser.set_interrupt_check(interrupt.clone());
+        }
         Self {
             produced_any_in_doc: false,
             synthesized_null_emitted: false,
-            parser: GranitParser::StringParser(parser),
+            parser,
             input: Some(input),
             look: None,
             look_leading_comments: Vec::new(),
@@ -410,7 +491,10 @@ impl<'a> LiveEvents<'a> {
 
             budget_report,
             budget_report_cb,
+            warnings,
+            unknown_keys,
 
+            interrupt,
             last_location: Location::UNKNOWN,
             last_consumed_event_location: Location::UNKNOWN,
             last_consumed_event_kind: None,
@@ -421,8 +505,11 @@ impl<'a> LiveEvents<'a> {
             property_map: property_map.clone(),
             #[cfg(feature = "properties")]
             property_syntax,
+            #[cfg(feature = "properties")]
+            ros_substitutions,
             per_anchor_expansions: Vec::new(),
             seen_doc_end: false,
+            document_version: None,
 
             pending_error,
 
@@ -584,6 +671,7 @@ impl<'a> LiveEvents<'a> {
                     return Err(Error::unexpected("consumed event").with_location(location));
                 }
             }
+            self.check_interrupt(ev.location())?;
             // Count replayed events for alias-bomb hardening.
             self.total_replayed_events = self
                 .total_replayed_events
@@ -624,6 +712,7 @@ impl<'a> LiveEvents<'a> {
             };
             let location =
                 location_from_span(&span).with_source_id(self.parser.current_source_id());
+            self.check_interrupt(location)?;
 
             // Validate indentation if the parser provided a hint for this span.
             if let Some(indent) = span.indent {
@@ -673,6 +762,7 @@ impl<'a> LiveEvents<'a> {
                         value: val,
                         tag: tag_s,
                         raw_tag: tag.as_ref().map(|t| Cow::Owned(t.to_string())),
+                        tag_prefix_len: tag.as_deref().map_or(0, directive_prefix_len),
                         style,
                         anchor: anchor_id,
                         location,
@@ -713,6 +803,7 @@ impl<'a> LiveEvents<'a> {
                         anchor: anchor_id,
                         tag: tag_s,
                         raw_tag: tag.as_ref().map(|t| Cow::Owned(t.to_string())),
+                        tag_prefix_len: tag.as_deref().map_or(0, directive_prefix_len),
                         location,
                     };
                     // Existing frames go deeper with this start.
@@ -778,6 +869,7 @@ impl<'a> LiveEvents<'a> {
                         anchor: anchor_id,
                         tag: tag_s,
                         raw_tag: tag.as_ref().map(|t| Cow::Owned(t.to_string())),
+                        tag_prefix_len: tag.as_deref().map_or(0, directive_prefix_len),
                         location,
                     };
                     self.bump_depth_on_start();
@@ -858,6 +950,7 @@ impl<'a> LiveEvents<'a> {
                                 value: String::new().into(),
                                 tag: SfTag::Null,
                                 raw_tag: None,
+                                tag_prefix_len: 0,
                                 style: ScalarStyle::Plain,
                                 anchor: anchor_id,
                                 location,
@@ -888,9 +981,10 @@ impl<'a> LiveEvents<'a> {
                     return self.next_impl();
                 }
 
-                Event::DocumentStart(..) => {
+                Event::DocumentStart(_, version) => {
                     // Skip doc start and reset per-document state.
                     self.reset_document_state();
+                    self.document_version = version.map(|v| (v.major, v.minor));
                     self.last_location = location;
                     continue;
                 }
@@ -929,6 +1023,7 @@ impl<'a> LiveEvents<'a> {
                 value: String::new().into(),
                 tag: SfTag::Null,
                 raw_tag: None,
+                tag_prefix_len: 0,
                 style: ScalarStyle::Plain,
                 anchor: 0,
                 location: self.last_location,
@@ -974,6 +1069,17 @@ impl<'a> LiveEvents<'a> {
         self.last_consumed_event_kind = None;
     }
 
+    /// Fail with [`Error::Interrupted`] at `location` if the deadline has passed or
+    /// the parse was cancelled.
+    fn check_interrupt(&self, location: Location) -> Result<(), Error> {
+        match &self.interrupt {
+            Some(interrupt) => interrupt
+                .check()
+                .map_err(|reason| Error::Interrupted { reason, location }),
+            None => Ok(()),
+        }
+    }
+
     /// Observe the configured budget for a replayed (injected) event.
     ///
     /// Reconstructs a parser Event equivalent to the Ev and passes it to the
@@ -1230,9 +1336,35 @@ impl<'de> Events<'de> for LiveEvents<'de> {
     fn property_syntax(&self) -> PropertySyntax {
         self.property_syntax
     }
+
+    #[cfg(feature = "properties")]
+    fn ros_substitutions(&self) -> Option<&Rc<RosSubstitutions>> {
+        self.ros_substitutions.as_ref()
+    }
 }
 
 impl LiveEvents<'_> {
+    /// Collector for non-fatal findings configured in [`Options::warnings`].
+    pub(crate) fn warnings(&self) -> Option<&Warnings> {
+        self.warnings.as_ref()
+    }
+
+    /// Policy for keys skipped by the target type, from [`Options::unknown_keys`].
+    pub(crate) fn unknown_keys(&self) -> UnknownKeyPolicy {
+        self.unknown_keys
+    }
+
+    /// Version from the `%YAML` directive of the document about to be deserialized.
+    ///
+    /// Peeks the first node so that the document start has been read; a peek error is
+    /// kept and returned by the next read.
+    pub(crate) fn document_version(&mut self) -> Option<(u32, u32)> {
+        if let Err(err) = self.peek() {
+            self.pending_error = Some(err);
+        }
+        self.document_version
+    }
+
     pub(crate) fn seen_doc_end(&self) -> bool {
         self.seen_doc_end
     }
@@ -1274,6 +1406,10 @@ impl LiveEvents<'_> {
                 location_from_span(&span).with_source_id(self.parser.current_source_id());
             self.last_location = location;
 
+            if self.check_interrupt(location).is_err() {
+                // Deadline passed or cancelled while skipping recovery content.
+                return false;
+            }
             if let Some(ref mut budget) = self.budget
                 && budget.observe(&raw).is_err()
             {
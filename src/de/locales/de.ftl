@@ -0,0 +1,131 @@
+# German messages of serde-saphyr.
+
+## Locations, suggestions and snippet labels
+
+location-suffix = { $message } in Zeile { $line }, Spalte { $column }
+snippet-location = Zeile { $line } Spalte { $column }
+alias-defined-at = definiert in Zeile { $line }, Spalte { $column }
+value-from-anchor = Dieser Wert stammt indirekt vom Anker in Zeile { $line } Spalte { $column }:
+did-you-mean = meinten Sie `{ $suggestion }`?
+suggestion-suffix = { $message }; { $hint }
+root-path = <Wurzel>
+label-defined = (definiert)
+label-defined-here = (hier definiert)
+label-value-used-here = der Wert wird hier verwendet
+label-defined-window = hier definiert
+
+## Validation
+
+validation-issue = Validierungsfehler bei { $path }: { $entry }
+validation-base = Validierungsfehler: { $entry } für `{ $path }`
+validation-invalid-here = hier ungültig, { $message }
+validation-failed =
+    { $count ->
+        [one] Validierung für { $count } Dokument fehlgeschlagen
+       *[other] Validierung für { $count } Dokumente fehlgeschlagen
+    }
+
+## Errors
+
+error-eof = unerwartetes Dateiende
+error-multiple-documents = nur ein YAML-Dokument erwartet, aber mehrere gefunden
+error-unexpected = { $expected } erwartet
+error-merge-value-not-map-or-seq-of-maps = YAML-Merge-Wert muss ein Mapping oder eine Sequenz von Mappings sein
+error-merge-key-not-allowed = Merge-Schlüssel ist hier nicht erlaubt
+error-invalid-binary-base64 = ungültiges !!binary-Base64
+error-binary-not-utf8 = !!binary-Skalar ist kein gültiges UTF-8 und kann nicht als Zeichenkette gespeichert werden.
+error-tagged-scalar-into-string = getaggter Skalar kann nicht als Zeichenkette gelesen werden
+error-unexpected-sequence-end = unerwartetes Ende der Sequenz
+error-unexpected-mapping-end = unerwartetes Ende des Mappings
+error-invalid-boolean-strict = ungültiger Wahrheitswert (true oder false erwartet)
+error-null-not-allowed = null ist hier nicht erlaubt
+error-invalid-char-not-single-scalar = hier ist nur ein einzelnes Zeichen erlaubt
+error-bytes-missing-binary-tag = !!binary-Tag fehlt
+error-unexpected-value-for-unit = unerwarteter Wert für Unit
+error-expected-empty-mapping-for-unit-struct = leeres Mapping erwartet
+error-unexpected-container-end = unerwartetes Ende eines Containers
+error-unexpected-container-end-reading-key = unerwartetes Ende eines Containers beim Lesen eines Schlüssels
+error-internal-seed-reused-for-map-key = interner Fehler: Seed für Mapping-Schlüssel wiederverwendet
+error-value-requested-before-key = Wert vor dem Schlüssel angefordert
+error-expected-string-key-for-enum = Zeichenketten-Schlüssel für extern getaggtes Enum erwartet
+error-enum-expected-scalar-or-mapping = extern getaggtes Enum erwartet einen Skalar oder ein Mapping
+error-unexpected-value-for-unit-variant = unerwarteter Wert für Enum-Variante ohne Daten
+error-expected-mapping-end-after-variant = Ende des Mappings nach dem Wert der Enum-Variante erwartet
+error-invalid-utf8-input = Eingabe des YAML-Parsers ist kein gültiges UTF-8
+error-too-complex = YAML-Dokument zu groß oder zu komplex
+error-alias-replay-limit = YAML-Dokument zu groß oder zu komplex: total_replayed_events={ $total } > { $max }
+error-alias-expansion-limit = YAML-Dokument zu groß oder zu komplex: Anker-ID { $anchor }: { $expansions } > { $max }
+error-alias-stack-depth = YAML-Dokument zu groß oder zu komplex: Tiefe={ $depth } > { $max }
+error-folded-block-scalar-indent = gefaltete Block-Skalare müssen ihren Inhalt einrücken
+error-internal-depth-underflow = interner Fehler: Tiefe unterschritten
+error-internal-recursion-stack-empty = interner Fehler: Rekursionsstapel leer
+error-recursive-reference = rekursive Referenz ist hier nicht erlaubt
+error-invalid-scalar = ungültiger Wert für { $type }
+error-non-finite-float = Wert `{ $value }` ist keine endliche Zahl
+error-invalid-type = ungültiger Typ: { $unexpected }, erwartet { $expected }
+error-invalid-value = ungültiger Wert: { $unexpected }, erwartet { $expected }
+error-unknown-variant = unbekannte Variante `{ $variant }`, erwartet eine von { $expected }
+error-unknown-field = unbekanntes Feld `{ $field }`, erwartet eines von { $expected }
+error-missing-field = Feld `{ $field }` fehlt
+error-duplicate-key = doppelter Mapping-Schlüssel: { $key } ist hier nicht erlaubt
+error-duplicate-key-unnamed = doppelter Mapping-Schlüssel ist hier nicht erlaubt
+error-duplicate-omap-key = doppelter Schlüssel ist hier nicht erlaubt
+error-set-value-not-null = Einträge einer Menge dürfen keine Werte haben
+error-invalid-pairs-entry = jeder Eintrag muss ein einzelnes Schlüssel-Wert-Paar sein
+error-raw-yaml-unavailable = der Quelltext ist für diesen Wert nicht verfügbar
+error-tagged-enum-mismatch = getaggtes Enum `{ $tagged }` passt nicht zum Ziel-Enum `{ $target }`
+error-container-end-mismatch = Ende einer Liste oder eines Mappings ohne Anfang
+error-unknown-anchor = Verweis auf unbekannten Wert
+error-cyclic-include = zyklisches Include erkannt
+error-unsupported-include-form = !include unterstützt derzeit nur die Skalarform: !include <Pfad>
+error-include-failed = Include konnte nicht aufgelöst werden
+error-unresolved-property = Eigenschaft `{ $name }` fehlt
+error-invalid-property-name = Ungültiger Name: '{ $name }'
+error-property-unset = Eigenschaft `{ $name }` fehlt
+error-property-unset-message = Eigenschaft `{ $name }` fehlt: { $message }
+error-property-empty = Eigenschaft `{ $name }` ist leer
+error-property-empty-message = Eigenschaft `{ $name }` ist leer: { $message }
+error-unresolved-substitution = nicht auflösbare Ersetzung `{ $substitution }`
+error-invalid-substitution = ungültige Ersetzung `{ $substitution }`
+error-budget = YAML-Dokument zu groß oder zu komplex: { $breach }
+error-deadline-exceeded = Lesen dauerte zu lange; hier abgebrochen
+error-cancelled = Lesen wurde abgebrochen; hier gestoppt
+error-io = E/A-Fehler: { $cause }
+error-quoting-required = Wert muss in Anführungszeichen stehen
+error-cannot-borrow = Hier ist nur eine einzelne Zeichenkette ohne Escape-Sequenzen erlaubt
+error-indentation = falsche Einrückung: erwartet { $required }, gefunden { $actual } Leerzeichen
+
+## Indentation requirements, used as $required of error-indentation
+
+indent-unchecked = ungeprüft
+indent-divisible = durch { $width } teilbar
+indent-even = gerade
+indent-uniform = einheitlich
+indent-uniform-width = einheitlich ({ $width } Leerzeichen)
+
+## Budget breaches, used as $breach of error-budget
+
+budget-events = zu viele Parser-Ereignisse ({ $count })
+budget-aliases = zu viele Aliase ({ $count })
+budget-anchors = zu viele Anker ({ $count })
+budget-depth = zu tief verschachtelt ({ $count } Ebenen)
+budget-inclusion-depth = Includes zu tief verschachtelt ({ $count } Ebenen)
+budget-documents = zu viele Dokumente ({ $count })
+budget-nodes = zu viele Knoten ({ $count })
+budget-scalar-bytes = Skalarwerte zu groß ({ $count } Bytes)
+budget-comment-bytes = Kommentare zu groß ({ $count } Bytes)
+budget-merge-keys = zu viele Merge-Schlüssel ({ $count })
+budget-alias-anchor-ratio = zu viele Aliase ({ $aliases }) für { $anchors } Anker
+budget-unbalanced = unausgeglichene Struktur
+budget-input-bytes = Eingabe zu groß ({ $count } Bytes)
+
+## Warnings
+
+warning-yaml11-boolean = `{ $value }` wurde als Wahrheitswert { $boolean } gelesen; YAML 1.2 liest es als Zeichenkette, schreiben Sie stattdessen `{ $boolean }`
+warning-legacy-octal = `{ $value }` wurde als veraltete Oktalzahl gelesen; schreiben Sie stattdessen `0o{ $digits }`
+warning-legacy-octal-string = `{ $value }` wurde nicht als Oktalzahl gelesen, YAML 1.1 liest es aber als solche; setzen Sie es in Anführungszeichen oder entfernen Sie die führenden Nullen
+warning-ignored-key = unbekannter Schlüssel `{ $key }` wurde ignoriert
+warning-duplicate-key = doppelter Schlüssel `{ $key }` ersetzt den früheren Wert
+warning-duplicate-key-unnamed = doppelter Schlüssel ersetzt den früheren Wert
+warning-merge-override = zusammengeführter Schlüssel `{ $key }` wird überschrieben
+warning-merge-override-unnamed = zusammengeführter Schlüssel wird überschrieben
@@ -0,0 +1,134 @@
+# English messages of serde-saphyr.
+#
+# This catalog is the reference for all message ids understood by `FluentLocalizer`.
+# Copy it to start a new translation; messages left out fall back to English.
+
+## Locations, suggestions and snippet labels
+
+location-suffix = { $message } at line { $line }, column { $column }
+snippet-location = line { $line } column { $column }
+alias-defined-at = defined at line { $line }, column { $column }
+value-from-anchor = This value comes indirectly from the anchor at line { $line } column { $column }:
+did-you-mean = did you mean `{ $suggestion }`?
+suggestion-suffix = { $message }; { $hint }
+root-path = <root>
+label-defined = (defined)
+label-defined-here = (defined here)
+label-value-used-here = the value is used here
+label-defined-window = defined here
+
+## Validation
+
+validation-issue = validation error at { $path }: { $entry }
+validation-base = validation error: { $entry } for `{ $path }`
+validation-invalid-here = invalid here, { $message }
+validation-failed =
+    { $count ->
+        [one] validation failed for { $count } document
+       *[other] validation failed for { $count } documents
+    }
+
+## Errors
+
+error-eof = unexpected end of file
+error-multiple-documents = only single YAML document expected but multiple found
+error-unexpected = expected { $expected }
+error-merge-value-not-map-or-seq-of-maps = YAML merge value must be mapping or sequence of mappings
+error-merge-key-not-allowed = merge key not allowed here
+error-invalid-binary-base64 = invalid !!binary base64
+error-binary-not-utf8 = !!binary scalar is not valid UTF-8 so cannot be stored into string.
+error-tagged-scalar-into-string = cannot deserialize tagged scalar into string
+error-unexpected-sequence-end = unexpected sequence end
+error-unexpected-mapping-end = unexpected mapping end
+error-invalid-boolean-strict = invalid boolean (true or false expected)
+error-null-not-allowed = null is not allowed here
+error-invalid-char-not-single-scalar = only single character allowed here
+error-bytes-missing-binary-tag = missing !!binary tag
+error-unexpected-value-for-unit = unexpected value for unit
+error-expected-empty-mapping-for-unit-struct = expected empty mapping here
+error-unexpected-container-end = unexpected container end
+error-unexpected-container-end-reading-key = unexpected container end while reading key
+error-internal-seed-reused-for-map-key = internal error: seed reused for map key
+error-value-requested-before-key = value requested before key
+error-expected-string-key-for-enum = expected string key for externally tagged enum
+error-enum-expected-scalar-or-mapping = externally tagged enum expected scalar or mapping
+error-unexpected-value-for-unit-variant = unexpected value for unit enum variant
+error-expected-mapping-end-after-variant = expected end of mapping after enum variant value
+error-invalid-utf8-input = YAML parser input is not valid UTF-8
+error-too-complex = YAML document too large or too complex
+error-alias-replay-limit = YAML document too large or too complex: total_replayed_events={ $total } > { $max }
+error-alias-expansion-limit = YAML document too large or too complex: anchor id { $anchor }: { $expansions } > { $max }
+error-alias-stack-depth = YAML document too large or too complex: depth={ $depth } > { $max }
+error-folded-block-scalar-indent = folded block scalars must indent their content
+error-internal-depth-underflow = internal depth underflow
+error-internal-recursion-stack-empty = internal recursion stack empty
+error-recursive-reference = Recursive reference not allowed here
+error-invalid-scalar = invalid { $type }
+error-non-finite-float = value `{ $value }` is not a finite number
+error-invalid-type = invalid type: { $unexpected }, expected { $expected }
+error-invalid-value = invalid value: { $unexpected }, expected { $expected }
+error-unknown-variant = unknown variant `{ $variant }`, expected one of { $expected }
+error-unknown-field = unknown field `{ $field }`, expected one of { $expected }
+error-missing-field = missing field `{ $field }`
+error-duplicate-key = duplicate mapping key: { $key } not allowed here
+error-duplicate-key-unnamed = duplicate mapping key not allowed here
+error-duplicate-omap-key = duplicate key not allowed here
+error-set-value-not-null = set entries cannot have values
+error-invalid-pairs-entry = each entry must be a single key: value pair
+error-raw-yaml-unavailable = raw source text is not available for this value
+error-tagged-enum-mismatch = tagged enum `{ $tagged }` does not match target enum `{ $target }`
+error-container-end-mismatch = list or mapping end with no start
+error-unknown-anchor = reference to unknown value
+error-cyclic-include = cyclic include detected
+error-unsupported-include-form = !include currently only supports the scalar form: !include <path>
+error-include-failed = failed to resolve include
+error-unresolved-property = missing property `{ $name }`
+error-invalid-property-name = Invalid name: '{ $name }'
+error-property-unset = missing property `{ $name }`
+error-property-unset-message = missing property `{ $name }`: { $message }
+error-property-empty = empty property `{ $name }`
+error-property-empty-message = empty property `{ $name }`: { $message }
+error-unresolved-substitution = unresolved substitution `{ $substitution }`
+error-invalid-substitution = invalid substitution `{ $substitution }`
+error-budget = YAML document too large or too complex: { $breach }
+error-deadline-exceeded = took too long to read; stopped here
+error-cancelled = reading was cancelled; stopped here
+error-io = IO error: { $cause }
+error-quoting-required = value requires quoting
+error-cannot-borrow = Only single string with no escape sequences is allowed here
+error-indentation = incorrect indentation: expected { $required }, found { $actual } spaces
+
+## Indentation requirements, used as $required of error-indentation
+
+indent-unchecked = unchecked
+indent-divisible = divisible by { $width }
+indent-even = even
+indent-uniform = uniform
+indent-uniform-width = uniform ({ $width } spaces)
+
+## Budget breaches, used as $breach of error-budget
+
+budget-events = too many parser events ({ $count })
+budget-aliases = too many aliases ({ $count })
+budget-anchors = too many anchors ({ $count })
+budget-depth = nesting too deep ({ $count } levels)
+budget-inclusion-depth = includes nested too deep ({ $count } levels)
+budget-documents = too many documents ({ $count })
+budget-nodes = too many nodes ({ $count })
+budget-scalar-bytes = scalar values too large ({ $count } bytes)
+budget-comment-bytes = comments too large ({ $count } bytes)
+budget-merge-keys = too many merge keys ({ $count })
+budget-alias-anchor-ratio = too many aliases ({ $aliases }) for { $anchors } anchors
+budget-unbalanced = unbalanced structure
+budget-input-bytes = input too large ({ $count } bytes)
+
+## Warnings
+
+warning-yaml11-boolean = `{ $value }` was read as the boolean { $boolean }; YAML 1.2 reads it as a string, write `{ $boolean }` instead
+warning-legacy-octal = `{ $value }` was read as a legacy octal number; write `0o{ $digits }` instead
+warning-legacy-octal-string = `{ $value }` was not read as an octal number, but YAML 1.1 reads it as one; quote it or remove the leading zeros
+warning-ignored-key = unknown key `{ $key }` was ignored
+warning-duplicate-key = duplicate key `{ $key }` replaces the earlier value
+warning-duplicate-key-unnamed = duplicate key replaces the earlier value
+warning-merge-override = merged key `{ $key }` is overridden
+warning-merge-override-unnamed = merged key is overridden
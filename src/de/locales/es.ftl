@@ -0,0 +1,131 @@
+# Spanish messages of serde-saphyr.
+
+## Locations, suggestions and snippet labels
+
+location-suffix = { $message } en la línea { $line }, columna { $column }
+snippet-location = línea { $line } columna { $column }
+alias-defined-at = definido en la línea { $line }, columna { $column }
+value-from-anchor = Este valor proviene indirectamente del ancla en la línea { $line } columna { $column }:
+did-you-mean = ¿quiso decir `{ $suggestion }`?
+suggestion-suffix = { $message }; { $hint }
+root-path = <raíz>
+label-defined = (definido)
+label-defined-here = (definido aquí)
+label-value-used-here = el valor se usa aquí
+label-defined-window = definido aquí
+
+## Validation
+
+validation-issue = error de validación en { $path }: { $entry }
+validation-base = error de validación: { $entry } para `{ $path }`
+validation-invalid-here = no válido aquí, { $message }
+validation-failed =
+    { $count ->
+        [one] la validación falló para { $count } documento
+       *[other] la validación falló para { $count } documentos
+    }
+
+## Errors
+
+error-eof = fin de archivo inesperado
+error-multiple-documents = se esperaba un solo documento YAML, pero se encontraron varios
+error-unexpected = se esperaba { $expected }
+error-merge-value-not-map-or-seq-of-maps = el valor de fusión YAML debe ser un mapeo o una secuencia de mapeos
+error-merge-key-not-allowed = clave de fusión no permitida aquí
+error-invalid-binary-base64 = base64 de !!binary no válido
+error-binary-not-utf8 = el escalar !!binary no es UTF-8 válido, por lo que no se puede guardar en una cadena.
+error-tagged-scalar-into-string = no se puede leer un escalar etiquetado como cadena
+error-unexpected-sequence-end = fin de secuencia inesperado
+error-unexpected-mapping-end = fin de mapeo inesperado
+error-invalid-boolean-strict = booleano no válido (se esperaba true o false)
+error-null-not-allowed = null no está permitido aquí
+error-invalid-char-not-single-scalar = aquí solo se permite un carácter
+error-bytes-missing-binary-tag = falta la etiqueta !!binary
+error-unexpected-value-for-unit = valor inesperado para unit
+error-expected-empty-mapping-for-unit-struct = se esperaba un mapeo vacío
+error-unexpected-container-end = fin de contenedor inesperado
+error-unexpected-container-end-reading-key = fin de contenedor inesperado al leer una clave
+error-internal-seed-reused-for-map-key = error interno: seed reutilizado para una clave de mapeo
+error-value-requested-before-key = valor solicitado antes de la clave
+error-expected-string-key-for-enum = se esperaba una clave de texto para un enum etiquetado externamente
+error-enum-expected-scalar-or-mapping = un enum etiquetado externamente espera un escalar o un mapeo
+error-unexpected-value-for-unit-variant = valor inesperado para una variante de enum sin datos
+error-expected-mapping-end-after-variant = se esperaba el fin del mapeo tras el valor de la variante del enum
+error-invalid-utf8-input = la entrada del analizador YAML no es UTF-8 válido
+error-too-complex = documento YAML demasiado grande o complejo
+error-alias-replay-limit = documento YAML demasiado grande o complejo: total_replayed_events={ $total } > { $max }
+error-alias-expansion-limit = documento YAML demasiado grande o complejo: ancla n.º { $anchor }: { $expansions } > { $max }
+error-alias-stack-depth = documento YAML demasiado grande o complejo: profundidad={ $depth } > { $max }
+error-folded-block-scalar-indent = los escalares de bloque plegados deben sangrar su contenido
+error-internal-depth-underflow = error interno: profundidad negativa
+error-internal-recursion-stack-empty = error interno: pila de recursión vacía
+error-recursive-reference = referencia recursiva no permitida aquí
+error-invalid-scalar = valor { $type } no válido
+error-non-finite-float = el valor `{ $value }` no es un número finito
+error-invalid-type = tipo no válido: { $unexpected }, se esperaba { $expected }
+error-invalid-value = valor no válido: { $unexpected }, se esperaba { $expected }
+error-unknown-variant = variante desconocida `{ $variant }`, se esperaba una de { $expected }
+error-unknown-field = campo desconocido `{ $field }`, se esperaba uno de { $expected }
+error-missing-field = falta el campo `{ $field }`
+error-duplicate-key = clave de mapeo duplicada: { $key } no está permitida aquí
+error-duplicate-key-unnamed = clave de mapeo duplicada no permitida aquí
+error-duplicate-omap-key = clave duplicada no permitida aquí
+error-set-value-not-null = las entradas de un conjunto no pueden tener valores
+error-invalid-pairs-entry = cada entrada debe ser un único par clave: valor
+error-raw-yaml-unavailable = el texto fuente no está disponible para este valor
+error-tagged-enum-mismatch = el enum etiquetado `{ $tagged }` no coincide con el enum de destino `{ $target }`
+error-container-end-mismatch = fin de lista o mapeo sin inicio
+error-unknown-anchor = referencia a un valor desconocido
+error-cyclic-include = se detectó una inclusión cíclica
+error-unsupported-include-form = !include solo admite por ahora la forma escalar: !include <ruta>
+error-include-failed = no se pudo resolver la inclusión
+error-unresolved-property = falta la propiedad `{ $name }`
+error-invalid-property-name = Nombre no válido: '{ $name }'
+error-property-unset = falta la propiedad `{ $name }`
+error-property-unset-message = falta la propiedad `{ $name }`: { $message }
+error-property-empty = la propiedad `{ $name }` está vacía
+error-property-empty-message = la propiedad `{ $name }` está vacía: { $message }
+error-unresolved-substitution = sustitución sin resolver `{ $substitution }`
+error-invalid-substitution = sustitución no válida `{ $substitution }`
+error-budget = documento YAML demasiado grande o complejo: { $breach }
+error-deadline-exceeded = la lectura tardó demasiado; se detuvo aquí
+error-cancelled = la lectura se canceló; se detuvo aquí
+error-io = error de E/S: { $cause }
+error-quoting-required = el valor debe ir entre comillas
+error-cannot-borrow = Aquí solo se permite una cadena simple sin secuencias de escape
+error-indentation = sangría incorrecta: se esperaba { $required }, se encontraron { $actual } espacios
+
+## Indentation requirements, used as $required of error-indentation
+
+indent-unchecked = sin comprobar
+indent-divisible = divisible por { $width }
+indent-even = par
+indent-uniform = uniforme
+indent-uniform-width = uniforme ({ $width } espacios)
+
+## Budget breaches, used as $breach of error-budget
+
+budget-events = demasiados eventos del analizador ({ $count })
+budget-aliases = demasiados alias ({ $count })
+budget-anchors = demasiadas anclas ({ $count })
+budget-depth = anidamiento demasiado profundo ({ $count } niveles)
+budget-inclusion-depth = inclusiones anidadas demasiado profundas ({ $count } niveles)
+budget-documents = demasiados documentos ({ $count })
+budget-nodes = demasiados nodos ({ $count })
+budget-scalar-bytes = valores escalares demasiado grandes ({ $count } bytes)
+budget-comment-bytes = comentarios demasiado grandes ({ $count } bytes)
+budget-merge-keys = demasiadas claves de fusión ({ $count })
+budget-alias-anchor-ratio = demasiados alias ({ $aliases }) para { $anchors } anclas
+budget-unbalanced = estructura desequilibrada
+budget-input-bytes = entrada demasiado grande ({ $count } bytes)
+
+## Warnings
+
+warning-yaml11-boolean = `{ $value }` se leyó como el booleano { $boolean }; YAML 1.2 lo lee como cadena, escriba `{ $boolean }` en su lugar
+warning-legacy-octal = `{ $value }` se leyó como un número octal heredado; escriba `0o{ $digits }` en su lugar
+warning-legacy-octal-string = `{ $value }` no se leyó como un número octal, pero YAML 1.1 lo lee así; póngalo entre comillas o quite los ceros iniciales
+warning-ignored-key = se ignoró la clave desconocida `{ $key }`
+warning-duplicate-key = la clave duplicada `{ $key }` reemplaza el valor anterior
+warning-duplicate-key-unnamed = una clave duplicada reemplaza el valor anterior
+warning-merge-override = la clave fusionada `{ $key }` se sobrescribe
+warning-merge-override-unnamed = una clave fusionada se sobrescribe
@@ -0,0 +1,131 @@
+# French messages of serde-saphyr.
+
+## Locations, suggestions and snippet labels
+
+location-suffix = { $message } à la ligne { $line }, colonne { $column }
+snippet-location = ligne { $line } colonne { $column }
+alias-defined-at = défini à la ligne { $line }, colonne { $column }
+value-from-anchor = Cette valeur provient indirectement de l’ancre à la ligne { $line } colonne { $column } :
+did-you-mean = vouliez-vous dire `{ $suggestion }` ?
+suggestion-suffix = { $message } ; { $hint }
+root-path = <racine>
+label-defined = (défini)
+label-defined-here = (défini ici)
+label-value-used-here = la valeur est utilisée ici
+label-defined-window = défini ici
+
+## Validation
+
+validation-issue = erreur de validation à { $path } : { $entry }
+validation-base = erreur de validation : { $entry } pour `{ $path }`
+validation-invalid-here = invalide ici, { $message }
+validation-failed =
+    { $count ->
+        [one] échec de la validation pour { $count } document
+       *[other] échec de la validation pour { $count } documents
+    }
+
+## Errors
+
+error-eof = fin de fichier inattendue
+error-multiple-documents = un seul document YAML attendu, mais plusieurs trouvés
+error-unexpected = { $expected } attendu
+error-merge-value-not-map-or-seq-of-maps = la valeur de fusion YAML doit être un mapping ou une séquence de mappings
+error-merge-key-not-allowed = clé de fusion non autorisée ici
+error-invalid-binary-base64 = base64 !!binary invalide
+error-binary-not-utf8 = le scalaire !!binary n’est pas de l’UTF-8 valide et ne peut donc pas être stocké dans une chaîne.
+error-tagged-scalar-into-string = impossible de lire un scalaire étiqueté comme chaîne
+error-unexpected-sequence-end = fin de séquence inattendue
+error-unexpected-mapping-end = fin de mapping inattendue
+error-invalid-boolean-strict = booléen invalide (true ou false attendu)
+error-null-not-allowed = null n’est pas autorisé ici
+error-invalid-char-not-single-scalar = un seul caractère est autorisé ici
+error-bytes-missing-binary-tag = étiquette !!binary manquante
+error-unexpected-value-for-unit = valeur inattendue pour unit
+error-expected-empty-mapping-for-unit-struct = mapping vide attendu ici
+error-unexpected-container-end = fin de conteneur inattendue
+error-unexpected-container-end-reading-key = fin de conteneur inattendue pendant la lecture d’une clé
+error-internal-seed-reused-for-map-key = erreur interne : seed réutilisé pour une clé de mapping
+error-value-requested-before-key = valeur demandée avant la clé
+error-expected-string-key-for-enum = clé de type chaîne attendue pour une énumération étiquetée en externe
+error-enum-expected-scalar-or-mapping = une énumération étiquetée en externe attend un scalaire ou un mapping
+error-unexpected-value-for-unit-variant = valeur inattendue pour une variante d’énumération sans données
+error-expected-mapping-end-after-variant = fin de mapping attendue après la valeur de la variante d’énumération
+error-invalid-utf8-input = l’entrée de l’analyseur YAML n’est pas de l’UTF-8 valide
+error-too-complex = document YAML trop grand ou trop complexe
+error-alias-replay-limit = document YAML trop grand ou trop complexe : total_replayed_events={ $total } > { $max }
+error-alias-expansion-limit = document YAML trop grand ou trop complexe : ancre n° { $anchor } : { $expansions } > { $max }
+error-alias-stack-depth = document YAML trop grand ou trop complexe : profondeur={ $depth } > { $max }
+error-folded-block-scalar-indent = les scalaires de bloc repliés doivent indenter leur contenu
+error-internal-depth-underflow = erreur interne : profondeur négative
+error-internal-recursion-stack-empty = erreur interne : pile de récursion vide
+error-recursive-reference = référence récursive non autorisée ici
+error-invalid-scalar = valeur { $type } invalide
+error-non-finite-float = la valeur `{ $value }` n’est pas un nombre fini
+error-invalid-type = type invalide : { $unexpected }, { $expected } attendu
+error-invalid-value = valeur invalide : { $unexpected }, { $expected } attendu
+error-unknown-variant = variante inconnue `{ $variant }`, attendu l’une de { $expected }
+error-unknown-field = champ inconnu `{ $field }`, attendu l’un de { $expected }
+error-missing-field = champ `{ $field }` manquant
+error-duplicate-key = clé de mapping en double : { $key } n’est pas autorisée ici
+error-duplicate-key-unnamed = clé de mapping en double non autorisée ici
+error-duplicate-omap-key = clé en double non autorisée ici
+error-set-value-not-null = les entrées d’un ensemble ne peuvent pas avoir de valeur
+error-invalid-pairs-entry = chaque entrée doit être une seule paire clé: valeur
+error-raw-yaml-unavailable = le texte source n’est pas disponible pour cette valeur
+error-tagged-enum-mismatch = l’énumération étiquetée `{ $tagged }` ne correspond pas à l’énumération cible `{ $target }`
+error-container-end-mismatch = fin de liste ou de mapping sans début
+error-unknown-anchor = référence à une valeur inconnue
+error-cyclic-include = inclusion cyclique détectée
+error-unsupported-include-form = !include ne prend en charge que la forme scalaire : !include <chemin>
+error-include-failed = impossible de résoudre l’inclusion
+error-unresolved-property = propriété `{ $name }` manquante
+error-invalid-property-name = Nom invalide : '{ $name }'
+error-property-unset = propriété `{ $name }` manquante
+error-property-unset-message = propriété `{ $name }` manquante : { $message }
+error-property-empty = propriété `{ $name }` vide
+error-property-empty-message = propriété `{ $name }` vide : { $message }
+error-unresolved-substitution = substitution non résolue `{ $substitution }`
+error-invalid-substitution = substitution invalide `{ $substitution }`
+error-budget = document YAML trop grand ou trop complexe : { $breach }
+error-deadline-exceeded = la lecture a pris trop de temps ; arrêtée ici
+error-cancelled = la lecture a été annulée ; arrêtée ici
+error-io = erreur d’E/S : { $cause }
+error-quoting-required = la valeur doit être entre guillemets
+error-cannot-borrow = Seule une chaîne simple sans séquence d’échappement est autorisée ici
+error-indentation = indentation incorrecte : { $required } attendu, { $actual } espaces trouvés
+
+## Indentation requirements, used as $required of error-indentation
+
+indent-unchecked = non vérifiée
+indent-divisible = divisible par { $width }
+indent-even = paire
+indent-uniform = uniforme
+indent-uniform-width = uniforme ({ $width } espaces)
+
+## Budget breaches, used as $breach of error-budget
+
+budget-events = trop d’événements d’analyse ({ $count })
+budget-aliases = trop d’alias ({ $count })
+budget-anchors = trop d’ancres ({ $count })
+budget-depth = imbrication trop profonde ({ $count } niveaux)
+budget-inclusion-depth = inclusions imbriquées trop profondément ({ $count } niveaux)
+budget-documents = trop de documents ({ $count })
+budget-nodes = trop de nœuds ({ $count })
+budget-scalar-bytes = valeurs scalaires trop grandes ({ $count } octets)
+budget-comment-bytes = commentaires trop grands ({ $count } octets)
+budget-merge-keys = trop de clés de fusion ({ $count })
+budget-alias-anchor-ratio = trop d’alias ({ $aliases }) pour { $anchors } ancres
+budget-unbalanced = structure déséquilibrée
+budget-input-bytes = entrée trop grande ({ $count } octets)
+
+## Warnings
+
+warning-yaml11-boolean = `{ $value }` a été lu comme le booléen { $boolean } ; YAML 1.2 le lit comme une chaîne, écrivez plutôt `{ $boolean }`
+warning-legacy-octal = `{ $value }` a été lu comme un nombre octal hérité ; écrivez plutôt `0o{ $digits }`
+warning-legacy-octal-string = `{ $value }` n’a pas été lu comme un nombre octal, mais YAML 1.1 le lit ainsi ; mettez-le entre guillemets ou supprimez les zéros initiaux
+warning-ignored-key = la clé inconnue `{ $key }` a été ignorée
+warning-duplicate-key = la clé en double `{ $key }` remplace la valeur précédente
+warning-duplicate-key-unnamed = une clé en double remplace la valeur précédente
+warning-merge-override = la clé fusionnée `{ $key }` est remplacée
+warning-merge-override-unnamed = une clé fusionnée est remplacée
@@ -0,0 +1,127 @@
+# Japanese messages of serde-saphyr.
+
+## Locations, suggestions and snippet labels
+
+location-suffix = { $message }（{ $line } 行 { $column } 列）
+snippet-location = { $line } 行 { $column } 列
+alias-defined-at = { $line } 行 { $column } 列で定義
+value-from-anchor = この値は { $line } 行 { $column } 列のアンカーから間接的に取得されています:
+did-you-mean = `{ $suggestion }` のことですか？
+suggestion-suffix = { $message }。{ $hint }
+root-path = <ルート>
+label-defined = （定義）
+label-defined-here = （ここで定義）
+label-value-used-here = 値はここで使用されています
+label-defined-window = ここで定義
+
+## Validation
+
+validation-issue = { $path } の検証エラー: { $entry }
+validation-base = 検証エラー: `{ $path }` の { $entry }
+validation-invalid-here = ここが無効です。{ $message }
+validation-failed = { $count } 件のドキュメントで検証に失敗しました
+
+## Errors
+
+error-eof = 予期しないファイルの終端です
+error-multiple-documents = YAML ドキュメントは 1 つだけのはずですが、複数見つかりました
+error-unexpected = { $expected } が必要です
+error-merge-value-not-map-or-seq-of-maps = YAML のマージ値はマッピングまたはマッピングのシーケンスでなければなりません
+error-merge-key-not-allowed = ここではマージキーを使用できません
+error-invalid-binary-base64 = !!binary の Base64 が無効です
+error-binary-not-utf8 = !!binary スカラーが有効な UTF-8 ではないため、文字列として格納できません。
+error-tagged-scalar-into-string = タグ付きスカラーを文字列として読み込めません
+error-unexpected-sequence-end = 予期しないシーケンスの終端です
+error-unexpected-mapping-end = 予期しないマッピングの終端です
+error-invalid-boolean-strict = 無効な真偽値です（true または false が必要です）
+error-null-not-allowed = ここでは null を使用できません
+error-invalid-char-not-single-scalar = ここでは 1 文字だけ使用できます
+error-bytes-missing-binary-tag = !!binary タグがありません
+error-unexpected-value-for-unit = unit に対する予期しない値です
+error-expected-empty-mapping-for-unit-struct = ここでは空のマッピングが必要です
+error-unexpected-container-end = 予期しないコンテナの終端です
+error-unexpected-container-end-reading-key = キーの読み込み中に予期しないコンテナの終端がありました
+error-internal-seed-reused-for-map-key = 内部エラー: マッピングキーに seed が再利用されました
+error-value-requested-before-key = キーより先に値が要求されました
+error-expected-string-key-for-enum = 外部タグ付き列挙型には文字列のキーが必要です
+error-enum-expected-scalar-or-mapping = 外部タグ付き列挙型にはスカラーまたはマッピングが必要です
+error-unexpected-value-for-unit-variant = データを持たない列挙型バリアントに対する予期しない値です
+error-expected-mapping-end-after-variant = 列挙型バリアントの値の後にはマッピングの終端が必要です
+error-invalid-utf8-input = YAML パーサーへの入力が有効な UTF-8 ではありません
+error-too-complex = YAML ドキュメントが大きすぎるか複雑すぎます
+error-alias-replay-limit = YAML ドキュメントが大きすぎるか複雑すぎます: total_replayed_events={ $total } > { $max }
+error-alias-expansion-limit = YAML ドキュメントが大きすぎるか複雑すぎます: アンカー ID { $anchor }: { $expansions } > { $max }
+error-alias-stack-depth = YAML ドキュメントが大きすぎるか複雑すぎます: 深さ={ $depth } > { $max }
+error-folded-block-scalar-indent = 折り返しブロックスカラーの内容はインデントする必要があります
+error-internal-depth-underflow = 内部エラー: 深さがアンダーフローしました
+error-internal-recursion-stack-empty = 内部エラー: 再帰スタックが空です
+error-recursive-reference = ここでは再帰参照を使用できません
+error-invalid-scalar = 無効な { $type } です
+error-non-finite-float = 値 `{ $value }` は有限の数値ではありません
+error-invalid-type = 無効な型です: { $unexpected }、{ $expected } が必要です
+error-invalid-value = 無効な値です: { $unexpected }、{ $expected } が必要です
+error-unknown-variant = 不明なバリアント `{ $variant }` です。次のいずれかが必要です: { $expected }
+error-unknown-field = 不明なフィールド `{ $field }` です。次のいずれかが必要です: { $expected }
+error-missing-field = フィールド `{ $field }` がありません
+error-duplicate-key = マッピングキーが重複しています: ここでは { $key } を使用できません
+error-duplicate-key-unnamed = ここではマッピングキーを重複できません
+error-duplicate-omap-key = ここではキーを重複できません
+error-set-value-not-null = セットの要素には値を指定できません
+error-invalid-pairs-entry = 各要素は 1 つのキーと値の組でなければなりません
+error-raw-yaml-unavailable = この値のソーステキストは利用できません
+error-tagged-enum-mismatch = タグ付き列挙型 `{ $tagged }` は対象の列挙型 `{ $target }` と一致しません
+error-container-end-mismatch = 開始のないリストまたはマッピングの終端です
+error-unknown-anchor = 不明な値への参照です
+error-cyclic-include = 循環インクルードが検出されました
+error-unsupported-include-form = !include は現在スカラー形式 (!include <パス>) のみ対応しています
+error-include-failed = インクルードを解決できませんでした
+error-unresolved-property = プロパティ `{ $name }` がありません
+error-invalid-property-name = 無効な名前です: '{ $name }'
+error-property-unset = プロパティ `{ $name }` がありません
+error-property-unset-message = プロパティ `{ $name }` がありません: { $message }
+error-property-empty = プロパティ `{ $name }` が空です
+error-property-empty-message = プロパティ `{ $name }` が空です: { $message }
+error-unresolved-substitution = 置換 `{ $substitution }` を解決できません
+error-invalid-substitution = 置換 `{ $substitution }` が無効です
+error-budget = YAML ドキュメントが大きすぎるか複雑すぎます: { $breach }
+error-deadline-exceeded = 読み込みに時間がかかりすぎたため、ここで停止しました
+error-cancelled = 読み込みがキャンセルされたため、ここで停止しました
+error-io = 入出力エラー: { $cause }
+error-quoting-required = 値を引用符で囲む必要があります
+error-cannot-borrow = ここではエスケープシーケンスを含まない単一の文字列のみ使用できます
+error-indentation = インデントが正しくありません: { $required } が必要ですが、{ $actual } 個の空白がありました
+
+## Indentation requirements, used as $required of error-indentation
+
+indent-unchecked = 未検査
+indent-divisible = { $width } で割り切れる幅
+indent-even = 偶数の幅
+indent-uniform = 均一な幅
+indent-uniform-width = 均一な幅（空白 { $width } 個）
+
+## Budget breaches, used as $breach of error-budget
+
+budget-events = パーサーイベントが多すぎます（{ $count }）
+budget-aliases = エイリアスが多すぎます（{ $count }）
+budget-anchors = アンカーが多すぎます（{ $count }）
+budget-depth = 入れ子が深すぎます（{ $count } 階層）
+budget-inclusion-depth = インクルードの入れ子が深すぎます（{ $count } 階層）
+budget-documents = ドキュメントが多すぎます（{ $count }）
+budget-nodes = ノードが多すぎます（{ $count }）
+budget-scalar-bytes = スカラー値が大きすぎます（{ $count } バイト）
+budget-comment-bytes = コメントが大きすぎます（{ $count } バイト）
+budget-merge-keys = マージキーが多すぎます（{ $count }）
+budget-alias-anchor-ratio = アンカー { $anchors } 個に対してエイリアスが多すぎます（{ $aliases }）
+budget-unbalanced = 構造の対応が取れていません
+budget-input-bytes = 入力が大きすぎます（{ $count } バイト）
+
+## Warnings
+
+warning-yaml11-boolean = `{ $value }` は真偽値 { $boolean } として読み込まれました。YAML 1.2 では文字列として読み込まれるため、代わりに `{ $boolean }` と書いてください
+warning-legacy-octal = `{ $value }` は旧形式の 8 進数として読み込まれました。代わりに `0o{ $digits }` と書いてください
+warning-legacy-octal-string = `{ $value }` は 8 進数として読み込まれませんでしたが、YAML 1.1 では 8 進数として読み込まれます。引用符で囲むか、先頭のゼロを削除してください
+warning-ignored-key = 不明なキー `{ $key }` は無視されました
+warning-duplicate-key = 重複したキー `{ $key }` が前の値を置き換えます
+warning-duplicate-key-unnamed = 重複したキーが前の値を置き換えます
+warning-merge-override = マージされたキー `{ $key }` は上書きされます
+warning-merge-override-unnamed = マージされたキーは上書きされます
@@ -58,6 +58,11 @@ pub enum ExternalMessageSource {
     Garde,
     /// Text produced by `validator` validation rules.
     Validator,
+    /// Text reported by a custom validation hook such as [`crate::from_str_with_validator`].
+    Custom,
+    /// Unknown keys rejected under [`crate::UnknownKeyPolicy::Error`], with code
+    /// `unknown_key` and the key as parameter `key`.
+    UnknownKeys,
 }
 
 /// A best-effort description of an external message.
@@ -129,6 +134,21 @@ pub trait Localizer {
         }
     }
 
+    /// Hint naming the expected field or variant that an unknown name most likely
+    /// misspells. Also used as `miette` help text.
+    ///
+    /// Default: ``"did you mean `{suggestion}`?"``.
+    fn did_you_mean(&self, suggestion: &str) -> String {
+        format!("did you mean `{suggestion}`?")
+    }
+
+    /// Attach the [`Localizer::did_you_mean`] hint to an unknown field or variant message.
+    ///
+    /// Default: `"{base}; {did_you_mean}"`.
+    fn attach_suggestion<'a>(&self, base: Cow<'a, str>, suggestion: &str) -> Cow<'a, str> {
+        Cow::Owned(format!("{base}; {}", self.did_you_mean(suggestion)))
+    }
+
     /// Label used when a path has no leaf.
     ///
     /// Default `<root>`
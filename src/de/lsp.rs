@@ -0,0 +1,723 @@
+//! Language Server Protocol support.
+//!
+//! This module is feature-gated behind the `lsp` feature. A [`Server`] speaks LSP over
+//! any byte stream (normally stdio) and publishes serde-saphyr diagnostics for YAML files
+//! as they are opened and edited: syntax errors, budget breaches, type mismatches, unknown
+//! fields, validation issues and, as warnings, everything the [`crate::Warnings`]
+//! collector reports. Each file is checked against the Rust type registered for the first
+//! glob its path matches, so the editor shows exactly what the service would report when
+//! loading the same file.
+//!
+//! ```rust,no_run
+//! use serde::Deserialize;
+//! use serde_saphyr::lsp::Server;
+//!
+//! #[derive(Deserialize)]
+//! #[serde(deny_unknown_fields)]
+//! struct Robot {
+//!     name: String,
+//!     joints: Vec<String>,
+//! }
+//!
+//! Server::new()
+//!     .register::<Robot>("**/robots/*.yaml")
+//!     .run_stdio()
+//!     .expect("LSP connection failed");
+//! ```
+//!
+//! Globs are matched against the file path of the document URI: `*` and `?` match within
+//! one path segment, `**` matches any number of segments. Only full-document
+//! synchronization is supported, which every LSP client implements.
+
+use std::borrow::Cow;
+use std::io::{self, BufRead, Write};
+
+use serde_core::de::DeserializeOwned;
+use serde_json::{Value, json};
+
+use crate::localizer::Localizer;
+use crate::path_map::format_path_with_resolved_leaf;
+use crate::{
+    Error, Location, Locations, MessageFormatter, Options, SourceRegistry, ValidationContext,
+    Warning, Warnings,
+};
+
+/// Largest accepted LSP message body. Bigger messages end the session with an error.
+const MAX_MESSAGE_BYTES: usize = 64 * 1024 * 1024;
+
+/// Position in a text document: 0-based line and UTF-16 code unit offset, as in LSP.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Position {
+    /// 0-based line.
+    pub line: u32,
+    /// 0-based offset within the line, in UTF-16 code units.
+    pub character: u32,
+}
+
+/// Range in a text document, end exclusive.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Range {
+    /// First position of the range.
+    pub start: Position,
+    /// Position just after the range.
+    pub end: Position,
+}
+
+/// Severity of a [`Diagnostic`].
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    /// The document cannot be loaded.
+    Error,
+    /// The document loads, but a [`Warning`] was reported.
+    Warning,
+}
+
+/// One diagnostic published for a document.
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// Where the problem is.
+    pub range: Range,
+    /// Whether the problem prevents loading.
+    pub severity: Severity,
+    /// Stable error code (see [`crate::error_codes`]); `None` for warnings.
+    pub code: Option<&'static str>,
+    /// Message text, produced by the server's [`MessageFormatter`].
+    pub message: String,
+    /// Other places in the same document that explain the problem, such as the anchor a
+    /// value comes from.
+    pub related: Vec<(Range, String)>,
+}
+
+type Check = Box<dyn Fn(&str, Options) -> Result<(), Error>>;
+
+struct Registration {
+    glob: String,
+    check: Check,
+}
+
+/// LSP server publishing serde-saphyr diagnostics for registered file globs.
+pub struct Server {
+    registrations: Vec<Registration>,
+    options: Options,
+    formatter: Box<dyn MessageFormatter>,
+}
+
+impl Default for Server {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Server {
+    /// Create a server with no registrations, default [`Options`] and the default
+    /// message formatter.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            registrations: Vec::new(),
+            options: Options::default(),
+            formatter: Box::new(crate::DefaultMessageFormatter),
+        }
+    }
+
+    /// Check files whose path matches `glob` by deserializing them into `T`.
+    #[must_use]
+    pub fn register<T: DeserializeOwned>(self, glob: &str) -> Self {
+        self.register_with(glob, |yaml, options| {
+            crate::from_str_with_options::<T>(yaml, options).map(drop)
+        })
+    }
+
+    /// Like [`Server::register`], also running a validation hook as in
+    /// [`crate::from_str_with_validator`]. Reported issues become diagnostics on the
+    /// offending values.
+    #[must_use]
+    pub fn register_validated<T, F>(self, glob: &str, validate: F) -> Self
+    where
+        T: DeserializeOwned,
+        F: Fn(&T, &mut ValidationContext) + 'static,
+    {
+        self.register_with(glob, move |yaml, options| {
+            crate::from_str_with_options_and_validator::<T, _>(yaml, options, &validate).map(drop)
+        })
+    }
+
+    /// Check files whose path matches `glob` with a custom loader, for example one using
+    /// `garde` validation or reading multiple documents.
+    ///
+    /// The loader receives the document text and the server options, extended with a
+    /// warnings collector and a source registry, and must pass these options on.
+    #[must_use]
+    pub fn register_with<F>(mut self, glob: &str, check: F) -> Self
+    where
+        F: Fn(&str, Options) -> Result<(), Error> + 'static,
+    {
+        self.registrations.push(Registration {
+            glob: glob.to_owned(),
+            check: Box::new(check),
+        });
+        self
+    }
+
+    /// Options used for every check, such as budgets, policies or an include resolver.
+    #[must_use]
+    pub fn with_options(mut self, options: Options) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Message formatter used for diagnostic text, for example a localized one.
+    #[must_use]
+    pub fn with_formatter(mut self, formatter: impl MessageFormatter + 'static) -> Self {
+        self.formatter = Box::new(formatter);
+        self
+    }
+
+    /// Whether a registration covers the document at `uri`.
+    #[must_use]
+    pub fn handles(&self, uri: &str) -> bool {
+        self.registration_for(uri).is_some()
+    }
+
+    /// Check `text`, the content of the document at `uri`, and return its diagnostics.
+    ///
+    /// Returns an empty list if the document loads cleanly or no registration covers it.
+    #[must_use]
+    pub fn diagnostics(&self, uri: &str, text: &str) -> Vec<Diagnostic> {
+        let Some(registration) = self.registration_for(uri) else {
+            return Vec::new();
+        };
+
+        let warnings = Warnings::new();
+        let registry = SourceRegistry::new();
+        let options = self
+            .options
+            .clone()
+            .with_warnings(&warnings)
+            .with_source_registry(&registry);
+
+        let ctx = Context {
+            text,
+            registry: &registry,
+            formatter: self.formatter.as_ref(),
+        };
+        let mut out = Vec::new();
+        if let Err(err) = (registration.check)(text, options) {
+            ctx.push_error(&mut out, &err);
+        }
+        for warning in warnings.take() {
+            ctx.push_warning(&mut out, &warning);
+        }
+        out
+    }
+
+    /// Serve LSP over standard input and output until the client sends `exit`.
+    pub fn run_stdio(&self) -> io::Result<()> {
+        self.run(io::stdin().lock(), io::stdout().lock())
+    }
+
+    /// Serve LSP messages read from `input`, writing responses and notifications to
+    /// `output`, until the client sends `exit` or closes `input`.
+    pub fn run<R: BufRead, W: Write>(&self, mut input: R, mut output: W) -> io::Result<()> {
+        while let Some(body) = read_message(&mut input)? {
+            let Ok(message) = serde_json::from_slice::<Value>(&body) else {
+                let error = json!({ "code": -32700, "message": "parse error" });
+                write_message(
+                    &mut output,
+                    &json!({ "jsonrpc": "2.0", "id": null, "error": error }),
+                )?;
+                continue;
+            };
+
+            let method = message["method"].as_str().unwrap_or_default();
+            let params = &message["params"];
+            match method {
+                "initialize" => respond(
+                    &mut output,
+                    &message,
+                    json!({
+                        "capabilities": {
+                            "textDocumentSync": { "openClose": true, "change": 1 },
+                        },
+                        "serverInfo": {
+                            "name": "serde-saphyr",
+                            "version": env!("CARGO_PKG_VERSION"),
+                        },
+                    }),
+                )?,
+                "shutdown" => respond(&mut output, &message, Value::Null)?,
+                "exit" => return Ok(()),
+                "textDocument/didOpen" => {
+                    let document = &params["textDocument"];
+                    if let (Some(uri), Some(text)) =
+                        (document["uri"].as_str(), document["text"].as_str())
+                    {
+                        self.publish(&mut output, uri, text)?;
+                    }
+                }
+                "textDocument/didChange" => {
+                    let uri = params["textDocument"]["uri"].as_str();
+                    let text = params["contentChanges"]
+                        .as_array()
+                        .and_then(|changes| changes.last())
+                        .and_then(|change| change["text"].as_str());
+                    if let (Some(uri), Some(text)) = (uri, text) {
+                        self.publish(&mut output, uri, text)?;
+                    }
+                }
+                "textDocument/didClose" => {
+                    if let Some(uri) = params["textDocument"]["uri"].as_str()
+                        && self.handles(uri)
+                    {
+                        publish_diagnostics(&mut output, uri, &[])?;
+                    }
+                }
+                _ if message.get("id").is_some() => {
+                    let error = json!({ "code": -32601, "message": "method not found" });
+                    write_message(
+                        &mut output,
+                        &json!({ "jsonrpc": "2.0", "id": message["id"], "error": error }),
+                    )?;
+                }
+                // Other notifications (`initialized`, `$/...`, saves) need no answer.
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn publish<W: Write>(&self, output: &mut W, uri: &str, text: &str) -> io::Result<()> {
+        if !self.handles(uri) {
+            return Ok(());
+        }
+        publish_diagnostics(output, uri, &self.diagnostics(uri, text))
+    }
+
+    fn registration_for(&self, uri: &str) -> Option<&Registration> {
+        let path = uri_path(uri);
+        self.registrations
+            .iter()
+            .find(|registration| glob_matches(&registration.glob, &path))
+    }
+}
+
+/// Borrowed state for converting errors and warnings of one check into diagnostics.
+struct Context<'a> {
+    text: &'a str,
+    registry: &'a SourceRegistry,
+    formatter: &'a dyn MessageFormatter,
+}
+
+impl Context<'_> {
+    fn l10n(&self) -> &dyn Localizer {
+        self.formatter.localizer()
+    }
+
+    fn push_error(&self, out: &mut Vec<Diagnostic>, err: &Error) {
+        match err {
+            Error::WithSnippet { error, .. } => self.push_error(out, error),
+            Error::ValidationErrors { errors, .. } => {
+                for err in errors {
+                    self.push_error(out, err);
+                }
+            }
+            Error::ValidationError {
+                source,
+                issues,
+                locations,
+            } => {
+                let l10n = self.l10n();
+                for issue in issues {
+                    let original_leaf = issue
+                        .path
+                        .leaf_string()
+                        .unwrap_or_else(|| l10n.root_path_label().into_owned());
+                    let (locs, resolved_leaf) = locations
+                        .search_with_ancestor_fallback(&issue.path)
+                        .unwrap_or((Locations::UNKNOWN, original_leaf));
+                    let resolved_path = format_path_with_resolved_leaf(&issue.path, &resolved_leaf);
+                    let entry =
+                        issue.display_entry_overridden(l10n, source.external_message_source());
+                    let message = l10n.validation_base_message(&entry, &resolved_path);
+                    self.push(out, Severity::Error, Some(err.code()), message, locs);
+                }
+            }
+            other => {
+                let mut message = self.formatter.format_message(other).into_owned();
+                if let Some(suggestion) = other.suggestion() {
+                    message.push('\n');
+                    message.push_str(&self.l10n().did_you_mean(suggestion));
+                }
+                let locs = other.locations().unwrap_or(Locations::UNKNOWN);
+                self.push(out, Severity::Error, Some(other.code()), message, locs);
+            }
+        }
+    }
+
+    fn push_warning(&self, out: &mut Vec<Diagnostic>, warning: &Warning) {
+        let message = self.formatter.format_warning(warning).into_owned();
+        let locs = Locations::same(&warning.location).unwrap_or(Locations::UNKNOWN);
+        self.push(out, Severity::Warning, None, message, locs);
+    }
+
+    fn push(
+        &self,
+        out: &mut Vec<Diagnostic>,
+        severity: Severity,
+        code: Option<&'static str>,
+        message: String,
+        locs: Locations,
+    ) {
+        let primary = locs.primary_location().unwrap_or(Location::UNKNOWN);
+        let (range, included_from) = self.document_range(primary);
+        let message = match included_from {
+            Some(name) => format!("{name}: {message}"),
+            None => message,
+        };
+
+        let mut related = Vec::new();
+        let defined = locs.defined_location;
+        if defined != Location::UNKNOWN && defined != primary {
+            let (range, _) = self.document_range(defined);
+            related.push((range, self.l10n().defined_here().into_owned()));
+        }
+
+        out.push(Diagnostic {
+            range,
+            severity,
+            code,
+            message,
+            related,
+        });
+    }
+
+    /// Range of `location` in the open document.
+    ///
+    /// A location inside an included file is mapped to the `!include` that brought it in;
+    /// the name of the included source is returned alongside.
+    fn document_range(&self, location: Location) -> (Range, Option<String>) {
+        if location == Location::UNKNOWN {
+            return (Range::EMPTY, None);
+        }
+
+        let mut location = location;
+        let mut included_from = None;
+        while let Some(info) = self.registry.get(location.source_id()) {
+            let Some(parent) = info.parent() else {
+                break;
+            };
+            if included_from.is_none() {
+                included_from = Some(info.name().to_owned());
+            }
+            location = info.include_location();
+            if location == Location::UNKNOWN || location.source_id() != parent {
+                return (Range::EMPTY, included_from);
+            }
+        }
+
+        let start = position_of(self.text, location.line(), location.column());
+        let len = usize::try_from(location.span().len()).unwrap_or(usize::MAX);
+        let end = advance(self.text, start, len.max(1));
+        (Range { start, end }, included_from)
+    }
+}
+
+impl Range {
+    /// The empty range at the start of the document, for problems without a location.
+    const EMPTY: Range = Range {
+        start: Position {
+            line: 0,
+            character: 0,
+        },
+        end: Position {
+            line: 0,
+            character: 0,
+        },
+    };
+}
+
+/// LSP position of the 1-based, character-based `line` and `column` in `text`.
+fn position_of(text: &str, line: u64, column: u64) -> Position {
+    let line_index = line.saturating_sub(1);
+    let Some(line_text) = usize::try_from(line_index)
+        .ok()
+        .and_then(|index| text.split('\n').nth(index))
+    else {
+        return Position {
+            line: saturating_u32(line_index),
+            character: 0,
+        };
+    };
+    let chars = usize::try_from(column.saturating_sub(1)).unwrap_or(usize::MAX);
+    let character: usize = line_text.chars().take(chars).map(char::len_utf16).sum();
+    Position {
+        line: saturating_u32(line_index),
+        character: saturating_u32(character as u64),
+    }
+}
+
+/// Position `chars` characters after `start`, stopping at the end of `text`.
+fn advance(text: &str, start: Position, chars: usize) -> Position {
+    let mut rest = text
+        .split('\n')
+        .skip(start.line as usize)
+        .flat_map(|line| line.chars().chain(std::iter::once('\n')));
+
+    // Skip to `start` (counted in UTF-16 units on its line).
+    let mut skipped = 0;
+    while skipped < start.character as usize {
+        match rest.next() {
+            Some(ch) => skipped += ch.len_utf16(),
+            None => return start,
+        }
+    }
+
+    let mut end = start;
+    for ch in rest.take(chars) {
+        if ch == '\n' {
+            end.line += 1;
+            end.character = 0;
+        } else {
+            end.character += saturating_u32(ch.len_utf16() as u64);
+        }
+    }
+    end
+}
+
+fn saturating_u32(value: u64) -> u32 {
+    u32::try_from(value).unwrap_or(u32::MAX)
+}
+
+/// File path of a `file:` URI with percent escapes decoded; other URIs are returned as is.
+fn uri_path(uri: &str) -> Cow<'_, str> {
+    let Some(path) = uri.strip_prefix("file://") else {
+        return Cow::Borrowed(uri);
+    };
+    if !path.contains('%') {
+        return Cow::Borrowed(path);
+    }
+
+    let bytes = path.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes
+            .get(i + 1..i + 3)
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match (bytes[i], hex) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            (byte, _) => {
+                decoded.push(byte);
+                i += 1;
+            }
+        }
+    }
+    Cow::Owned(String::from_utf8_lossy(&decoded).into_owned())
+}
+
+/// Match `path` against `glob`: `*` and `?` stay within a segment, `**` spans segments.
+fn glob_matches(glob: &str, path: &str) -> bool {
+    fn matches(glob: &[u8], path: &[u8]) -> bool {
+        match glob {
+            [] => path.is_empty(),
+            [b'*', b'*', b'/', rest @ ..] => {
+                // `**/` matches zero or more whole segments.
+                matches(rest, path)
+                    || path
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, byte)| **byte == b'/')
+                        .any(|(i, _)| matches(rest, &path[i + 1..]))
+            }
+            [b'*', b'*', rest @ ..] => (0..=path.len()).any(|i| matches(rest, &path[i..])),
+            [b'*', rest @ ..] => {
+                let segment = path.iter().position(|byte| *byte == b'/');
+                let limit = segment.unwrap_or(path.len());
+                (0..=limit).any(|i| matches(rest, &path[i..]))
+            }
+            [b'?', rest @ ..] => match path {
+                [byte, tail @ ..] if *byte != b'/' => {
+                    // Skip a whole UTF-8 character.
+                    let extra = tail.iter().take_while(|b| (**b & 0xC0) == 0x80).count();
+                    matches(rest, &tail[extra..])
+                }
+                _ => false,
+            },
+            [first, rest @ ..] => path
+                .split_first()
+                .is_some_and(|(byte, tail)| byte == first && matches(rest, tail)),
+        }
+    }
+
+    let glob = glob.strip_prefix("./").unwrap_or(glob);
+    if glob.starts_with('/') || glob.starts_with("**") {
+        matches(glob.as_bytes(), path.as_bytes())
+    } else {
+        // Relative globs match at any directory depth.
+        let path = path.as_bytes();
+        matches(glob.as_bytes(), path)
+            || path
+                .iter()
+                .enumerate()
+                .filter(|(_, byte)| **byte == b'/')
+                .any(|(i, _)| matches(glob.as_bytes(), &path[i + 1..]))
+    }
+}
+
+/// Read one `Content-Length` framed message body; `None` at end of input.
+fn read_message<R: BufRead>(input: &mut R) -> io::Result<Option<Vec<u8>>> {
+    let mut content_length = None;
+    let mut line = String::new();
+    loop {
+        line.clear();
+        if input.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        let header = line.trim_end_matches(['\r', '\n']);
+        if header.is_empty() {
+            if content_length.is_some() {
+                break;
+            }
+            continue;
+        }
+        if let Some((name, value)) = header.split_once(':')
+            && name.trim().eq_ignore_ascii_case("content-length")
+        {
+            let length = value.trim().parse::<usize>().map_err(|_| {
+                io::Error::new(io::ErrorKind::InvalidData, "invalid Content-Length header")
+            })?;
+            if length > MAX_MESSAGE_BYTES {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "LSP message exceeds the size limit",
+                ));
+            }
+            content_length = Some(length);
+        }
+    }
+
+    let mut body = vec![0; content_length.unwrap_or(0)];
+    input.read_exact(&mut body)?;
+    Ok(Some(body))
+}
+
+fn write_message<W: Write>(output: &mut W, message: &Value) -> io::Result<()> {
+    let body = message.to_string();
+    write!(output, "Content-Length: {}\r\n\r\n{body}", body.len())?;
+    output.flush()
+}
+
+fn respond<W: Write>(output: &mut W, request: &Value, result: Value) -> io::Result<()> {
+    write_message(
+        output,
+        &json!({ "jsonrpc": "2.0", "id": request["id"], "result": result }),
+    )
+}
+
+fn publish_diagnostics<W: Write>(
+    output: &mut W,
+    uri: &str,
+    diagnostics: &[Diagnostic],
+) -> io::Result<()> {
+    let diagnostics: Vec<Value> = diagnostics
+        .iter()
+        .map(|diagnostic| {
+            let mut value = json!({
+                "range": range_json(diagnostic.range),
+                "severity": match diagnostic.severity {
+                    Severity::Error => 1,
+                    Severity::Warning => 2,
+                },
+                "source": "serde-saphyr",
+                "message": diagnostic.message,
+            });
+            if let Some(code) = diagnostic.code {
+                value["code"] = json!(code);
+            }
+            if !diagnostic.related.is_empty() {
+                value["relatedInformation"] = diagnostic
+                    .related
+                    .iter()
+                    .map(|(range, message)| {
+                        json!({
+                            "location": { "uri": uri, "range": range_json(*range) },
+                            "message": message,
+                        })
+                    })
+                    .collect();
+            }
+            value
+        })
+        .collect();
+
+    write_message(
+        output,
+        &json!({
+            "jsonrpc": "2.0",
+            "method": "textDocument/publishDiagnostics",
+            "params": { "uri": uri, "diagnostics": diagnostics },
+        }),
+    )
+}
+
+fn range_json(range: Range) -> Value {
+    json!({
+        "start": { "line": range.start.line, "character": range.start.character },
+        "end": { "line": range.end.line, "character": range.end.character },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn globs_match_paths() {
+        assert!(glob_matches("**/*.yaml", "/home/me/robot.yaml"));
+        assert!(glob_matches("*.yaml", "/home/me/robot.yaml"));
+        assert!(glob_matches("robots/*.yaml", "/srv/robots/arm.yaml"));
+        assert!(glob_matches("/srv/**/arm.yaml", "/srv/arm.yaml"));
+        assert!(glob_matches("/srv/**/arm.yaml", "/srv/a/b/arm.yaml"));
+        assert!(glob_matches("/srv/ar?.yaml", "/srv/arm.yaml"));
+        assert!(!glob_matches("/srv/*.yaml", "/srv/robots/arm.yaml"));
+        assert!(!glob_matches("robots/*.yaml", "/srv/robots.yaml"));
+        assert!(!glob_matches("*.yaml", "/srv/arm.yml"));
+    }
+
+    #[test]
+    fn file_uris_are_decoded() {
+        assert_eq!(
+            uri_path("file:///tmp/my%20robot.yaml"),
+            "/tmp/my robot.yaml"
+        );
+        assert_eq!(uri_path("untitled:Untitled-1"), "untitled:Untitled-1");
+    }
+
+    #[test]
+    fn positions_count_utf16_units() {
+        let text = "a: 1\n😀x: [\n";
+        assert_eq!(
+            position_of(text, 2, 2),
+            Position {
+                line: 1,
+                character: 2
+            }
+        );
+        let start = position_of(text, 1, 4);
+        assert_eq!(
+            advance(text, start, 3),
+            Position {
+                line: 1,
+                character: 2
+            }
+        );
+    }
+}
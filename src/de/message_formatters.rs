@@ -1,10 +1,10 @@
 use crate::Location;
+use crate::cancellation::Interruption;
 use crate::de_error::{Error, MessageFormatter, UserMessageFormatter};
 use crate::localizer::{ExternalMessage, Localizer};
 
 use std::borrow::Cow;
 
-#[cfg(any(feature = "garde", feature = "validator"))]
 use crate::{
     Locations,
     de_error::ValidationIssue,
@@ -23,7 +23,6 @@ pub struct DefaultMessageFormatter;
 /// Alias for the default developer-oriented formatter.
 pub type DeveloperMessageFormatter = DefaultMessageFormatter;
 
-#[cfg(any(feature = "garde", feature = "validator"))]
 fn format_validation_issues(
     l10n: &dyn Localizer,
     source: &ExternalMessageSource,
@@ -59,6 +58,20 @@ fn format_validation_issues(
     l10n.join_validation_issues(&lines)
 }
 
+/// Append the localized "did you mean" hint of [`Error::suggestion`], if any.
+fn with_suggestion<'a>(
+    formatter: &dyn MessageFormatter,
+    err: &Error,
+    base: String,
+) -> Cow<'a, str> {
+    match err.suggestion() {
+        Some(suggestion) => formatter
+            .localizer()
+            .attach_suggestion(Cow::Owned(base), suggestion),
+        None => Cow::Owned(base),
+    }
+}
+
 fn default_format_message<'a>(formatter: &dyn MessageFormatter, err: &'a Error) -> Cow<'a, str> {
     match err {
         Error::WithSnippet { error, .. } => default_format_message(formatter, error),
@@ -96,6 +109,12 @@ fn default_format_message<'a>(formatter: &dyn MessageFormatter, err: &'a Error)
         Error::PropertyRequiredButEmpty { name, message, .. } => {
             Cow::Owned(format!("empty property `{name}`: {message}"))
         }
+        Error::UnresolvedSubstitution { substitution, .. } => {
+            Cow::Owned(format!("unresolved substitution `{substitution}`"))
+        }
+        Error::InvalidSubstitution { substitution, .. } => {
+            Cow::Owned(format!("invalid substitution `{substitution}`"))
+        }
         Error::Eof { .. } => Cow::Borrowed("unexpected end of input"),
         Error::MultipleDocuments { hint, .. } => {
             Cow::Owned(format!("multiple YAML documents detected; {hint}"))
@@ -200,16 +219,24 @@ fn default_format_message<'a>(formatter: &dyn MessageFormatter, err: &'a Error)
         } => Cow::Owned(format!("invalid value: {unexpected}, expected {expected}")),
         Error::SerdeUnknownVariant {
             variant, expected, ..
-        } => Cow::Owned(format!(
-            "unknown variant `{variant}`, expected one of {}",
-            expected.join(", ")
-        )),
+        } => with_suggestion(
+            formatter,
+            err,
+            format!(
+                "unknown variant `{variant}`, expected one of {}",
+                expected.join(", ")
+            ),
+        ),
         Error::SerdeUnknownField {
             field, expected, ..
-        } => Cow::Owned(format!(
-            "unknown field `{field}`, expected one of {}",
-            expected.join(", ")
-        )),
+        } => with_suggestion(
+            formatter,
+            err,
+            format!(
+                "unknown field `{field}`, expected one of {}",
+                expected.join(", ")
+            ),
+        ),
         Error::SerdeMissingField { field, .. } => Cow::Owned(format!("missing field `{field}`")),
         Error::UnexpectedContainerEndWhileReadingKeyNode { .. } => {
             Cow::Borrowed("unexpected container end while reading key")
@@ -222,6 +249,17 @@ fn default_format_message<'a>(formatter: &dyn MessageFormatter, err: &'a Error)
                 "duplicate mapping key, set DuplicateKeyPolicy in Options if acceptable",
             ),
         },
+        Error::DuplicateOmapKey { key, .. } => match key {
+            Some(k) => Cow::Owned(format!("duplicate !!omap key: {k}")),
+            None => Cow::Borrowed("duplicate !!omap key"),
+        },
+        Error::SetValueNotNull { .. } => Cow::Borrowed("!!set entries must have null values"),
+        Error::InvalidPairsEntry { tag, .. } => Cow::Owned(format!(
+            "{tag} entries must be mappings with exactly one key"
+        )),
+        Error::RawYamlUnavailable { .. } => {
+            Cow::Borrowed("RawYaml requires the source text of the document being parsed")
+        }
         Error::TaggedEnumMismatch { tagged, target, .. } => Cow::Owned(format!(
             "tagged enum `{tagged}` does not match target enum `{target}`",
         )),
@@ -341,6 +379,10 @@ fn default_format_message<'a>(formatter: &dyn MessageFormatter, err: &'a Error)
             Cow::Owned(full_msg)
         }
         Error::Budget { breach, .. } => Cow::Owned(format!("budget breached: {breach:?}")),
+        Error::Interrupted { reason, .. } => match reason {
+            Interruption::DeadlineExceeded => Cow::Borrowed("parsing deadline exceeded"),
+            Interruption::Cancelled => Cow::Borrowed("parsing cancelled"),
+        },
         Error::QuotingRequired { value, .. } => {
             Cow::Owned(format!("The string value [{value}] must be quoted"))
         }
@@ -366,7 +408,6 @@ fn default_format_message<'a>(formatter: &dyn MessageFormatter, err: &'a Error)
             }
         }
 
-        #[cfg(any(feature = "garde", feature = "validator"))]
         Error::ValidationError {
             source,
             issues,
@@ -380,7 +421,6 @@ fn default_format_message<'a>(formatter: &dyn MessageFormatter, err: &'a Error)
                 locations,
             ))
         }
-        #[cfg(any(feature = "garde", feature = "validator"))]
         Error::ValidationErrors { errors, .. } => Cow::Owned(format!(
             "validation failed for {} document(s)",
             errors.len()
@@ -420,7 +460,10 @@ impl DefaultMessageFormatter {
     }
 }
 
-fn user_format_message<'a>(formatter: &dyn MessageFormatter, err: &'a Error) -> Cow<'a, str> {
+pub(crate) fn user_format_message<'a>(
+    formatter: &dyn MessageFormatter,
+    err: &'a Error,
+) -> Cow<'a, str> {
     if let Error::WithSnippet { error, .. } = err {
         return user_format_message(formatter, error);
     }
@@ -490,10 +533,22 @@ fn user_format_message<'a>(formatter: &dyn MessageFormatter, err: &'a Error) ->
             Some(k) => Cow::Owned(format!("duplicate mapping key: {k} not allowed here")),
             None => Cow::Borrowed("duplicate mapping key not allowed here"),
         },
+        Error::DuplicateOmapKey { .. } => Cow::Borrowed("duplicate key not allowed here"),
+        Error::SetValueNotNull { .. } => Cow::Borrowed("set entries cannot have values"),
+        Error::InvalidPairsEntry { .. } => {
+            Cow::Borrowed("each entry must be a single key: value pair")
+        }
+        Error::RawYamlUnavailable { .. } => {
+            Cow::Borrowed("raw source text is not available for this value")
+        }
         Error::QuotingRequired { .. } => Cow::Borrowed("value requires quoting"),
         Error::Budget { breach, .. } => Cow::Owned(format!(
             "YAML document too large or too complex: limits breached: {breach:?}"
         )),
+        Error::Interrupted { reason, .. } => match reason {
+            Interruption::DeadlineExceeded => Cow::Borrowed("took too long to read; stopped here"),
+            Interruption::Cancelled => Cow::Borrowed("reading was cancelled; stopped here"),
+        },
         Error::CannotBorrowTransformedString { .. } => {
             Cow::Borrowed("Only single string with no escape sequences is allowed here")
         }
@@ -643,6 +698,14 @@ mod tests {
         Error::InvalidPropertyName { name: "${ab-cd}".to_owned(), location: loc() },
         "Invalid name: '${ab-cd}'"
     )]
+    #[case::unresolved_substitution(
+        Error::UnresolvedSubstitution { substitution: "$(arg robot)".to_owned(), location: loc() },
+        "unresolved substitution `$(arg robot)`"
+    )]
+    #[case::invalid_substitution(
+        Error::InvalidSubstitution { substitution: "$(find robot)".to_owned(), location: loc() },
+        "invalid substitution `$(find robot)`"
+    )]
     fn default_exact_messages(#[case] err: Error, #[case] expected: &str) {
         let formatter = DefaultMessageFormatter;
         assert_eq!(formatter.format_message(&err), expected);
@@ -673,6 +736,30 @@ mod tests {
         },
         &["unknown field", "xyz"]
     )]
+    #[case::duplicate_omap_key(
+        Error::DuplicateOmapKey { key: Some("mykey".to_owned()), location: loc() },
+        &["mykey", "!!omap"]
+    )]
+    #[case::set_value_not_null(
+        Error::SetValueNotNull { location: loc() },
+        &["!!set", "null"]
+    )]
+    #[case::invalid_pairs_entry(
+        Error::InvalidPairsEntry { tag: "!!pairs", location: loc() },
+        &["!!pairs", "one key"]
+    )]
+    #[case::raw_yaml_unavailable(
+        Error::RawYamlUnavailable { location: loc() },
+        &["RawYaml", "source text"]
+    )]
+    #[case::interrupted_deadline(
+        Error::Interrupted { reason: Interruption::DeadlineExceeded, location: loc() },
+        &["deadline exceeded"]
+    )]
+    #[case::interrupted_cancelled(
+        Error::Interrupted { reason: Interruption::Cancelled, location: loc() },
+        &["cancelled"]
+    )]
     #[case::io_error(
         Error::IOError { cause: std::io::Error::other("disk full") },
         &["IO error", "disk full"]
@@ -836,6 +923,30 @@ mod tests {
         Error::QuotingRequired { value: "yes".to_owned(), location: loc() },
         "value requires quoting"
     )]
+    #[case::duplicate_omap_key(
+        Error::DuplicateOmapKey { key: None, location: loc() },
+        "duplicate key not allowed here"
+    )]
+    #[case::set_value_not_null(
+        Error::SetValueNotNull { location: loc() },
+        "set entries cannot have values"
+    )]
+    #[case::invalid_pairs_entry(
+        Error::InvalidPairsEntry { tag: "!!omap", location: loc() },
+        "each entry must be a single key: value pair"
+    )]
+    #[case::raw_yaml_unavailable(
+        Error::RawYamlUnavailable { location: loc() },
+        "raw source text is not available for this value"
+    )]
+    #[case::interrupted_deadline(
+        Error::Interrupted { reason: Interruption::DeadlineExceeded, location: loc() },
+        "took too long to read; stopped here"
+    )]
+    #[case::interrupted_cancelled(
+        Error::Interrupted { reason: Interruption::Cancelled, location: loc() },
+        "reading was cancelled; stopped here"
+    )]
     #[case::cannot_borrow_transformed_string(
         Error::CannotBorrowTransformedString {
             reason: TransformReason::EscapeSequence,
@@ -942,7 +1053,6 @@ mod tests {
         assert_eq!(formatter.localizer().root_path_label(), "<root>");
     }
 
-    #[cfg(any(feature = "garde", feature = "validator"))]
     #[test]
     fn validation_message_prefers_reference_location() {
         use crate::de_error::{ValidationIssue, ValidationSource};
@@ -12,7 +12,6 @@ use crate::Location;
 use crate::de_error::CroppedRegion;
 use crate::de_snippet::sanitize_terminal_snippet_preserve_len;
 use crate::{MessageFormatter, RenderOptions};
-#[cfg(any(feature = "garde", feature = "validator"))]
 use crate::{
     location::Locations,
     path_map::{PathKey, PathMap, format_path_with_resolved_leaf},
@@ -59,9 +58,11 @@ pub fn to_miette_report_with_formatter(
 #[derive(Clone, Debug)]
 struct ErrorDiagnostic {
     message: String,
+    code: Option<&'static str>,
     src: Arc<NamedSource<String>>,
     labels: Vec<LabeledSpan>,
     related: Vec<ErrorDiagnostic>,
+    help: Option<String>,
 }
 
 impl fmt::Display for ErrorDiagnostic {
@@ -73,6 +74,11 @@ impl fmt::Display for ErrorDiagnostic {
 impl std::error::Error for ErrorDiagnostic {}
 
 impl Diagnostic for ErrorDiagnostic {
+    fn code<'a>(&'a self) -> Option<Box<dyn fmt::Display + 'a>> {
+        self.code
+            .map(|code| Box::new(code) as Box<dyn fmt::Display>)
+    }
+
     fn source_code(&self) -> Option<&dyn miette::SourceCode> {
         Some(&*self.src)
     }
@@ -85,6 +91,12 @@ impl Diagnostic for ErrorDiagnostic {
         }
     }
 
+    fn help<'a>(&'a self) -> Option<Box<dyn fmt::Display + 'a>> {
+        self.help
+            .as_ref()
+            .map(|help| Box::new(help) as Box<dyn fmt::Display>)
+    }
+
     fn related(&self) -> Option<Box<dyn Iterator<Item = &dyn Diagnostic> + '_>> {
         if self.related.is_empty() {
             return None;
@@ -100,7 +112,6 @@ fn build_diagnostic(
     regions: &[CroppedRegion],
 ) -> ErrorDiagnostic {
     match err {
-        #[cfg(any(feature = "garde", feature = "validator"))]
         Error::ValidationError {
             issues, locations, ..
         } => {
@@ -124,13 +135,14 @@ fn build_diagnostic(
                         " (multiple errors)"
                     }
                 ),
+                code: Some(err.code()),
                 src,
                 labels: Vec::new(),
                 related,
+                help: None,
             }
         }
 
-        #[cfg(any(feature = "garde", feature = "validator"))]
         Error::ValidationErrors { errors, .. } => {
             let mut related = Vec::new();
             for e in errors {
@@ -144,9 +156,11 @@ fn build_diagnostic(
 
             ErrorDiagnostic {
                 message: format!("validation failed for {} document(s)", errors.len()),
+                code: Some(err.code()),
                 src,
                 labels: Vec::new(),
                 related,
+                help: None,
             }
         }
 
@@ -183,9 +197,11 @@ fn build_diagnostic(
                     if let Some(span) = span {
                         diag.related.push(ErrorDiagnostic {
                             message: "included from here".to_owned(),
+                            code: None,
                             src: synthetic_src,
                             labels: vec![LabeledSpan::new_with_span(None, span)],
                             related: Vec::new(),
+                            help: None,
                         });
                     }
                 }
@@ -205,9 +221,11 @@ fn build_diagnostic(
 
             ErrorDiagnostic {
                 message: formatter.format_message(err).into_owned(),
+                code: Some(err.code()),
                 src: actual_src,
                 labels,
                 related: Vec::new(),
+                help: None,
             }
         }
 
@@ -228,9 +246,13 @@ fn build_diagnostic(
 
             ErrorDiagnostic {
                 message: formatter.format_message(other).into_owned(),
+                code: Some(other.code()),
                 src: actual_src,
                 labels,
                 related: Vec::new(),
+                help: other
+                    .suggestion()
+                    .map(|suggestion| formatter.localizer().did_you_mean(suggestion)),
             }
         }
     }
@@ -255,7 +277,6 @@ fn insert_selected_region_key<'a>(
     }
 }
 
-#[cfg(any(feature = "garde", feature = "validator"))]
 fn build_validation_entry_diagnostic(
     src: &Arc<NamedSource<String>>,
     path_key: &PathKey,
@@ -282,9 +303,11 @@ fn build_validation_entry_diagnostic(
 
     ErrorDiagnostic {
         message: base_msg,
+        code: None,
         src: actual_src,
         labels,
         related: Vec::new(),
+        help: None,
     }
 }
 
@@ -22,11 +22,19 @@ pub mod budget;
 #[cfg(feature = "deserialize")]
 pub(crate) mod buffered_input;
 #[cfg(feature = "deserialize")]
+pub mod cancellation;
+#[cfg(feature = "deserialize")]
+pub mod diff;
+#[cfg(feature = "deserialize")]
 pub(crate) mod error;
+#[cfg(feature = "deserialize")]
+pub mod error_codes;
 #[cfg(feature = "figment")]
 pub mod figment;
 #[cfg(feature = "figment2")]
 pub mod figment2;
+#[cfg(feature = "fluent")]
+pub mod fluent_localizer;
 #[cfg(feature = "deserialize")]
 pub(crate) mod include;
 #[cfg(all(feature = "deserialize", feature = "include"))]
@@ -35,19 +43,23 @@ pub(crate) mod include_stack;
 pub(crate) mod indentation;
 #[cfg(feature = "deserialize")]
 pub(crate) mod input_source;
-#[cfg(any(feature = "garde", feature = "validator"))]
+#[cfg(feature = "deserialize")]
 pub(crate) mod lib_validate;
 #[cfg(feature = "deserialize")]
+pub mod lint;
+#[cfg(feature = "deserialize")]
 pub(crate) mod live_events;
 #[cfg(feature = "deserialize")]
 pub mod localizer;
+#[cfg(feature = "lsp")]
+pub mod lsp;
 #[cfg(feature = "deserialize")]
 pub(crate) mod message_formatters;
 #[cfg(feature = "miette")]
 pub mod miette;
 #[cfg(feature = "deserialize")]
 pub mod options;
-#[cfg(any(feature = "garde", feature = "validator"))]
+#[cfg(feature = "deserialize")]
 pub mod path_map;
 #[cfg(feature = "properties")]
 pub mod properties;
@@ -57,19 +69,31 @@ pub(crate) mod properties_redaction;
 pub(crate) mod ring_reader;
 #[cfg(feature = "robotics")]
 pub mod robotics;
+#[cfg(feature = "properties")]
+pub mod ros_substitutions;
 #[cfg(all(feature = "deserialize", feature = "include_fs"))]
 pub(crate) mod safe_resolver;
 #[cfg(feature = "deserialize")]
 pub(crate) mod snippet;
 #[cfg(feature = "deserialize")]
+pub mod source_map;
+#[cfg(feature = "deserialize")]
+pub mod source_registry;
+#[cfg(feature = "deserialize")]
+pub(crate) mod suggest;
+#[cfg(feature = "deserialize")]
 pub(crate) mod tags;
+#[cfg(feature = "deserialize")]
+pub mod warnings;
 
 pub(crate) mod api;
 mod cfg;
+mod collection_deser;
 mod commented_deser;
 mod deserializer;
 mod events;
 mod key_nodes;
+mod raw_deser;
 mod spanned_deser;
 #[cfg(test)]
 mod tests;
@@ -86,7 +110,9 @@ pub use self::deserializer::YamlDeserializer;
 pub use self::error::Error;
 #[cfg(feature = "properties")]
 pub use self::options::PropertySyntax;
-pub use self::options::{AliasLimits, DuplicateKeyPolicy, MergeKeyPolicy, Options};
+pub use self::options::{
+    AliasLimits, DuplicateKeyPolicy, MergeKeyPolicy, Options, UnknownKeyPolicy,
+};
 pub use crate::location::Location;
 
 pub(crate) use self::cfg::Cfg;
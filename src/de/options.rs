@@ -1,12 +1,19 @@
+use crate::Schema;
 use crate::budget::Budget;
+use crate::cancellation::CancellationToken;
 use crate::indentation::RequireIndent;
 #[cfg(feature = "properties")]
+use crate::ros_substitutions::RosSubstitutions;
+use crate::source_registry::SourceRegistry;
+use crate::warnings::Warnings;
+#[cfg(feature = "properties")]
 use std::collections::HashMap;
 #[cfg(feature = "include_fs")]
 use std::io;
 #[cfg(feature = "include_fs")]
 use std::path::Path;
 use std::rc::Rc;
+use std::time::{Duration, Instant};
 
 #[cfg(feature = "serde_derived_types")]
 const fn default_emit_comments() -> bool {
@@ -51,6 +58,28 @@ pub enum PropertySyntax {
     BracedOrBare,
 }
 
+/// Policy for mapping keys that the target type does not use.
+///
+/// A key counts as unknown when Serde skips its value, which is what derived structs
+/// without `#[serde(deny_unknown_fields)]` do. Unknown keys inside a `#[serde(flatten)]`
+/// struct are dropped by Serde itself and are not seen.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde_derived_types",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+#[cfg_attr(feature = "serde_derived_types", serde(rename_all = "snake_case"))]
+pub enum UnknownKeyPolicy {
+    /// Skip unknown keys silently, as Serde does.
+    #[default]
+    Ignore,
+
+    /// Read the whole document, then fail with one [`crate::Error::ValidationError`]
+    /// listing every unknown key with its path and location.
+    Error,
+}
+
 /// Merge key handling policy for YAML mappings.
 #[non_exhaustive]
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
@@ -199,8 +228,28 @@ pub struct Options {
     /// Default: [`MergeKeyPolicy::Merge`].
     #[cfg_attr(feature = "serde_derived_types", serde(default))]
     pub merge_keys: MergeKeyPolicy,
+    /// Policy for mapping keys that the target type skips.
+    ///
+    /// Use [`UnknownKeyPolicy::Error`] to reject typos in optional fields without adding
+    /// `#[serde(deny_unknown_fields)]` to every type. To only report such keys, collect
+    /// [`crate::WarningKind::IgnoredKey`] findings with [`Options::with_warnings`].
+    ///
+    /// Default: [`UnknownKeyPolicy::Ignore`].
+    #[cfg_attr(feature = "serde_derived_types", serde(default))]
+    pub unknown_keys: UnknownKeyPolicy,
     /// Limits for alias replay to harden against alias bombs.
     pub alias_limits: AliasLimits,
+    /// YAML schema resolving untagged plain scalars, for both typeless targets and
+    /// typed `bool`, integer, float, string and `Option` fields.
+    ///
+    /// When set, it replaces [`Options::legacy_octal_numbers`] and
+    /// [`Options::strict_booleans`], and [`Options::no_schema`] rejects exactly the
+    /// unquoted strings the schema resolves to another type. With [`Schema::Core`] or
+    /// `None`, a document starting with `%YAML 1.1` is read with [`Schema::Yaml11`].
+    ///
+    /// Default: `None`, the lenient resolution controlled by the individual flags.
+    #[cfg_attr(feature = "serde_derived_types", serde(default))]
+    pub schema: Option<Schema>,
     /// Enable legacy octal parsing where values starting with `0` are treated as base-8.
     /// They are deprecated in YAML 1.2. Default: false.
     pub legacy_octal_numbers: bool,
@@ -217,6 +266,13 @@ pub struct Options {
     /// conversion functions (deg, rad) and simple mathematical expressions such as deg(180),
     /// rad(pi), 1 + 2*(3 - 4/5), or rad(pi/2). `robotics` feature must also be enabled.
     pub angle_conversions: bool,
+    /// Converts physical units in float scalars to SI base units: lengths (`mm(12)`, `12mm`,
+    /// `cm`, `in`, ...) to metres, times (`ms(250)`, `min`, `h`, ...) to seconds and
+    /// frequencies (`Hz(50)`, `kHz`, `MHz`) to hertz. Unit names ignore case (`hz(50)`), but
+    /// `5mhz` and `12MM` are errors because `m` (milli) and `M` (mega) would be confused.
+    /// Only terms of the same kind can be added.
+    /// Implies `angle_conversions`. `robotics` feature must also be enabled.
+    pub unit_conversions: bool,
     /// If true, values that can be parsed as booleans or numbers are rejected as
     /// unquoted strings. This flag is intended for teams that want to enforce
     /// compatibility with YAML parsers that infer types from unquoted values,
@@ -257,6 +313,27 @@ pub struct Options {
     /// Indentation requirement for the parsed document.
     pub require_indent: RequireIndent,
 
+    /// Optional collector for non-fatal findings such as YAML 1.1 booleans or ignored keys.
+    /// Install it with [`Options::with_warnings`]; see [`crate::warnings`].
+    #[cfg_attr(feature = "serde_derived_types", serde(skip))]
+    pub warnings: Option<Warnings>,
+
+    /// Optional registry that keeps the name and text of every source read, keyed by
+    /// [`crate::Location::source_id`]. Install it with [`Options::with_source_registry`].
+    #[cfg_attr(feature = "serde_derived_types", serde(skip))]
+    pub source_registry: Option<SourceRegistry>,
+
+    /// Point in time after which parsing stops with [`crate::Error::Interrupted`].
+    /// Set it with [`Options::with_deadline`] or [`Options::with_timeout`]; see
+    /// [`crate::cancellation`].
+    #[cfg_attr(feature = "serde_derived_types", serde(skip))]
+    pub deadline: Option<Instant>,
+
+    /// Token that stops parsing with [`crate::Error::Interrupted`] once cancelled.
+    /// Install it with [`Options::with_cancellation`].
+    #[cfg_attr(feature = "serde_derived_types", serde(skip))]
+    pub cancellation: Option<CancellationToken>,
+
     /// Optional include resolver callback.
     ///
     /// When provided, it can push parsers onto the internal parser stack to resolve `!include`
@@ -276,6 +353,12 @@ pub struct Options {
     #[cfg(feature = "properties")]
     #[cfg_attr(feature = "serde_derived_types", serde(default))]
     pub property_syntax: PropertySyntax,
+
+    /// Resolvers for ROS 2 launch-style `$(...)` substitutions in scalar values.
+    /// Install them with [`Options::with_ros_substitutions`].
+    #[cfg(feature = "properties")]
+    #[cfg_attr(feature = "serde_derived_types", serde(skip))]
+    pub ros_substitutions: Option<Rc<RosSubstitutions>>,
 }
 
 #[cfg(feature = "include")]
@@ -329,6 +412,78 @@ impl Options {
         self
     }
 
+    /// Collects non-fatal findings into `warnings` instead of discarding them.
+    ///
+    /// The collector is shared: keep a clone and call [`Warnings::take`] after loading.
+    ///
+    /// ```rust
+    /// use serde_saphyr::Warnings;
+    ///
+    /// let warnings = Warnings::new();
+    /// let options = serde_saphyr::options! {}.with_warnings(&warnings);
+    /// let on: bool = serde_saphyr::from_str_with_options("on", options).unwrap();
+    /// assert!(on);
+    /// assert_eq!(warnings.len(), 1);
+    /// ```
+    #[must_use]
+    pub fn with_warnings(mut self, warnings: &Warnings) -> Self {
+        self.warnings = Some(warnings.clone());
+        self
+    }
+
+    /// Records the root input and every included source into `registry`.
+    ///
+    /// The registry is shared: keep a clone to look up sources and render snippets
+    /// after loading; see [`crate::source_registry`].
+    ///
+    /// ```rust
+    /// use serde_saphyr::SourceRegistry;
+    ///
+    /// let registry = SourceRegistry::new();
+    /// let options = serde_saphyr::options! {}.with_source_registry(&registry);
+    /// let _: u8 = serde_saphyr::from_str_with_options("1", options).unwrap();
+    /// assert_eq!(registry.sources()[0].text(), Some("1"));
+    /// ```
+    #[must_use]
+    pub fn with_source_registry(mut self, registry: &SourceRegistry) -> Self {
+        self.source_registry = Some(registry.clone());
+        self
+    }
+
+    /// Stops parsing with [`crate::Error::Interrupted`] once `deadline` has passed.
+    ///
+    /// The deadline is checked between parser events and around include resolution,
+    /// so a reader that blocks is only interrupted when it returns.
+    #[must_use]
+    pub fn with_deadline(mut self, deadline: Instant) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// Stops parsing once `timeout` has elapsed, measured from this call.
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    ///
+    /// let options = serde_saphyr::options! {}.with_timeout(Duration::from_secs(5));
+    /// let v: Vec<u8> = serde_saphyr::from_str_with_options("[1, 2]", options).unwrap();
+    /// assert_eq!(v, [1, 2]);
+    /// ```
+    #[must_use]
+    pub fn with_timeout(self, timeout: Duration) -> Self {
+        self.with_deadline(Instant::now() + timeout)
+    }
+
+    /// Stops parsing with [`crate::Error::Interrupted`] once `token` is cancelled.
+    ///
+    /// The token is shared: keep a clone and call [`CancellationToken::cancel`], for
+    /// example from another thread or a request handler.
+    #[must_use]
+    pub fn with_cancellation(mut self, token: &CancellationToken) -> Self {
+        self.cancellation = Some(token.clone());
+        self
+    }
+
     /// Installs a property map used for `${NAME}` interpolation in plain scalars.
     ///
     /// This is the intended public API for the `properties` feature. It consumes the provided
@@ -355,6 +510,30 @@ impl Options {
         self
     }
 
+    /// Enables ROS 2 launch-style `$(...)` substitutions in plain scalars, answered by
+    /// `substitutions`.
+    ///
+    /// Substitutions are expanded together with `${NAME}` properties, which stay disabled
+    /// unless [`Options::with_properties`] is also used. See [`crate::ros_substitutions`]
+    /// for the supported forms.
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "properties")]
+    /// # {
+    /// use serde_saphyr::{Options, RosSubstitutions};
+    ///
+    /// let substitutions = RosSubstitutions::new().with_env_resolver(|name| std::env::var(name).ok());
+    /// let options = Options::default().with_ros_substitutions(substitutions);
+    /// # let _ = options;
+    /// # }
+    /// ```
+    #[cfg(feature = "properties")]
+    #[must_use]
+    pub fn with_ros_substitutions(mut self, substitutions: RosSubstitutions) -> Self {
+        self.ros_substitutions = Some(Rc::new(substitutions));
+        self
+    }
+
     /// Sets the include resolver callback to be used during parsing.
     ///
     /// This method is for advances use cases. If you just want to include files from the
@@ -482,16 +661,23 @@ impl Default for Options {
             emit_comments: true,
             duplicate_keys: DuplicateKeyPolicy::Error,
             merge_keys: MergeKeyPolicy::Merge,
+            unknown_keys: UnknownKeyPolicy::Ignore,
             alias_limits: AliasLimits::default(),
+            schema: None,
             legacy_octal_numbers: false,
             strict_booleans: false,
             angle_conversions: false,
+            unit_conversions: false,
             ignore_binary_tag_for_string: false,
             no_schema: false,
             reject_non_finite_typeless_float: true,
             with_snippet: true,
             crop_radius: 64,
             require_indent: RequireIndent::Unchecked,
+            warnings: None,
+            source_registry: None,
+            deadline: None,
+            cancellation: None,
 
             #[cfg(feature = "include")]
             include_resolver: None,
@@ -499,6 +685,8 @@ impl Default for Options {
             property_map: None,
             #[cfg(feature = "properties")]
             property_syntax: PropertySyntax::Braced,
+            #[cfg(feature = "properties")]
+            ros_substitutions: None,
         }
     }
 }
@@ -519,7 +707,9 @@ impl std::fmt::Debug for Options {
             .field("emit_comments", &self.emit_comments)
             .field("duplicate_keys", &self.duplicate_keys)
             .field("merge_keys", &self.merge_keys)
+            .field("unknown_keys", &self.unknown_keys)
             .field("alias_limits", &self.alias_limits)
+            .field("schema", &self.schema)
             .field("legacy_octal_numbers", &self.legacy_octal_numbers)
             .field("strict_booleans", &self.strict_booleans)
             .field(
@@ -527,6 +717,7 @@ impl std::fmt::Debug for Options {
                 &self.ignore_binary_tag_for_string,
             )
             .field("angle_conversions", &self.angle_conversions)
+            .field("unit_conversions", &self.unit_conversions)
             .field("no_schema", &self.no_schema)
             .field(
                 "reject_non_finite_typeless_float",
@@ -535,6 +726,10 @@ impl std::fmt::Debug for Options {
             .field("with_snippet", &self.with_snippet)
             .field("crop_radius", &self.crop_radius)
             .field("require_indent", &self.require_indent)
+            .field("warnings", &self.warnings)
+            .field("source_registry", &self.source_registry)
+            .field("deadline", &self.deadline)
+            .field("cancellation", &self.cancellation)
             .field("include_resolver", &{
                 #[cfg(feature = "include")]
                 {
@@ -573,6 +768,16 @@ impl std::fmt::Debug for Options {
                     "disabled".to_string()
                 }
             })
+            .field("ros_substitutions", &{
+                #[cfg(feature = "properties")]
+                {
+                    format!("{:?}", self.ros_substitutions)
+                }
+                #[cfg(not(feature = "properties"))]
+                {
+                    "disabled".to_string()
+                }
+            })
             .finish()
     }
 }
@@ -597,7 +802,9 @@ mod tests {
         assert!(opts.emit_comments);
         assert!(matches!(opts.duplicate_keys, DuplicateKeyPolicy::Error));
         assert!(matches!(opts.merge_keys, MergeKeyPolicy::Merge));
+        assert_eq!(opts.unknown_keys, UnknownKeyPolicy::Ignore);
         assert_eq!(opts.alias_limits.max_total_replayed_events, 1_000_000);
+        assert_eq!(opts.schema, None);
         assert!(!opts.legacy_octal_numbers);
         assert!(!opts.strict_booleans);
         assert!(!opts.ignore_binary_tag_for_string);
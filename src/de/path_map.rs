@@ -21,11 +21,15 @@
 //!
 //! Any non-direct pass succeeds only if it yields exactly one candidate; otherwise the result is
 //! considered ambiguous.
+//!
+//! The same recorded paths back [`crate::source_map::SourceMap`], which only needs direct
+//! lookups; the fuzzy passes are used by the validation integrations.
 
 use crate::location::Locations;
 
 use std::borrow::Cow;
 use std::collections::HashMap;
+use std::fmt;
 use std::mem;
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
@@ -34,8 +38,12 @@ pub(crate) enum PathKind {
     Index,
 }
 
+/// One segment of a [`PathKey`]: a mapping key or a sequence index.
+///
+/// Built through `From<&str>`, `From<String>` and `From<usize>`, usually by
+/// [`PathKey::join`] or [`crate::path_key!`].
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
-pub(crate) struct PathSegment {
+pub struct PathSegment {
     pub(crate) kind: PathKind,
     pub(crate) name: String,
 }
@@ -76,7 +84,7 @@ impl From<usize> for PathSegment {
     }
 }
 
-/// A structured path to a value reported by a validation library.
+/// A structured path to a value in the deserialized document.
 ///
 /// The empty path refers to the root value. Use [`PathKey::join_key`] and
 /// [`PathKey::join_index`] to build paths to nested mapping and sequence values.
@@ -122,7 +130,9 @@ impl PathKey {
         }
     }
 
-    pub(crate) fn join<T: Into<PathSegment>>(mut self, seg: T) -> Self {
+    /// Return this path extended with a key (`&str`, `String`) or an index (`usize`).
+    #[must_use]
+    pub fn join<T: Into<PathSegment>>(mut self, seg: T) -> Self {
         self.segments.push(seg.into());
         self
     }
@@ -161,6 +171,14 @@ impl PathKey {
     }
 }
 
+/// Formats as `servers[0].port`; the root path is `<root>`.
+impl fmt::Display for PathKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let leaf = self.leaf_string().unwrap_or_default();
+        f.write_str(&format_path_with_resolved_leaf(self, &leaf))
+    }
+}
+
 pub(crate) fn format_path_with_resolved_leaf(path: &PathKey, resolved_leaf: &str) -> String {
     let mut out = String::new();
     let last_index = path.segments.len().saturating_sub(1);
@@ -1,4 +1,5 @@
 use super::options::PropertySyntax;
+use super::ros_substitutions::{RosSubstitutions, SubstitutionError, find_substitution_close};
 use std::borrow::Cow;
 use std::collections::HashMap;
 
@@ -15,6 +16,22 @@ pub(crate) enum PropertyError {
     /// `${NAME:?text}` referenced a variable that was present but empty.
     /// `message` may be empty.
     RequiredButEmpty { name: String, message: String },
+    /// A `$(...)` substitution had no value.
+    /// The string is the substitution as written, before nested substitutions were expanded.
+    UnresolvedSubstitution(String),
+    /// A `$(...)` substitution was unknown, had the wrong arguments, or did not evaluate.
+    /// The string is the substitution as written.
+    InvalidSubstitution(String),
+}
+
+/// What [`interpolate`] expands in one plain scalar.
+#[derive(Clone, Copy)]
+pub(crate) struct Interpolation<'a> {
+    /// Values for `${NAME}` references; `None` leaves them literal.
+    pub(crate) vars: Option<&'a HashMap<String, String>>,
+    pub(crate) syntax: PropertySyntax,
+    /// Resolvers for `$(...)` substitutions; `None` leaves them literal.
+    pub(crate) ros: Option<&'a RosSubstitutions>,
 }
 
 /// Checks whether a character is valid as the first character of a variable name.
@@ -141,10 +158,14 @@ fn find_braced_reference_close(input: &str, body_start: usize) -> Option<usize>
 
 fn resolve_operator_text<'a>(
     text: &'a str,
-    vars: &'a HashMap<String, String>,
+    ctx: Interpolation<'a>,
 ) -> Result<Cow<'a, str>, PropertyError> {
-    if text.contains("${") {
-        interpolate_compose_style(Cow::Borrowed(text), vars, PropertySyntax::Braced)
+    if text.contains("${") || (ctx.ros.is_some() && text.contains("$(")) {
+        let ctx = Interpolation {
+            syntax: PropertySyntax::Braced,
+            ..ctx
+        };
+        interpolate(Cow::Borrowed(text), ctx)
     } else {
         Ok(Cow::Borrowed(text))
     }
@@ -153,25 +174,24 @@ fn resolve_operator_text<'a>(
 fn resolve_brace<'a>(
     brace: &'a BraceRef<'a>,
     vars: &'a HashMap<String, String>,
+    ctx: Interpolation<'a>,
 ) -> Result<Cow<'a, str>, PropertyError> {
     let name = brace.name;
     let value = vars.get(name).map(String::as_str);
     match (&brace.op, value) {
         (BraceOp::Required, Some(v)) => Ok(Cow::Borrowed(v)),
         (BraceOp::Required, None) => Err(PropertyError::Unresolved(name.to_owned())),
-        (BraceOp::DefaultIfUnset(text), None) => resolve_operator_text(text, vars),
+        (BraceOp::DefaultIfUnset(text), None) => resolve_operator_text(text, ctx),
         (BraceOp::DefaultIfUnset(_), Some(v)) => Ok(Cow::Borrowed(v)),
-        (BraceOp::DefaultIfUnsetOrEmpty(text), None | Some("")) => {
-            resolve_operator_text(text, vars)
-        }
+        (BraceOp::DefaultIfUnsetOrEmpty(text), None | Some("")) => resolve_operator_text(text, ctx),
         (BraceOp::DefaultIfUnsetOrEmpty(_), Some(v)) => Ok(Cow::Borrowed(v)),
-        (BraceOp::AlternateIfSet(text), Some(_)) => resolve_operator_text(text, vars),
+        (BraceOp::AlternateIfSet(text), Some(_)) => resolve_operator_text(text, ctx),
         (BraceOp::AlternateIfSet(_), None) => Ok(Cow::Borrowed("")),
         (BraceOp::AlternateIfSetAndNonEmpty(_), None | Some("")) => Ok(Cow::Borrowed("")),
-        (BraceOp::AlternateIfSetAndNonEmpty(text), Some(_)) => resolve_operator_text(text, vars),
+        (BraceOp::AlternateIfSetAndNonEmpty(text), Some(_)) => resolve_operator_text(text, ctx),
         (BraceOp::ErrorIfUnset(_), Some(v)) => Ok(Cow::Borrowed(v)),
         (BraceOp::ErrorIfUnset(msg), None) => {
-            let message = resolve_operator_text(msg, vars)?.into_owned();
+            let message = resolve_operator_text(msg, ctx)?.into_owned();
             Err(PropertyError::RequiredButUnset {
                 name: name.to_owned(),
                 message,
@@ -179,14 +199,14 @@ fn resolve_brace<'a>(
         }
         (BraceOp::ErrorIfUnsetOrEmpty(_), Some(v)) if !v.is_empty() => Ok(Cow::Borrowed(v)),
         (BraceOp::ErrorIfUnsetOrEmpty(msg), Some(_)) => {
-            let message = resolve_operator_text(msg, vars)?.into_owned();
+            let message = resolve_operator_text(msg, ctx)?.into_owned();
             Err(PropertyError::RequiredButEmpty {
                 name: name.to_owned(),
                 message,
             })
         }
         (BraceOp::ErrorIfUnsetOrEmpty(msg), None) => {
-            let message = resolve_operator_text(msg, vars)?.into_owned();
+            let message = resolve_operator_text(msg, ctx)?.into_owned();
             Err(PropertyError::RequiredButUnset {
                 name: name.to_owned(),
                 message,
@@ -199,15 +219,31 @@ fn resolve_brace<'a>(
 /// See [`BraceOp`] for the supported forms.
 /// Pass [`PropertySyntax::BracedOrBare`] to also recognize the bare `$NAME` form
 /// (which uses Required semantics).
-///
-/// Values in `vars` are taken as final.
-/// Placeholders inside map entries are not re-expanded. Braced placeholders inside
-/// default, alternate, and error text from the input are expanded recursively.
-/// Returns `Cow::Borrowed` when nothing changed so the common no-`$` path stays allocation-free.
+#[cfg(test)]
 pub(crate) fn interpolate_compose_style<'s>(
     input: Cow<'s, str>,
     vars: &HashMap<String, String>,
     syntax: PropertySyntax,
+) -> Result<Cow<'s, str>, PropertyError> {
+    let ctx = Interpolation {
+        vars: Some(vars),
+        syntax,
+        ros: None,
+    };
+    interpolate(input, ctx)
+}
+
+/// Expands `${...}` property references and `$(...)` ROS substitutions in `input`, in one pass.
+/// Either kind is left literal when `ctx` has nothing to resolve it with.
+///
+/// Values in `vars` and values returned by the substitution resolvers are taken as final.
+/// Placeholders inside them are not re-expanded. Braced placeholders inside default,
+/// alternate, and error text from the input, and substitutions nested inside `$(...)`, are
+/// expanded recursively.
+/// Returns `Cow::Borrowed` when nothing changed so the common no-`$` path stays allocation-free.
+pub(crate) fn interpolate<'s>(
+    input: Cow<'s, str>,
+    ctx: Interpolation<'_>,
 ) -> Result<Cow<'s, str>, PropertyError> {
     if !input.contains('$') {
         return Ok(input);
@@ -246,6 +282,44 @@ pub(crate) fn interpolate_compose_style<'s>(
             continue;
         }
 
+        if bytes[next] == b'(' {
+            // a $(.. substitution, so resolve it if ROS substitutions are enabled
+            let Some(ros) = ctx.ros else {
+                i += 1;
+                continue;
+            };
+            let Some(close) = find_substitution_close(input_str, next + 1) else {
+                i += 1;
+                continue;
+            };
+
+            let raw = &input_str[i..=close];
+            let body = interpolate(Cow::Borrowed(&input_str[next + 1..close]), ctx)?;
+            let value = ros.resolve(&body).map_err(|err| match err {
+                SubstitutionError::Unresolved => {
+                    PropertyError::UnresolvedSubstitution(raw.to_owned())
+                }
+                SubstitutionError::Invalid => PropertyError::InvalidSubstitution(raw.to_owned()),
+            })?;
+
+            if changed {
+                out.push_str(&input_str[last..i]);
+            } else {
+                out.push_str(&input_str[..i]);
+                changed = true;
+            }
+            out.push_str(&value);
+
+            i = close + 1;
+            last = i;
+            continue;
+        }
+
+        let Some(vars) = ctx.vars else {
+            i += 1; // properties are not enabled, so treat as literal
+            continue;
+        };
+
         if bytes[next] == b'{' {
             // a ${.. reference, so parse as braced
             let Some((brace, end)) =
@@ -255,7 +329,7 @@ pub(crate) fn interpolate_compose_style<'s>(
                 continue;
             };
 
-            let value = resolve_brace(&brace, vars)?;
+            let value = resolve_brace(&brace, vars, ctx)?;
 
             if changed {
                 out.push_str(&input_str[last..i]);
@@ -267,7 +341,7 @@ pub(crate) fn interpolate_compose_style<'s>(
 
             i = end;
             last = i;
-        } else if syntax == PropertySyntax::Braced {
+        } else if ctx.syntax == PropertySyntax::Braced {
             i += 1; // not a ${.. reference, so skip and treat as literal
             continue;
         } else {
@@ -306,7 +380,10 @@ pub(crate) fn interpolate_compose_style<'s>(
 
 #[cfg(test)]
 mod tests {
-    use super::{PropertyError, PropertySyntax, interpolate_compose_style};
+    use super::{
+        Interpolation, PropertyError, PropertySyntax, interpolate, interpolate_compose_style,
+    };
+    use crate::ros_substitutions::RosSubstitutions;
     use rstest::rstest;
     use std::borrow::Cow;
     use std::collections::HashMap;
@@ -569,4 +646,69 @@ mod tests {
         .unwrap();
         assert_eq!(output.as_ref(), "$SET");
     }
+
+    fn interpolate_ros(
+        input: &str,
+        vars: Option<&HashMap<String, String>>,
+    ) -> Result<String, PropertyError> {
+        let ros = RosSubstitutions::new()
+            .with_args(HashMap::from([
+                (String::from("pkg"), String::from("nav2")),
+                (String::from("echo"), String::from("${SET} $(arg pkg)")),
+            ]))
+            .with_package_share_resolver(|package| Some(format!("/share/{package}")));
+        let ctx = Interpolation {
+            vars,
+            syntax: PropertySyntax::Braced,
+            ros: Some(&ros),
+        };
+        interpolate(Cow::Borrowed(input), ctx).map(Cow::into_owned)
+    }
+
+    #[rstest]
+    #[case::arg("$(arg pkg)", "nav2")]
+    #[case::embedded("share: $(find-pkg-share nav2)/params", "share: /share/nav2/params")]
+    #[case::nested("$(find-pkg-share $(arg pkg))", "/share/nav2")]
+    #[case::mixed_with_property("${SET}:$(arg pkg)", "value:nav2")]
+    #[case::property_inside_substitution("$(find-pkg-share ${SET})", "/share/value")]
+    #[case::substitution_in_default("${MISSING:-$(arg pkg)}", "nav2")]
+    #[case::resolved_value_is_final("$(arg echo)", "${SET} $(arg pkg)")]
+    #[case::escaped("$$(arg pkg)", "$(arg pkg)")]
+    #[case::unclosed("$(arg pkg", "$(arg pkg")]
+    fn ros_substitution_resolves(#[case] input: &str, #[case] expected: &str) {
+        assert_eq!(interpolate_ros(input, Some(&vars())).unwrap(), expected);
+    }
+
+    #[test]
+    fn ros_substitution_without_properties_leaves_braces_literal() {
+        assert_eq!(
+            interpolate_ros("${SET}/$(arg pkg)", None).unwrap(),
+            "${SET}/nav2"
+        );
+    }
+
+    #[test]
+    fn properties_without_ros_substitutions_leave_parentheses_literal() {
+        let output =
+            interpolate_compose_style(Cow::Borrowed("$(arg pkg)"), &vars(), PropertySyntax::Braced)
+                .unwrap();
+        assert_eq!(output.as_ref(), "$(arg pkg)");
+    }
+
+    #[rstest]
+    #[case::unresolved(
+        "$(arg missing)",
+        PropertyError::UnresolvedSubstitution("$(arg missing)".into())
+    )]
+    #[case::nested_reports_innermost(
+        "$(find-pkg-share $(arg missing))",
+        PropertyError::UnresolvedSubstitution("$(arg missing)".into())
+    )]
+    #[case::outer_reports_source_text(
+        "$(find $(arg pkg))",
+        PropertyError::InvalidSubstitution("$(find $(arg pkg))".into())
+    )]
+    fn ros_substitution_errors(#[case] input: &str, #[case] expected: PropertyError) {
+        assert_eq!(interpolate_ros(input, Some(&vars())).unwrap_err(), expected);
+    }
 }
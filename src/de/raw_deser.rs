@@ -0,0 +1,258 @@
+//! Internal support for `RawYaml`: capturing the source text of a node.
+//!
+//! The node is skipped like an ignored value while the byte extent of its events is
+//! tracked. The matching slice of the input is then handed to the public wrapper
+//! together with the node location.
+
+use granit_parser::ScalarStyle;
+use serde_core::de::value::BorrowedStrDeserializer;
+use serde_core::de::{self, IntoDeserializer, Visitor};
+
+use super::events::{Ev, Events, eof_with_loc};
+use super::spanned_deser::LocationDeser;
+use super::{Error, Location};
+use crate::location::ROOT_SOURCE_ID;
+use crate::raw_yaml::INTERNAL_RAW_MARKER;
+use crate::{Deserializer, Span};
+
+/// Dispatch for the internal `__yaml_raw` newtype.
+///
+/// Synthesizes `{ __serde_saphyr_private_raw: true, text: &str, location: Location }`.
+pub(super) fn deserialize_yaml_raw<'de, V>(
+    de: Deserializer<'de, '_>,
+    visitor: V,
+) -> Result<V::Value, Error>
+where
+    V: Visitor<'de>,
+{
+    let location = match de.ev.peek()? {
+        Some(ev) => ev.location(),
+        None => return Err(eof_with_loc(de.ev)),
+    };
+    let unavailable = Error::RawYamlUnavailable { location };
+    let Some(input) = de.ev.input_for_borrowing() else {
+        return Err(unavailable);
+    };
+    let Some(start) = location
+        .span()
+        .byte_offset()
+        .filter(|_| location.source_id() == ROOT_SOURCE_ID)
+        .and_then(|offset| usize::try_from(offset).ok())
+    else {
+        return Err(unavailable);
+    };
+    let Some(extent) = skip_node_tracking_extent(de.ev, start)? else {
+        return Err(unavailable);
+    };
+
+    let node_start = if extent.block_scalar {
+        block_scalar_header(input, start)
+    } else {
+        Some(block_sequence_indicators(input, start, extent.leading_seqs))
+    };
+    let Some((node_start, text)) =
+        node_start.and_then(|node_start| Some((node_start, input.get(node_start..extent.end)?)))
+    else {
+        return Err(unavailable);
+    };
+
+    visitor.visit_newtype_struct(RawDeser {
+        text,
+        location: text_location(input, location, node_start, text),
+    })
+}
+
+/// Byte range information collected while skipping the captured node.
+struct NodeExtent {
+    /// End byte offset of the last token of the node.
+    end: usize,
+    /// Number of sequences opening at the node start. Block sequences report the
+    /// position of their first item, after the `- ` indicator.
+    leading_seqs: usize,
+    /// The node is a literal or folded scalar, which the parser reports from its
+    /// first content line, after the `|` / `>` header.
+    block_scalar: bool,
+}
+
+/// Consume one node and collect the byte extent of its source text.
+///
+/// Returns `None` when an event of the node comes from another source (for example
+/// an included file), because the node then has no contiguous text in the input.
+fn skip_node_tracking_extent(
+    ev: &mut dyn Events<'_>,
+    start: usize,
+) -> Result<Option<NodeExtent>, Error> {
+    let mut extent = NodeExtent {
+        end: start,
+        leading_seqs: 0,
+        block_scalar: false,
+    };
+    let mut depth = 0usize;
+    let mut leading = true;
+    let mut foreign = false;
+    loop {
+        let Some(event) = ev.next()? else {
+            return Err(eof_with_loc(ev));
+        };
+        let location = event.location();
+        let span = location.span();
+        let offset = span
+            .byte_offset()
+            .and_then(|offset| usize::try_from(offset).ok());
+        let len = span
+            .byte_len()
+            .and_then(|len| usize::try_from(len).ok())
+            .unwrap_or(0);
+        leading &= offset == Some(start);
+        match event {
+            Ev::SeqStart { .. } => {
+                depth += 1;
+                // Flow sequences start at their `[`, which has a length.
+                extent.leading_seqs += usize::from(leading && len == 0);
+            }
+            Ev::MapStart { .. } => depth += 1,
+            Ev::SeqEnd { .. } | Ev::MapEnd { .. } => depth = depth.saturating_sub(1),
+            Ev::Scalar { style, .. } => {
+                extent.block_scalar =
+                    depth == 0 && matches!(style, ScalarStyle::Literal | ScalarStyle::Folded);
+            }
+            Ev::Taken { location } => {
+                return Err(Error::unexpected("consumed event").with_location(location));
+            }
+        }
+        foreign |= location.source_id() != ROOT_SOURCE_ID;
+        // Block collection ends are zero-length and sit at the next token, so only
+        // events that cover text extend the captured range.
+        if let Some(offset) = offset
+            && len > 0
+        {
+            extent.end = extent.end.max(offset + len);
+        }
+        if depth == 0 {
+            return Ok((!foreign).then_some(extent));
+        }
+    }
+}
+
+/// Step back over up to `count` block sequence indicators (`- `) before `start`.
+fn block_sequence_indicators(input: &str, mut start: usize, count: usize) -> usize {
+    for _ in 0..count {
+        let before = input[..start].trim_end_matches([' ', '\t']);
+        if before.len() == start || !before.ends_with('-') {
+            break;
+        }
+        start = before.len() - 1;
+    }
+    start
+}
+
+/// Find the `|` / `>` header of a block scalar whose content starts at `start`.
+///
+/// The header is the last token of the nearest non-blank line before the content,
+/// ignoring a trailing comment.
+fn block_scalar_header(input: &str, start: usize) -> Option<usize> {
+    let head = input[..start].trim_end_matches([' ', '\t', '\r', '\n']);
+    let line_start = head.rfind('\n').map_or(0, |i| i + 1);
+    let mut header = None;
+    let mut pos = line_start;
+    for token in head[line_start..].split([' ', '\t']) {
+        if token.starts_with('#') {
+            break;
+        }
+        if let Some(rest) = token.strip_prefix(['|', '>'])
+            && rest.chars().all(|c| matches!(c, '0'..='9' | '+' | '-'))
+        {
+            header = Some(pos);
+        }
+        pos += token.len() + 1;
+    }
+    header
+}
+
+/// Location of the captured text: the node location moved back to `node_start`,
+/// with a span covering the whole text.
+fn text_location(input: &str, location: Location, node_start: usize, text: &str) -> Location {
+    let start = location.span().byte_offset().unwrap_or_default() as usize;
+    let skipped = &input[node_start..start.max(node_start)];
+    let skipped_chars = skipped.chars().count();
+    let lines_back = skipped.matches('\n').count();
+    let line = location.line() as usize - lines_back;
+    let column = if lines_back == 0 {
+        location.column() as usize - skipped_chars
+    } else {
+        let line_start = input[..node_start].rfind('\n').map_or(0, |i| i + 1);
+        input[line_start..node_start].chars().count() + 1
+    };
+    let span = Span::new(
+        location.span().offset() - skipped_chars as u64,
+        text.chars().count() as u64,
+    )
+    .with_byte_info(node_start as u64, text.len() as u64);
+    Location::new(line, column)
+        .with_span(span)
+        .with_source_id(location.source_id())
+}
+
+/// Internal deserializer yielding the struct-like view of a captured node.
+struct RawDeser<'de> {
+    text: &'de str,
+    location: Location,
+}
+
+impl<'de> de::Deserializer<'de> for RawDeser<'de> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_map(RawMapAccess {
+            text: self.text,
+            location: self.location,
+            state: 0,
+        })
+    }
+
+    serde_core::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct enum identifier ignored_any
+    }
+}
+
+/// Map access yielding the marker, `text` and `location` fields in order.
+struct RawMapAccess<'de> {
+    text: &'de str,
+    location: Location,
+    /// 0..=3 field state cursor.
+    state: u8,
+}
+
+impl<'de> de::MapAccess<'de> for RawMapAccess<'de> {
+    type Error = Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Error>
+    where
+        K: de::DeserializeSeed<'de>,
+    {
+        let key = match self.state {
+            0 => INTERNAL_RAW_MARKER,
+            1 => "text",
+            2 => "location",
+            _ => return Ok(None),
+        };
+        self.state += 1;
+        seed.deserialize(key.into_deserializer()).map(Some)
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Error>
+    where
+        V: de::DeserializeSeed<'de>,
+    {
+        match self.state {
+            1 => seed.deserialize(true.into_deserializer()),
+            2 => seed.deserialize(BorrowedStrDeserializer::new(self.text)),
+            3 => seed.deserialize(LocationDeser {
+                location: self.location,
+            }),
+            _ => Err(Error::msg("invalid RawYaml internal state")),
+        }
+    }
+}
@@ -15,6 +15,20 @@
 ///     - `deg(<expr>)` — interpret as degrees, convert to radians
 ///     - `rad(<expr>)` — interpret as radians (no conversion)
 /// - Sexagesimal degrees: `hh:mm[:ss[.frac]]` (e.g., `12:30`, `-0:30:30.5`), converted to radians
+/// - With `unit_conversions`, physical units converted to SI base units, written as functions
+///   (`mm(12)`, `ms(250)`, `Hz(50)`) or number suffixes (`12mm`, `250ms`, `50Hz`). Names
+///   are matched ignoring case (`hz(50)`, `khz`), except that a leading `m` (milli) or `M`
+///   (mega) must be written as below, so `5mhz` and `12MM` are errors:
+///     - length: `m`, `km`, `cm`, `mm`, `um`, `in`, `ft` → metres
+///     - time: `s`, `min`, `h`, `ms`, `us`, `ns` → seconds
+///     - frequency: `Hz`, `kHz`, `MHz` → hertz
+///
+/// # Unit kinds
+/// With `unit_conversions`, each term has a kind: plain number, angle, length, time or
+/// frequency. Only terms of the same kind can be added (`mm(12) + cm(1)`, not
+/// `mm(12) + ms(1)` or `mm(12) + 1`); plain numbers scale any kind, `1/s(2)` is a frequency,
+/// and a time multiplied by a frequency is a plain number. Angles and plain numbers mix as
+/// without units. Sexagesimal time (`01:30`) is a time.
 ///
 /// # Tag interaction
 /// - If no `deg`/`rad` is used, `SfTag::Degrees` converts to radians.
@@ -58,8 +72,84 @@ impl FromF64 for f32 {
     }
 }
 
-// Evaluator result: (value, used_unitized, saw_plain_outside)
-type Eval = (f64, bool, bool);
+// Evaluator result: (value, used_unitized, saw_plain_outside, kind)
+type Eval = (f64, bool, bool, Quantity);
+
+/// Kind of an evaluated term, checked when `unit_conversions` is enabled.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Quantity {
+    /// Dimensionless number.
+    Number,
+    /// Value of `deg(...)` / `rad(...)`, in radians.
+    Angle,
+    /// Metres.
+    Length,
+    /// Seconds.
+    Time,
+    /// Hertz.
+    Frequency,
+}
+
+impl Quantity {
+    fn name(self) -> &'static str {
+        match self {
+            Quantity::Number => "a plain number",
+            Quantity::Angle => "an angle",
+            Quantity::Length => "a length",
+            Quantity::Time => "a time",
+            Quantity::Frequency => "a frequency",
+        }
+    }
+
+    fn is_physical(self) -> bool {
+        matches!(
+            self,
+            Quantity::Length | Quantity::Time | Quantity::Frequency
+        )
+    }
+}
+
+/// Unit functions and suffixes: (name, factor to the SI base unit, kind).
+const UNITS: &[(&str, f64, Quantity)] = &[
+    ("m", 1.0, Quantity::Length),
+    ("km", 1e3, Quantity::Length),
+    ("cm", 1e-2, Quantity::Length),
+    ("mm", 1e-3, Quantity::Length),
+    ("um", 1e-6, Quantity::Length),
+    ("in", 0.0254, Quantity::Length),
+    ("ft", 0.3048, Quantity::Length),
+    ("s", 1.0, Quantity::Time),
+    ("min", 60.0, Quantity::Time),
+    ("h", 3600.0, Quantity::Time),
+    ("ms", 1e-3, Quantity::Time),
+    ("us", 1e-6, Quantity::Time),
+    ("ns", 1e-9, Quantity::Time),
+    ("Hz", 1.0, Quantity::Frequency),
+    ("kHz", 1e3, Quantity::Frequency),
+    ("MHz", 1e6, Quantity::Frequency),
+];
+
+/// Look up a unit ignoring case (`hz`, `khz`, `IN`). `Err` holds the unit when an `m` or `M`
+/// prefix of an SI unit is written in the other case (`mhz`, `12MM`): `m` is milli and `M`
+/// is mega, so a guess could be off by a factor of 10⁹.
+fn unit(ident: &str) -> Result<Option<(f64, Quantity)>, &'static str> {
+    let Some(&(name, factor, kind)) = UNITS
+        .iter()
+        .find(|(name, _, _)| ident.eq_ignore_ascii_case(name))
+    else {
+        return Ok(None);
+    };
+    let (prefix, rest) = name.split_at(1);
+    if ident[..1] != *prefix
+        && prefix.eq_ignore_ascii_case("m")
+        && ["m", "s", "Hz"]
+            .iter()
+            .any(|si| rest.eq_ignore_ascii_case(si))
+    {
+        return Err(name);
+    }
+    Ok(Some((factor, kind)))
+}
 
 /// Parse/evaluate expression with angle conversion semantics.
 pub(crate) fn parse_yaml12_float_angle_converting<T>(
@@ -67,16 +157,34 @@ pub(crate) fn parse_yaml12_float_angle_converting<T>(
     location: Location,
     tag: SfTag,
 ) -> Result<T, Error>
+where
+    T: FromF64,
+{
+    parse_yaml12_float_unit_converting(s, location, tag, false)
+}
+
+/// Parse/evaluate expression with angle conversion semantics, and with physical units
+/// converted to SI base units if `units` is set.
+pub(crate) fn parse_yaml12_float_unit_converting<T>(
+    s: &str,
+    location: Location,
+    tag: SfTag,
+    units: bool,
+) -> Result<T, Error>
 where
     T: FromF64,
 {
     let mut p = Parser::new(s, location, tag);
+    p.units = units;
     p.skip_ws();
-    let (mut value, used_unit, saw_plain) = p.expr()?; // parse whole expression
+    let (mut value, used_unit, saw_plain, kind) = p.expr()?; // parse whole expression
     p.skip_ws();
     if !p.eof() {
         return Err(p.err("unexpected trailing characters in scalar"));
     }
+    if kind.is_physical() && matches!(tag, SfTag::Degrees | SfTag::Radians) {
+        return Err(p.err(&format!("expected an angle, found {}", kind.name())));
+    }
 
     // Tag-based conversion only if no unitized constructs were used.
     if used_unit {
@@ -104,6 +212,8 @@ struct Parser<'a> {
     depth: u32,
     tag: SfTag,
     sexagesimal_is_time: bool,
+    /// Whether unit functions and suffixes are recognized (`unit_conversions`).
+    units: bool,
 }
 
 impl<'a> Parser<'a> {
@@ -117,6 +227,7 @@ impl<'a> Parser<'a> {
             depth: 0,
             tag,
             sexagesimal_is_time: true,
+            units: false,
         }
     }
     #[inline]
@@ -167,54 +278,97 @@ impl<'a> Parser<'a> {
 
     /// expr := term (('+'|'-') term)*
     fn expr(&mut self) -> Result<Eval, Error> {
-        let (mut v, mut used_unit, mut saw_plain) = self.term()?;
+        let (mut v, mut used_unit, mut saw_plain, mut kind) = self.term()?;
         loop {
             self.skip_ws();
             match self.peek() {
                 Some(b'+') => {
                     self.bump();
-                    let (rhs, uu, sp) = self.term()?;
+                    let (rhs, uu, sp, k) = self.term()?;
                     v += rhs;
                     used_unit |= uu;
                     saw_plain |= sp;
+                    kind = self.sum_kind(kind, k)?;
                 }
                 Some(b'-') => {
                     self.bump();
-                    let (rhs, uu, sp) = self.term()?;
+                    let (rhs, uu, sp, k) = self.term()?;
                     v -= rhs;
                     used_unit |= uu;
                     saw_plain |= sp;
+                    kind = self.sum_kind(kind, k)?;
                 }
                 _ => break,
             }
         }
-        Ok((v, used_unit, saw_plain))
+        Ok((v, used_unit, saw_plain, kind))
     }
 
     /// term := unary (('*'|'/') unary)*
     fn term(&mut self) -> Result<Eval, Error> {
-        let (mut v, mut used_unit, mut saw_plain) = self.unary()?;
+        let (mut v, mut used_unit, mut saw_plain, mut kind) = self.unary()?;
         loop {
             self.skip_ws();
             match self.peek() {
                 Some(b'*') => {
                     self.bump();
-                    let (rhs, uu, sp) = self.unary()?;
+                    let (rhs, uu, sp, k) = self.unary()?;
                     v *= rhs;
                     used_unit |= uu;
                     saw_plain |= sp;
+                    kind = self.product_kind(kind, k)?;
                 }
                 Some(b'/') => {
                     self.bump();
-                    let (rhs, uu, sp) = self.unary()?;
+                    let (rhs, uu, sp, k) = self.unary()?;
                     v /= rhs;
                     used_unit |= uu;
                     saw_plain |= sp;
+                    kind = self.quotient_kind(kind, k)?;
                 }
                 _ => break,
             }
         }
-        Ok((v, used_unit, saw_plain))
+        Ok((v, used_unit, saw_plain, kind))
+    }
+
+    /// Kind of `lhs ± rhs`: only terms of the same kind add up.
+    fn sum_kind(&self, lhs: Quantity, rhs: Quantity) -> Result<Quantity, Error> {
+        match (lhs, rhs) {
+            _ if lhs == rhs => Ok(lhs),
+            (Quantity::Number | Quantity::Angle, Quantity::Number | Quantity::Angle) => {
+                Ok(Quantity::Angle)
+            }
+            _ => Err(self.err(&format!(
+                "cannot add {} and {}: use the same kind of unit on both sides",
+                lhs.name(),
+                rhs.name()
+            ))),
+        }
+    }
+
+    /// Kind of `lhs * rhs`.
+    fn product_kind(&self, lhs: Quantity, rhs: Quantity) -> Result<Quantity, Error> {
+        match (lhs, rhs) {
+            (Quantity::Number, kind) | (kind, Quantity::Number) => Ok(kind),
+            (Quantity::Angle, Quantity::Angle) => Ok(Quantity::Angle),
+            (Quantity::Time, Quantity::Frequency) | (Quantity::Frequency, Quantity::Time) => {
+                Ok(Quantity::Number)
+            }
+            _ => Err(self.err(&format!("cannot multiply {} by {}", lhs.name(), rhs.name()))),
+        }
+    }
+
+    /// Kind of `lhs / rhs`.
+    fn quotient_kind(&self, lhs: Quantity, rhs: Quantity) -> Result<Quantity, Error> {
+        match (lhs, rhs) {
+            (kind, Quantity::Number) => Ok(kind),
+            _ if lhs == rhs && lhs.is_physical() => Ok(Quantity::Number),
+            (Quantity::Number | Quantity::Angle, Quantity::Angle) => Ok(Quantity::Angle),
+            (Quantity::Number, Quantity::Time) => Ok(Quantity::Frequency),
+            (Quantity::Number, Quantity::Frequency) => Ok(Quantity::Time),
+            _ => Err(self.err(&format!("cannot divide {} by {}", lhs.name(), rhs.name()))),
+        }
     }
 
     /// unary := ('+'|'-')* primary
@@ -233,8 +387,8 @@ impl<'a> Parser<'a> {
                 _ => break,
             }
         }
-        let (v, used_unit, saw_plain) = self.primary()?;
-        Ok((sign * v, used_unit, saw_plain))
+        let (v, used_unit, saw_plain, kind) = self.primary()?;
+        Ok((sign * v, used_unit, saw_plain, kind))
     }
 
     /// primary :=
@@ -252,10 +406,10 @@ impl<'a> Parser<'a> {
                     self.enter()?;
                     let r = self.expr();
                     self.exit();
-                    let (v, used, plain) = r?;
+                    let (v, used, plain, kind) = r?;
                     self.skip_ws();
                     match self.bump() {
-                        Some(b')') => Ok((v, used, plain)),
+                        Some(b')') => Ok((v, used, plain, kind)),
                         _ => Err(self.err("expected ')'")),
                     }
                 }
@@ -273,11 +427,11 @@ impl<'a> Parser<'a> {
         // `.inf` / `.nan` (case-insensitive)
         if self.starts_ci(".inf") {
             self.i += 4;
-            return Ok((f64::INFINITY, false, true));
+            return Ok((f64::INFINITY, false, true, Quantity::Number));
         }
         if self.starts_ci(".nan") {
             self.i += 4;
-            return Ok((f64::NAN, false, true));
+            return Ok((f64::NAN, false, true, Quantity::Number));
         }
 
         // Sexagesimal look-ahead (starts with digits or '.'? only digits make sense here):
@@ -391,11 +545,40 @@ impl<'a> Parser<'a> {
             .map_err(|_| self.err("invalid utf-8 in numeric literal"))
             .and_then(|s| f64::from_str(s).map_err(|_| self.err("invalid float literal")))
         {
-            Ok(v) => Ok((v, false, true)),
+            Ok(v) => self.unit_suffix(v),
             Err(_) => Err(self.err("invalid float literal")),
         }
     }
 
+    /// Apply a unit suffix written right after a number (`12mm`), if units are enabled.
+    fn unit_suffix(&mut self, v: f64) -> Result<Eval, Error> {
+        if !self.units || !self.peek().is_some_and(is_ident_start) {
+            return Ok((v, false, true, Quantity::Number));
+        }
+        let start = self.i;
+        let mut end = start;
+        while self.b.get(end).copied().is_some_and(is_ident_cont) {
+            end += 1;
+        }
+        match self.unit(&self.s[start..end])? {
+            Some((factor, kind)) => {
+                self.i = end;
+                Ok((v * factor, true, false, kind))
+            }
+            // Left for the trailing-characters error.
+            None => Ok((v, false, true, Quantity::Number)),
+        }
+    }
+
+    /// [`unit`], rejecting names where milli and mega cannot be told apart.
+    fn unit(&self, ident: &str) -> Result<Option<(f64, Quantity)>, Error> {
+        unit(ident).map_err(|name| {
+            self.err(&format!(
+                "ambiguous unit `{ident}`: `m` is milli and `M` is mega, did you mean `{name}`?"
+            ))
+        })
+    }
+
     /// Parse identifiers / keywords: pi, tau, inf, nan, deg(...), rad(...)
     fn parse_ident_or_special(&mut self) -> Result<Eval, Error> {
         let start = self.i;
@@ -410,46 +593,70 @@ impl<'a> Parser<'a> {
 
         // Case-insensitive match without allocating a lowercase copy
         if ident.eq_ignore_ascii_case("pi") {
-            return Ok((PI, false, true));
+            return Ok((PI, false, true, Quantity::Number));
         }
         if ident.eq_ignore_ascii_case("tau") {
-            return Ok((2.0 * PI, false, true));
+            return Ok((2.0 * PI, false, true, Quantity::Number));
         }
         if ident.eq_ignore_ascii_case("inf") {
-            return Ok((f64::INFINITY, false, true));
+            return Ok((f64::INFINITY, false, true, Quantity::Number));
         }
         if ident.eq_ignore_ascii_case("nan") {
-            return Ok((f64::NAN, false, true));
+            return Ok((f64::NAN, false, true, Quantity::Number));
         }
-        if ident.eq_ignore_ascii_case("deg") || ident.eq_ignore_ascii_case("rad") {
-            self.skip_ws();
-            if self.bump() != Some(b'(') {
-                return Err(self.err("expected '(' after function name"));
+        if self.units
+            && let Some((factor, kind)) = self.unit(ident)?
+        {
+            let (v, _, _, inner) = self.function_argument()?;
+            if inner != Quantity::Number {
+                return Err(self.err(&format!(
+                    "argument of {ident}(...) must be a plain number, found {}",
+                    inner.name()
+                )));
             }
-            // Inside unit functions, treat sexagesimal as degrees (angle) rather than time.
-            let old_mode = self.sexagesimal_is_time;
-            self.sexagesimal_is_time = false;
-            self.enter()?;
-            let r = self.expr();
-            self.exit();
-            self.sexagesimal_is_time = old_mode;
-            let (v, _used_inner, _plain_inner) = r?;
-            self.skip_ws();
-            if self.bump() != Some(b')') {
-                return Err(self.err("expected ')' after function argument"));
+            return Ok((v * factor, true, false, kind));
+        }
+        if ident.eq_ignore_ascii_case("deg") || ident.eq_ignore_ascii_case("rad") {
+            let (v, _used_inner, _plain_inner, inner) = self.function_argument()?;
+            if inner.is_physical() {
+                return Err(self.err(&format!(
+                    "argument of {ident}(...) must be a plain number, found {}",
+                    inner.name()
+                )));
             }
 
             let used_unit = true;
             if ident.eq_ignore_ascii_case("deg") {
-                Ok((v * DEG2RAD, used_unit, false))
+                Ok((v * DEG2RAD, used_unit, false, Quantity::Angle))
             } else {
-                Ok((v, used_unit, false))
+                Ok((v, used_unit, false, Quantity::Angle))
             }
         } else {
             Err(self.err("unknown identifier"))
         }
     }
 
+    /// Parse `'(' expr ')'` after a function name.
+    fn function_argument(&mut self) -> Result<Eval, Error> {
+        self.skip_ws();
+        if self.bump() != Some(b'(') {
+            return Err(self.err("expected '(' after function name"));
+        }
+        // Inside unit functions, treat sexagesimal as degrees (angle) rather than time.
+        let old_mode = self.sexagesimal_is_time;
+        self.sexagesimal_is_time = false;
+        self.enter()?;
+        let r = self.expr();
+        self.exit();
+        self.sexagesimal_is_time = old_mode;
+        let r = r?;
+        self.skip_ws();
+        if self.bump() != Some(b')') {
+            return Err(self.err("expected ')' after function argument"));
+        }
+        Ok(r)
+    }
+
     #[inline]
     fn starts_ci(&self, kw: &str) -> bool {
         let end = self.i + kw.len();
@@ -534,20 +741,29 @@ impl<'a> Parser<'a> {
         if self.sexagesimal_is_time {
             if matches!(self.tag, SfTag::Degrees | SfTag::Radians) {
                 let degrees = deg_whole + f64::from(mins_u) / 60.0 + secs / 3600.0;
-                Ok(Some((degrees * DEG2RAD, true, false)))
+                Ok(Some((degrees * DEG2RAD, true, false, Quantity::Angle)))
             } else {
                 // Time mode: hh:mm[:ss[.frac]] → total seconds
                 let total_seconds = deg_whole * 3600.0 + f64::from(mins_u) * 60.0 + secs;
-                Ok(Some((total_seconds, true, false)))
+                Ok(Some((total_seconds, true, false, self.time_kind())))
             }
         } else if matches!(self.tag, SfTag::TimeStamp) {
             // Explicit time tag forces time semantics even inside functions (unlikely combo)
             let total_seconds = deg_whole * 3600.0 + f64::from(mins_u) * 60.0 + secs;
-            Ok(Some((total_seconds, true, false)))
+            Ok(Some((total_seconds, true, false, self.time_kind())))
         } else {
             // Angle mode (inside unit functions): interpret as degrees numeric; conversion is handled by the wrapping unit (deg()/rad()).
             let degrees = deg_whole + f64::from(mins_u) / 60.0 + secs / 3600.0;
-            Ok(Some((degrees, true, false)))
+            Ok(Some((degrees, true, false, Quantity::Number)))
+        }
+    }
+
+    /// Sexagesimal time is a time only when units are checked.
+    fn time_kind(&self) -> Quantity {
+        if self.units {
+            Quantity::Time
+        } else {
+            Quantity::Number
         }
     }
 
@@ -641,7 +857,9 @@ fn is_ident_cont(c: u8) -> bool {
 
 #[cfg(test)]
 mod tests {
-    use crate::robotics::{DEG2RAD, parse_yaml12_float_angle_converting};
+    use crate::robotics::{
+        DEG2RAD, parse_yaml12_float_angle_converting, parse_yaml12_float_unit_converting,
+    };
     use crate::tags::SfTag;
     use crate::{Error, Location};
     use core::f64::consts::PI;
@@ -906,4 +1124,68 @@ mod tests {
         assert_err("rad)", SfTag::Radians);
         assert_err("deg()", SfTag::Radians);
     }
+
+    // physical units
+    #[track_caller]
+    fn assert_units(s: &str, expected: f64) {
+        let v: f64 = parse_yaml12_float_unit_converting(s, loc(), SfTag::None, true).unwrap();
+        assert_almost_eq_f64(v, expected, 1e-12);
+    }
+
+    #[track_caller]
+    fn assert_units_err(s: &str, tag: SfTag) {
+        let r: Result<f64, Error> = parse_yaml12_float_unit_converting(s, loc(), tag, true);
+        assert!(r.is_err(), "expected error for `{s}`, got {:?}", r.ok());
+    }
+
+    #[test]
+    fn unit_functions_and_suffixes() {
+        assert_units("mm(12)", 0.012);
+        assert_units("12mm", 0.012);
+        assert_units("cm(1) + 5mm", 0.015);
+        assert_units("in(1)", 0.0254);
+        assert_units("ms(250)", 0.25);
+        assert_units("50Hz", 50.0);
+        assert_units("2h + 30min", 9000.0);
+        assert_units("1/ms(20)", 50.0);
+        assert_units("2 * s(3)", 6.0);
+        assert_units("01:30 + 500ms", 5400.5);
+        assert_units("ms(10) * kHz(1)", 10.0);
+        assert_units("5MHz", 5e6);
+        assert_units("deg(90) + 0.5", PI / 2.0 + 0.5);
+    }
+
+    #[test]
+    fn unit_kinds_are_checked() {
+        assert_units_err("mm(12) + ms(1)", SfTag::None);
+        assert_units_err("mm(12) + 1", SfTag::None);
+        assert_units_err("deg(90) - cm(1)", SfTag::None);
+        assert_units_err("mm(2) * mm(3)", SfTag::None);
+        assert_units_err("mm(cm(1))", SfTag::None);
+        assert_units_err("deg(mm(1))", SfTag::None);
+        assert_units_err("12 mm", SfTag::None);
+        assert_units_err("12parsec", SfTag::None);
+        assert_units_err("mm(3)", SfTag::Degrees);
+    }
+
+    #[test]
+    fn unit_names_ignore_case_unless_milli_and_mega_are_confused() {
+        assert_units("hz(50)", 50.0);
+        assert_units("2khz", 2e3);
+        assert_units("IN(1)", 0.0254);
+        assert_units("MIN(1)", 60.0);
+        for s in ["5mHz", "5mhz", "12MM", "Mm(1)", "1Ms"] {
+            let err =
+                parse_yaml12_float_unit_converting::<f64>(s, loc(), SfTag::None, true).unwrap_err();
+            assert!(err.to_string().contains("ambiguous unit"), "{s}: {err}");
+        }
+    }
+
+    #[test]
+    fn units_require_the_option() {
+        assert_err("mm(12)", SfTag::None);
+        assert_err("12mm", SfTag::None);
+        // Without units, sexagesimal time still mixes with plain numbers.
+        assert_ok64("01:00 + 30", SfTag::None, 3630.0);
+    }
 }
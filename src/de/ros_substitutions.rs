@@ -0,0 +1,268 @@
+//! ROS 2 launch-style `$(...)` substitutions in plain scalars.
+//!
+//! ROS parameter and launch files commonly refer to launch arguments, environment variables
+//! and package share directories with substitutions like `$(arg robot_name)` or
+//! `$(find-pkg-share my_robot)/urdf/robot.urdf`. Install a [`RosSubstitutions`] with
+//! [`crate::Options::with_ros_substitutions`] to expand them while deserializing. Nothing is
+//! looked up on its own: each substitution is answered by a resolver supplied by the caller.
+//!
+//! | Substitution | Resolved by |
+//! |---|---|
+//! | `$(arg NAME)` | [`RosSubstitutions::with_arg_resolver`] |
+//! | `$(env NAME)`, `$(env NAME DEFAULT)` | [`RosSubstitutions::with_env_resolver`] |
+//! | `$(optenv NAME)`, `$(optenv NAME DEFAULT)` | [`RosSubstitutions::with_env_resolver`], empty if unset |
+//! | `$(find-pkg-share PACKAGE)` | [`RosSubstitutions::with_package_share_resolver`] |
+//! | `$(eval EXPRESSION)` | the `robotics` expression evaluator (`robotics` feature) |
+//!
+//! Substitutions are expanded in the same pass as `${NAME}` properties and follow the same
+//! rules: only plain scalars outside of mapping keys are expanded, `$$` is a literal `$`,
+//! substitutions may be nested (`$(find-pkg-share $(arg package))`), and resolved values are
+//! taken as final. Resolved values are redacted from error messages the same way as property
+//! values, and errors about a substitution quote it as written in the source.
+//!
+//! ```rust
+//! use serde::Deserialize;
+//! use serde_saphyr::RosSubstitutions;
+//!
+//! #[derive(Deserialize)]
+//! struct Robot {
+//!     name: String,
+//!     description: String,
+//! }
+//!
+//! let substitutions = RosSubstitutions::new()
+//!     .with_arg_resolver(|name| (name == "robot").then(|| "r2d2".to_string()))
+//!     .with_package_share_resolver(|package| Some(format!("/opt/ros/share/{package}")));
+//! let options = serde_saphyr::options! {}.with_ros_substitutions(substitutions);
+//!
+//! let yaml = "name: $(arg robot)\ndescription: $(find-pkg-share $(arg robot))/urdf/robot.urdf\n";
+//! let robot: Robot = serde_saphyr::from_str_with_options(yaml, options)?;
+//! assert_eq!(robot.name, "r2d2");
+//! assert_eq!(robot.description, "/opt/ros/share/r2d2/urdf/robot.urdf");
+//! # Ok::<(), serde_saphyr::Error>(())
+//! ```
+
+use std::collections::HashMap;
+use std::fmt;
+use std::rc::Rc;
+
+#[cfg(feature = "robotics")]
+use crate::location::Location;
+#[cfg(feature = "robotics")]
+use crate::tags::SfTag;
+
+type Resolver = Rc<dyn Fn(&str) -> Option<String>>;
+
+/// Resolvers for ROS 2 launch-style `$(...)` substitutions.
+///
+/// A substitution whose resolver is not installed, or whose resolver returns `None`, fails
+/// with [`crate::Error::UnresolvedSubstitution`]. Cloning is cheap: resolvers are shared.
+#[derive(Clone, Default)]
+pub struct RosSubstitutions {
+    args: Option<Resolver>,
+    env: Option<Resolver>,
+    package_share: Option<Resolver>,
+}
+
+impl RosSubstitutions {
+    /// Create substitutions with no resolvers installed.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Resolve `$(arg NAME)` with `resolver`.
+    #[must_use]
+    pub fn with_arg_resolver(
+        mut self,
+        resolver: impl Fn(&str) -> Option<String> + 'static,
+    ) -> Self {
+        self.args = Some(Rc::new(resolver));
+        self
+    }
+
+    /// Resolve `$(arg NAME)` from a fixed map of launch arguments.
+    #[must_use]
+    pub fn with_args(self, args: HashMap<String, String>) -> Self {
+        self.with_arg_resolver(move |name| args.get(name).cloned())
+    }
+
+    /// Resolve `$(env NAME)` and `$(optenv NAME)` with `resolver`.
+    ///
+    /// The process environment is not read unless the resolver does so, for example with
+    /// `|name| std::env::var(name).ok()`.
+    #[must_use]
+    pub fn with_env_resolver(
+        mut self,
+        resolver: impl Fn(&str) -> Option<String> + 'static,
+    ) -> Self {
+        self.env = Some(Rc::new(resolver));
+        self
+    }
+
+    /// Resolve `$(find-pkg-share PACKAGE)` to the share directory of `PACKAGE`.
+    #[must_use]
+    pub fn with_package_share_resolver(
+        mut self,
+        resolver: impl Fn(&str) -> Option<String> + 'static,
+    ) -> Self {
+        self.package_share = Some(Rc::new(resolver));
+        self
+    }
+
+    /// Resolve the body of one `$(...)` substitution, with nested substitutions already
+    /// expanded.
+    pub(crate) fn resolve(&self, body: &str) -> Result<String, SubstitutionError> {
+        let body = body.trim();
+        let (command, rest) = body
+            .split_once(char::is_whitespace)
+            .map_or((body, ""), |(command, rest)| (command, rest.trim()));
+        let (name, default) = rest
+            .split_once(char::is_whitespace)
+            .map_or((rest, None), |(name, default)| (name, Some(default.trim())));
+
+        match command {
+            "arg" | "find-pkg-share" if name.is_empty() || default.is_some() => {
+                Err(SubstitutionError::Invalid)
+            }
+            "arg" => lookup(self.args.as_ref(), name).ok_or(SubstitutionError::Unresolved),
+            "find-pkg-share" => {
+                lookup(self.package_share.as_ref(), name).ok_or(SubstitutionError::Unresolved)
+            }
+            "env" | "optenv" if name.is_empty() => Err(SubstitutionError::Invalid),
+            "env" => lookup(self.env.as_ref(), name)
+                .or_else(|| default.map(str::to_owned))
+                .ok_or(SubstitutionError::Unresolved),
+            "optenv" => Ok(lookup(self.env.as_ref(), name)
+                .unwrap_or_else(|| default.unwrap_or_default().to_owned())),
+            "eval" if !rest.is_empty() => eval(rest),
+            _ => Err(SubstitutionError::Invalid),
+        }
+    }
+}
+
+impl fmt::Debug for RosSubstitutions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RosSubstitutions")
+            .field("args", &self.args.is_some())
+            .field("env", &self.env.is_some())
+            .field("package_share", &self.package_share.is_some())
+            .finish()
+    }
+}
+
+/// Why a `$(...)` substitution could not be expanded.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum SubstitutionError {
+    /// No resolver is installed, or it had no value.
+    Unresolved,
+    /// Unknown substitution, wrong number of arguments, or an expression that did not evaluate.
+    Invalid,
+}
+
+fn lookup(resolver: Option<&Resolver>, name: &str) -> Option<String> {
+    resolver.and_then(|resolve| resolve(name))
+}
+
+/// Evaluates with the robotics expression evaluator; `$(eval 2 * pi)` gives `6.283185307179586`.
+#[cfg(feature = "robotics")]
+fn eval(expression: &str) -> Result<String, SubstitutionError> {
+    crate::parse_scalars::parse_yaml12_float::<f64>(
+        expression,
+        Location::UNKNOWN,
+        SfTag::None,
+        true,
+        false,
+    )
+    .ok()
+    .filter(|value| value.is_finite())
+    .map(|value| value.to_string())
+    .ok_or(SubstitutionError::Invalid)
+}
+
+#[cfg(not(feature = "robotics"))]
+fn eval(_: &str) -> Result<String, SubstitutionError> {
+    Err(SubstitutionError::Invalid)
+}
+
+/// Returns the byte index of the `)` closing the substitution whose body starts at
+/// `body_start`, counting nested parentheses.
+pub(crate) fn find_substitution_close(input: &str, body_start: usize) -> Option<usize> {
+    let mut depth = 0usize;
+    for (i, byte) in input.bytes().enumerate().skip(body_start) {
+        match byte {
+            b'(' => depth += 1,
+            b')' if depth == 0 => return Some(i),
+            b')' => depth -= 1,
+            _ => {}
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{RosSubstitutions, SubstitutionError, find_substitution_close};
+    use rstest::rstest;
+    use std::collections::HashMap;
+
+    fn substitutions() -> RosSubstitutions {
+        RosSubstitutions::new()
+            .with_args(HashMap::from([("robot".to_owned(), "r2d2".to_owned())]))
+            .with_env_resolver(|name| (name == "HOME").then(|| "/home/ros".to_owned()))
+            .with_package_share_resolver(|package| Some(format!("/share/{package}")))
+    }
+
+    #[rstest]
+    #[case::arg("arg robot", "r2d2")]
+    #[case::arg_padded("  arg   robot ", "r2d2")]
+    #[case::env("env HOME", "/home/ros")]
+    #[case::env_default_unused("env HOME /tmp", "/home/ros")]
+    #[case::env_default("env UNSET /tmp", "/tmp")]
+    #[case::optenv("optenv HOME", "/home/ros")]
+    #[case::optenv_unset("optenv UNSET", "")]
+    #[case::optenv_default("optenv UNSET a b", "a b")]
+    #[case::find_pkg_share("find-pkg-share nav2", "/share/nav2")]
+    fn resolves(#[case] body: &str, #[case] expected: &str) {
+        assert_eq!(substitutions().resolve(body).as_deref(), Ok(expected));
+    }
+
+    #[rstest]
+    #[case::arg_unset("arg missing", SubstitutionError::Unresolved)]
+    #[case::env_unset("env UNSET", SubstitutionError::Unresolved)]
+    #[case::arg_without_name("arg", SubstitutionError::Invalid)]
+    #[case::arg_extra("arg robot extra", SubstitutionError::Invalid)]
+    #[case::unknown("find robot", SubstitutionError::Invalid)]
+    #[case::empty("", SubstitutionError::Invalid)]
+    #[case::eval_empty("eval", SubstitutionError::Invalid)]
+    fn rejects(#[case] body: &str, #[case] expected: SubstitutionError) {
+        assert_eq!(substitutions().resolve(body), Err(expected));
+    }
+
+    #[test]
+    fn missing_resolver_is_unresolved() {
+        let substitutions = RosSubstitutions::new();
+        for body in ["arg robot", "env HOME", "find-pkg-share nav2"] {
+            assert_eq!(
+                substitutions.resolve(body),
+                Err(SubstitutionError::Unresolved)
+            );
+        }
+        assert_eq!(substitutions.resolve("optenv HOME").as_deref(), Ok(""));
+    }
+
+    #[cfg(feature = "robotics")]
+    #[rstest]
+    #[case::integer("eval 1 + 2", "3")]
+    #[case::function("eval deg(180) / pi", "1")]
+    #[case::fraction("eval 3 / 4", "0.75")]
+    fn eval_uses_robotics_evaluator(#[case] body: &str, #[case] expected: &str) {
+        assert_eq!(substitutions().resolve(body).as_deref(), Ok(expected));
+    }
+
+    #[test]
+    fn close_counts_nested_parentheses() {
+        assert_eq!(find_substitution_close("$(eval (1 + 2) * 3)", 2), Some(18));
+        assert_eq!(find_substitution_close("$(arg a", 2), None);
+    }
+}
@@ -1,10 +1,13 @@
+use std::borrow::Cow;
 use std::fmt;
 
 use annotate_snippets::{
-    AnnotationKind, Level, Renderer, Snippet as AnnotateSnippet, renderer::DecorStyle,
+    AnnotationKind, Level, Renderer, Snippet as AnnotateSnippet,
+    renderer::{AnsiColor, DecorStyle, Effects, Style},
 };
 
 use crate::Location;
+use crate::de_error::RenderTarget;
 use crate::localizer::Localizer;
 
 /// Borrowed YAML source information used for snippet rendering.
@@ -594,6 +597,14 @@ mod tests {
         assert_eq!(next_char_boundary("a", 1), None);
         assert_eq!(next_char_boundary("a", 0), Some(1));
     }
+
+    #[test]
+    fn to_html_drops_only_the_escape_byte_of_a_stray_sequence() {
+        assert_eq!(
+            to_html("a\x1b[ b <i>m c"),
+            "<pre class=\"serde-saphyr-diagnostic\">a[ b &lt;i&gt;m c</pre>"
+        );
+    }
 }
 
 /// Parameters controlling how to render a diagnostic snippet.
@@ -611,6 +622,10 @@ pub(crate) struct Snippet<'a> {
     /// Maximum number of *columns* to keep on each side of the error column when cropping
     /// very long lines. `0` effectively disables snippet rendering at higher layers.
     pub(crate) crop_radius: usize,
+    /// Stable error code shown in the title, as in `error[SY0101]: ...`.
+    pub(crate) code: Option<&'static str>,
+    /// Output format; [`RenderTarget::Html`] emits marker styles that [`to_html`] converts.
+    pub(crate) target: RenderTarget,
 }
 
 impl<'a> Snippet<'a> {
@@ -621,9 +636,23 @@ impl<'a> Snippet<'a> {
             source: SnippetSource { text, path },
             mapping: LineMapping::Identity,
             crop_radius,
+            code: None,
+            target: RenderTarget::Plain,
         }
     }
 
+    #[inline]
+    /// Render for `target` instead of plain text.
+    pub(crate) fn with_target(self, target: RenderTarget) -> Self {
+        Self { target, ..self }
+    }
+
+    #[inline]
+    /// Show `code` in the title of the rendered snippet.
+    pub(crate) fn with_code(self, code: Option<&'static str>) -> Self {
+        Self { code, ..self }
+    }
+
     #[inline]
     /// Convert this snippet configuration into one that renders snippets for a text fragment.
     ///
@@ -657,12 +686,30 @@ impl<'a> Snippet<'a> {
         label: &str,
         location: &Location,
     ) -> fmt::Result {
+        let (msg, label, path) = if self.target == RenderTarget::Html {
+            (
+                neutralize_escapes(msg),
+                neutralize_escapes(label),
+                neutralize_escapes(self.source.path),
+            )
+        } else {
+            (msg.into(), label.into(), self.source.path.into())
+        };
+        let msg = msg.as_ref();
+        let coded;
+        let plain_msg = match self.code {
+            Some(code) => {
+                coded = format!("[{code}] {msg}");
+                coded.as_str()
+            }
+            None => msg,
+        };
         if location == &Location::UNKNOWN {
-            return write!(f, "{msg}");
+            return write!(f, "{plain_msg}");
         }
 
         let Ok(window) = resolve_render_window(self.source.text, location, self.mapping) else {
-            return fmt_with_location(f, l10n, msg, location);
+            return fmt_with_location(f, l10n, plain_msg, location);
         };
 
         // Horizontal cropping (by character columns) for very long lines.
@@ -682,23 +729,145 @@ impl<'a> Snippet<'a> {
 
         let loc_prefix = l10n.snippet_location_prefix(*location);
 
-        let report = &[level.primary_title(format!("{loc_prefix}: {msg}")).element(
+        let mut title = level.primary_title(format!("{loc_prefix}: {msg}"));
+        if let Some(code) = self.code {
+            title = title.id(code);
+        }
+        let report = &[title.element(
             AnnotateSnippet::source(&window_text)
                 .line_start(window.rows.window_start_absolute_row)
-                .path(self.source.path)
+                .path(path.as_ref())
                 .fold(false)
                 .annotation(
                     AnnotationKind::Primary
                         .span(local_start..local_end)
-                        .label(label),
+                        .label(label.as_ref()),
                 ),
         )];
 
-        // Prefer rustc-like caret markers and avoid ANSI colors in `Display` output.
-        // This keeps error strings stable (e.g. for tests) and avoids emitting escape
-        // sequences when the output is not a TTY.
-        let renderer = Renderer::plain().decor_style(DecorStyle::Ascii);
-        write!(f, "{}", renderer.render(report))
+        // Prefer rustc-like caret markers and avoid ANSI colors in `Display` output unless
+        // asked for. This keeps error strings stable (e.g. for tests) and avoids emitting
+        // escape sequences when the output is not a TTY.
+        let renderer = match self.target {
+            RenderTarget::Plain => Renderer::plain(),
+            RenderTarget::Ansi => Renderer::styled(),
+            RenderTarget::Html => html_marker_renderer(),
+        };
+        write!(
+            f,
+            "{}",
+            renderer.decor_style(DecorStyle::Ascii).render(report)
+        )
+    }
+}
+
+/// Replace ESC in text bound for [`RenderTarget::Html`] output with `␛`, as annotate-snippets
+/// does in titles, so user text cannot produce the sequences that [`to_html`] turns into markup.
+pub(crate) fn neutralize_escapes(text: &str) -> Cow<'_, str> {
+    if text.contains('\x1b') {
+        Cow::Owned(text.replace('\x1b', "\u{241b}"))
+    } else {
+        Cow::Borrowed(text)
+    }
+}
+
+/// CSS class of each marker style used by [`html_marker_renderer`].
+///
+/// Every style must be distinct so that [`to_html`] can map its escape sequence back.
+const HTML_CLASSES: [(Style, &str); 8] = [
+    (AnsiColor::Red.on_default(), "error"),
+    (AnsiColor::Yellow.on_default(), "warning"),
+    (AnsiColor::Blue.on_default(), "info"),
+    (AnsiColor::Green.on_default(), "note"),
+    (AnsiColor::Cyan.on_default(), "help"),
+    (AnsiColor::Magenta.on_default(), "line-num"),
+    (Style::new().effects(Effects::BOLD), "emphasis"),
+    (AnsiColor::BrightBlack.on_default(), "context"),
+];
+
+/// Renderer whose styles are placeholders for the [`HTML_CLASSES`], not real colors.
+fn html_marker_renderer() -> Renderer {
+    let [
+        error,
+        warning,
+        info,
+        note,
+        help,
+        line_num,
+        emphasis,
+        context,
+    ] = HTML_CLASSES.map(|(style, _)| style);
+    Renderer::styled()
+        .error(error)
+        .warning(warning)
+        .info(info)
+        .note(note)
+        .help(help)
+        .line_num(line_num)
+        .emphasis(emphasis)
+        .context(context)
+}
+
+/// Convert text rendered with [`RenderTarget::Html`] into an HTML fragment.
+///
+/// All text is escaped; marker styles become `<span class="...">` elements. Any other escape
+/// sequence is dropped.
+pub(crate) fn to_html(rendered: &str) -> String {
+    let mut out = String::with_capacity(rendered.len() + 64);
+    out.push_str("<pre class=\"serde-saphyr-diagnostic\">");
+
+    let mut open = false;
+    let mut rest = rendered;
+    while let Some(esc) = rest.find('\x1b') {
+        escape_html_into(&mut out, &rest[..esc]);
+        let seq = &rest[esc..];
+        let params = seq.get(2..).filter(|_| seq[1..].starts_with('['));
+        let Some(end) = params
+            .and_then(|params| params.find(|c: char| !c.is_ascii_digit() && c != ';'))
+            .filter(|&at| params.is_some_and(|params| params[at..].starts_with('m')))
+            .map(|at| at + 2)
+        else {
+            // Not an SGR sequence: drop the escape byte itself.
+            rest = &seq[1..];
+            continue;
+        };
+        let seq_text = &seq[..=end];
+        rest = &seq[end + 1..];
+
+        if open {
+            out.push_str("</span>");
+            open = false;
+        }
+        let class = HTML_CLASSES
+            .iter()
+            .find(|(style, _)| style.render().to_string() == seq_text)
+            .map(|(_, class)| *class);
+        if let Some(class) = class {
+            out.push_str("<span class=\"");
+            out.push_str(class);
+            out.push_str("\">");
+            open = true;
+        }
+    }
+    escape_html_into(&mut out, rest);
+    if open {
+        out.push_str("</span>");
+    }
+
+    out.push_str("</pre>");
+    out
+}
+
+fn escape_html_into(out: &mut String, text: &str) {
+    for ch in text.chars() {
+        match ch {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(ch),
+        }
     }
 }
 
@@ -0,0 +1,135 @@
+//! Source map: where every deserialized value came from.
+//!
+//! [`from_str_with_source_map`] returns the deserialized value together with a
+//! [`SourceMap`] from key paths to [`Locations`]. Checks that run after deserialization
+//! can use it to point at the exact line without wrapping fields into [`crate::Spanned`].
+//!
+//! ```rust
+//! use serde::Deserialize;
+//!
+//! #[derive(Deserialize)]
+//! struct Server { host: String, port: u16 }
+//!
+//! #[derive(Deserialize)]
+//! struct Config { servers: Vec<Server> }
+//!
+//! let yaml = "servers:\n  - host: a\n    port: 80\n  - host: b\n    port: 0\n";
+//! let (config, source_map) =
+//!     serde_saphyr::from_str_with_source_map::<Config>(yaml)?;
+//!
+//! let bad = config.servers.iter().position(|s| s.port == 0).unwrap();
+//! let locations = source_map
+//!     .get(&serde_saphyr::path_key!["servers", bad, "port"])
+//!     .unwrap();
+//! assert_eq!(locations.reference_location.line(), 5);
+//! # Ok::<(), serde_saphyr::Error>(())
+//! ```
+
+use serde_core::de::DeserializeOwned;
+
+use super::path_map::{PathKey, PathMap, PathRecorder};
+use super::with_deserializer::{
+    enforce_single_document_and_finish, normalize_str_input, run_with_document_scope,
+};
+use crate::de::api::StrSnippetContext;
+use crate::de::{Error, Options};
+use crate::live_events::LiveEvents;
+use crate::location::Locations;
+
+/// Map from the key path of each deserialized value to its source [`Locations`].
+///
+/// Paths use the key spelling found in the YAML document, so a field renamed with
+/// `#[serde(rename = "...")]` is looked up under its YAML name. For a value reached
+/// through an alias, [`Locations::reference_location`] points at the alias and
+/// [`Locations::defined_location`] at the anchored node. The empty path refers to the
+/// root container.
+#[derive(Debug, Clone)]
+pub struct SourceMap {
+    paths: PathMap,
+}
+
+impl SourceMap {
+    /// Locations of the value at `path`, if it was present in the document.
+    #[must_use]
+    pub fn get(&self, path: &PathKey) -> Option<Locations> {
+        self.paths.map.get(path).copied()
+    }
+
+    /// Iterate over all recorded paths and their locations, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (&PathKey, &Locations)> {
+        self.paths.map.iter()
+    }
+
+    /// Number of recorded paths.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.paths.map.len()
+    }
+
+    /// Whether no path was recorded.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.paths.map.is_empty()
+    }
+}
+
+/// Deserialize a single YAML document and return it together with its [`SourceMap`].
+pub fn from_str_with_source_map<T: DeserializeOwned>(input: &str) -> Result<(T, SourceMap), Error> {
+    from_str_with_options_and_source_map(input, Options::default())
+}
+
+/// Deserialize a single YAML document with configurable [`Options`] and return it
+/// together with its [`SourceMap`].
+pub fn from_str_with_options_and_source_map<T: DeserializeOwned>(
+    input: &str,
+    options: Options,
+) -> Result<(T, SourceMap), Error> {
+    let (value, paths) = from_str_with_path_recorder(input, options, |_, _| Ok(()), |_| true)?;
+    Ok((value, SourceMap { paths }))
+}
+
+/// Deserialize a single document while recording key paths, run `check` on the result,
+/// and return both the value and the recorded paths.
+///
+/// `synthesized_null_is_eof` decides whether an error raised for an empty document is
+/// reported as end of input instead.
+pub(crate) fn from_str_with_path_recorder<T, F, P>(
+    input: &str,
+    options: Options,
+    check: F,
+    synthesized_null_is_eof: P,
+) -> Result<(T, PathMap), Error>
+where
+    T: DeserializeOwned,
+    F: FnOnce(&T, &PathMap) -> Result<(), Error>,
+    P: Fn(&Error) -> bool,
+{
+    let input = normalize_str_input(input);
+    let snippet_ctx = StrSnippetContext::new(input, options.with_snippet, options.crop_radius);
+    let cfg = crate::de::Cfg::from_options(&options);
+    let mut src = LiveEvents::from_str(input, options);
+    let mut recorder = PathRecorder::new();
+    let wrap_err = |e, src: &LiveEvents<'_>| snippet_ctx.attach_snippet(e, src);
+
+    let value = run_with_document_scope(
+        &mut src,
+        |src| {
+            let value = crate::de::with_root_redaction(
+                crate::de::YamlDeserializer::new_with_path_recorder(src, cfg, &mut recorder),
+                |de| T::deserialize(de),
+            )?;
+            check(&value, &recorder.map)?;
+            Ok(value)
+        },
+        wrap_err,
+        synthesized_null_is_eof,
+    )?;
+
+    enforce_single_document_and_finish(
+        &mut src,
+        "use from_multiple or from_multiple_with_options",
+        wrap_err,
+    )?;
+
+    Ok((value, recorder.map))
+}
@@ -0,0 +1,205 @@
+//! Names and texts of the sources a document was read from.
+//!
+//! Every [`Location`] carries a [`Location::source_id`]. With includes, several sources
+//! contribute to one value, and the id alone does not say which file a location belongs
+//! to. Install a [`SourceRegistry`] with [`crate::Options::with_source_registry`] to keep
+//! the id → source mapping after deserialization, and use it to render snippets for any
+//! location, for example one taken from a [`crate::Spanned`] value.
+//!
+//! ```rust
+//! use serde::Deserialize;
+//! use serde_saphyr::{SourceRegistry, Spanned};
+//!
+//! #[derive(Deserialize)]
+//! struct Config {
+//!     port: Spanned<u16>,
+//! }
+//!
+//! let registry = SourceRegistry::new();
+//! let options = serde_saphyr::options! {}.with_source_registry(&registry);
+//! let config: Config = serde_saphyr::from_str_with_options("port: 80\n", options)?;
+//!
+//! let source = registry.get(config.port.referenced.source_id()).unwrap();
+//! assert_eq!(source.name(), "<input>");
+//! let rendered = config.port.render_snippet(&registry, "privileged port");
+//! assert!(rendered.contains("privileged port"));
+//! # Ok::<(), serde_saphyr::Error>(())
+//! ```
+
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::rc::Rc;
+
+use crate::de::Error;
+use crate::location::Location;
+
+/// Same default as [`crate::Options::crop_radius`].
+const DEFAULT_CROP_RADIUS: usize = 64;
+
+/// One source that contributed to a document: the root input or an included file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SourceInfo {
+    id: u32,
+    name: String,
+    resolved_id: Option<String>,
+    text: Option<Rc<str>>,
+    parent: Option<u32>,
+    include_location: Location,
+}
+
+impl SourceInfo {
+    /// The numeric id used by [`Location::source_id`].
+    #[must_use]
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Display name, as given by the include resolver (`<input>` for the root).
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Canonical id returned by the include resolver, used for cycle detection.
+    /// `None` for the root input.
+    #[must_use]
+    pub fn resolved_id(&self) -> Option<&str> {
+        self.resolved_id.as_deref()
+    }
+
+    /// Source text, if it is available. Reader inputs are not retained.
+    #[must_use]
+    pub fn text(&self) -> Option<&str> {
+        self.text.as_deref()
+    }
+
+    /// Id of the source that included this one; `None` for the root input.
+    #[must_use]
+    pub fn parent(&self) -> Option<u32> {
+        self.parent
+    }
+
+    /// Location of the include tag in the parent source, or [`Location::UNKNOWN`].
+    #[must_use]
+    pub fn include_location(&self) -> Location {
+        self.include_location
+    }
+}
+
+/// Shared collector of [`SourceInfo`] records, keyed by source id.
+///
+/// Cloning is cheap and all clones see the same records. Each parse that uses the
+/// registry clears it first, because source ids are only unique within one parse.
+#[derive(Clone, Default)]
+pub struct SourceRegistry {
+    sources: Rc<RefCell<BTreeMap<u32, SourceInfo>>>,
+}
+
+impl SourceRegistry {
+    /// Create an empty registry.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The source with the given id.
+    #[must_use]
+    pub fn get(&self, id: u32) -> Option<SourceInfo> {
+        self.sources.borrow().get(&id).cloned()
+    }
+
+    /// The source a location belongs to.
+    #[must_use]
+    pub fn source_of(&self, location: &Location) -> Option<SourceInfo> {
+        self.get(location.source_id())
+    }
+
+    /// All recorded sources, ordered by id.
+    #[must_use]
+    pub fn sources(&self) -> Vec<SourceInfo> {
+        self.sources.borrow().values().cloned().collect()
+    }
+
+    /// Number of recorded sources.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.sources.borrow().len()
+    }
+
+    /// True if no source has been recorded.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.sources.borrow().is_empty()
+    }
+
+    /// Render `message` with a snippet of the source `location` belongs to.
+    ///
+    /// For locations inside included files, the include sites are shown as well. If
+    /// the source text is not available, only the name, line and column are given.
+    #[must_use]
+    pub fn render_snippet(&self, location: &Location, message: &str) -> String {
+        let sources = self.sources.borrow();
+        let err = Error::msg(message).with_location(*location);
+        let Some(source) = sources.get(&location.source_id()) else {
+            return err.to_string();
+        };
+        let Some(text) = source.text() else {
+            return format!(
+                "{}:{}:{}: {message}",
+                source.name,
+                location.line(),
+                location.column()
+            );
+        };
+
+        let mut err = err.with_snippet_named(text, &source.name, DEFAULT_CROP_RADIUS);
+        let mut child = source;
+        while let Some(parent) = child.parent.and_then(|id| sources.get(&id)) {
+            if let Some(parent_text) = parent.text() {
+                err = err.with_additional_snippet_named(
+                    parent_text,
+                    &parent.name,
+                    &child.include_location,
+                    DEFAULT_CROP_RADIUS,
+                );
+            }
+            child = parent;
+        }
+        err.to_string()
+    }
+
+    pub(crate) fn clear(&self) {
+        self.sources.borrow_mut().clear();
+    }
+
+    pub(crate) fn record(
+        &self,
+        id: u32,
+        name: &str,
+        resolved_id: Option<&str>,
+        text: Option<Rc<str>>,
+        parent: Option<u32>,
+        include_location: Location,
+    ) {
+        self.sources.borrow_mut().insert(
+            id,
+            SourceInfo {
+                id,
+                name: name.to_owned(),
+                resolved_id: resolved_id.map(str::to_owned),
+                text,
+                parent,
+                include_location,
+            },
+        );
+    }
+}
+
+impl fmt::Debug for SourceRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries(self.sources.borrow().values())
+            .finish()
+    }
+}
@@ -183,9 +183,9 @@ impl<'de> de::MapAccess<'de> for SpannedMapAccess<'de, '_> {
 ///
 /// This is used by `SpannedMapAccess` to emit `referenced` and `defined` fields
 /// without requiring `Location` to be represented in the YAML input.
-struct LocationDeser {
+pub(super) struct LocationDeser {
     /// The concrete location to serialize into a struct-like map.
-    location: Location,
+    pub(super) location: Location,
 }
 
 impl<'de> de::Deserializer<'de> for LocationDeser {
@@ -0,0 +1,93 @@
+//! "Did you mean" suggestions for unknown field and variant names.
+
+/// The expected name closest to `input`, if any is close enough to be a likely typo.
+///
+/// Names are compared ignoring ASCII case and the separators `_`, `-` and space, so
+/// `maxReplicas`, `max-replicas` and `MAX_REPLICAS` all match `max_replicas`. Otherwise
+/// the name with the smallest edit distance (adjacent transpositions count as one edit)
+/// wins, provided the distance is at most a third of the input length, and at least one.
+pub(crate) fn closest_name(input: &str, expected: &[&'static str]) -> Option<&'static str> {
+    let normalized = normalize(input);
+    if normalized.is_empty() {
+        return None;
+    }
+
+    if let Some(exact) = expected
+        .iter()
+        .find(|candidate| normalize(candidate) == normalized)
+    {
+        return Some(exact);
+    }
+
+    let max_distance = (normalized.len() / 3).max(1);
+    expected
+        .iter()
+        .map(|candidate| {
+            (
+                edit_distance(&normalized, &normalize(candidate)),
+                *candidate,
+            )
+        })
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+fn normalize(name: &str) -> Vec<char> {
+    name.chars()
+        .filter(|c| !matches!(c, '_' | '-' | ' '))
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
+
+/// Optimal string alignment distance: Levenshtein plus adjacent transpositions.
+fn edit_distance(a: &[char], b: &[char]) -> usize {
+    let width = b.len() + 1;
+    let mut rows = vec![0usize; (a.len() + 1) * width];
+    for (j, cell) in rows.iter_mut().take(width).enumerate() {
+        *cell = j;
+    }
+    for i in 1..=a.len() {
+        rows[i * width] = i;
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            let mut best = (rows[(i - 1) * width + j] + 1)
+                .min(rows[i * width + j - 1] + 1)
+                .min(rows[(i - 1) * width + j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                best = best.min(rows[(i - 2) * width + j - 2] + 1);
+            }
+            rows[i * width + j] = best;
+        }
+    }
+    rows[a.len() * width + b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    const FIELDS: &[&str] = &["name", "max_replicas", "min_replicas", "ports"];
+
+    #[rstest]
+    #[case("nmae", Some("name"))]
+    #[case("Name", Some("name"))]
+    #[case("maxReplicas", Some("max_replicas"))]
+    #[case("MAX-REPLICAS", Some("max_replicas"))]
+    #[case("max_replica", Some("max_replicas"))]
+    #[case("port", Some("ports"))]
+    #[case("image", None)]
+    #[case("", None)]
+    fn closest_name_cases(#[case] input: &str, #[case] expected: Option<&str>) {
+        assert_eq!(closest_name(input, FIELDS), expected);
+    }
+
+    #[test]
+    fn edit_distance_counts_transposition_as_one() {
+        let a: Vec<char> = "abcd".chars().collect();
+        let b: Vec<char> = "acbd".chars().collect();
+        assert_eq!(edit_distance(&a, &b), 1);
+        assert_eq!(edit_distance(&a, &[]), 4);
+    }
+}
@@ -78,6 +78,12 @@ pub(crate) enum SfTag {
     Null,
     Seq,
     Map,
+    /// YAML 1.1 `!!set`: a mapping whose values are all null.
+    Set,
+    /// YAML 1.1 `!!omap`: a sequence of single-pair mappings with unique keys.
+    Omap,
+    /// YAML 1.1 `!!pairs`: like `!!omap`, but keys may repeat.
+    Pairs,
     TimeStamp,
     Binary,
     String,
@@ -125,6 +131,13 @@ static TAG_LOOKUP_MAP: LazyLock<BTreeMap<&'static str, SfTag>> = LazyLock::new(|
         ("!map", SfTag::Map),
         ("tag:yaml.org,2002:map", SfTag::Map),
         ("tag:yaml.org,2002:!map", SfTag::Map),
+        // YAML 1.1 collection types
+        ("!!set", SfTag::Set),
+        ("tag:yaml.org,2002:set", SfTag::Set),
+        ("!!omap", SfTag::Omap),
+        ("tag:yaml.org,2002:omap", SfTag::Omap),
+        ("!!pairs", SfTag::Pairs),
+        ("tag:yaml.org,2002:pairs", SfTag::Pairs),
         // string (null key or value with this tag can be serialized into empty string)
         ("!!str", SfTag::String),
         ("!str", SfTag::String),
@@ -174,6 +187,26 @@ fn core_suffix_to_sf_tag(suffix: &str) -> Option<SfTag> {
     }
 }
 
+/// Length of the prefix a `%TAG` directive registered for the handle `tag` was written with,
+/// or 0 if its handle resolved without a directive (`!local`, `!!str`, verbatim `!<...>`).
+///
+/// `%TAG !k8s! tag:example.com,2026:` makes `!k8s!Deployment` resolve to
+/// `tag:example.com,2026:Deployment`; the prefix length lets a tagged enum read the variant
+/// name `Deployment` whatever the prefix looks like.
+pub(crate) fn directive_prefix_len(tag: &Tag) -> usize {
+    let default_prefix = match tag.original_handle() {
+        "" => return 0,
+        "!" => "!",
+        "!!" => "tag:yaml.org,2002:",
+        _ => "",
+    };
+    if tag.handle() == default_prefix {
+        0
+    } else {
+        tag.handle().len()
+    }
+}
+
 impl SfTag {
     pub(crate) fn from_optional_cow(tag: &Option<Cow<Tag>>) -> SfTag {
         match parse_include_tag(tag) {
@@ -211,6 +244,9 @@ impl SfTag {
             | SfTag::Null
             | SfTag::Seq
             | SfTag::Map
+            | SfTag::Set
+            | SfTag::Omap
+            | SfTag::Pairs
             | SfTag::TimeStamp
             | SfTag::Degrees
             | SfTag::Radians => false,
@@ -277,8 +313,10 @@ mod tests {
         let timestamp = Tag::with_original_handle("tag:yaml.org,2002:", "timestamp", "!!");
         let binary = Tag::with_original_handle("tag:yaml.org,2002:", "binary", "!!");
         let unknown = Tag::with_original_handle("tag:yaml.org,2002:", "application", "!!");
+        let omap = Tag::with_original_handle("tag:yaml.org,2002:", "omap", "!!");
 
         assert_eq!(sf_tag(timestamp), SfTag::TimeStamp);
+        assert_eq!(sf_tag(omap), SfTag::Omap);
         assert_eq!(sf_tag(binary), SfTag::Binary);
         assert_eq!(sf_tag(unknown), SfTag::Other);
     }
@@ -2,10 +2,8 @@ use std::borrow::Cow;
 
 use granit_parser::ScalarStyle;
 
-#[cfg(feature = "properties")]
-use super::PropertySyntax;
 use super::cfg::Cfg;
-use super::events::{Ev, Events, ReplayEvents, attach_alias_locations_if_missing};
+use super::events::{Ev, Events, ReplayContext, ReplayEvents, attach_alias_locations_if_missing};
 use super::key_nodes::*;
 use super::tags::SfTag;
 use super::{DuplicateKeyPolicy, Error, Location, MergeKeyPolicy, Options};
@@ -25,6 +23,7 @@ fn scalar(
         value: Cow::Borrowed(value),
         tag,
         raw_tag: raw_tag.map(Cow::Borrowed),
+        tag_prefix_len: 0,
         style,
         anchor: 0,
         location,
@@ -36,6 +35,7 @@ fn seq_start(tag: SfTag, raw_tag: Option<&'static str>, location: Location) -> E
         anchor: 0,
         tag,
         raw_tag: raw_tag.map(Cow::Borrowed),
+        tag_prefix_len: 0,
         location,
     }
 }
@@ -53,6 +53,7 @@ fn tagged_map_start(tag: SfTag, raw_tag: Option<&'static str>, location: Locatio
         anchor: 0,
         tag,
         raw_tag: raw_tag.map(Cow::Borrowed),
+        tag_prefix_len: 0,
         location,
     }
 }
@@ -61,14 +62,8 @@ fn map_end(location: Location) -> Ev<'static> {
     Ev::MapEnd { location }
 }
 
-#[cfg(not(feature = "properties"))]
 fn replay_events(buf: Vec<Ev<'static>>) -> ReplayEvents<'static> {
-    ReplayEvents::new(buf)
-}
-
-#[cfg(feature = "properties")]
-fn replay_events(buf: Vec<Ev<'static>>) -> ReplayEvents<'static> {
-    ReplayEvents::new(buf, None, PropertySyntax::Braced)
+    ReplayEvents::new(buf, ReplayContext::default())
 }
 
 fn captured_fingerprint(events: Vec<Ev<'static>>) -> KeyFingerprint<'static> {
@@ -77,23 +72,13 @@ fn captured_fingerprint(events: Vec<Ev<'static>>) -> KeyFingerprint<'static> {
     node.take_fingerprint()
 }
 
-#[cfg(not(feature = "properties"))]
 fn replay_events_with_reference(
     buf: Vec<Ev<'static>>,
     reference: Location,
 ) -> ReplayEvents<'static> {
-    ReplayEvents::with_reference(buf, reference)
+    ReplayEvents::with_reference(buf, reference, ReplayContext::default())
 }
 
-#[cfg(feature = "properties")]
-fn replay_events_with_reference(
-    buf: Vec<Ev<'static>>,
-    reference: Location,
-) -> ReplayEvents<'static> {
-    ReplayEvents::with_reference(buf, reference, None, PropertySyntax::Braced)
-}
-
-#[cfg(not(feature = "properties"))]
 fn pending_from_events(
     events: Vec<Ev<'static>>,
     location: Location,
@@ -105,23 +90,7 @@ fn pending_from_events(
         reference_location,
         MergeKeyPolicy::Merge,
         DuplicateKeyPolicy::Error,
-    )
-}
-
-#[cfg(feature = "properties")]
-fn pending_from_events(
-    events: Vec<Ev<'static>>,
-    location: Location,
-    reference_location: Location,
-) -> Result<Vec<PendingEntry<'static>>, Error> {
-    pending_entries_from_events(
-        events,
-        location,
-        reference_location,
-        MergeKeyPolicy::Merge,
-        DuplicateKeyPolicy::Error,
-        None,
-        PropertySyntax::Braced,
+        &ReplayContext::default(),
     )
 }
 
@@ -253,38 +222,67 @@ fn attach_alias_locations_prefers_dual_locations_and_existing_errors() {
 #[test]
 fn simple_tagged_enum_helpers_accept_only_simple_variant_names() {
     assert_eq!(
-        simple_tagged_enum_name(&Some(Cow::Borrowed("!Widget")), &SfTag::Other),
+        simple_tagged_enum_name(&Some(Cow::Borrowed("!Widget")), 0, &SfTag::Other),
         Some("Widget".to_owned())
     );
     assert_eq!(
-        simple_tagged_enum_name(&Some(Cow::Borrowed("!map")), &SfTag::Map),
+        simple_tagged_enum_name(&Some(Cow::Borrowed("!map")), 0, &SfTag::Map),
         None
     );
     assert_eq!(
-        simple_tagged_enum_name(&Some(Cow::Borrowed("tag:yaml.org,2002:map")), &SfTag::Map),
+        simple_tagged_enum_name(
+            &Some(Cow::Borrowed("tag:yaml.org,2002:map")),
+            0,
+            &SfTag::Map
+        ),
         None
     );
     assert_eq!(
         simple_tagged_enum_name(
             &Some(Cow::Borrowed("!<tag:yaml.org,2002:Widget>")),
+            0,
             &SfTag::Other
         ),
         Some("Widget".to_owned())
     );
     assert_eq!(
-        simple_tagged_enum_name(&Some(Cow::Borrowed("!")), &SfTag::Other),
+        simple_tagged_enum_name(
+            &Some(Cow::Borrowed("tag:example.com,2026:Deployment")),
+            21,
+            &SfTag::Other
+        ),
+        Some("Deployment".to_owned())
+    );
+    assert_eq!(
+        simple_tagged_enum_name(
+            &Some(Cow::Borrowed("tag:example.com,2026:Deployment")),
+            0,
+            &SfTag::Other
+        ),
+        None
+    );
+    assert_eq!(
+        simple_tagged_enum_name(
+            &Some(Cow::Borrowed("https://example.com/Deployment")),
+            0,
+            &SfTag::Other
+        ),
+        None
+    );
+    assert_eq!(
+        simple_tagged_enum_name(&Some(Cow::Borrowed("!")), 0, &SfTag::Other),
         None
     );
     assert_eq!(
-        simple_tagged_enum_name(&Some(Cow::Borrowed("!bad:name")), &SfTag::Other),
+        simple_tagged_enum_name(&Some(Cow::Borrowed("!bad:name")), 0, &SfTag::Other),
         None
     );
     assert_eq!(
-        simple_tagged_enum_name(&Some(Cow::Borrowed("!bang!oops")), &SfTag::Other),
+        simple_tagged_enum_name(&Some(Cow::Borrowed("!bang!oops")), 0, &SfTag::Other),
         None
     );
     assert_eq!(
-        simple_tagged_enum_name(&Some(Cow::Borrowed("!Widget")), &SfTag::String),
+        simple_tagged_enum_name(&Some(Cow::Borrowed("!Widget")), 0, &SfTag::String),
         None
     );
 
@@ -715,6 +713,7 @@ fn tagged_payload_helpers_strip_root_tags_and_build_external_map_events() {
         Some(Ev::MapStart {
             tag: SfTag::None,
             raw_tag: None,
+            tag_prefix_len: 0,
             ..
         })
     ));
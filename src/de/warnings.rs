@@ -0,0 +1,498 @@
+//! Non-fatal findings collected while deserializing.
+//!
+//! Some inputs are accepted but are likely not what the author meant: YAML 1.1 boolean
+//! spellings such as `yes` or `on`, numbers with a leading zero, keys the target type does
+//! not know, and values silently replaced by a duplicate or merged key. Install a
+//! [`Warnings`] collector with [`crate::Options::with_warnings`] to receive them as
+//! [`Warning`]s without failing the load.
+//!
+//! ```rust
+//! use serde::Deserialize;
+//! use serde_saphyr::warnings::{WarningKind, Warnings};
+//!
+//! #[derive(Deserialize)]
+//! struct Config {
+//!     enabled: bool,
+//! }
+//!
+//! let warnings = Warnings::new();
+//! let options = serde_saphyr::options! {}.with_warnings(&warnings);
+//! let yaml = "enabled: yes\ncolour: red\n";
+//! let config: Config = serde_saphyr::from_str_with_options(yaml, options)?;
+//! assert!(config.enabled);
+//!
+//! let found = warnings.take();
+//! assert!(matches!(found[0].kind, WarningKind::Yaml11Boolean { .. }));
+//! assert!(matches!(&found[1].kind, WarningKind::IgnoredKey { key } if key == "colour"));
+//! for warning in &found {
+//!     eprintln!("{}", warning.render_with_source(yaml, Default::default()));
+//! }
+//! # Ok::<(), serde_saphyr::Error>(())
+//! ```
+//!
+//! Keys are reported as ignored when the target type skips their value, which is what
+//! derived structs without `#[serde(deny_unknown_fields)]` do. Unknown keys inside a
+//! `#[serde(flatten)]` struct are dropped by Serde itself and cannot be observed. To
+//! reject such keys instead, see [`crate::UnknownKeyPolicy`].
+
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::fmt;
+use std::rc::Rc;
+
+use annotate_snippets::Level;
+
+use crate::de::{Error, UnknownKeyPolicy};
+use crate::de_error::{
+    RenderOptions, RenderTarget, SnippetMode, ValidationIssue, ValidationSource,
+};
+use crate::location::{Location, Locations};
+use crate::parse_scalars::leading_zero_decimal;
+use crate::path_map::{PathKey, PathKind, PathMap, PathSegment};
+
+/// What a [`Warning`] is about.
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WarningKind {
+    /// A YAML 1.1 boolean spelling (`yes`, `no`, `on`, `off`, `y`, `n`) was read as a
+    /// boolean. YAML 1.2 parsers read these as strings.
+    Yaml11Boolean {
+        /// The scalar as written.
+        value: String,
+        /// The boolean it was read as.
+        interpreted_as: bool,
+    },
+    /// A plain number with a leading zero such as `0755`. YAML 1.1 reads it as octal,
+    /// YAML 1.2 does not.
+    LegacyOctal {
+        /// The scalar as written.
+        value: String,
+        /// True if it was read as octal, because of [`crate::Options::legacy_octal_numbers`]
+        /// or [`crate::Schema::Yaml11`]; false if it was read as a string or, under
+        /// [`crate::Schema::Core`], as a decimal number.
+        read_as_octal: bool,
+    },
+    /// The target type skipped the value of this mapping key.
+    IgnoredKey {
+        /// The key as written.
+        key: String,
+    },
+    /// Under [`crate::DuplicateKeyPolicy::LastWins`], a repeated key replaced the value
+    /// given earlier in the same mapping.
+    DuplicateKeyOverride {
+        /// The key, if it is a scalar.
+        key: Option<String>,
+    },
+    /// A key brought in by a merge (`<<`) was overridden by an explicit key or by an
+    /// earlier merge source.
+    MergeOverride {
+        /// The key, if it is a scalar.
+        key: Option<String>,
+    },
+}
+
+/// A non-fatal finding, with the location and key path of the value it is about.
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Warning {
+    /// What was found.
+    pub kind: WarningKind,
+    /// Where it was found.
+    pub location: Location,
+    /// Key path of the value, using the key spelling of the YAML document.
+    pub path: PathKey,
+}
+
+impl Warning {
+    /// Developer-oriented message text, without a location suffix.
+    ///
+    /// This is what [`crate::MessageFormatter::format_warning`] returns unless overridden.
+    #[must_use]
+    pub fn default_message(&self) -> String {
+        match &self.kind {
+            WarningKind::Yaml11Boolean {
+                value,
+                interpreted_as,
+            } => format!(
+                "`{value}` was read as the boolean {interpreted_as}; YAML 1.2 reads it as a \
+                 string, write `{interpreted_as}` instead"
+            ),
+            WarningKind::LegacyOctal {
+                value,
+                read_as_octal: true,
+            } => format!(
+                "`{value}` was read as a legacy octal number; write `0o{}` instead",
+                value.trim_start_matches(['+', '-']).trim_start_matches('0')
+            ),
+            WarningKind::LegacyOctal {
+                value,
+                read_as_octal: false,
+            } => format!(
+                "`{value}` was not read as an octal number, but YAML 1.1 reads it as one; \
+                 quote it or remove the leading zeros"
+            ),
+            WarningKind::IgnoredKey { key } => {
+                format!("unknown key `{key}` was ignored")
+            }
+            WarningKind::DuplicateKeyOverride { key: Some(key) } => {
+                format!("duplicate key `{key}` replaces the earlier value")
+            }
+            WarningKind::DuplicateKeyOverride { key: None } => {
+                "duplicate key replaces the earlier value".to_owned()
+            }
+            WarningKind::MergeOverride { key: Some(key) } => {
+                format!("merged key `{key}` is overridden")
+            }
+            WarningKind::MergeOverride { key: None } => "merged key is overridden".to_owned(),
+        }
+    }
+
+    /// Render this warning with the built-in developer formatter.
+    #[must_use]
+    pub fn render(&self) -> String {
+        self.render_with_options(RenderOptions::default())
+    }
+
+    /// Render this warning, message and location, without a snippet.
+    #[must_use]
+    pub fn render_with_options(&self, options: RenderOptions<'_>) -> String {
+        let l10n = options.formatter.localizer();
+        let msg = options.formatter.format_warning(self);
+        let rendered = if self.location == Location::UNKNOWN {
+            msg.into_owned()
+        } else {
+            l10n.attach_location(msg, self.location).into_owned()
+        };
+        match options.target {
+            // Nothing here is markup yet, so every ESC comes from user text.
+            RenderTarget::Html => {
+                crate::de_snippet::to_html(&crate::de_snippet::neutralize_escapes(&rendered))
+            }
+            _ => rendered,
+        }
+    }
+
+    /// Render this warning with a snippet of `source`, the YAML text it was found in.
+    ///
+    /// Falls back to [`Warning::render_with_options`] when snippets are disabled or the
+    /// location is not inside `source`.
+    #[must_use]
+    pub fn render_with_source(&self, source: &str, mut options: RenderOptions<'_>) -> String {
+        if options.snippets == SnippetMode::Off {
+            return self.render_with_options(options);
+        }
+
+        struct SnippetDisplay<'a> {
+            warning: &'a Warning,
+            source: &'a str,
+            options: RenderOptions<'a>,
+        }
+
+        impl fmt::Display for SnippetDisplay<'_> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                let msg = self.options.formatter.format_warning(self.warning);
+                crate::de_snippet::Snippet::new(self.source, "<input>", DEFAULT_CROP_RADIUS)
+                    .with_target(self.options.target)
+                    .fmt_or_fallback(
+                        f,
+                        Level::WARNING,
+                        self.options.formatter.localizer(),
+                        msg.as_ref(),
+                        &self.warning.location,
+                    )
+            }
+        }
+
+        options.target = options.target.resolve();
+        let rendered = SnippetDisplay {
+            warning: self,
+            source,
+            options,
+        }
+        .to_string();
+        match options.target {
+            RenderTarget::Html => crate::de_snippet::to_html(&rendered),
+            _ => rendered,
+        }
+    }
+}
+
+/// Same default as [`crate::Options::crop_radius`].
+const DEFAULT_CROP_RADIUS: usize = 64;
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.render())
+    }
+}
+
+/// Shared collector for [`Warning`]s.
+///
+/// Cloning is cheap and yields a handle to the same collection, so keep one handle and
+/// pass another to [`crate::Options::with_warnings`]. Warnings accumulate across loads
+/// until [`Warnings::take`] is called.
+#[derive(Clone, Default)]
+pub struct Warnings {
+    inner: Rc<RefCell<Vec<Warning>>>,
+}
+
+impl Warnings {
+    /// Create an empty collector.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Remove and return all collected warnings, in the order they were found.
+    #[must_use]
+    pub fn take(&self) -> Vec<Warning> {
+        std::mem::take(&mut *self.inner.borrow_mut())
+    }
+
+    /// Number of collected warnings.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.inner.borrow().len()
+    }
+
+    /// Whether no warning was collected.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.inner.borrow().is_empty()
+    }
+}
+
+impl fmt::Debug for Warnings {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Warnings")
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+/// Collection state of one deserialization operation.
+///
+/// The deserializer cannot thread a collector through Serde's visitor calls, so the
+/// active collector lives in a thread-local stack, like the redaction scopes. A scope is
+/// opened per document by `run_with_document_scope`; nested loads started from inside a
+/// `Deserialize` impl open their own scope and do not report into the outer one.
+struct WarningScope {
+    sink: Option<Warnings>,
+    /// Keys skipped by the target type, collected under [`UnknownKeyPolicy::Error`].
+    unknown_keys: Option<Vec<(PathKey, String, Location)>>,
+    /// Key path of the value being deserialized, with the location of each key or
+    /// element. The segment is `None` for non-scalar keys.
+    path: Vec<(Option<PathSegment>, Location)>,
+    /// Nesting depth of values skipped through `deserialize_ignored_any`.
+    ignoring: usize,
+}
+
+impl WarningScope {
+    fn current_path(&self) -> PathKey {
+        PathKey {
+            segments: self
+                .path
+                .iter()
+                .filter_map(|(seg, _)| seg.clone())
+                .collect(),
+        }
+    }
+
+    fn push(&self, kind: WarningKind, location: Location) {
+        if let Some(sink) = &self.sink {
+            sink.inner.borrow_mut().push(Warning {
+                kind,
+                location,
+                path: self.current_path(),
+            });
+        }
+    }
+}
+
+thread_local! {
+    static WARNING_SCOPES: RefCell<Vec<WarningScope>> = const { RefCell::new(Vec::new()) };
+}
+
+struct WarningScopeGuard;
+
+impl Drop for WarningScopeGuard {
+    fn drop(&mut self) {
+        WARNING_SCOPES.with(|cell| {
+            let _ = cell.borrow_mut().pop();
+        });
+    }
+}
+
+/// Run one document's deserialization with `sink` as the active collector.
+///
+/// Under [`UnknownKeyPolicy::Error`], a successful `f` is turned into an error listing
+/// every key the target type skipped.
+pub(crate) fn with_warning_scope<T>(
+    sink: Option<Warnings>,
+    unknown_keys: UnknownKeyPolicy,
+    f: impl FnOnce() -> Result<T, Error>,
+) -> Result<T, Error> {
+    WARNING_SCOPES.with(|cell| {
+        cell.borrow_mut().push(WarningScope {
+            sink,
+            unknown_keys: (unknown_keys == UnknownKeyPolicy::Error).then(Vec::new),
+            path: Vec::new(),
+            ignoring: 0,
+        });
+    });
+    let _guard = WarningScopeGuard;
+    let value = f()?;
+    let unknown = WARNING_SCOPES.with(|cell| {
+        cell.borrow_mut()
+            .last_mut()
+            .and_then(|scope| scope.unknown_keys.take())
+    });
+    match unknown {
+        Some(keys) if !keys.is_empty() => Err(unknown_keys_error(keys)),
+        _ => Ok(value),
+    }
+}
+
+/// One validation issue per unknown key, located at the key.
+fn unknown_keys_error(keys: Vec<(PathKey, String, Location)>) -> Error {
+    let mut locations = PathMap::new();
+    let issues = keys
+        .into_iter()
+        .map(|(path, key, location)| {
+            locations.insert(
+                path.clone(),
+                Locations {
+                    reference_location: location,
+                    defined_location: location,
+                },
+            );
+            ValidationIssue::new(path, "unknown_key")
+                .with_message("unknown key")
+                .with_params(vec![("key".to_owned(), key)])
+        })
+        .collect();
+    Error::validation_error(ValidationSource::UnknownKeys, issues, locations)
+}
+
+/// Run `f` with the active scope, if it collects warnings or unknown keys.
+fn with_active<T>(f: impl FnOnce(&mut WarningScope) -> T) -> Option<T> {
+    WARNING_SCOPES.with(|cell| {
+        let mut scopes = cell.borrow_mut();
+        match scopes.last_mut() {
+            Some(scope) if scope.sink.is_some() || scope.unknown_keys.is_some() => Some(f(scope)),
+            _ => None,
+        }
+    })
+}
+
+/// Whether a collector is installed for the current document.
+pub(crate) fn is_collecting() -> bool {
+    with_active(|scope| scope.sink.is_some()).unwrap_or(false)
+}
+
+/// Record a warning about the value being deserialized. `kind` is only built when a
+/// collector is installed.
+pub(crate) fn warn(location: Location, kind: impl FnOnce() -> WarningKind) {
+    with_active(|scope| {
+        if scope.ignoring == 0 && scope.sink.is_some() {
+            scope.push(kind(), location);
+        }
+    });
+}
+
+/// Deserialize a skipped value. When it is the value of a mapping key, report the key
+/// as ignored and record it as unknown; nested keys of the skipped value are not
+/// reported separately.
+pub(crate) fn ignoring<T>(f: impl FnOnce() -> T) -> T {
+    let entered = with_active(|scope| {
+        if scope.ignoring == 0
+            && let Some((Some(segment), location)) = scope.path.last()
+            && segment.kind == PathKind::Key
+        {
+            let key = segment.name.clone();
+            let location = *location;
+            let path = scope.current_path();
+            if let Some(unknown) = &mut scope.unknown_keys {
+                unknown.push((path, key.clone(), location));
+            }
+            scope.push(WarningKind::IgnoredKey { key }, location);
+        }
+        scope.ignoring += 1;
+    })
+    .is_some();
+    let result = f();
+    if entered {
+        with_active(|scope| scope.ignoring -= 1);
+    }
+    result
+}
+
+/// Keeps a mapping key or sequence index on the warning path while its value is
+/// deserialized. Does nothing when no collector is installed.
+pub(crate) struct WarningPathGuard {
+    pushed: bool,
+}
+
+impl WarningPathGuard {
+    /// Enter the value of a mapping key; `key` is `None` for non-scalar keys.
+    pub(crate) fn key(key: Option<&str>, key_location: Location) -> Self {
+        let pushed = with_active(|scope| {
+            scope.path.push((key.map(PathSegment::from), key_location));
+        })
+        .is_some();
+        Self { pushed }
+    }
+
+    /// Enter a sequence element.
+    pub(crate) fn index(index: usize, location: Location) -> Self {
+        let pushed = with_active(|scope| {
+            scope.path.push((Some(PathSegment::from(index)), location));
+        })
+        .is_some();
+        Self { pushed }
+    }
+}
+
+impl Drop for WarningPathGuard {
+    fn drop(&mut self) {
+        if self.pushed {
+            with_active(|scope| scope.path.pop());
+        }
+    }
+}
+
+/// Report `raw`, already read as the boolean `value`, unless it is spelled `true` or
+/// `false`.
+pub(crate) fn warn_yaml11_bool(raw: &str, value: bool, location: Location) {
+    let t = raw.trim();
+    if !(t.eq_ignore_ascii_case("true") || t.eq_ignore_ascii_case("false")) {
+        warn(location, || WarningKind::Yaml11Boolean {
+            value: t.to_owned(),
+            interpreted_as: value,
+        });
+    }
+}
+
+/// Whether `raw` is a plain number with a leading zero that YAML 1.1 reads as octal.
+pub(crate) fn is_legacy_octal(raw: &str) -> bool {
+    let t = raw.trim();
+    leading_zero_decimal(t)
+        && t.trim_start_matches(['+', '-'])
+            .bytes()
+            .all(|b| (b'0'..=b'7').contains(&b))
+}
+
+/// Report `raw` if it is a legacy octal literal; `read_as_octal` tells how it was read.
+pub(crate) fn warn_legacy_octal(raw: &str, location: Location, read_as_octal: bool) {
+    if is_legacy_octal(raw) {
+        warn(location, || WarningKind::LegacyOctal {
+            value: raw.trim().to_owned(),
+            read_as_octal,
+        });
+    }
+}
+
+/// Message text for formatters that do not override [`crate::MessageFormatter::format_warning`].
+pub(crate) fn default_warning_message(warning: &Warning) -> Cow<'_, str> {
+    Cow::Owned(warning.default_message())
+}
@@ -20,8 +20,15 @@ where
     W: Fn(Error, &LiveEvents<'de>) -> Error,
     P: Fn(&Error) -> bool,
 {
+    let warnings = src.warnings().cloned();
+    let unknown_keys = src.unknown_keys();
     let value_res = crate::anchor_store::with_document_scope(|| {
-        crate::properties_redaction::with_interp_redaction_scope(|| f(src))
+        crate::warnings::with_warning_scope(warnings, unknown_keys, || {
+            crate::properties_redaction::with_interp_redaction_scope(|| {
+                let version = src.document_version();
+                crate::schema::with_document_version(version, || f(src))
+            })
+        })
     });
     match value_res {
         Ok(v) => {
@@ -16,21 +16,24 @@ compile_error!(
 
 #[cfg(all(feature = "deserialize", feature = "properties"))]
 pub use self::de::PropertySyntax;
-#[cfg(all(feature = "deserialize", any(feature = "garde", feature = "validator")))]
+#[cfg(feature = "deserialize")]
 pub use self::de_error::{ValidationIssue, ValidationSource};
 #[cfg(feature = "serialize")]
 pub use self::ser::{
-    Error as SerializeError, error as ser_error,
-    options::{CommentPosition, SerializerOptions},
+    DocumentWriter, Error as SerializeError,
+    budget::SerializerBudget,
+    error as ser_error,
+    field_styles::FieldStyle,
+    options::{CommentPosition, SerializerOptions, TagDirective},
 };
 #[cfg(feature = "deserialize")]
 pub use self::{
     de::{
         Budget, DuplicateKeyPolicy, Error, Error as DeserializeError, MergeKeyPolicy, Options,
-        budget, localizer, options,
+        UnknownKeyPolicy, budget, localizer, options,
     },
     de_error::{
-        CroppedRegion, MessageFormatter, RenderOptions, SnippetMode, TransformReason,
+        CroppedRegion, MessageFormatter, RenderOptions, RenderTarget, SnippetMode, TransformReason,
         UserMessageFormatter,
     },
     indentation::RequireIndent,
@@ -48,29 +51,57 @@ pub use anchors::{
     ArcAnchor, ArcRecursion, ArcRecursive, ArcWeakAnchor, RcAnchor, RcRecursion, RcRecursive,
     RcWeakAnchor,
 };
+#[cfg(feature = "robotics")]
+pub use angle::{AngleFormat, AngleStyle, Radians};
+#[cfg(feature = "deserialize")]
+pub use de::cancellation::{self, CancellationToken, Interruption};
+#[cfg(feature = "deserialize")]
+pub use de::diff;
+#[cfg(feature = "deserialize")]
+pub use de::error_codes::{self, ErrorCodeInfo};
 #[cfg(feature = "figment")]
 pub use de::figment;
 #[cfg(feature = "figment2")]
 pub use de::figment2;
+#[cfg(feature = "fluent")]
+pub use de::fluent_localizer::{self, FluentLocalizer};
+#[cfg(feature = "deserialize")]
+pub use de::lint::{self, LintConfig, LintIssue, LintLevel};
+#[cfg(feature = "lsp")]
+pub use de::lsp;
 #[cfg(feature = "miette")]
 pub use de::miette;
-#[cfg(any(feature = "garde", feature = "validator"))]
+#[cfg(feature = "deserialize")]
 pub use de::path_map;
 #[cfg(feature = "properties")]
 pub use de::properties;
 #[cfg(feature = "robotics")]
 pub use de::robotics;
+#[cfg(feature = "properties")]
+pub use de::ros_substitutions::{self, RosSubstitutions};
 #[cfg(all(feature = "deserialize", feature = "include_fs"))]
 pub use de::safe_resolver::{SafeFileReadMode, SafeFileResolver, SymlinkPolicy};
 #[cfg(feature = "deserialize")]
+pub use de::source_map::{self, from_str_with_options_and_source_map, from_str_with_source_map};
+#[cfg(feature = "deserialize")]
+pub use de::source_registry::{self, SourceInfo, SourceRegistry};
+#[cfg(feature = "deserialize")]
+pub use de::warnings::{self, Warning, WarningKind, Warnings};
+#[cfg(feature = "deserialize")]
 pub use granit_parser;
 pub use location::{Location, Locations};
 pub use long_strings::{FoldStr, FoldString, LitStr, LitString};
+#[cfg(feature = "deserialize")]
+pub use raw_yaml::{OwnedRawYaml, RawYaml};
+#[cfg(feature = "robotics")]
+pub use rotation::{Quaternion, Rotation, RotationFormat};
+pub use schema::Schema;
 pub use span::Span;
 pub use spanned::Spanned;
 #[cfg(any(feature = "serialize", feature = "deserialize"))]
 pub use wrappers::{
-    Commented, DoubleQuoted, FlowMap, FlowSeq, NullableTilde, SingleQuoted, SpaceAfter,
+    Commented, DoubleQuoted, FlowMap, FlowSeq, NullableTilde, SingleQuoted, SpaceAfter, TaggedOmap,
+    TaggedPairs, TaggedSet,
 };
 
 #[cfg(all(feature = "deserialize", feature = "include"))]
@@ -90,6 +121,8 @@ pub use self::ser::api::{
 #[cfg(feature = "deserialize")]
 mod anchor_store;
 mod anchors;
+#[cfg(feature = "robotics")]
+pub mod angle;
 #[cfg(all(
     feature = "serialize",
     feature = "deserialize",
@@ -103,6 +136,11 @@ mod de;
 mod location;
 mod long_strings;
 mod parse_scalars;
+#[cfg(feature = "deserialize")]
+mod raw_yaml;
+#[cfg(feature = "robotics")]
+pub mod rotation;
+mod schema;
 #[cfg(feature = "serialize")]
 pub mod ser;
 mod span;
@@ -112,7 +150,7 @@ mod wrappers;
 
 #[cfg(all(feature = "deserialize", feature = "include"))]
 pub(crate) use de::include_stack;
-#[cfg(any(feature = "garde", feature = "validator"))]
+#[cfg(feature = "deserialize")]
 use de::lib_validate;
 #[cfg(feature = "deserialize")]
 pub(crate) use de::{
@@ -122,7 +160,7 @@ pub(crate) use de::{
 
 #[cfg(feature = "deserialize")]
 pub use de::YamlDeserializer as Deserializer;
-#[cfg(any(feature = "garde", feature = "validator"))]
+#[cfg(feature = "deserialize")]
 pub use lib_validate::*;
 #[cfg(feature = "serialize")]
 pub use ser::YamlSerializer as Serializer;
@@ -5,6 +5,11 @@ pub use crate::span::Span;
 use granit_parser::Span as ParserSpan;
 use serde_core::de::{self, IgnoredAny, MapAccess, Visitor};
 
+/// Source id of the document given to the entry point. With the `include` feature the
+/// parser stack numbers sources from 1, otherwise every location uses 0.
+#[cfg(feature = "deserialize")]
+pub(crate) const ROOT_SOURCE_ID: u32 = if cfg!(feature = "include") { 1 } else { 0 };
+
 /// Row/column location within the source YAML document (1-indexed, character-based).
 ///
 /// This type is used for both:
@@ -292,7 +297,7 @@ pub struct Locations {
 }
 
 impl Locations {
-    #[cfg_attr(not(any(feature = "garde", feature = "validator")), allow(dead_code))]
+    #[cfg_attr(not(feature = "deserialize"), allow(dead_code))]
     pub(crate) const UNKNOWN: Locations = Locations {
         reference_location: Location::UNKNOWN,
         defined_location: Location::UNKNOWN,
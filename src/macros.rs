@@ -36,6 +36,36 @@ macro_rules! options {
     };
 }
 
+/// Construct a [`crate::path_map::PathKey`] from a list of keys and indices.
+///
+/// String segments become mapping keys, `usize` segments sequence indices; an empty
+/// list is the root path.
+///
+/// ```rust
+/// # #[cfg(feature = "deserialize")]
+/// # {
+/// use serde_saphyr::path_map::PathKey;
+///
+/// let path = serde_saphyr::path_key!["servers", 2, "port"];
+/// assert_eq!(path, PathKey::new().join_key("servers").join_index(2).join_key("port"));
+/// # }
+/// ```
+#[cfg(feature = "deserialize")]
+#[macro_export]
+macro_rules! path_key {
+    ( $( $seg:expr ),* $(,)? ) => {
+        $crate::path_map::PathKey::new() $( .join($seg) )*
+    };
+}
+
+#[cfg(not(feature = "deserialize"))]
+#[macro_export]
+macro_rules! path_key {
+    ( $( $tt:tt )* ) => {
+        compile_error!("serde-saphyr `path_key!` requires feature `deserialize`");
+    };
+}
+
 /// Implementation detail for [`options!`].
 ///
 /// This is `#[macro_export]` so that `$crate::...` can resolve it from expansions in
@@ -122,6 +152,44 @@ macro_rules! budget {
     };
 }
 
+/// Construct `Some([``crate::SerializerBudget``])` from `Default` and a list of field assignments.
+///
+/// This macro returns `Some(SerializerBudget)` so it can be embedded directly inside
+/// [`crate::ser_options!`] as the value for `SerializerOptions::budget`.
+///
+/// Example:
+///
+/// ```rust
+/// # #[cfg(feature = "serialize")]
+/// # {
+/// let options = serde_saphyr::ser_options! {
+///     budget: serde_saphyr::ser_budget! {
+///         max_depth: 16,
+///     },
+/// };
+/// # let _ = options;
+/// # }
+/// ```
+#[cfg(feature = "serialize")]
+#[macro_export]
+macro_rules! ser_budget {
+    ( $( $field:ident : $value:expr ),* $(,)? ) => {{
+        let mut b = $crate::SerializerBudget::default();
+        $(
+            b.$field = $value;
+        )*
+        Some(b)
+    }};
+}
+
+#[cfg(not(feature = "serialize"))]
+#[macro_export]
+macro_rules! ser_budget {
+    ( $( $field:ident : $value:expr ),* $(,)? ) => {
+        compile_error!("serde-saphyr `ser_budget!` requires feature `serialize`");
+    };
+}
+
 /// Construct [`crate::options::AliasLimits`] from `Default` and a list of field assignments.
 ///
 /// This macro returns `AliasLimits` directly so it can be embedded inside
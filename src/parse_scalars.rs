@@ -294,11 +294,15 @@ pub(crate) fn parse_yaml12_float<T>(
     location: Location,
     tag: SfTag,
     angle_conversions: bool,
+    unit_conversions: bool,
 ) -> Result<T, Error>
 where
     T: FromStr + crate::robotics::FromF64,
     T: num_traits::Float,
 {
+    if unit_conversions {
+        return crate::robotics::parse_yaml12_float_unit_converting(s, location, tag, true);
+    }
     if angle_conversions {
         return crate::robotics::parse_yaml12_float_angle_converting(s, location, tag);
     }
@@ -318,6 +322,7 @@ pub(crate) fn parse_yaml12_float<T>(
     location: Location,
     _tag: SfTag,
     _angle_conversions: bool,
+    _unit_conversions: bool,
 ) -> Result<T, Error>
 where
     T: FromStr,
@@ -354,8 +359,10 @@ pub(crate) fn try_parse_float_incl_overflow(
     location: Location,
     tag: SfTag,
     angle_conversions: bool,
+    unit_conversions: bool,
 ) -> Option<f64> {
-    if let Ok(v) = parse_yaml12_float::<f64>(s, location, tag, angle_conversions) {
+    if let Ok(v) = parse_yaml12_float::<f64>(s, location, tag, angle_conversions, unit_conversions)
+    {
         return Some(v);
     }
 
@@ -376,7 +383,7 @@ pub(crate) fn try_parse_float_incl_overflow(
 pub(crate) fn maybe_not_string(s: &str, style: &ScalarStyle, strict_booleans: bool) -> bool {
     let location = Location::UNKNOWN;
     style == &ScalarStyle::Plain
-        && (parse_yaml12_float::<f64>(s, location, SfTag::None, false).is_ok()
+        && (parse_yaml12_float::<f64>(s, location, SfTag::None, false, false).is_ok()
             || parse_int_signed::<i128>(s, "i128", location, false).is_ok()
             || maybe_bool(s, strict_booleans)
             || scalar_is_nullish(s, &ScalarStyle::Plain))
@@ -613,13 +620,13 @@ mod tests {
     fn parse_yaml12_floats_handle_nan_and_infinity_forms() {
         let loc = sample_location();
 
-        let nan: f64 = parse_yaml12_float(" .NaN ", loc, SfTag::None, false).unwrap();
+        let nan: f64 = parse_yaml12_float(" .NaN ", loc, SfTag::None, false, false).unwrap();
         assert!(nan.is_nan());
 
-        let inf: f64 = parse_yaml12_float("+.INF", loc, SfTag::None, false).unwrap();
+        let inf: f64 = parse_yaml12_float("+.INF", loc, SfTag::None, false, false).unwrap();
         assert!(inf.is_infinite() && inf.is_sign_positive());
 
-        let neg_inf: f64 = parse_yaml12_float("-.Inf", loc, SfTag::None, false).unwrap();
+        let neg_inf: f64 = parse_yaml12_float("-.Inf", loc, SfTag::None, false, false).unwrap();
         assert!(neg_inf.is_infinite() && neg_inf.is_sign_negative());
     }
 
@@ -633,7 +640,7 @@ mod tests {
     #[case::plus_infinity("+Infinity")]
     #[case::minus_infinity("-Infinity")]
     fn parse_yaml12_float_rejects_rust_nonfinite_spellings(#[case] input: &str) {
-        assert!(parse_yaml12_float::<f64>(input, loc(), SfTag::None, false).is_err());
+        assert!(parse_yaml12_float::<f64>(input, loc(), SfTag::None, false, false).is_err());
     }
 
     fn loc() -> Location {
@@ -649,11 +656,11 @@ mod tests {
     #[test]
     fn test_normal_values() {
         assert_eq!(
-            parse_yaml12_float::<f32>("1.5", loc(), SfTag::None, false).unwrap(),
+            parse_yaml12_float::<f32>("1.5", loc(), SfTag::None, false, false).unwrap(),
             1.5f32
         );
         assert_eq!(
-            parse_yaml12_float::<f32>("-123.456", loc(), SfTag::None, false).unwrap(),
+            parse_yaml12_float::<f32>("-123.456", loc(), SfTag::None, false, false).unwrap(),
             -123.456f32
         );
     }
@@ -661,24 +668,24 @@ mod tests {
     #[test]
     fn test_zero_values() {
         assert_eq!(
-            parse_yaml12_float::<f32>("0", loc(), SfTag::None, false).unwrap(),
+            parse_yaml12_float::<f32>("0", loc(), SfTag::None, false, false).unwrap(),
             0.0f32
         );
         assert_eq!(
-            parse_yaml12_float::<f32>("-0", loc(), SfTag::None, false).unwrap(),
+            parse_yaml12_float::<f32>("-0", loc(), SfTag::None, false, false).unwrap(),
             -0.0f32
         );
     }
 
     #[test]
     fn test_nan_and_infinity() {
-        let nan: f32 = parse_yaml12_float(".nan", loc(), SfTag::None, false).unwrap();
+        let nan: f32 = parse_yaml12_float(".nan", loc(), SfTag::None, false, false).unwrap();
         assert!(nan.is_nan());
 
-        let inf: f64 = parse_yaml12_float(".inf", loc(), SfTag::None, false).unwrap();
+        let inf: f64 = parse_yaml12_float(".inf", loc(), SfTag::None, false, false).unwrap();
         assert!(inf.is_infinite() && inf.is_sign_positive());
 
-        let ninf: f32 = parse_yaml12_float("-.Inf", loc(), SfTag::None, false).unwrap();
+        let ninf: f32 = parse_yaml12_float("-.Inf", loc(), SfTag::None, false, false).unwrap();
         assert!(ninf.is_infinite() && ninf.is_sign_negative());
     }
 
@@ -687,13 +694,13 @@ mod tests {
         // Smallest positive subnormal f32
         let smallest = f32::from_bits(1) as f64;
         let val: f32 =
-            parse_yaml12_float(&format!("{}", smallest), loc(), SfTag::None, false).unwrap();
+            parse_yaml12_float(&format!("{}", smallest), loc(), SfTag::None, false, false).unwrap();
         assert_eq!(val, f32::from_bits(1));
     }
 
     #[test]
     fn test_negative_zero_preserved() {
-        let val: f32 = parse_yaml12_float("-0.0", loc(), SfTag::None, false).unwrap();
+        let val: f32 = parse_yaml12_float("-0.0", loc(), SfTag::None, false, false).unwrap();
         assert_eq!(val.to_bits(), (-0.0f32).to_bits());
     }
 }
@@ -0,0 +1,226 @@
+//! Captured YAML source text for deferred parsing.
+//!
+//! `RawYaml` keeps the exact source slice of a node instead of parsing it, so a
+//! part of a document can be inspected first and deserialized into a concrete type
+//! later, similar to `serde_json::value::RawValue`.
+//!
+//! ```rust
+//! use serde::Deserialize;
+//! use serde_saphyr::RawYaml;
+//!
+//! #[derive(Deserialize)]
+//! struct Plugin<'a> {
+//!     kind: String,
+//!     #[serde(borrow)]
+//!     config: RawYaml<'a>,
+//! }
+//!
+//! #[derive(Debug, Deserialize, PartialEq)]
+//! struct Http {
+//!     port: u16,
+//! }
+//!
+//! let yaml = "kind: http\nconfig:\n  port: 8080\n";
+//! let plugin: Plugin = serde_saphyr::from_str(yaml).unwrap();
+//! assert_eq!(plugin.config.get(), "port: 8080");
+//! assert_eq!(plugin.config.location().line(), 3);
+//!
+//! let http: Http = plugin.config.parse().unwrap();
+//! assert_eq!(http, Http { port: 8080 });
+//! ```
+
+use std::fmt;
+use std::marker::PhantomData;
+
+use serde_core::Deserialize;
+use serde_core::de::{self, DeserializeOwned, Deserializer, Visitor};
+
+use crate::{Error, Location, Options};
+
+pub(crate) const INTERNAL_RAW_MARKER: &str = "__serde_saphyr_private_raw";
+
+/// The unparsed source text of a YAML node, borrowed from the input.
+///
+/// Only available when deserializing from a string (`from_str` and friends): reader
+/// inputs, included files and values buffered by serde (`#[serde(flatten)]`,
+/// untagged enums) fail with [`Error::RawYamlUnavailable`]. For an alias, the text
+/// of the anchored node is captured.
+///
+/// The text runs from the start of the node to its last token. An anchor or tag
+/// written before the node is not part of it, and neither are trailing comments.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RawYaml<'a> {
+    text: &'a str,
+    location: Location,
+}
+
+/// The unparsed source text of a YAML node, owned.
+///
+/// Same as [`RawYaml`] but does not borrow from the input, so it can be used in
+/// types deserialized with `DeserializeOwned` bounds. The input must still be a string.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OwnedRawYaml {
+    text: String,
+    location: Location,
+}
+
+impl<'a> RawYaml<'a> {
+    /// The captured source text.
+    #[must_use]
+    pub fn get(&self) -> &'a str {
+        self.text
+    }
+
+    /// Where the node starts in the original document.
+    #[must_use]
+    pub fn location(&self) -> Location {
+        self.location
+    }
+
+    /// Copy the text into an [`OwnedRawYaml`].
+    #[must_use]
+    pub fn into_owned(self) -> OwnedRawYaml {
+        OwnedRawYaml {
+            text: self.text.to_owned(),
+            location: self.location,
+        }
+    }
+
+    /// Parse the captured text into `T` with default options.
+    ///
+    /// Line and column numbers in errors refer to the original document.
+    pub fn parse<T: DeserializeOwned>(&self) -> Result<T, Error> {
+        self.parse_with_options(Options::default())
+    }
+
+    /// Parse the captured text into `T` with the given options.
+    pub fn parse_with_options<T: DeserializeOwned>(&self, options: Options) -> Result<T, Error> {
+        deserialize_raw(self.text, self.location, options)
+    }
+}
+
+impl OwnedRawYaml {
+    /// The captured source text.
+    #[must_use]
+    pub fn get(&self) -> &str {
+        &self.text
+    }
+
+    /// Where the node starts in the original document.
+    #[must_use]
+    pub fn location(&self) -> Location {
+        self.location
+    }
+
+    /// Borrow as a [`RawYaml`].
+    #[must_use]
+    pub fn as_raw(&self) -> RawYaml<'_> {
+        RawYaml {
+            text: &self.text,
+            location: self.location,
+        }
+    }
+
+    /// Return the captured text, dropping the location.
+    #[must_use]
+    pub fn into_string(self) -> String {
+        self.text
+    }
+
+    /// Parse the captured text into `T` with default options.
+    ///
+    /// Line and column numbers in errors refer to the original document.
+    pub fn parse<T: DeserializeOwned>(&self) -> Result<T, Error> {
+        self.parse_with_options(Options::default())
+    }
+
+    /// Parse the captured text into `T` with the given options.
+    pub fn parse_with_options<T: DeserializeOwned>(&self, options: Options) -> Result<T, Error> {
+        deserialize_raw(&self.text, self.location, options)
+    }
+}
+
+impl fmt::Display for RawYaml<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.text)
+    }
+}
+
+impl fmt::Display for OwnedRawYaml {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.text)
+    }
+}
+
+/// Parse `text` as if it still sat at `location`: the node keeps its original
+/// indentation, so nested block collections parse and reported lines and columns
+/// match the source document.
+fn deserialize_raw<T: DeserializeOwned>(
+    text: &str,
+    location: Location,
+    options: Options,
+) -> Result<T, Error> {
+    let lines = location.line().saturating_sub(1) as usize;
+    let columns = location.column().saturating_sub(1) as usize;
+    let mut padded = String::with_capacity(lines + columns + text.len());
+    padded.extend(std::iter::repeat_n('\n', lines));
+    padded.extend(std::iter::repeat_n(' ', columns));
+    padded.push_str(text);
+    crate::from_str_with_options(&padded, options)
+}
+
+impl<'de: 'a, 'a> Deserialize<'de> for RawYaml<'a> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct RawVisitor<'a>(PhantomData<&'a ()>);
+
+        impl<'de: 'a, 'a> Visitor<'de> for RawVisitor<'a> {
+            type Value = RawYaml<'a>;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a YAML node read from string input")
+            }
+
+            fn visit_newtype_struct<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                deserializer.deserialize_any(self)
+            }
+
+            fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+            where
+                A: de::MapAccess<'de>,
+            {
+                if map.next_key::<String>()?.as_deref() != Some(INTERNAL_RAW_MARKER) {
+                    return Err(de::Error::custom(
+                        "RawYaml can only be read by the serde-saphyr deserializer",
+                    ));
+                }
+                let _ = map.next_value::<de::IgnoredAny>()?;
+                let mut text = None;
+                let mut location = None;
+                while let Some(key) = map.next_key::<String>()? {
+                    match key.as_str() {
+                        "text" => text = Some(map.next_value::<&'de str>()?),
+                        "location" => location = Some(map.next_value()?),
+                        _ => {
+                            let _ = map.next_value::<de::IgnoredAny>()?;
+                        }
+                    }
+                }
+                Ok(RawYaml {
+                    text: text.ok_or_else(|| de::Error::missing_field("text"))?,
+                    location: location.ok_or_else(|| de::Error::missing_field("location"))?,
+                })
+            }
+        }
+
+        deserializer.deserialize_newtype_struct("__yaml_raw", RawVisitor(PhantomData))
+    }
+}
+
+impl<'de> Deserialize<'de> for OwnedRawYaml {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        RawYaml::deserialize(deserializer).map(RawYaml::into_owned)
+    }
+}
@@ -0,0 +1,488 @@
+//! Rotations for the robotics dialect.
+//!
+//! Pose data is usually written as roll/pitch/yaw in degrees but consumed as a
+//! quaternion. [`Quaternion`] and [`Rotation`] accept every common spelling and
+//! normalize the result:
+//!
+//! - `rpy(deg(90), 0, 0)`: roll, pitch and yaw (fixed X, Y, Z axes, as in ROS);
+//! - `axis_angle(0, 0, 1, deg(90))`: rotation about an axis;
+//! - `quat(0, 0, 0.7071, 0.7071)`: quaternion components `x, y, z, w`;
+//! - `[x, y, z, w]` and `[roll, pitch, yaw]` sequences;
+//! - `{x, y, z, w}`, `{roll, pitch, yaw}` and `{axis: [x, y, z], angle}` mappings.
+//!
+//! Function arguments are evaluated with the robotics expression evaluator, so
+//! `deg(...)`, `pi` and arithmetic always work inside them. Sequence and mapping
+//! components are read like `f64` fields: expressions, `!degrees [90, 0, 0]` and
+//! `!degrees {roll: 90}` need [`crate::Options::angle_conversions`]. A tag on a mapping
+//! converts its angle values but not the nested `axis` sequence. Angles without a unit
+//! are radians.
+//!
+//! ```rust
+//! use serde::Deserialize;
+//! use serde_saphyr::Rotation;
+//!
+//! #[derive(Deserialize)]
+//! struct Pose {
+//!     orientation: Rotation,
+//! }
+//!
+//! let pose: Pose = serde_saphyr::from_str("orientation: rpy(0, 0, deg(90))\n").unwrap();
+//! let q = pose.orientation.quaternion();
+//! assert!((q.z - std::f64::consts::FRAC_1_SQRT_2).abs() < 1e-12);
+//! assert!((q.w - std::f64::consts::FRAC_1_SQRT_2).abs() < 1e-12);
+//! ```
+
+use std::fmt;
+
+use serde_core::de::{self, Deserialize, Deserializer, MapAccess, SeqAccess, Visitor};
+use serde_core::ser::{Serialize, SerializeStruct, SerializeTuple, Serializer};
+
+use crate::Location;
+use crate::robotics::parse_yaml12_float_angle_converting;
+use crate::tags::SfTag;
+
+/// A rotation quaternion with components `x, y, z, w`.
+///
+/// Deserializing accepts every form listed in the [module documentation](self) and
+/// normalizes the result. Serializes as a `{x, y, z, w}` mapping, as ROS messages do.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Quaternion {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+    pub w: f64,
+}
+
+impl Quaternion {
+    /// The rotation that does nothing.
+    pub const IDENTITY: Quaternion = Quaternion {
+        x: 0.0,
+        y: 0.0,
+        z: 0.0,
+        w: 1.0,
+    };
+
+    /// Quaternion from raw components, not normalized.
+    #[must_use]
+    pub const fn new(x: f64, y: f64, z: f64, w: f64) -> Self {
+        Self { x, y, z, w }
+    }
+
+    /// Rotation by `roll`, `pitch` and `yaw` radians about the fixed X, Y and Z axes.
+    #[must_use]
+    pub fn from_rpy(roll: f64, pitch: f64, yaw: f64) -> Self {
+        let (sr, cr) = (roll / 2.0).sin_cos();
+        let (sp, cp) = (pitch / 2.0).sin_cos();
+        let (sy, cy) = (yaw / 2.0).sin_cos();
+        Self {
+            x: sr * cp * cy - cr * sp * sy,
+            y: cr * sp * cy + sr * cp * sy,
+            z: cr * cp * sy - sr * sp * cy,
+            w: cr * cp * cy + sr * sp * sy,
+        }
+    }
+
+    /// Rotation by `angle` radians about `axis`. `None` if the axis has zero length.
+    #[must_use]
+    pub fn from_axis_angle(axis: [f64; 3], angle: f64) -> Option<Self> {
+        let [x, y, z] = axis;
+        let len = (x * x + y * y + z * z).sqrt();
+        if !(len.is_finite() && len > 0.0) {
+            return None;
+        }
+        let (s, c) = (angle / 2.0).sin_cos();
+        Some(Self {
+            x: x / len * s,
+            y: y / len * s,
+            z: z / len * s,
+            w: c,
+        })
+    }
+
+    /// Roll, pitch and yaw in radians, the inverse of [`Quaternion::from_rpy`].
+    #[must_use]
+    pub fn to_rpy(&self) -> [f64; 3] {
+        let Self { x, y, z, w } = *self;
+        let roll = (2.0 * (w * x + y * z)).atan2(1.0 - 2.0 * (x * x + y * y));
+        let pitch = (2.0 * (w * y - z * x)).clamp(-1.0, 1.0).asin();
+        let yaw = (2.0 * (w * z + x * y)).atan2(1.0 - 2.0 * (y * y + z * z));
+        [roll, pitch, yaw]
+    }
+
+    /// Euclidean length of the four components.
+    #[must_use]
+    pub fn norm(&self) -> f64 {
+        (self.x * self.x + self.y * self.y + self.z * self.z + self.w * self.w).sqrt()
+    }
+
+    /// Scaled to unit length. `None` if the length is zero or not finite.
+    #[must_use]
+    pub fn normalized(&self) -> Option<Self> {
+        let n = self.norm();
+        (n.is_finite() && n > 0.0).then(|| Self {
+            x: self.x / n,
+            y: self.y / n,
+            z: self.z / n,
+            w: self.w / n,
+        })
+    }
+}
+
+impl Default for Quaternion {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+/// How a [`Rotation`] is written when serialized.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RotationFormat {
+    /// `{x, y, z, w}` mapping.
+    #[default]
+    Quaternion,
+    /// `[x, y, z, w]` sequence.
+    QuaternionSeq,
+    /// `[roll, pitch, yaw]` sequence in radians.
+    Rpy,
+    /// `rpy(deg(roll), deg(pitch), deg(yaw))` expression, rounded to nine decimals.
+    RpyDegrees,
+}
+
+/// A normalized rotation that remembers how it should be serialized.
+///
+/// Deserializes like [`Quaternion`]; the format is then [`RotationFormat::default`]
+/// and can be changed with [`Rotation::with_format`].
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Rotation {
+    quaternion: Quaternion,
+    format: RotationFormat,
+}
+
+impl Rotation {
+    /// Rotation from a quaternion, normalized. `None` if it has zero length.
+    #[must_use]
+    pub fn from_quaternion(quaternion: Quaternion) -> Option<Self> {
+        quaternion.normalized().map(|quaternion| Self {
+            quaternion,
+            format: RotationFormat::default(),
+        })
+    }
+
+    /// Rotation by `roll`, `pitch` and `yaw` radians about the fixed X, Y and Z axes.
+    #[must_use]
+    pub fn from_rpy(roll: f64, pitch: f64, yaw: f64) -> Self {
+        Self {
+            quaternion: Quaternion::from_rpy(roll, pitch, yaw),
+            format: RotationFormat::default(),
+        }
+    }
+
+    /// Rotation by `angle` radians about `axis`. `None` if the axis has zero length.
+    #[must_use]
+    pub fn from_axis_angle(axis: [f64; 3], angle: f64) -> Option<Self> {
+        Quaternion::from_axis_angle(axis, angle).map(|quaternion| Self {
+            quaternion,
+            format: RotationFormat::default(),
+        })
+    }
+
+    /// Use `format` when serializing.
+    #[must_use]
+    pub fn with_format(mut self, format: RotationFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// The unit quaternion.
+    #[must_use]
+    pub fn quaternion(&self) -> Quaternion {
+        self.quaternion
+    }
+
+    /// Roll, pitch and yaw in radians.
+    #[must_use]
+    pub fn to_rpy(&self) -> [f64; 3] {
+        self.quaternion.to_rpy()
+    }
+
+    /// The serialization format.
+    #[must_use]
+    pub fn format(&self) -> RotationFormat {
+        self.format
+    }
+}
+
+impl From<Rotation> for Quaternion {
+    fn from(rotation: Rotation) -> Self {
+        rotation.quaternion
+    }
+}
+
+impl Serialize for Quaternion {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut s = serializer.serialize_struct("Quaternion", 4)?;
+        s.serialize_field("x", &self.x)?;
+        s.serialize_field("y", &self.y)?;
+        s.serialize_field("z", &self.z)?;
+        s.serialize_field("w", &self.w)?;
+        s.end()
+    }
+}
+
+impl Serialize for Rotation {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        fn tuple<S: Serializer>(serializer: S, values: &[f64]) -> Result<S::Ok, S::Error> {
+            let mut t = serializer.serialize_tuple(values.len())?;
+            for v in values {
+                t.serialize_element(v)?;
+            }
+            t.end()
+        }
+
+        let q = self.quaternion;
+        match self.format {
+            RotationFormat::Quaternion => q.serialize(serializer),
+            RotationFormat::QuaternionSeq => tuple(serializer, &[q.x, q.y, q.z, q.w]),
+            RotationFormat::Rpy => tuple(serializer, &q.to_rpy()),
+            RotationFormat::RpyDegrees => {
+                let [r, p, y] = q
+                    .to_rpy()
+                    .map(|a| (a.to_degrees() * 1e9).round() / 1e9 + 0.0);
+                serializer.collect_str(&format_args!("rpy(deg({r}), deg({p}), deg({y}))"))
+            }
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Quaternion {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(RotationVisitor)
+    }
+}
+
+impl<'de> Deserialize<'de> for Rotation {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Quaternion::deserialize(deserializer).map(|quaternion| Self {
+            quaternion,
+            format: RotationFormat::default(),
+        })
+    }
+}
+
+struct RotationVisitor;
+
+impl<'de> Visitor<'de> for RotationVisitor {
+    type Value = Quaternion;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(
+            "a rotation: rpy(r, p, y), axis_angle(x, y, z, angle), quat(x, y, z, w), \
+             a [x, y, z, w] or [roll, pitch, yaw] sequence, or a mapping",
+        )
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        parse_rotation_expression(v).map_err(E::custom)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut values = Vec::with_capacity(4);
+        while let Some(v) = seq.next_element::<f64>()? {
+            if values.len() == 4 {
+                return Err(de::Error::invalid_length(5, &"3 or 4 components"));
+            }
+            values.push(v);
+        }
+        match values[..] {
+            [roll, pitch, yaw] => Ok(Quaternion::from_rpy(roll, pitch, yaw)),
+            [x, y, z, w] => normalize(Quaternion::new(x, y, z, w)).map_err(de::Error::custom),
+            _ => Err(de::Error::invalid_length(
+                values.len(),
+                &"3 or 4 components",
+            )),
+        }
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        const FIELDS: &[&str] = &["x", "y", "z", "w", "roll", "pitch", "yaw", "axis", "angle"];
+        let mut components: [Option<f64>; 7] = [None; 7];
+        let mut axis: Option<[f64; 3]> = None;
+        let mut angle = None;
+        while let Some(key) = map.next_key::<String>()? {
+            let slot = match key.as_str() {
+                "axis" => {
+                    axis = Some(map.next_value()?);
+                    continue;
+                }
+                "angle" => {
+                    angle = Some(map.next_value::<f64>()?);
+                    continue;
+                }
+                other => FIELDS[..7]
+                    .iter()
+                    .position(|field| *field == other)
+                    .ok_or_else(|| de::Error::unknown_field(other, FIELDS))?,
+            };
+            if components[slot].is_some() {
+                return Err(de::Error::duplicate_field(FIELDS[slot]));
+            }
+            components[slot] = Some(map.next_value()?);
+        }
+
+        let quaternion = match (components, axis, angle) {
+            ([Some(x), Some(y), Some(z), Some(w), None, None, None], None, None) => {
+                Quaternion::new(x, y, z, w)
+            }
+            ([None, None, None, None, roll, pitch, yaw], None, None)
+                if roll.is_some() || pitch.is_some() || yaw.is_some() =>
+            {
+                Quaternion::from_rpy(
+                    roll.unwrap_or(0.0),
+                    pitch.unwrap_or(0.0),
+                    yaw.unwrap_or(0.0),
+                )
+            }
+            ([None, None, None, None, None, None, None], Some(axis), Some(angle)) => {
+                Quaternion::from_axis_angle(axis, angle)
+                    .ok_or_else(|| de::Error::custom("rotation axis has zero length"))?
+            }
+            _ => {
+                return Err(de::Error::custom(
+                    "expected either x, y, z, w; roll, pitch, yaw; or axis and angle",
+                ));
+            }
+        };
+        normalize(quaternion).map_err(de::Error::custom)
+    }
+}
+
+fn normalize(quaternion: Quaternion) -> Result<Quaternion, &'static str> {
+    quaternion
+        .normalized()
+        .ok_or("rotation quaternion has zero length")
+}
+
+/// Parse `rpy(...)`, `axis_angle(...)` or `quat(...)`.
+fn parse_rotation_expression(s: &str) -> Result<Quaternion, String> {
+    let s = s.trim();
+    let (name, args) = s
+        .strip_suffix(')')
+        .and_then(|s| s.split_once('('))
+        .ok_or_else(|| format!("expected rpy(...), axis_angle(...) or quat(...), found `{s}`"))?;
+    let args = split_arguments(args)
+        .into_iter()
+        .map(|arg| {
+            parse_yaml12_float_angle_converting::<f64>(arg, Location::UNKNOWN, SfTag::None)
+                .map_err(|err| format!("invalid rotation argument `{}`: {err}", arg.trim()))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let name = name.trim();
+    let quaternion = match (name.to_ascii_lowercase().as_str(), &args[..]) {
+        ("rpy", &[roll, pitch, yaw]) => Quaternion::from_rpy(roll, pitch, yaw),
+        ("axis_angle", &[x, y, z, angle]) => {
+            Quaternion::from_axis_angle([x, y, z], angle).ok_or("rotation axis has zero length")?
+        }
+        ("quat" | "quaternion", &[x, y, z, w]) => Quaternion::new(x, y, z, w),
+        ("rpy", _) => return Err(format!("rpy(...) takes 3 arguments, found {}", args.len())),
+        ("axis_angle" | "quat" | "quaternion", _) => {
+            return Err(format!(
+                "{name}(...) takes 4 arguments, found {}",
+                args.len()
+            ));
+        }
+        _ => return Err(format!("unknown rotation function `{name}`")),
+    };
+    Ok(normalize(quaternion)?)
+}
+
+/// Split at commas that are not nested in parentheses.
+fn split_arguments(args: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0usize;
+    let mut start = 0;
+    for (i, c) in args.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                parts.push(&args[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&args[start..]);
+    parts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::f64::consts::{FRAC_1_SQRT_2, FRAC_PI_2};
+    use rstest::rstest;
+
+    #[track_caller]
+    fn assert_quat(actual: Quaternion, expected: Quaternion) {
+        for (a, e) in [
+            (actual.x, expected.x),
+            (actual.y, expected.y),
+            (actual.z, expected.z),
+            (actual.w, expected.w),
+        ] {
+            assert!(
+                (a - e).abs() < 1e-12,
+                "expected {expected:?}, got {actual:?}"
+            );
+        }
+    }
+
+    #[rstest]
+    #[case::rpy_roll(
+        "rpy(deg(90), 0, 0)",
+        Quaternion::new(FRAC_1_SQRT_2, 0.0, 0.0, FRAC_1_SQRT_2)
+    )]
+    #[case::rpy_yaw(
+        "RPY(0, 0, pi/2)",
+        Quaternion::new(0.0, 0.0, FRAC_1_SQRT_2, FRAC_1_SQRT_2)
+    )]
+    #[case::axis_angle(
+        "axis_angle(0, 0, 2, deg(90))",
+        Quaternion::new(0.0, 0.0, FRAC_1_SQRT_2, FRAC_1_SQRT_2)
+    )]
+    #[case::quat_normalized(
+        "quat(0, 0, 1, 1)",
+        Quaternion::new(0.0, 0.0, FRAC_1_SQRT_2, FRAC_1_SQRT_2)
+    )]
+    fn expressions(#[case] input: &str, #[case] expected: Quaternion) {
+        assert_quat(parse_rotation_expression(input).unwrap(), expected);
+    }
+
+    #[rstest]
+    #[case::wrong_arity("rpy(1, 2)", "takes 3 arguments")]
+    #[case::unknown("euler(1, 2, 3)", "unknown rotation function")]
+    #[case::bad_argument("rpy(1, 2, foo)", "invalid rotation argument `foo`")]
+    #[case::zero_axis("axis_angle(0, 0, 0, 1)", "zero length")]
+    #[case::zero_quat("quat(0, 0, 0, 0)", "zero length")]
+    #[case::not_a_call("90", "expected rpy(...)")]
+    fn expression_errors(#[case] input: &str, #[case] message: &str) {
+        let err = parse_rotation_expression(input).unwrap_err();
+        assert!(err.contains(message), "{err}");
+    }
+
+    #[test]
+    fn rpy_round_trips() {
+        let rpy = [0.3, -0.4, 2.5];
+        let back = Quaternion::from_rpy(rpy[0], rpy[1], rpy[2]).to_rpy();
+        for (a, e) in back.iter().zip(rpy) {
+            assert!((a - e).abs() < 1e-12, "{back:?}");
+        }
+        assert!((Quaternion::from_rpy(0.0, FRAC_PI_2, 0.0).to_rpy()[1] - FRAC_PI_2).abs() < 1e-7);
+    }
+
+    #[test]
+    fn arguments_split_at_top_level_commas() {
+        assert_eq!(split_arguments("deg(1), (2), 3"), ["deg(1)", " (2)", " 3"]);
+    }
+}
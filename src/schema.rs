@@ -0,0 +1,497 @@
+//! YAML schemas: the rules that resolve untagged plain scalars to null, booleans,
+//! integers, floats or strings.
+//!
+//! The same [`Schema`] is used when reading ([`crate::Options::schema`]) and when writing
+//! ([`crate::SerializerOptions::schema`]), so a document written for a schema reads back
+//! with the same types under that schema.
+
+#[cfg(feature = "deserialize")]
+use std::cell::RefCell;
+
+/// A YAML schema selecting which plain scalar spellings are null, boolean or numeric.
+///
+/// Only plain, untagged scalars are resolved by the schema; quoted scalars are strings and
+/// explicitly tagged scalars keep their tag. Spellings are matched exactly, as the schema
+/// specifications define them.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde_derived_types",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+#[cfg_attr(feature = "serde_derived_types", serde(rename_all = "snake_case"))]
+pub enum Schema {
+    /// YAML 1.2 Failsafe schema: every plain scalar is a string.
+    ///
+    /// Typed `bool`, integer and float targets still need a value, so they are parsed
+    /// with the [`Schema::Core`] spellings.
+    Failsafe,
+
+    /// YAML 1.2 JSON schema: only `null`, `true`, `false` and JSON numbers such as `-1`,
+    /// `0.5` or `1e3`. Everything else, including `~`, `True`, `0x1F` and `.inf`, is a
+    /// string.
+    Json,
+
+    /// YAML 1.2 Core schema: `~`/`null`/`Null`/`NULL` and empty, `true`/`True`/`TRUE`
+    /// and the `false` forms, decimal, `0o` octal and `0x` hex integers, and floats
+    /// including `.inf` and `.nan`.
+    Core,
+
+    /// YAML 1.1 types, as used by PyYAML: booleans also spelled `y`/`yes`/`on` and
+    /// `n`/`no`/`off` in any of three casings, `0b` binary, `0` octal and sexagesimal
+    /// (`1:30`) integers, `_` digit separators and sexagesimal floats (`1:30.5`).
+    ///
+    /// A document starting with a `%YAML 1.1` directive is read with this schema unless
+    /// [`Schema::Failsafe`] or [`Schema::Json`] is selected.
+    Yaml11,
+}
+
+/// An untagged plain scalar resolved by a [`Schema`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum Resolved {
+    Null,
+    Bool(bool),
+    Int(IntLiteral),
+    Float(f64),
+    Str,
+}
+
+/// Integer literal as sign and magnitude, so both `i128::MIN` and `u128::MAX` fit.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct IntLiteral {
+    pub(crate) negative: bool,
+    pub(crate) magnitude: u128,
+}
+
+#[cfg(feature = "deserialize")]
+impl IntLiteral {
+    pub(crate) fn to_i128(self) -> Option<i128> {
+        if self.negative {
+            0i128.checked_sub_unsigned(self.magnitude)
+        } else {
+            i128::try_from(self.magnitude).ok()
+        }
+    }
+
+    pub(crate) fn to_u128(self) -> Option<u128> {
+        (!self.negative || self.magnitude == 0).then_some(self.magnitude)
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    pub(crate) fn to_f64(self) -> f64 {
+        let value = self.magnitude as f64;
+        if self.negative { -value } else { value }
+    }
+}
+
+impl Schema {
+    /// Resolve an untagged plain scalar.
+    pub(crate) fn resolve(self, s: &str) -> Resolved {
+        if self.is_null(s) {
+            Resolved::Null
+        } else if let Some(b) = self.parse_bool(s) {
+            Resolved::Bool(b)
+        } else if let Some(int) = self.parse_int(s) {
+            Resolved::Int(int)
+        } else if let Some(float) = self.parse_float(s) {
+            Resolved::Float(float)
+        } else {
+            Resolved::Str
+        }
+    }
+
+    /// True if the untagged plain scalar `s` resolves to a string.
+    pub(crate) fn is_string(self, s: &str) -> bool {
+        self.resolve(s) == Resolved::Str
+    }
+
+    /// The schema used for typed `bool`, integer and float targets.
+    #[cfg(feature = "deserialize")]
+    pub(crate) fn for_typed(self) -> Self {
+        match self {
+            Schema::Failsafe => Schema::Core,
+            other => other,
+        }
+    }
+
+    pub(crate) fn is_null(self, s: &str) -> bool {
+        match self {
+            Schema::Failsafe => false,
+            Schema::Json => s == "null",
+            Schema::Core | Schema::Yaml11 => matches!(s, "" | "~" | "null" | "Null" | "NULL"),
+        }
+    }
+
+    pub(crate) fn parse_bool(self, s: &str) -> Option<bool> {
+        match (self, s) {
+            (Schema::Failsafe, _) => None,
+            (Schema::Json, "true") => Some(true),
+            (Schema::Json, "false") => Some(false),
+            (Schema::Json, _) => None,
+            (Schema::Core | Schema::Yaml11, "true" | "True" | "TRUE") => Some(true),
+            (Schema::Core | Schema::Yaml11, "false" | "False" | "FALSE") => Some(false),
+            (Schema::Yaml11, "y" | "Y" | "yes" | "Yes" | "YES" | "on" | "On" | "ON") => Some(true),
+            (Schema::Yaml11, "n" | "N" | "no" | "No" | "NO" | "off" | "Off" | "OFF") => Some(false),
+            (Schema::Core | Schema::Yaml11, _) => None,
+        }
+    }
+
+    pub(crate) fn parse_int(self, s: &str) -> Option<IntLiteral> {
+        match self {
+            Schema::Failsafe => None,
+            Schema::Json => {
+                let (negative, digits) = match s.strip_prefix('-') {
+                    Some(rest) => (true, rest),
+                    None => (false, s),
+                };
+                if digits.len() > 1 && digits.starts_with('0') {
+                    return None;
+                }
+                let magnitude = radix_digits(digits, 10, false)?;
+                Some(IntLiteral {
+                    negative,
+                    magnitude,
+                })
+            }
+            Schema::Core => {
+                let (negative, magnitude) = if let Some(digits) = s.strip_prefix("0o") {
+                    (false, radix_digits(digits, 8, false)?)
+                } else if let Some(digits) = s.strip_prefix("0x") {
+                    (false, radix_digits(digits, 16, false)?)
+                } else {
+                    let (negative, digits) = split_sign(s);
+                    (negative, radix_digits(digits, 10, false)?)
+                };
+                Some(IntLiteral {
+                    negative,
+                    magnitude,
+                })
+            }
+            Schema::Yaml11 => {
+                let (negative, rest) = split_sign(s);
+                let magnitude = if let Some(digits) = rest.strip_prefix("0b") {
+                    radix_digits(digits, 2, true)?
+                } else if let Some(digits) = rest.strip_prefix("0x") {
+                    radix_digits(digits, 16, true)?
+                } else if rest == "0" {
+                    0
+                } else if let Some(digits) = rest.strip_prefix('0') {
+                    radix_digits(digits, 8, true)?
+                } else if rest.contains(':') {
+                    sexagesimal(rest, false)?
+                } else if rest.starts_with(|c: char| c.is_ascii_digit()) {
+                    radix_digits(rest, 10, true)?
+                } else {
+                    return None;
+                };
+                Some(IntLiteral {
+                    negative,
+                    magnitude,
+                })
+            }
+        }
+    }
+
+    pub(crate) fn parse_float(self, s: &str) -> Option<f64> {
+        match self {
+            Schema::Failsafe => None,
+            Schema::Json => json_number(s).then(|| s.parse().ok()).flatten(),
+            Schema::Core => {
+                special_float(s).or_else(|| core_float(s).then(|| s.parse().ok()).flatten())
+            }
+            Schema::Yaml11 => special_float(s).or_else(|| yaml11_float(s)),
+        }
+    }
+
+    /// Parse a typed float target: float spellings and, as every schema allows for
+    /// floats, integer ones.
+    #[cfg(feature = "deserialize")]
+    pub(crate) fn parse_typed_float(self, s: &str) -> Option<f64> {
+        let schema = self.for_typed();
+        schema
+            .parse_float(s)
+            .or_else(|| schema.parse_int(s).map(IntLiteral::to_f64))
+    }
+}
+
+fn split_sign(s: &str) -> (bool, &str) {
+    match s.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, s.strip_prefix('+').unwrap_or(s)),
+    }
+}
+
+/// Digits in `radix`, optionally with `_` separators; at least one digit is required.
+fn radix_digits(digits: &str, radix: u32, underscores: bool) -> Option<u128> {
+    let mut value: u128 = 0;
+    let mut seen = false;
+    for c in digits.chars() {
+        if c == '_' && underscores {
+            continue;
+        }
+        let digit = c.to_digit(radix)?;
+        value = value
+            .checked_mul(u128::from(radix))?
+            .checked_add(u128::from(digit))?;
+        seen = true;
+    }
+    seen.then_some(value)
+}
+
+/// YAML 1.1 base-60 integer such as `1:30:00`: a decimal head, then `:[0-5]?[0-9]` parts.
+fn sexagesimal(s: &str, zero_head: bool) -> Option<u128> {
+    let mut parts = s.split(':');
+    let head = parts.next()?;
+    let first = head.chars().next()?;
+    if !(first.is_ascii_digit() && (zero_head || first != '0')) {
+        return None;
+    }
+    let mut value = radix_digits(head, 10, true)?;
+    let mut any = false;
+    for part in parts {
+        let digit = match part.as_bytes() {
+            [d] if d.is_ascii_digit() => d - b'0',
+            [t @ b'0'..=b'5', d] if d.is_ascii_digit() => (t - b'0') * 10 + (d - b'0'),
+            _ => return None,
+        };
+        value = value.checked_mul(60)?.checked_add(u128::from(digit))?;
+        any = true;
+    }
+    any.then_some(value)
+}
+
+/// `.inf`, `-.Inf`, `+.INF`, `.nan`, `.NaN` and `.NAN`, shared by Core and YAML 1.1.
+fn special_float(s: &str) -> Option<f64> {
+    let (negative, rest) = split_sign(s);
+    match rest {
+        ".inf" | ".Inf" | ".INF" if negative => Some(f64::NEG_INFINITY),
+        ".inf" | ".Inf" | ".INF" => Some(f64::INFINITY),
+        ".nan" | ".NaN" | ".NAN" if rest.len() == s.len() => Some(f64::NAN),
+        _ => None,
+    }
+}
+
+fn count_digits(s: &str) -> usize {
+    s.bytes().take_while(u8::is_ascii_digit).count()
+}
+
+/// An optional exponent: `[eE]`, a sign (optional unless `sign_required`) and digits.
+fn exponent(rest: &str, sign_required: bool) -> bool {
+    if rest.is_empty() {
+        return true;
+    }
+    let Some(exp) = rest.strip_prefix(['e', 'E']) else {
+        return false;
+    };
+    let digits = match exp.strip_prefix(['+', '-']) {
+        Some(digits) => digits,
+        None if sign_required => return false,
+        None => exp,
+    };
+    !digits.is_empty() && count_digits(digits) == digits.len()
+}
+
+/// `-?(0|[1-9][0-9]*)(\.[0-9]*)?([eE][-+]?[0-9]+)?`
+fn json_number(s: &str) -> bool {
+    let rest = s.strip_prefix('-').unwrap_or(s);
+    let int_len = count_digits(rest);
+    if int_len == 0 || (int_len > 1 && rest.starts_with('0')) {
+        return false;
+    }
+    let rest = &rest[int_len..];
+    let rest = match rest.strip_prefix('.') {
+        Some(frac) => &frac[count_digits(frac)..],
+        None => rest,
+    };
+    exponent(rest, false)
+}
+
+/// `[-+]?(\.[0-9]+|[0-9]+(\.[0-9]*)?)([eE][-+]?[0-9]+)?`
+fn core_float(s: &str) -> bool {
+    let rest = s.strip_prefix(['+', '-']).unwrap_or(s);
+    let rest = if let Some(frac) = rest.strip_prefix('.') {
+        let len = count_digits(frac);
+        if len == 0 {
+            return false;
+        }
+        &frac[len..]
+    } else {
+        let len = count_digits(rest);
+        if len == 0 {
+            return false;
+        }
+        match rest[len..].strip_prefix('.') {
+            Some(frac) => &frac[count_digits(frac)..],
+            None => &rest[len..],
+        }
+    };
+    exponent(rest, false)
+}
+
+/// The PyYAML float forms: `[-+]?[0-9][0-9_]*\.[0-9_]*([eE][-+][0-9]+)?`,
+/// `\.[0-9][0-9_]*([eE][-+][0-9]+)?` and the sexagesimal `[-+]?[0-9][0-9_]*(:[0-5]?[0-9])+\.[0-9_]*`.
+fn yaml11_float(s: &str) -> Option<f64> {
+    let (negative, rest) = split_sign(s);
+    let (whole, frac) = rest.split_once('.')?;
+    let is_frac_digit = |b: u8| b.is_ascii_digit() || b == b'_';
+
+    if whole.contains(':') {
+        if !frac.bytes().all(is_frac_digit) {
+            return None;
+        }
+        let whole = sexagesimal(whole, true)?;
+        let frac: String = frac.chars().filter(|&c| c != '_').collect();
+        #[allow(clippy::cast_precision_loss)]
+        let value = whole as f64 + format!("0.{frac}0").parse::<f64>().ok()?;
+        return Some(if negative { -value } else { value });
+    }
+
+    let head_ok = if whole.is_empty() {
+        rest.len() == s.len() && frac.starts_with(|c: char| c.is_ascii_digit())
+    } else {
+        whole.starts_with(|c: char| c.is_ascii_digit()) && whole.bytes().all(is_frac_digit)
+    };
+    let frac_len = frac.bytes().take_while(|&b| is_frac_digit(b)).count();
+    if !head_ok || !exponent(&frac[frac_len..], true) {
+        return None;
+    }
+    let cleaned: String = s.chars().filter(|&c| c != '_').collect();
+    cleaned.parse().ok()
+}
+
+#[cfg(feature = "deserialize")]
+thread_local! {
+    static DOCUMENT_VERSIONS: RefCell<Vec<Option<(u32, u32)>>> = const { RefCell::new(Vec::new()) };
+}
+
+#[cfg(feature = "deserialize")]
+struct DocumentVersionGuard;
+
+#[cfg(feature = "deserialize")]
+impl Drop for DocumentVersionGuard {
+    fn drop(&mut self) {
+        DOCUMENT_VERSIONS.with(|cell| {
+            let _ = cell.borrow_mut().pop();
+        });
+    }
+}
+
+/// Run one document's deserialization with the version of its `%YAML` directive, if any.
+#[cfg(feature = "deserialize")]
+pub(crate) fn with_document_version<T>(version: Option<(u32, u32)>, f: impl FnOnce() -> T) -> T {
+    DOCUMENT_VERSIONS.with(|cell| cell.borrow_mut().push(version));
+    let _guard = DocumentVersionGuard;
+    f()
+}
+
+/// The schema for the current document: `configured`, or [`Schema::Yaml11`] when the
+/// document declares `%YAML 1.1` and either the Core schema or no schema was chosen.
+#[cfg(feature = "deserialize")]
+pub(crate) fn document_schema(configured: Option<Schema>) -> Option<Schema> {
+    if !matches!(configured, None | Some(Schema::Core)) {
+        return configured;
+    }
+    let yaml_11 = DOCUMENT_VERSIONS.with(|cell| {
+        cell.borrow()
+            .last()
+            .is_some_and(|version| *version == Some((1, 1)))
+    });
+    if yaml_11 {
+        Some(Schema::Yaml11)
+    } else {
+        configured
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(Schema::Failsafe, "true", Resolved::Str)]
+    #[case(Schema::Failsafe, "~", Resolved::Str)]
+    #[case(Schema::Json, "null", Resolved::Null)]
+    #[case(Schema::Json, "Null", Resolved::Str)]
+    #[case(Schema::Json, "~", Resolved::Str)]
+    #[case(Schema::Json, "True", Resolved::Str)]
+    #[case(Schema::Json, "-0", Resolved::Int(IntLiteral { negative: true, magnitude: 0 }))]
+    #[case(Schema::Json, "012", Resolved::Str)]
+    #[case(Schema::Json, "+1", Resolved::Str)]
+    #[case(Schema::Json, "1.5e3", Resolved::Float(1500.0))]
+    #[case(Schema::Json, ".5", Resolved::Str)]
+    #[case(Schema::Json, ".inf", Resolved::Str)]
+    #[case(Schema::Core, "", Resolved::Null)]
+    #[case(Schema::Core, "NULL", Resolved::Null)]
+    #[case(Schema::Core, "nUll", Resolved::Str)]
+    #[case(Schema::Core, "TRUE", Resolved::Bool(true))]
+    #[case(Schema::Core, "yes", Resolved::Str)]
+    #[case(Schema::Core, "012", Resolved::Int(IntLiteral { negative: false, magnitude: 12 }))]
+    #[case(Schema::Core, "0o17", Resolved::Int(IntLiteral { negative: false, magnitude: 15 }))]
+    #[case(Schema::Core, "0x1F", Resolved::Int(IntLiteral { negative: false, magnitude: 31 }))]
+    #[case(Schema::Core, "0b11", Resolved::Str)]
+    #[case(Schema::Core, "1_000", Resolved::Str)]
+    #[case(Schema::Core, "-.5", Resolved::Float(-0.5))]
+    #[case(Schema::Core, "1.", Resolved::Float(1.0))]
+    #[case(Schema::Core, "-.Inf", Resolved::Float(f64::NEG_INFINITY))]
+    #[case(Schema::Core, "1:30", Resolved::Str)]
+    #[case(Schema::Yaml11, "Y", Resolved::Bool(true))]
+    #[case(Schema::Yaml11, "off", Resolved::Bool(false))]
+    #[case(Schema::Yaml11, "oFF", Resolved::Str)]
+    #[case(Schema::Yaml11, "0b1010", Resolved::Int(IntLiteral { negative: false, magnitude: 10 }))]
+    #[case(Schema::Yaml11, "-0x_1F", Resolved::Int(IntLiteral { negative: true, magnitude: 31 }))]
+    #[case(Schema::Yaml11, "0755", Resolved::Int(IntLiteral { negative: false, magnitude: 493 }))]
+    #[case(Schema::Yaml11, "09", Resolved::Str)]
+    #[case(Schema::Yaml11, "1_000", Resolved::Int(IntLiteral { negative: false, magnitude: 1000 }))]
+    #[case(Schema::Yaml11, "190:20:30", Resolved::Int(IntLiteral { negative: false, magnitude: 685_230 }))]
+    #[case(Schema::Yaml11, "1:60", Resolved::Str)]
+    #[case(Schema::Yaml11, "1:30.5", Resolved::Float(90.5))]
+    #[case(Schema::Yaml11, "1_000.5", Resolved::Float(1000.5))]
+    #[case(Schema::Yaml11, "1.5e+3", Resolved::Float(1500.0))]
+    #[case(Schema::Yaml11, "1.5e3", Resolved::Str)]
+    #[case(Schema::Yaml11, "1e3", Resolved::Str)]
+    #[case(Schema::Yaml11, ".5", Resolved::Float(0.5))]
+    fn resolves_plain_scalars(
+        #[case] schema: Schema,
+        #[case] input: &str,
+        #[case] expected: Resolved,
+    ) {
+        assert_eq!(schema.resolve(input), expected, "{schema:?} {input:?}");
+    }
+
+    #[test]
+    fn nan_resolves_in_core_and_yaml11_only() {
+        for schema in [Schema::Core, Schema::Yaml11] {
+            assert!(matches!(schema.resolve(".NaN"), Resolved::Float(v) if v.is_nan()));
+            assert_eq!(schema.resolve("-.nan"), Resolved::Str);
+        }
+        assert_eq!(Schema::Json.resolve(".nan"), Resolved::Str);
+    }
+
+    #[test]
+    fn int_literal_bounds() {
+        let min = Schema::Core.parse_int("-170141183460469231731687303715884105728");
+        assert_eq!(min.and_then(IntLiteral::to_i128), Some(i128::MIN));
+        let max = Schema::Core.parse_int("340282366920938463463374607431768211455");
+        assert_eq!(max.and_then(IntLiteral::to_u128), Some(u128::MAX));
+        assert_eq!(max.and_then(IntLiteral::to_i128), None);
+        assert_eq!(
+            Schema::Core.parse_int("-1").and_then(IntLiteral::to_u128),
+            None
+        );
+    }
+
+    #[cfg(feature = "deserialize")]
+    #[test]
+    fn yaml_11_directive_overrides_core_and_default_but_not_json() {
+        with_document_version(Some((1, 1)), || {
+            assert_eq!(document_schema(None), Some(Schema::Yaml11));
+            assert_eq!(document_schema(Some(Schema::Core)), Some(Schema::Yaml11));
+            assert_eq!(document_schema(Some(Schema::Json)), Some(Schema::Json));
+        });
+        with_document_version(Some((1, 2)), || {
+            assert_eq!(document_schema(Some(Schema::Core)), Some(Schema::Core));
+            assert_eq!(document_schema(None), None);
+        });
+    }
+}
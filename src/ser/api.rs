@@ -81,7 +81,8 @@ pub fn to_fmt_writer_with_options<W: std::fmt::Write, T: serde_core::Serialize>(
     options: SerializerOptions,
 ) -> std::result::Result<(), crate::ser::Error> {
     let mut ser = crate::ser::YamlSerializer::with_options(output, options)?;
-    value.serialize(&mut ser)
+    value.serialize(&mut ser)?;
+    ser.end_document()
 }
 
 /// Serialize a value as YAML into any [`std::io::Write`] target, with options.
@@ -92,42 +93,18 @@ pub fn to_io_writer_with_options<W: std::io::Write, T: serde_core::Serialize>(
     value: &T,
     options: SerializerOptions,
 ) -> std::result::Result<(), crate::ser::Error> {
-    struct Adapter<'a, W: std::io::Write> {
-        output: &'a mut W,
-        last_err: Option<std::io::Error>,
-    }
-    impl<W: std::io::Write> std::fmt::Write for Adapter<'_, W> {
-        fn write_str(&mut self, s: &str) -> std::fmt::Result {
-            if let Err(e) = self.output.write_all(s.as_bytes()) {
-                self.last_err = Some(e);
-                return Err(std::fmt::Error);
-            }
-            Ok(())
-        }
-        fn write_char(&mut self, c: char) -> std::fmt::Result {
-            let mut buf = [0u8; 4];
-            let s = c.encode_utf8(&mut buf);
-            self.write_str(s)
-        }
-    }
-    let mut adapter = Adapter {
-        output,
-        last_err: None,
-    };
+    let mut adapter = crate::ser::IoAdapter::new(output);
     let mut ser = crate::ser::YamlSerializer::with_options(&mut adapter, options)?;
-    match value.serialize(&mut ser) {
-        Ok(()) => Ok(()),
-        Err(e) => {
-            if let Some(io_error) = adapter.last_err.take() {
-                return Err(crate::ser::Error::from(io_error));
-            }
-            Err(e)
-        }
-    }
+    value
+        .serialize(&mut ser)
+        .and_then(|()| ser.end_document())
+        .map_err(|e| adapter.restore_io_error(e))
 }
 
 /// Serialize multiple documents into a YAML string.
 ///
+/// Use [`crate::DocumentWriter`] to stream documents one at a time instead.
+///
 /// Serializes each value in the provided slice as an individual YAML document.
 /// Documents are separated by a standard YAML document start marker ("---\n").
 /// No marker is emitted before the first document.
@@ -181,20 +158,9 @@ pub fn to_string_multiple_with_options<T: serde_core::Serialize>(
     values: &[T],
     options: SerializerOptions,
 ) -> std::result::Result<String, crate::ser::Error> {
-    options.consistent()?;
-    let mut out = String::new();
-    let mut first = true;
-    let yaml_12 = options.yaml_12;
+    let mut writer = crate::ser::DocumentWriter::new(String::new(), options)?;
     for v in values {
-        if !first {
-            if yaml_12 {
-                out.push_str("...\n");
-            } else {
-                out.push_str("---\n");
-            }
-        }
-        first = false;
-        to_fmt_writer_with_options(&mut out, v, options.clone())?;
+        writer.write(v)?;
     }
-    Ok(out)
+    writer.finish()
 }
@@ -0,0 +1,148 @@
+//! Output limits for YAML serialization.
+//!
+//! Services that echo user-controlled data back as YAML can be driven into memory
+//! exhaustion by huge or deeply nested values, and a self-referencing `Rc<RefCell<_>>`
+//! graph recurses until the stack overflows. A [`SerializerBudget`] caps the emitted
+//! size, nesting depth and node count and reports a typed
+//! [`Error::Budget`](crate::ser::Error::Budget) when any of them is breached.
+//!
+//! Example: refuse to emit more than 100 nodes.
+//!
+//! ```rust
+//! use serde_saphyr::ser::budget::BudgetBreach;
+//! use serde_saphyr::ser::Error;
+//!
+//! let options = serde_saphyr::ser_options! {
+//!     budget: serde_saphyr::ser_budget! {
+//!         max_nodes: 100,
+//!     },
+//! };
+//!
+//! let values: Vec<u32> = (0..1_000).collect();
+//! let err = serde_saphyr::to_string_with_options(&values, options).unwrap_err();
+//! assert!(matches!(err, Error::Budget { breach: BudgetBreach::Nodes { .. } }));
+//! ```
+
+use std::fmt;
+
+/// Limits enforced while serializing a single YAML document.
+///
+/// The defaults mirror the deserializer's [`Budget`](crate::Budget) so that anything
+/// emitted within them can also be read back with default options. Construct it via the
+/// [`ser_budget!`](crate::ser_budget!) macro to stay compatible with future fields.
+#[non_exhaustive]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SerializerBudget {
+    /// Maximum number of bytes written to the output.
+    ///
+    /// The limit is checked before each node is emitted, taking the length of scalar
+    /// text into account, so the output may exceed it by at most the indentation and
+    /// indicators of a single node.
+    ///
+    /// Default: 268,435,456 (256 MiB)
+    pub max_output_bytes: usize,
+    /// Maximum nesting depth of sequences, mappings and enum variant wrappers.
+    ///
+    /// This also stops runaway recursion through cyclic `Rc`/`Arc` graphs that are not
+    /// wrapped into anchor types.
+    ///
+    /// Default: 64
+    pub max_depth: usize,
+    /// Maximum number of nodes (scalars, keys, aliases and collections).
+    ///
+    /// Default: 250,000
+    pub max_nodes: usize,
+}
+
+impl Default for SerializerBudget {
+    fn default() -> Self {
+        Self {
+            max_output_bytes: 256 * 1024 * 1024,
+            max_depth: 64,
+            max_nodes: 250_000,
+        }
+    }
+}
+
+/// The specific serializer limit that was exceeded.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BudgetBreach {
+    /// Emitting the next node would exceed [`SerializerBudget::max_output_bytes`].
+    OutputBytes {
+        /// Bytes the output would have reached.
+        bytes: usize,
+    },
+    /// Nesting exceeded [`SerializerBudget::max_depth`].
+    Depth {
+        /// Depth reached when the breach occurred.
+        depth: usize,
+    },
+    /// The number of emitted nodes exceeded [`SerializerBudget::max_nodes`].
+    Nodes {
+        /// Nodes counted when the breach occurred.
+        nodes: usize,
+    },
+}
+
+impl fmt::Display for BudgetBreach {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BudgetBreach::OutputBytes { bytes } => {
+                write!(f, "output size limit exceeded ({bytes} bytes)")
+            }
+            BudgetBreach::Depth { depth } => write!(f, "nesting depth limit exceeded ({depth})"),
+            BudgetBreach::Nodes { nodes } => write!(f, "node count limit exceeded ({nodes})"),
+        }
+    }
+}
+
+/// Running totals for one serializer, checked against an optional budget.
+pub(crate) struct BudgetTracker {
+    budget: Option<SerializerBudget>,
+    nodes: usize,
+    depth: usize,
+}
+
+impl BudgetTracker {
+    pub(crate) fn new(budget: Option<SerializerBudget>) -> Self {
+        Self {
+            budget,
+            nodes: 0,
+            depth: 0,
+        }
+    }
+
+    /// Account for one node whose text will add `len` bytes to `written` bytes of output.
+    pub(crate) fn node(&mut self, written: usize, len: usize) -> Result<(), BudgetBreach> {
+        let Some(budget) = &self.budget else {
+            return Ok(());
+        };
+        self.nodes = self.nodes.saturating_add(1);
+        if self.nodes > budget.max_nodes {
+            return Err(BudgetBreach::Nodes { nodes: self.nodes });
+        }
+        let bytes = written.saturating_add(len);
+        if bytes > budget.max_output_bytes {
+            return Err(BudgetBreach::OutputBytes { bytes });
+        }
+        Ok(())
+    }
+
+    /// Account for a collection node and enter one nesting level.
+    pub(crate) fn enter(&mut self, written: usize) -> Result<(), BudgetBreach> {
+        self.node(written, 0)?;
+        self.depth = self.depth.saturating_add(1);
+        if let Some(budget) = &self.budget
+            && self.depth > budget.max_depth
+        {
+            return Err(BudgetBreach::Depth { depth: self.depth });
+        }
+        Ok(())
+    }
+
+    /// Leave a nesting level entered with [`BudgetTracker::enter`].
+    pub(crate) fn leave(&mut self) {
+        self.depth = self.depth.saturating_sub(1);
+    }
+}
@@ -0,0 +1,232 @@
+//! Streaming writer for multi-document YAML output.
+//!
+//! [`crate::to_string_multiple`] needs every document up front. A [`DocumentWriter`]
+//! instead accepts one value at a time and writes it straight to the destination, so
+//! log-style streams of any length can be produced in constant memory.
+//!
+//! ```rust
+//! use serde::Serialize;
+//! use serde_saphyr::{DocumentWriter, SerializerOptions};
+//!
+//! #[derive(Serialize)]
+//! struct Event<'a> { level: &'a str, msg: &'a str }
+//!
+//! let mut writer = DocumentWriter::new(String::new(), SerializerOptions::default())?
+//!     .with_document_end_markers(true);
+//! writer.write(&Event { level: "info", msg: "started" })?;
+//! writer.write(&Event { level: "warn", msg: "disk almost full" })?;
+//! let yaml = writer.finish()?;
+//!
+//! assert_eq!(
+//!     yaml,
+//!     "level: info\nmsg: started\n...\n---\nlevel: warn\nmsg: disk almost full\n...\n"
+//! );
+//! # Ok::<(), serde_saphyr::ser::Error>(())
+//! ```
+
+use std::fmt::{self, Write};
+use std::io;
+
+use serde_core::Serialize;
+
+use super::options::{SerializerOptions, TagDirective};
+use super::{Error, Result, YamlSerializer};
+
+/// Adapter that lets the serializer write into a [`std::io::Write`] destination.
+///
+/// `fmt::Write` cannot carry an I/O error, so the adapter keeps the last one and the
+/// caller reports it in place of the generic formatting error.
+pub struct IoAdapter<W: io::Write> {
+    output: W,
+    last_err: Option<io::Error>,
+}
+
+impl<W: io::Write> IoAdapter<W> {
+    pub(crate) fn new(output: W) -> Self {
+        Self {
+            output,
+            last_err: None,
+        }
+    }
+
+    /// Replace a formatting error with the I/O error that caused it, if any.
+    pub(crate) fn restore_io_error(&mut self, error: Error) -> Error {
+        match self.last_err.take() {
+            Some(io_error) => Error::from(io_error),
+            None => error,
+        }
+    }
+
+    /// Return the wrapped `io::Write` destination.
+    pub fn into_inner(self) -> W {
+        self.output
+    }
+}
+
+impl<W: io::Write> Write for IoAdapter<W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        if let Err(e) = self.output.write_all(s.as_bytes()) {
+            self.last_err = Some(e);
+            return Err(fmt::Error);
+        }
+        Ok(())
+    }
+
+    fn write_char(&mut self, c: char) -> fmt::Result {
+        let mut buf = [0u8; 4];
+        let s = c.encode_utf8(&mut buf);
+        self.write_str(s)
+    }
+}
+
+/// Writes a stream of YAML documents, one [`Serialize`] value at a time.
+///
+/// Documents after the first are separated by `---`. With [`SerializerOptions::yaml_12`]
+/// or registered `%TAG` directives, every document carries its own directives and an
+/// explicit start marker, and an end marker (`...`) precedes the directives of the next
+/// document as required by the YAML grammar. All documents share the same
+/// [`SerializerOptions`]; budgets apply per document. A
+/// [`SerializerOptions::header_comment`] is written once at the top of the stream, a
+/// [`SerializerOptions::footer_comment`] after every document.
+pub struct DocumentWriter<W: Write> {
+    out: W,
+    options: SerializerOptions,
+    explicit_start: bool,
+    end_markers: bool,
+    shared_anchor_numbering: bool,
+    next_anchor_id: u32,
+    documents: usize,
+    restore_error: fn(&mut W, Error) -> Error,
+}
+
+impl<W: Write> DocumentWriter<W> {
+    /// Create a writer that emits documents into a [`std::fmt::Write`] destination.
+    /// Returns an error if `options` are inconsistent.
+    pub fn new(out: W, options: SerializerOptions) -> Result<Self> {
+        options.consistent()?;
+        Ok(Self {
+            out,
+            options,
+            explicit_start: false,
+            end_markers: false,
+            shared_anchor_numbering: false,
+            next_anchor_id: 1,
+            documents: 0,
+            restore_error: |_, error| error,
+        })
+    }
+
+    /// Emit `---` before the first document as well. Off by default.
+    #[must_use]
+    pub fn with_explicit_start(mut self, explicit_start: bool) -> Self {
+        self.explicit_start = explicit_start;
+        self
+    }
+
+    /// Terminate every document with an explicit `...` end marker. Off by default.
+    ///
+    /// End markers let a consumer reading the stream line by line know that a document
+    /// is complete without waiting for the next one.
+    #[must_use]
+    pub fn with_document_end_markers(mut self, end_markers: bool) -> Self {
+        self.end_markers = end_markers;
+        self
+    }
+
+    /// Emit a `%TAG handle prefix` directive at the start of every document.
+    ///
+    /// This is a shorthand for adding an entry to [`SerializerOptions::tag_directives`],
+    /// so tags written by the serializer use the handle as well. `handle` must be `!`,
+    /// `!!` or a named handle such as `!k8s!` not registered yet; `prefix` must be
+    /// non-empty and contain no whitespace.
+    pub fn with_tag_directive(mut self, handle: &str, prefix: &str) -> Result<Self> {
+        self.options
+            .tag_directives
+            .push(TagDirective::new(handle, prefix));
+        self.options.consistent()?;
+        Ok(self)
+    }
+
+    /// Continue anchor numbering across documents instead of restarting at `a1`.
+    ///
+    /// Aliases never cross document boundaries, but unique names make it easier to grep
+    /// a long stream or to concatenate its documents later. Off by default.
+    #[must_use]
+    pub fn with_shared_anchor_numbering(mut self, shared: bool) -> Self {
+        self.shared_anchor_numbering = shared;
+        self
+    }
+
+    /// Serialize `value` as the next document of the stream.
+    pub fn write<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        let result = self.write_document(value);
+        result.map_err(|error| (self.restore_error)(&mut self.out, error))
+    }
+
+    fn write_document<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        let has_directives = self.options.yaml_12 || !self.options.tag_directives.is_empty();
+        if self.documents > 0 && has_directives && !self.end_markers {
+            // Directives are only allowed after an explicit end of the previous document.
+            self.out.write_str("...\n")?;
+        }
+        if self.documents == 0
+            && let Some(header) = self.options.header_comment.as_deref()
+        {
+            YamlSerializer::<W>::write_comment_lines(&mut self.out, header)?;
+        }
+        if has_directives {
+            YamlSerializer::write_directives(&mut self.out, &self.options)?;
+        } else if self.documents > 0 || self.explicit_start {
+            self.out.write_str("---\n")?;
+        }
+
+        let mut ser = YamlSerializer::from_options_unchecked(&mut self.out, &self.options);
+        ser.mark_document_started();
+        if self.shared_anchor_numbering {
+            ser.continue_anchor_numbering(self.next_anchor_id);
+        }
+        value.serialize(&mut ser)?;
+        ser.end_document()?;
+        self.next_anchor_id = ser.next_anchor_id();
+
+        if self.end_markers {
+            self.out.write_str("...\n")?;
+        }
+        self.documents += 1;
+        Ok(())
+    }
+
+    /// Number of documents written so far.
+    pub fn documents_written(&self) -> usize {
+        self.documents
+    }
+
+    /// Borrow the destination.
+    pub fn get_ref(&self) -> &W {
+        &self.out
+    }
+
+    /// Finish the stream and return the destination.
+    pub fn finish(self) -> Result<W> {
+        Ok(self.out)
+    }
+}
+
+impl<W: io::Write> DocumentWriter<IoAdapter<W>> {
+    /// Create a writer that emits documents into a [`std::io::Write`] destination.
+    ///
+    /// Wrap unbuffered destinations such as files or sockets into a
+    /// [`std::io::BufWriter`]; the serializer issues many small writes.
+    pub fn from_io_writer(out: W, options: SerializerOptions) -> Result<Self> {
+        let mut writer = DocumentWriter::new(IoAdapter::new(out), options)?;
+        writer.restore_error = |adapter, error| adapter.restore_io_error(error);
+        Ok(writer)
+    }
+
+    /// Flush the destination and return it.
+    pub fn into_io_writer(self) -> Result<W> {
+        let mut output = self.out.into_inner();
+        output.flush()?;
+        Ok(output)
+    }
+}
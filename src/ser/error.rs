@@ -1,5 +1,7 @@
 use std::{fmt, io};
 
+use super::budget::BudgetBreach;
+
 /// Error type used by the YAML serializer.
 ///
 /// This type is re-exported as `serde_saphyr::SerializeError` and
@@ -16,6 +18,8 @@ use std::{fmt, io};
 ///   `fmt::Write` target.
 /// - `IO` wraps a `std::io::Error` produced when writing to an `io::Write`
 ///   target.
+/// - `Budget` reports that a [`crate::ser::budget::SerializerBudget`] limit was
+///   exceeded.
 /// - `SingleQuotedRequiresEscaping` reports a `SingleQuoted` wrapper value
 ///   that needs YAML escape sequences and therefore cannot be emitted in
 ///   single-quoted style.
@@ -37,6 +41,8 @@ pub enum Error {
     /// A [`crate::SingleQuoted`] value contains a character that cannot be represented safely in
     /// YAML single-quoted style.
     SingleQuotedRequiresEscaping { ch: char },
+    /// A limit of the configured [`crate::ser::budget::SerializerBudget`] was exceeded.
+    Budget { breach: BudgetBreach },
 }
 
 impl serde_core::ser::Error for Error {
@@ -99,6 +105,7 @@ impl fmt::Display for Error {
             Error::IO { error } => write!(f, "I/O error: {error}"),
             Error::Unexpected { msg } => write!(f, "unexpected internal error: {msg}"),
             Error::InvalidOptions(msg) => write!(f, "invalid serialization options: {msg}"),
+            Error::Budget { breach } => write!(f, "serializer budget breached: {breach}"),
             Error::SingleQuotedRequiresEscaping { ch } => {
                 // Debug formatting keeps rejected control characters escaped in the error message.
                 write!(
@@ -118,7 +125,8 @@ impl std::error::Error for Error {
             Error::Message { .. }
             | Error::Unexpected { .. }
             | Error::InvalidOptions(_)
-            | Error::SingleQuotedRequiresEscaping { .. } => None,
+            | Error::SingleQuotedRequiresEscaping { .. }
+            | Error::Budget { .. } => None,
         }
     }
 }
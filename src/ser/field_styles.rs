@@ -0,0 +1,126 @@
+//! Path-keyed style annotations.
+//!
+//! [`SerializerOptions::field_styles`](crate::SerializerOptions::field_styles) maps key
+//! paths to a [`FieldStyle`], so the output of types that cannot be wrapped into
+//! [`crate::FlowSeq`], [`crate::LitString`] and friends can still be styled.
+//!
+//! A path is a dot-separated list of segments. Mapping keys and struct fields contribute
+//! their name, sequence elements their index. Enum variant names are not part of the path.
+//! In a pattern, `*` matches exactly one segment and `**` any number of segments,
+//! including none.
+//!
+//! ```rust
+//! use serde::Serialize;
+//! use serde_saphyr::ser::field_styles::FieldStyle;
+//!
+//! #[derive(Serialize)]
+//! struct Spec { ports: Vec<u16>, description: String }
+//!
+//! let options = serde_saphyr::ser_options! {
+//!     field_styles: vec![
+//!         ("ports".to_string(), FieldStyle::Flow),
+//!         ("**.description".to_string(), FieldStyle::DoubleQuoted),
+//!     ],
+//! };
+//! let spec = Spec { ports: vec![80, 443], description: "web".into() };
+//! let yaml = serde_saphyr::to_string_with_options(&spec, options).unwrap();
+//! assert_eq!(yaml, "ports: [80, 443]\ndescription: \"web\"\n");
+//! ```
+
+use crate::ser::Error;
+
+/// Presentation style applied to the value found at a key path.
+///
+/// A style only affects values it fits: [`FieldStyle::Flow`] applies to sequences and
+/// mappings, the other styles to strings. An explicit wrapper type on the value takes
+/// precedence over the path style.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum FieldStyle {
+    /// Emit a sequence or mapping in flow style (`[a, b]`, `{k: v}`).
+    Flow,
+    /// Emit a string as a literal block scalar (`|`).
+    Literal,
+    /// Emit a string as a folded block scalar (`>`).
+    Folded,
+    /// Emit a string in double quotes.
+    DoubleQuoted,
+    /// Emit a string in single quotes, or double quotes if it needs escaping.
+    SingleQuoted,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Segment {
+    Name(String),
+    /// `*`
+    One,
+    /// `**`
+    Any,
+}
+
+/// Compiled patterns, tried in the order they were configured.
+#[derive(Default)]
+pub(crate) struct FieldStyleMap {
+    patterns: Vec<(Vec<Segment>, FieldStyle)>,
+}
+
+impl FieldStyleMap {
+    pub(crate) fn new(styles: &[(String, FieldStyle)]) -> Self {
+        let patterns = styles
+            .iter()
+            .map(|(pattern, style)| {
+                let segments = pattern
+                    .split('.')
+                    .map(|segment| match segment {
+                        "*" => Segment::One,
+                        "**" => Segment::Any,
+                        name => Segment::Name(name.to_string()),
+                    })
+                    .collect();
+                (segments, *style)
+            })
+            .collect();
+        Self { patterns }
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.patterns.is_empty()
+    }
+
+    /// Style of the first pattern matching `path`.
+    pub(crate) fn lookup(&self, path: &[String]) -> Option<FieldStyle> {
+        self.patterns
+            .iter()
+            .find(|(segments, _)| matches(segments, path))
+            .map(|(_, style)| *style)
+    }
+}
+
+fn matches(pattern: &[Segment], path: &[String]) -> bool {
+    match pattern.split_first() {
+        None => path.is_empty(),
+        Some((Segment::Any, rest)) => (0..=path.len()).any(|skip| matches(rest, &path[skip..])),
+        Some((segment, rest)) => match path.split_first() {
+            Some((head, tail)) => {
+                let hit = match segment {
+                    Segment::Name(name) => name == head,
+                    Segment::One | Segment::Any => true,
+                };
+                hit && matches(rest, tail)
+            }
+            None => false,
+        },
+    }
+}
+
+/// Reject empty patterns and empty segments such as `a..b`.
+pub(crate) fn validate(styles: &[(String, FieldStyle)]) -> Result<(), Error> {
+    for (pattern, _) in styles {
+        if pattern.split('.').any(str::is_empty) {
+            return Err(Error::InvalidOptions(format!(
+                "invalid field style path {pattern:?}; segments must be non-empty"
+            )));
+        }
+    }
+    Ok(())
+}
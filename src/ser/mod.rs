@@ -35,7 +35,10 @@
 use serde_core::ser::Error as _;
 
 pub(crate) mod api;
+pub mod budget;
+mod document_writer;
 pub mod error;
+pub mod field_styles;
 pub mod options;
 pub(crate) mod quoting;
 mod serializer;
@@ -43,6 +46,7 @@ mod wrapper_impls;
 mod wrapping;
 mod zmij_format;
 
+pub use self::document_writer::{DocumentWriter, IoAdapter};
 pub use self::error::Error;
 pub use self::serializer::YamlSerializer;
 #[doc(hidden)]
@@ -77,6 +81,9 @@ const NAME_SPACE_AFTER: &str = "__yaml_space_after";
 const NAME_DOUBLE_QUOTED: &str = "__yaml_double_quoted";
 const NAME_SINGLE_QUOTED: &str = "__yaml_single_quoted";
 const NAME_NULLABLE_TILDE: &str = "__yaml_nullable_tilde";
+const NAME_TAGGED_SET: &str = "__yaml_tagged_set";
+const NAME_TAGGED_OMAP: &str = "__yaml_tagged_omap";
+const NAME_TAGGED_PAIRS: &str = "__yaml_tagged_pairs";
 
 #[cfg(test)]
 mod tests {
@@ -19,6 +19,11 @@
 //! assert!(buf.contains("a: 1"));
 //! ```
 
+use crate::Schema;
+#[cfg(feature = "robotics")]
+use crate::angle::AngleFormat;
+use crate::ser::budget::SerializerBudget;
+use crate::ser::field_styles::{self, FieldStyle};
 use crate::ser_error::Error;
 
 /// Placement style for comments emitted by [`crate::Commented`].
@@ -31,6 +36,67 @@ pub enum CommentPosition {
     Above,
 }
 
+/// A `%TAG` directive mapping a tag handle to a tag prefix.
+///
+/// Registered directives are written at the start of every document, and tags emitted
+/// by the serializer use the matching handle as a shorthand: with
+/// `%TAG !k8s! tag:example.com,2026:`, the tag `tag:example.com,2026:Deployment` is
+/// written as `!k8s!Deployment`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TagDirective {
+    /// Tag handle: `!`, `!!` or a named handle such as `!k8s!`.
+    pub handle: String,
+    /// Tag prefix the handle expands to, such as `tag:example.com,2026:`.
+    pub prefix: String,
+}
+
+impl TagDirective {
+    /// Create a directive mapping `handle` to `prefix`.
+    pub fn new(handle: impl Into<String>, prefix: impl Into<String>) -> Self {
+        Self {
+            handle: handle.into(),
+            prefix: prefix.into(),
+        }
+    }
+
+    pub(crate) fn validate(&self) -> Result<(), Error> {
+        if !is_valid_tag_handle(&self.handle) {
+            return Err(Error::InvalidOptions(format!(
+                "invalid %TAG handle {:?}; expected `!`, `!!` or `!name!`",
+                self.handle
+            )));
+        }
+        if self.prefix.is_empty()
+            || self
+                .prefix
+                .chars()
+                .any(|ch| ch.is_whitespace() || ch.is_control())
+        {
+            return Err(Error::InvalidOptions(format!(
+                "invalid %TAG prefix {:?}; it must be non-empty and contain no whitespace",
+                self.prefix
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Check the `c-tag-handle` production: `!`, `!!`, or `!` word characters `!`.
+fn is_valid_tag_handle(handle: &str) -> bool {
+    match handle {
+        "!" | "!!" => true,
+        _ => handle
+            .strip_prefix('!')
+            .and_then(|rest| rest.strip_suffix('!'))
+            .is_some_and(|name| {
+                !name.is_empty()
+                    && name
+                        .chars()
+                        .all(|ch| ch.is_ascii_alphanumeric() || ch == '-')
+            }),
+    }
+}
+
 /// Serializer options for YAML emission.
 ///
 /// This struct controls various aspects of YAML serialization, such as indentation,
@@ -150,6 +216,61 @@ pub struct SerializerOptions {
     /// like multiple x, y coordinates quoting y may be very annoying.
     /// Default: false.
     pub yaml_12: bool,
+
+    /// Schema the output is read with. Strings that it resolves to another type are
+    /// quoted, so they read back as strings: with [`Schema::Yaml11`] that includes
+    /// sexagesimal values like `1:30`, while with [`Schema::Core`] `yes` stays plain.
+    ///
+    /// The other quoting rules still apply, so strings like `true` or `0x1F` are quoted
+    /// under every schema. When set, it replaces the [`Self::yaml_12`] boolean heuristic;
+    /// [`Schema::Yaml11`] cannot be combined with `yaml_12`.
+    /// Default: `None`.
+    pub schema: Option<Schema>,
+
+    /// Optional limits on output size, nesting depth and node count.
+    ///
+    /// When set, exceeding any limit aborts serialization with
+    /// [`Error::Budget`](crate::ser::Error::Budget). Limits apply per document.
+    /// Use the [`ser_budget!`](crate::ser_budget!) macro to construct it. Default: `None`.
+    pub budget: Option<SerializerBudget>,
+
+    /// `%TAG` directives written at the start of every document.
+    ///
+    /// Tags emitted by the serializer (enum tags with [`Self::tagged_enums`] and
+    /// `!!binary`) use the longest matching prefix as a shorthand. A tag no handle
+    /// covers is written in verbatim form `!<...>`. A handle may be registered once.
+    /// Default: empty.
+    pub tag_directives: Vec<TagDirective>,
+
+    /// Tag prefix for enum tags emitted with [`Self::tagged_enums`].
+    ///
+    /// By default enum tags live in the YAML namespace `tag:yaml.org,2002:` and are
+    /// written as `!!Type`. Setting a prefix such as `tag:example.com,2026:` together
+    /// with a matching entry in [`Self::tag_directives`] writes them as `!k8s!Type`.
+    /// Default: `None`.
+    pub enum_tag_prefix: Option<String>,
+
+    /// Comment written once at the top of the output, before any directives, such as a
+    /// license notice or a "generated file, do not edit" banner.
+    ///
+    /// Every line of a multi-line text becomes its own `# ` comment line. Default: `None`.
+    pub header_comment: Option<String>,
+
+    /// Comment written after the content of every document, formatted like
+    /// [`Self::header_comment`]. Default: `None`.
+    pub footer_comment: Option<String>,
+
+    /// Styles applied by key path, such as `("spec.ports", FieldStyle::Flow)` or
+    /// `("**.description", FieldStyle::Literal)`, for types whose fields cannot be
+    /// wrapped. The first matching pattern wins; see [`crate::ser::field_styles`] for
+    /// the path syntax. Default: empty.
+    pub field_styles: Vec<(String, FieldStyle)>,
+
+    /// How [`crate::Radians`] values are written: `deg(90)` or `!degrees 90` when the
+    /// value is a whole number of degrees (within the tolerance and precision of the
+    /// format), plain radians otherwise. Default: [`AngleFormat::default`].
+    #[cfg(feature = "robotics")]
+    pub angle_format: AngleFormat,
 }
 
 // Below this length, block-string wrappers serialize as regular scalars
@@ -172,6 +293,37 @@ impl SerializerOptions {
                 Self::MAX_INDENT_STEP
             )));
         }
+        for (i, directive) in self.tag_directives.iter().enumerate() {
+            directive.validate()?;
+            if self.tag_directives[..i]
+                .iter()
+                .any(|earlier| earlier.handle == directive.handle)
+            {
+                return Err(Error::InvalidOptions(format!(
+                    "duplicate %TAG handle {:?}",
+                    directive.handle
+                )));
+            }
+        }
+        field_styles::validate(&self.field_styles)?;
+        #[cfg(feature = "robotics")]
+        self.angle_format
+            .validate()
+            .map_err(Error::InvalidOptions)?;
+        if self.yaml_12 && self.schema == Some(Schema::Yaml11) {
+            return Err(Error::InvalidOptions(
+                "schema Yaml11 cannot be combined with yaml_12".to_string(),
+            ));
+        }
+        if self
+            .enum_tag_prefix
+            .as_deref()
+            .is_some_and(|prefix| prefix.is_empty() || prefix.contains(char::is_whitespace))
+        {
+            return Err(Error::InvalidOptions(
+                "enum_tag_prefix must be non-empty and contain no whitespace".to_string(),
+            ));
+        }
         Ok(())
     }
 }
@@ -191,6 +343,15 @@ impl Default for SerializerOptions {
             quote_all: false,
             comment_position: CommentPosition::Inline,
             yaml_12: false,
+            schema: None,
+            budget: None,
+            tag_directives: Vec::new(),
+            enum_tag_prefix: None,
+            header_comment: None,
+            footer_comment: None,
+            field_styles: Vec::new(),
+            #[cfg(feature = "robotics")]
+            angle_format: AngleFormat::default(),
         }
     }
 }
@@ -1,3 +1,4 @@
+use crate::Schema;
 use crate::parse_scalars::parse_yaml11_bool;
 use std::fmt::{self, Write};
 
@@ -226,19 +227,35 @@ fn starts_with_document_marker(s: &str) -> bool {
     rest.is_empty() || rest.as_bytes().first().is_some_and(u8::is_ascii_whitespace)
 }
 
+/// Scalar resolution of the reader, as far as value quoting depends on it.
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct Resolution {
+    /// YAML 1.2 reader: YAML 1.1 boolean spellings are strings.
+    pub(crate) yaml_12: bool,
+    /// Schema selected with [`crate::SerializerOptions::schema`]. When set, it alone
+    /// decides which boolean spellings are quoted.
+    pub(crate) schema: Option<Schema>,
+}
+
 /// Like `is_ambiguous`, but used for VALUE position.
 ///
 /// For values we are more conservative: quote additional spellings that many YAML
 /// parsers accept as floats even if YAML 1.2 requires the leading-dot form.
 #[inline]
-fn is_ambiguous_value(s: &str, yaml_12: bool) -> bool {
+fn is_ambiguous_value(s: &str, resolution: Resolution) -> bool {
     if is_ambiguous(s) {
         return true;
     }
 
-    // YAML 1.1 boolean spellings: quote them as strings for compatibility and
-    // round-tripping (e.g. "YES", "no", "On", "off", "y", "n").
-    if !yaml_12 && parse_yaml11_bool(s).is_ok() {
+    // Strings the selected schema reads as another type, such as YAML 1.1
+    // sexagesimal numbers (e.g. "1:30").
+    if let Some(schema) = resolution.schema {
+        if !schema.is_string(s) {
+            return true;
+        }
+    } else if !resolution.yaml_12 && parse_yaml11_bool(s).is_ok() {
+        // YAML 1.1 boolean spellings: quote them as strings for compatibility and
+        // round-tripping (e.g. "YES", "no", "On", "off", "y", "n").
         return true;
     }
 
@@ -307,8 +324,8 @@ pub(crate) fn is_plain_safe(s: &str) -> bool {
 /// where certain characters would break parsing (e.g., commas and brackets) or where the token
 /// could be misinterpreted as a number or boolean.
 #[inline]
-pub(crate) fn is_plain_value_safe(s: &str, yaml_12: bool, in_flow: bool) -> bool {
-    if is_ambiguous_value(s, yaml_12) {
+pub(crate) fn is_plain_value_safe(s: &str, resolution: Resolution, in_flow: bool) -> bool {
+    if is_ambiguous_value(s, resolution) {
         return false;
     }
     if starts_with_document_marker(s) {
@@ -447,10 +464,17 @@ fn contains_any_or_is_control(string: &str, values: &[char]) -> bool {
 #[cfg(test)]
 mod tests {
     use super::{
-        is_controll_which_needs_escaping, is_numeric_looking, is_plain_safe, is_plain_value_safe,
+        Resolution, is_controll_which_needs_escaping, is_numeric_looking, is_plain_safe,
+        is_plain_value_safe,
     };
+    use crate::Schema;
     use rstest::rstest;
 
+    const YAML_12: Resolution = Resolution {
+        yaml_12: true,
+        schema: None,
+    };
+
     #[rstest]
     #[case::zero("0")]
     #[case::neg_int("-19")]
@@ -547,9 +571,12 @@ mod tests {
     #[case::document_start_marker_with_value("--- value")]
     #[case::document_end_marker_with_value("... value")]
     fn plain_values_reject_lossy_surrounding_whitespace(#[case] input: &str) {
-        assert!(!is_plain_value_safe(input, false, false), "{input:?}");
         assert!(
-            !is_plain_value_safe(input, true, true),
+            !is_plain_value_safe(input, Resolution::default(), false),
+            "{input:?}"
+        );
+        assert!(
+            !is_plain_value_safe(input, YAML_12, true),
             "flow value {input:?}"
         );
     }
@@ -586,10 +613,38 @@ mod tests {
     #[case::para_sep("a\u{2029}b")]
     fn format_chars_are_not_plain_safe(#[case] input: &str) {
         assert!(!is_plain_safe(input), "key {input:?}");
-        assert!(!is_plain_value_safe(input, false, false), "value {input:?}");
         assert!(
-            !is_plain_value_safe(input, true, true),
+            !is_plain_value_safe(input, Resolution::default(), false),
+            "value {input:?}"
+        );
+        assert!(
+            !is_plain_value_safe(input, YAML_12, true),
             "flow value {input:?}"
         );
     }
+
+    #[rstest]
+    #[case(Schema::Yaml11, "1:30", false)]
+    #[case(Schema::Yaml11, "0x_1F", false)]
+    #[case(Schema::Yaml11, "y", false)]
+    #[case(Schema::Core, "1:30", true)]
+    #[case(Schema::Core, "y", true)]
+    #[case(Schema::Json, "yes", true)]
+    #[case(Schema::Json, "true", false)]
+    #[case(Schema::Failsafe, "0x1F", false)]
+    fn schema_decides_value_quoting(
+        #[case] schema: Schema,
+        #[case] input: &str,
+        #[case] plain: bool,
+    ) {
+        let resolution = Resolution {
+            yaml_12: false,
+            schema: Some(schema),
+        };
+        assert_eq!(
+            is_plain_value_safe(input, resolution, false),
+            plain,
+            "{schema:?} {input:?}"
+        );
+    }
 }
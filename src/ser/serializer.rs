@@ -12,17 +12,22 @@ use serde_core::ser::{Serialize, Serializer};
 use std::collections::HashMap;
 use std::fmt::Write;
 
+#[cfg(feature = "robotics")]
+use crate::angle::{AngleFormat, NAME_RADIANS};
 use crate::long_strings::{NAME_FOLD_STR, NAME_LIT_STR};
 
-use super::options::{CommentPosition, SerializerOptions};
+use super::budget::BudgetTracker;
+use super::field_styles::{FieldStyle, FieldStyleMap};
+use super::options::{CommentPosition, SerializerOptions, TagDirective};
 use super::quoting::{
-    escape_double_quoted, is_auto_block_scalar_readable, is_block_scalar_content_safe,
+    Resolution, escape_double_quoted, is_auto_block_scalar_readable, is_block_scalar_content_safe,
     is_controll_which_needs_escaping, is_plain_value_safe,
 };
 use super::{
     Error, NAME_DOUBLE_QUOTED, NAME_FLOW_MAP, NAME_FLOW_SEQ, NAME_NULLABLE_TILDE,
-    NAME_SINGLE_QUOTED, NAME_SPACE_AFTER, NAME_TUPLE_ANCHOR, NAME_TUPLE_COMMENTED, NAME_TUPLE_WEAK,
-    Result, checked_depth_add, checked_indentation, wrapping, zmij_format,
+    NAME_SINGLE_QUOTED, NAME_SPACE_AFTER, NAME_TAGGED_OMAP, NAME_TAGGED_PAIRS, NAME_TAGGED_SET,
+    NAME_TUPLE_ANCHOR, NAME_TUPLE_COMMENTED, NAME_TUPLE_WEAK, Result, checked_depth_add,
+    checked_indentation, wrapping, zmij_format,
 };
 
 // ------------------------------------------------------------
@@ -61,11 +66,76 @@ impl PendingStrStyle {
     }
 }
 
+/// Output adapter that counts the bytes written to the destination.
+///
+/// The count feeds [`BudgetTracker`] so output-size limits can be checked before a node
+/// is emitted without requiring the destination to report its length.
+struct CountingWriter<'a, W: Write> {
+    inner: &'a mut W,
+    written: usize,
+}
+
+impl<W: Write> Write for CountingWriter<'_, W> {
+    #[inline]
+    fn write_str(&mut self, s: &str) -> std::fmt::Result {
+        self.inner.write_str(s)?;
+        self.written = self.written.saturating_add(s.len());
+        Ok(())
+    }
+
+    #[inline]
+    fn write_char(&mut self, c: char) -> std::fmt::Result {
+        self.inner.write_char(c)?;
+        self.written = self.written.saturating_add(c.len_utf8());
+        Ok(())
+    }
+}
+
 // Numeric anchor id used internally.
 type AnchorId = u32;
 
 const MAX_ANCHOR_NAME_BYTES: usize = 256;
 
+/// Prefix of the YAML tag namespace that the `!!` handle expands to by default.
+const YAML_TAG_PREFIX: &str = "tag:yaml.org,2002:";
+
+/// Write the tag `prefix` + `name`, using the handle of the longest matching directive.
+///
+/// Without a matching `%TAG` directive, the YAML namespace falls back to the built-in
+/// `!!` handle and any other tag is written verbatim as `!<...>`.
+fn write_tag(
+    out: &mut impl Write,
+    directives: &[TagDirective],
+    prefix: &str,
+    name: &str,
+) -> Result<()> {
+    if !is_tag_shorthand_suffix(name) {
+        write!(out, "!<{prefix}{name}>")?;
+        return Ok(());
+    }
+    let shorthand = directives
+        .iter()
+        .filter_map(|directive| {
+            let rest = prefix.strip_prefix(directive.prefix.as_str())?;
+            is_tag_shorthand_suffix(rest).then_some((directive.handle.as_str(), rest))
+        })
+        .min_by_key(|(_, rest)| rest.len());
+    match shorthand {
+        Some((handle, rest)) => write!(out, "{handle}{rest}{name}")?,
+        None if prefix == YAML_TAG_PREFIX && directives.iter().all(|d| d.handle != "!!") => {
+            write!(out, "!!{name}")?;
+        }
+        None => write!(out, "!<{prefix}{name}>")?,
+    }
+    Ok(())
+}
+
+/// Whether `text` may follow a tag handle without escaping (`ns-tag-char*`).
+fn is_tag_shorthand_suffix(text: &str) -> bool {
+    text.chars()
+        .all(|ch| ch.is_ascii_alphanumeric() || "-#;/?:@&=+$_.~*'()".contains(ch))
+}
+
 fn is_supported_anchor_name(name: &str) -> bool {
     !name.is_empty()
         && name.len() <= MAX_ANCHOR_NAME_BYTES
@@ -101,8 +171,23 @@ struct SerializerSettings {
     prefer_block_scalars: bool,
     /// Quote all string scalars.
     quote_all: bool,
-    /// Emit a YAML 1.2 directive and use YAML 1.2-friendly heuristics.
+    /// Emit a YAML 1.2 directive.
     yaml_12: bool,
+    /// Scalar resolution assumed by readers, which decides what strings to quote.
+    resolution: Resolution,
+    /// `%TAG` directives emitted before the document and used for tag shorthands.
+    tag_directives: Vec<TagDirective>,
+    /// Namespace of enum tags; `None` means the YAML namespace (`!!`).
+    enum_tag_prefix: Option<String>,
+    /// Comment written before the directives of the document.
+    header_comment: Option<String>,
+    /// Comment written after the content of the document.
+    footer_comment: Option<String>,
+    /// Styles applied by key path; key paths are tracked only when non-empty.
+    field_styles: FieldStyleMap,
+    /// Degree output of [`crate::Radians`] values.
+    #[cfg(feature = "robotics")]
+    angle_format: AngleFormat,
 }
 
 impl From<&SerializerOptions> for SerializerSettings {
@@ -118,6 +203,17 @@ impl From<&SerializerOptions> for SerializerSettings {
             prefer_block_scalars: options.prefer_block_scalars,
             quote_all: options.quote_all,
             yaml_12: options.yaml_12,
+            resolution: Resolution {
+                yaml_12: options.yaml_12,
+                schema: options.schema,
+            },
+            tag_directives: options.tag_directives.clone(),
+            enum_tag_prefix: options.enum_tag_prefix.clone(),
+            header_comment: options.header_comment.clone(),
+            footer_comment: options.footer_comment.clone(),
+            field_styles: FieldStyleMap::new(&options.field_styles),
+            #[cfg(feature = "robotics")]
+            angle_format: options.angle_format,
         }
     }
 }
@@ -167,6 +263,15 @@ struct SerializerState {
     current_map_depth: Option<usize>,
     /// Whether emission of the current document has begun.
     doc_started: bool,
+    /// Key path of the value being serialized, tracked for path-keyed styles.
+    path: Vec<String>,
+    /// Path style waiting for the first node of the current value.
+    pending_path_style: Option<FieldStyle>,
+    /// YAML-namespace tag (such as `omap`) waiting for the next collection.
+    pending_tag: Option<&'static str>,
+    /// The next float is a [`crate::Radians`] value.
+    #[cfg(feature = "robotics")]
+    pending_angle: bool,
 }
 
 impl Default for SerializerState {
@@ -183,6 +288,11 @@ impl Default for SerializerState {
             after_dash_depth: None,
             current_map_depth: None,
             doc_started: false,
+            path: Vec::new(),
+            pending_path_style: None,
+            pending_tag: None,
+            #[cfg(feature = "robotics")]
+            pending_angle: false,
         }
     }
 }
@@ -198,11 +308,14 @@ struct AnchorState {
     by_ptr: HashMap<usize, AnchorId, BuildNoHashHasher<usize>>,
     /// Next numeric id to use when generating anchor names.
     next_id: AnchorId,
+    /// Id of the first anchor allocated by this serializer. It is 1 unless numbering
+    /// continues from a previous document of the same stream.
+    first_id: AnchorId,
     /// Anchor to prefix onto the next scalar or complex node.
     pending_id: Option<AnchorId>,
     /// Optional custom anchor-name generator supplied by the caller.
     generator: Option<fn(usize) -> String>,
-    /// Cached custom names, indexed by `id - 1`.
+    /// Cached custom names, indexed by `id - first_id`.
     custom_names: Option<Vec<String>>,
 }
 
@@ -211,6 +324,7 @@ impl AnchorState {
         Self {
             by_ptr: HashMap::with_hasher(BuildNoHashHasher::default()),
             next_id: 1,
+            first_id: 1,
             pending_id: None,
             generator,
             custom_names: None,
@@ -245,13 +359,15 @@ impl AnchorState {
 /// ```
 pub struct YamlSerializer<'a, W: Write> {
     /// Destination writer where YAML text is emitted.
-    out: &'a mut W,
+    out: CountingWriter<'a, W>,
     /// Immutable caller-selected formatting behavior.
     settings: SerializerSettings,
     /// Mutable layout and traversal state shared with nested serializers.
     state: SerializerState,
     /// Anchor identities, names, and deferred anchor emission.
     anchors: AnchorState,
+    /// Node, depth and output-size accounting against the optional budget.
+    budget: BudgetTracker,
 }
 
 impl<'a, W: Write> YamlSerializer<'a, W> {
@@ -262,12 +378,16 @@ impl<'a, W: Write> YamlSerializer<'a, W> {
         Self::from_options_unchecked(out, &SerializerOptions::default())
     }
 
-    fn from_options_unchecked(out: &'a mut W, options: &SerializerOptions) -> Self {
+    pub(crate) fn from_options_unchecked(out: &'a mut W, options: &SerializerOptions) -> Self {
         Self {
-            out,
+            out: CountingWriter {
+                inner: out,
+                written: 0,
+            },
             settings: SerializerSettings::from(options),
             state: SerializerState::default(),
             anchors: AnchorState::new(options.anchor_generator),
+            budget: BudgetTracker::new(options.budget.clone()),
         }
     }
     /// Construct a [`Serializer`](crate::Serializer) with a specific indentation step.
@@ -287,8 +407,134 @@ impl<'a, W: Write> YamlSerializer<'a, W> {
         Ok(Self::from_options_unchecked(out, &options))
     }
 
+    /// Continue anchor numbering from a previous document so that names stay unique
+    /// across a stream. Must be called before anything is serialized.
+    pub(crate) fn continue_anchor_numbering(&mut self, next_id: u32) {
+        self.anchors.next_id = next_id;
+        self.anchors.first_id = next_id;
+    }
+
+    /// Id that the next newly discovered anchor would receive.
+    pub(crate) fn next_anchor_id(&self) -> u32 {
+        self.anchors.next_id
+    }
+
+    /// Write the `%YAML` and `%TAG` directives selected by `options`, followed by the
+    /// document start marker.
+    pub(crate) fn write_directives(out: &mut W, options: &SerializerOptions) -> Result<()> {
+        Self::write_directive_lines(out, options.yaml_12, &options.tag_directives)
+    }
+
+    fn write_directive_lines(
+        out: &mut impl Write,
+        yaml_12: bool,
+        tag_directives: &[TagDirective],
+    ) -> Result<()> {
+        if yaml_12 {
+            out.write_str("%YAML 1.2\n")?;
+        }
+        for directive in tag_directives {
+            writeln!(out, "%TAG {} {}", directive.handle, directive.prefix)?;
+        }
+        out.write_str("---\n")?;
+        Ok(())
+    }
+
+    /// Write the footer comment, if any, after the value has been serialized.
+    pub(crate) fn end_document(&mut self) -> Result<()> {
+        if let Some(footer) = self.settings.footer_comment.as_deref() {
+            if !self.state.at_line_start {
+                self.out.write_char('\n')?;
+                self.state.at_line_start = true;
+            }
+            Self::write_comment_lines(&mut self.out, footer)?;
+        }
+        Ok(())
+    }
+
+    /// Write `text` as full-line comments, one `# ` line per line of text.
+    pub(crate) fn write_comment_lines(out: &mut impl Write, text: &str) -> Result<()> {
+        for line in text.split(['\n', '\u{85}', '\u{2028}', '\u{2029}']) {
+            let line = Self::sanitize_comment_text(line.strip_suffix('\r').unwrap_or(line));
+            let line = line.trim_end();
+            if line.is_empty() {
+                out.write_str("#\n")?;
+            } else {
+                writeln!(out, "# {line}")?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Record that the caller already wrote the directives and document start marker.
+    pub(crate) fn mark_document_started(&mut self) {
+        self.state.doc_started = true;
+    }
+
     // -------- helpers --------
 
+    /// Whether key paths must be tracked for [`SerializerOptions::field_styles`].
+    #[inline]
+    fn tracks_paths(&self) -> bool {
+        !self.settings.field_styles.is_empty()
+    }
+
+    /// Enter the value at `segment` and stage the style configured for its path.
+    fn push_path(&mut self, segment: String) {
+        self.state.path.push(segment);
+        self.state.pending_path_style = self.settings.field_styles.lookup(&self.state.path);
+    }
+
+    fn pop_path(&mut self) {
+        self.state.path.pop();
+        self.state.pending_path_style = None;
+    }
+
+    /// Write a pending [`crate::Radians`] value of `degrees` in degree form if the
+    /// angle format finds it nice. Returns false if the caller should write radians.
+    #[cfg(feature = "robotics")]
+    fn serialize_angle_in_degrees(&mut self, degrees: f64) -> Result<bool> {
+        if !std::mem::take(&mut self.state.pending_angle) {
+            return Ok(false);
+        }
+        let Some(text) = self.settings.angle_format.degrees_text(degrees) else {
+            return Ok(false);
+        };
+        self.charge_node(text.len())?;
+        self.write_space_if_pending()?;
+        self.write_scalar_prefix_if_anchor()?;
+        if self.state.at_line_start {
+            self.write_indent(self.state.depth)?;
+        }
+        self.out.write_str(&text)?;
+        self.write_end_of_scalar()?;
+        Ok(true)
+    }
+
+    /// Charge one scalar-like node of `len` bytes against the budget.
+    #[inline]
+    fn charge_node(&mut self, len: usize) -> Result<()> {
+        // A scalar consumes the path style; styles that fit it were applied by the caller.
+        self.state.pending_path_style = None;
+        self.budget
+            .node(self.out.written, len)
+            .map_err(|breach| Error::Budget { breach })
+    }
+
+    /// Charge a collection node against the budget and enter one nesting level.
+    /// Every successful call must be paired with [`Self::leave_collection`].
+    #[inline]
+    fn enter_collection(&mut self) -> Result<()> {
+        self.budget
+            .enter(self.out.written)
+            .map_err(|breach| Error::Budget { breach })
+    }
+
+    #[inline]
+    fn leave_collection(&mut self) {
+        self.budget.leave();
+    }
+
     /// Determines if a string requires double quotes when `quote_all` is enabled.
     /// Returns true if the string contains single quotes, backslashes, or control characters
     /// that need escape processing.
@@ -418,8 +664,8 @@ impl<'a, W: Write> YamlSerializer<'a, W> {
     #[inline]
     fn write_anchor_name(&mut self, id: AnchorId) -> Result<()> {
         if let Some(names) = &self.anchors.custom_names {
-            // ids are 1-based; vec is 0-based
-            let idx = id as usize - 1;
+            // ids start at `first_id`; vec is 0-based
+            let idx = id.saturating_sub(self.anchors.first_id) as usize;
             if let Some(name) = names.get(idx) {
                 self.out.write_str(name)?;
             } else {
@@ -453,9 +699,16 @@ impl<'a, W: Write> YamlSerializer<'a, W> {
         if self.state.at_line_start {
             if !self.state.doc_started {
                 self.state.doc_started = true;
-                if self.settings.yaml_12 {
-                    self.out.write_str("%YAML 1.2\n---\n")?;
-                    // Still at start of a line after the directive and document start marker.
+                if let Some(header) = self.settings.header_comment.as_deref() {
+                    Self::write_comment_lines(&mut self.out, header)?;
+                }
+                if self.settings.yaml_12 || !self.settings.tag_directives.is_empty() {
+                    Self::write_directive_lines(
+                        &mut self.out,
+                        self.settings.yaml_12,
+                        &self.settings.tag_directives,
+                    )?;
+                    // Still at start of a line after the directives and document start marker.
                     self.state.at_line_start = true;
                 }
             }
@@ -487,7 +740,7 @@ impl<'a, W: Write> YamlSerializer<'a, W> {
     /// Delegates to the standalone function in `wrapping` module.
     fn write_folded_block(&mut self, s: &str, indent: usize) -> Result<()> {
         self::wrapping::write_folded_block(
-            self.out,
+            &mut self.out,
             s,
             indent,
             self.settings.indent_step,
@@ -503,7 +756,7 @@ impl<'a, W: Write> YamlSerializer<'a, W> {
     /// (`Variant: ...`), so they need the same ambiguity checks as regular
     /// map and struct keys.
     fn write_key_scalar(&mut self, s: &str) -> Result<()> {
-        let text = scalar_key_to_string(&s, self.settings.yaml_12)?;
+        let text = scalar_key_to_string(&s, self.settings.resolution)?;
         self.out.write_str(&text)?;
         Ok(())
     }
@@ -511,7 +764,7 @@ impl<'a, W: Write> YamlSerializer<'a, W> {
     /// Write a double-quoted string with necessary escapes.
     fn write_quoted(&mut self, s: &str) -> Result<()> {
         self.out.write_char('"')?;
-        escape_double_quoted(s, self.out)?;
+        escape_double_quoted(s, &mut self.out)?;
         self.out.write_char('"')?;
         Ok(())
     }
@@ -526,7 +779,7 @@ impl<'a, W: Write> YamlSerializer<'a, W> {
             } else {
                 self.write_single_quoted(s)
             }
-        } else if is_plain_value_safe(s, self.settings.yaml_12, self.state.in_flow > 0) {
+        } else if is_plain_value_safe(s, self.settings.resolution, self.state.in_flow > 0) {
             self.out.write_str(s)?;
             Ok(())
         } else {
@@ -538,18 +791,29 @@ impl<'a, W: Write> YamlSerializer<'a, W> {
     /// Serialize a tagged scalar of the form `!!Type value` using plain or quoted style for
     /// the value depending on its content.
     fn serialize_tagged_scalar(&mut self, enum_name: &str, variant: &str) -> Result<()> {
+        self.charge_node(enum_name.len() + variant.len())?;
         self.write_scalar_prefix_if_anchor()?;
         if self.state.at_line_start {
             self.write_indent(self.state.depth)?;
         }
-        self.out.write_str("!!")?;
-        self.out.write_str(enum_name)?;
+        let prefix = self
+            .settings
+            .enum_tag_prefix
+            .as_deref()
+            .unwrap_or(YAML_TAG_PREFIX);
+        write_tag(
+            &mut self.out,
+            &self.settings.tag_directives,
+            prefix,
+            enum_name,
+        )?;
         self.out.write_char(' ')?;
         self.write_plain_or_quoted_value(variant)?;
         self.write_end_of_scalar()
     }
 
     fn serialize_double_quoted_scalar(&mut self, value: &str) -> Result<()> {
+        self.charge_node(value.len())?;
         self.write_space_if_pending()?;
         self.write_scalar_prefix_if_anchor()?;
         if self.state.at_line_start {
@@ -560,6 +824,7 @@ impl<'a, W: Write> YamlSerializer<'a, W> {
     }
 
     fn serialize_single_quoted_scalar(&mut self, value: &str) -> Result<()> {
+        self.charge_node(value.len())?;
         if let Some(ch) = value
             .chars()
             .find(|ch| is_controll_which_needs_escaping(*ch))
@@ -576,6 +841,7 @@ impl<'a, W: Write> YamlSerializer<'a, W> {
     }
 
     fn serialize_tilde_null(&mut self) -> Result<()> {
+        self.charge_node(1)?;
         self.state.pending_flow = None;
         self.write_space_if_pending()?;
         self.write_scalar_prefix_if_anchor()?;
@@ -586,34 +852,82 @@ impl<'a, W: Write> YamlSerializer<'a, W> {
         self.write_end_of_scalar()
     }
 
-    /// If an anchor is pending for the next scalar, emit `&name ` prefix.
-    /// Used for in-flow scalars.
+    /// Whether an anchor or tag is waiting to be written before the next node.
     #[inline]
-    fn write_scalar_prefix_if_anchor(&mut self) -> Result<()> {
-        if let Some(id) = self.anchors.pending_id.take() {
-            if self.state.at_line_start {
-                self.write_indent(self.state.depth)?;
-            }
+    fn has_pending_properties(&self) -> bool {
+        self.anchors.pending_id.is_some() || self.state.pending_tag.is_some()
+    }
+
+    /// Write the pending `&anchor` and `!!tag` node properties, separated by a space.
+    fn write_node_properties(&mut self, id: Option<AnchorId>, tag: Option<&str>) -> Result<()> {
+        if let Some(id) = id {
             self.out.write_char('&')?;
             self.write_anchor_name(id)?;
-            self.out.write_char(' ')?;
         }
+        if let Some(tag) = tag {
+            if id.is_some() {
+                self.out.write_char(' ')?;
+            }
+            write_tag(
+                &mut self.out,
+                &self.settings.tag_directives,
+                YAML_TAG_PREFIX,
+                tag,
+            )?;
+        }
+        Ok(())
+    }
+
+    /// If an anchor or tag is pending for the next scalar, emit the `&name !!tag ` prefix.
+    /// Used for in-flow scalars and collections.
+    #[inline]
+    fn write_scalar_prefix_if_anchor(&mut self) -> Result<()> {
+        if !self.has_pending_properties() {
+            return Ok(());
+        }
+        if self.state.at_line_start {
+            self.write_indent(self.state.depth)?;
+        }
+        let id = self.anchors.pending_id.take();
+        let tag = self.state.pending_tag.take();
+        self.write_node_properties(id, tag)?;
+        self.out.write_char(' ')?;
         Ok(())
     }
 
-    /// If an anchor is pending for the next complex node (seq/map),
+    /// If an anchor or tag is pending for the next complex node (seq/map),
     /// emit it on its own line before the node.
     #[inline]
     fn write_anchor_for_complex_node(&mut self) -> Result<()> {
-        if let Some(id) = self.anchors.pending_id.take() {
-            if self.state.at_line_start {
-                self.write_indent(self.state.depth)?;
-            }
-            self.write_space_if_pending()?;
-            self.out.write_char('&')?;
-            self.write_anchor_name(id)?;
-            self.newline()?;
+        if !self.has_pending_properties() {
+            return Ok(());
+        }
+        if self.state.at_line_start {
+            self.write_indent(self.state.depth)?;
+        }
+        self.write_space_if_pending()?;
+        let id = self.anchors.pending_id.take();
+        let tag = self.state.pending_tag.take();
+        self.write_node_properties(id, tag)?;
+        self.newline()
+    }
+
+    /// Like [`Self::write_anchor_for_complex_node`] for a collection of `len` entries. An empty
+    /// collection written as `[]` or `{}` keeps its properties on the same line
+    /// (`key: !!omap []`), since a flow collection cannot start below its properties.
+    fn write_anchor_for_collection(&mut self, len: Option<usize>) -> Result<()> {
+        if len != Some(0) || !self.settings.empty_as_braces || !self.has_pending_properties() {
+            return self.write_anchor_for_complex_node();
+        }
+        if self.state.at_line_start {
+            self.write_indent(self.state.depth)?;
         }
+        self.write_space_if_pending()?;
+        let id = self.anchors.pending_id.take();
+        let tag = self.state.pending_tag.take();
+        self.write_node_properties(id, tag)?;
+        // The empty collection writes this space before its `[]` or `{}`.
+        self.state.pending_layout.pending_space_after_colon = true;
         Ok(())
     }
 
@@ -621,6 +935,7 @@ impl<'a, W: Write> YamlSerializer<'a, W> {
     /// Used when a previously defined anchor is referenced again.
     #[inline]
     fn write_alias_id(&mut self, id: AnchorId) -> Result<()> {
+        self.charge_node(0)?;
         if self.state.at_line_start {
             self.write_indent(self.state.depth)?;
         }
@@ -636,20 +951,98 @@ impl<'a, W: Write> YamlSerializer<'a, W> {
     /// Consumes any pending flow hint.
     #[inline]
     fn take_flow_for_seq(&mut self) -> bool {
+        let path_flow = self.state.pending_path_style.take() == Some(FieldStyle::Flow);
         if self.state.in_flow > 0 {
             true
         } else {
-            matches!(self.state.pending_flow.take(), Some(PendingFlow::AnySeq))
+            matches!(self.state.pending_flow.take(), Some(PendingFlow::AnySeq)) || path_flow
         }
     }
     /// Determine whether the next mapping should be emitted in flow style.
     /// Consumes any pending flow hint.
     #[inline]
     fn take_flow_for_map(&mut self) -> bool {
+        let path_flow = self.state.pending_path_style.take() == Some(FieldStyle::Flow);
         if self.state.in_flow > 0 {
             true
         } else {
-            matches!(self.state.pending_flow.take(), Some(PendingFlow::AnyMap))
+            matches!(self.state.pending_flow.take(), Some(PendingFlow::AnyMap)) || path_flow
+        }
+    }
+
+    /// Emit `Variant: value` for an externally tagged newtype variant.
+    fn write_newtype_variant<T: ?Sized + Serialize>(
+        &mut self,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<()> {
+        let was_inline_value = self.state.pending_layout.pending_space_after_colon;
+        let anchor_broke_line = self.has_pending_properties();
+        let after_dash_depth = self.state.after_dash_depth;
+        self.write_anchor_for_complex_node()?;
+
+        // If we are the value of a mapping key, YAML forbids "key: Variant: value" inline.
+        // Emit the variant mapping on the next line indented one level. Also, do not insert
+        // a space after the colon when the value may itself be a mapping; instead, defer
+        // space insertion to the value serializer via pending_space_after_colon.
+        if was_inline_value {
+            // consume the pending space request and start a new line
+            self.state.pending_layout.pending_space_after_colon = false;
+            if !self.state.at_line_start {
+                self.newline()?;
+            }
+            // When used as a mapping value, indent relative to the parent mapping's base,
+            // not the serializer's current depth (which may still be the outer level).
+            let base = self.state.current_map_depth.unwrap_or(self.state.depth);
+            let variant_depth = checked_depth_add(base, 1)?;
+            self.write_indent(variant_depth)?;
+            self.write_key_scalar(variant)?;
+            // Write ':' without trailing space, then mark that a space may be needed
+            // if the following value is a scalar.
+            self.out.write_str(":")?;
+            self.state.pending_layout.pending_space_after_colon = true;
+            self.state.at_line_start = false;
+            // Do not let any inline-after-dash hint leak into the variant's inner value.
+            // After `Variant:`, the next node is in value position and must choose its own layout.
+            self.state.pending_layout.pending_inline_map = false;
+            // Ensure that if the value is another variant or a mapping/sequence,
+            // it indents under this variant label rather than the parent map key.
+            let prev_map_depth = self.state.current_map_depth.replace(variant_depth);
+            let res = value.serialize(&mut *self);
+            self.state.current_map_depth = prev_map_depth;
+            return res;
+        }
+        // Otherwise (top-level or sequence context).
+        if self.state.at_line_start {
+            let depth = if anchor_broke_line {
+                match after_dash_depth {
+                    Some(depth) => checked_depth_add(depth, 1)?,
+                    None => self.state.depth,
+                }
+            } else {
+                self.state.depth
+            };
+            self.write_indent(depth)?;
+        }
+        self.write_key_scalar(variant)?;
+        // Write ':' without a space and defer spacing/newline to the value serializer.
+        self.out.write_str(":")?;
+        self.state.pending_layout.pending_space_after_colon = true;
+        self.state.at_line_start = false;
+        // Do not let SeqSer's "inline first key after dash" hint leak into the variant's inner value.
+        // Without this, a struct/map value can start as `Variant: a: 1`.
+        self.state.pending_layout.pending_inline_map = false;
+        // If this variant is inside a block sequence element (`- Variant:`), ensure the nested
+        // value indents under the variant label rather than aligning with the list indentation.
+        // SeqSer stores the dash's indentation depth in `after_dash_depth`.
+        if let Some(d) = self.state.after_dash_depth.take() {
+            let nested_depth = checked_depth_add(d, 1)?;
+            let prev_map_depth = self.state.current_map_depth.replace(nested_depth);
+            let res = value.serialize(&mut *self);
+            self.state.current_map_depth = prev_map_depth;
+            res
+        } else {
+            value.serialize(&mut *self)
         }
     }
 
@@ -683,6 +1076,7 @@ impl<'a, 'b, W: Write> Serializer for &'a mut YamlSerializer<'b, W> {
     // -------- Scalars --------
 
     fn serialize_bool(self, v: bool) -> Result<()> {
+        self.charge_node(5)?;
         self.write_space_if_pending()?;
         self.write_scalar_prefix_if_anchor()?;
         if self.state.at_line_start {
@@ -703,6 +1097,7 @@ impl<'a, 'b, W: Write> Serializer for &'a mut YamlSerializer<'b, W> {
         self.serialize_i64(i64::from(v))
     }
     fn serialize_i64(self, v: i64) -> Result<()> {
+        self.charge_node(0)?;
         self.write_space_if_pending()?;
         self.write_scalar_prefix_if_anchor()?;
         if self.state.at_line_start {
@@ -714,6 +1109,7 @@ impl<'a, 'b, W: Write> Serializer for &'a mut YamlSerializer<'b, W> {
     }
 
     fn serialize_i128(self, v: i128) -> Result<()> {
+        self.charge_node(0)?;
         self.write_space_if_pending()?;
         self.write_scalar_prefix_if_anchor()?;
         if self.state.at_line_start {
@@ -734,6 +1130,7 @@ impl<'a, 'b, W: Write> Serializer for &'a mut YamlSerializer<'b, W> {
         self.serialize_u64(u64::from(v))
     }
     fn serialize_u64(self, v: u64) -> Result<()> {
+        self.charge_node(0)?;
         self.write_space_if_pending()?;
         self.write_scalar_prefix_if_anchor()?;
         if self.state.at_line_start {
@@ -745,6 +1142,7 @@ impl<'a, 'b, W: Write> Serializer for &'a mut YamlSerializer<'b, W> {
     }
 
     fn serialize_u128(self, v: u128) -> Result<()> {
+        self.charge_node(0)?;
         self.write_space_if_pending()?;
         self.write_scalar_prefix_if_anchor()?;
         if self.state.at_line_start {
@@ -756,22 +1154,32 @@ impl<'a, 'b, W: Write> Serializer for &'a mut YamlSerializer<'b, W> {
     }
 
     fn serialize_f32(self, v: f32) -> Result<()> {
+        #[cfg(feature = "robotics")]
+        if self.serialize_angle_in_degrees(f64::from(v.to_degrees()))? {
+            return Ok(());
+        }
+        self.charge_node(0)?;
         self.write_space_if_pending()?;
         self.write_scalar_prefix_if_anchor()?;
         if self.state.at_line_start {
             self.write_indent(self.state.depth)?;
         }
-        zmij_format::write_float_string(self.out, v)?;
+        zmij_format::write_float_string(&mut self.out, v)?;
         self.write_end_of_scalar()
     }
 
     fn serialize_f64(self, v: f64) -> Result<()> {
+        #[cfg(feature = "robotics")]
+        if self.serialize_angle_in_degrees(v.to_degrees())? {
+            return Ok(());
+        }
+        self.charge_node(0)?;
         self.write_space_if_pending()?;
         self.write_scalar_prefix_if_anchor()?;
         if self.state.at_line_start {
             self.write_indent(self.state.depth)?;
         }
-        zmij_format::write_float_string(self.out, v)?;
+        zmij_format::write_float_string(&mut self.out, v)?;
         self.write_end_of_scalar()
     }
 
@@ -797,6 +1205,28 @@ impl<'a, 'b, W: Write> Serializer for &'a mut YamlSerializer<'b, W> {
             }
         }
 
+        if let Some(style) = self.state.pending_path_style.take()
+            && self.state.pending_str_style.is_none()
+        {
+            match style {
+                FieldStyle::DoubleQuoted => return self.serialize_double_quoted_scalar(v),
+                FieldStyle::SingleQuoted if !v.chars().any(is_controll_which_needs_escaping) => {
+                    return self.serialize_single_quoted_scalar(v);
+                }
+                FieldStyle::SingleQuoted => return self.serialize_double_quoted_scalar(v),
+                FieldStyle::Literal if self.state.in_flow == 0 => {
+                    self.state.pending_str_style =
+                        Some(PendingStrStyle::Explicit(StrStyle::Literal));
+                }
+                FieldStyle::Folded if self.state.in_flow == 0 => {
+                    self.state.pending_str_style =
+                        Some(PendingStrStyle::Explicit(StrStyle::Folded));
+                }
+                FieldStyle::Literal | FieldStyle::Folded | FieldStyle::Flow => {}
+            }
+        }
+        self.charge_node(v.len())?;
+
         // If no explicit style pending, auto-select block style.
         //
         // Controlled by `prefer_block_scalars`:
@@ -825,7 +1255,7 @@ impl<'a, 'b, W: Write> Serializer for &'a mut YamlSerializer<'b, W> {
                 // scalars, so ignore only those spaces for the eligibility probe.
                 let auto_fold_probe = v.trim_end_matches(' ');
                 let can_auto_fold = !auto_fold_probe.is_empty()
-                    && is_plain_value_safe(auto_fold_probe, self.settings.yaml_12, false);
+                    && is_plain_value_safe(auto_fold_probe, self.settings.resolution, false);
                 if can_auto_fold {
                     // Measure in characters, not bytes.
                     if v.chars().count() > self.settings.folded_wrap_col {
@@ -1037,10 +1467,17 @@ impl<'a, 'b, W: Write> Serializer for &'a mut YamlSerializer<'b, W> {
         }
 
         // Inline value position: emit !!binary with base64.
+        self.charge_node(v.len().div_ceil(3) * 4)?;
         self.write_space_if_pending()?;
         self.write_scalar_prefix_if_anchor()?;
         // No indent needed mid-line; mirror serialize_str behavior.
-        self.out.write_str("!!binary ")?;
+        write_tag(
+            &mut self.out,
+            &self.settings.tag_directives,
+            YAML_TAG_PREFIX,
+            "binary",
+        )?;
+        self.out.write_char(' ')?;
         let mut s = String::new();
         B64.encode_string(v, &mut s);
         self.out.write_str(&s)?;
@@ -1049,6 +1486,7 @@ impl<'a, 'b, W: Write> Serializer for &'a mut YamlSerializer<'b, W> {
     }
 
     fn serialize_none(self) -> Result<()> {
+        self.charge_node(4)?;
         self.write_space_if_pending()?;
         self.write_scalar_prefix_if_anchor()?;
         self.state.last_value_was_block = false;
@@ -1065,6 +1503,7 @@ impl<'a, 'b, W: Write> Serializer for &'a mut YamlSerializer<'b, W> {
     }
 
     fn serialize_unit(self) -> Result<()> {
+        self.charge_node(4)?;
         self.write_space_if_pending()?;
         self.write_scalar_prefix_if_anchor()?;
         self.state.last_value_was_block = false;
@@ -1106,6 +1545,14 @@ impl<'a, 'b, W: Write> Serializer for &'a mut YamlSerializer<'b, W> {
                 self.state.pending_flow = Some(PendingFlow::AnySeq);
                 return value.serialize(self);
             }
+            #[cfg(feature = "robotics")]
+            NAME_RADIANS => {
+                // Only a float consumes the flag; clear it for anything else.
+                self.state.pending_angle = true;
+                value.serialize(&mut *self)?;
+                self.state.pending_angle = false;
+                return Ok(());
+            }
             NAME_FLOW_MAP => {
                 self.state.pending_flow = Some(PendingFlow::AnyMap);
                 return value.serialize(self);
@@ -1155,6 +1602,14 @@ impl<'a, 'b, W: Write> Serializer for &'a mut YamlSerializer<'b, W> {
             NAME_NULLABLE_TILDE => {
                 return self.serialize_tilde_null();
             }
+            NAME_TAGGED_SET | NAME_TAGGED_OMAP | NAME_TAGGED_PAIRS => {
+                self.state.pending_tag = Some(match name {
+                    NAME_TAGGED_SET => "set",
+                    NAME_TAGGED_OMAP => "omap",
+                    _ => "pairs",
+                });
+                return value.serialize(self);
+            }
             _ => {}
         }
         // default: ignore the name, serialize the inner as-is
@@ -1168,79 +1623,16 @@ impl<'a, 'b, W: Write> Serializer for &'a mut YamlSerializer<'b, W> {
         variant: &'static str,
         value: &T,
     ) -> Result<()> {
-        let was_inline_value = self.state.pending_layout.pending_space_after_colon;
-        let anchor_broke_line = self.anchors.pending_id.is_some();
-        let after_dash_depth = self.state.after_dash_depth;
-        self.write_anchor_for_complex_node()?;
-
-        // If we are the value of a mapping key, YAML forbids "key: Variant: value" inline.
-        // Emit the variant mapping on the next line indented one level. Also, do not insert
-        // a space after the colon when the value may itself be a mapping; instead, defer
-        // space insertion to the value serializer via pending_space_after_colon.
-        if was_inline_value {
-            // consume the pending space request and start a new line
-            self.state.pending_layout.pending_space_after_colon = false;
-            if !self.state.at_line_start {
-                self.newline()?;
-            }
-            // When used as a mapping value, indent relative to the parent mapping's base,
-            // not the serializer's current depth (which may still be the outer level).
-            let base = self.state.current_map_depth.unwrap_or(self.state.depth);
-            let variant_depth = checked_depth_add(base, 1)?;
-            self.write_indent(variant_depth)?;
-            self.write_key_scalar(variant)?;
-            // Write ':' without trailing space, then mark that a space may be needed
-            // if the following value is a scalar.
-            self.out.write_str(":")?;
-            self.state.pending_layout.pending_space_after_colon = true;
-            self.state.at_line_start = false;
-            // Do not let any inline-after-dash hint leak into the variant's inner value.
-            // After `Variant:`, the next node is in value position and must choose its own layout.
-            self.state.pending_layout.pending_inline_map = false;
-            // Ensure that if the value is another variant or a mapping/sequence,
-            // it indents under this variant label rather than the parent map key.
-            let prev_map_depth = self.state.current_map_depth.replace(variant_depth);
-            let res = value.serialize(&mut *self);
-            self.state.current_map_depth = prev_map_depth;
-            return res;
-        }
-        // Otherwise (top-level or sequence context).
-        if self.state.at_line_start {
-            let depth = if anchor_broke_line {
-                match after_dash_depth {
-                    Some(depth) => checked_depth_add(depth, 1)?,
-                    None => self.state.depth,
-                }
-            } else {
-                self.state.depth
-            };
-            self.write_indent(depth)?;
-        }
-        self.write_key_scalar(variant)?;
-        // Write ':' without a space and defer spacing/newline to the value serializer.
-        self.out.write_str(":")?;
-        self.state.pending_layout.pending_space_after_colon = true;
-        self.state.at_line_start = false;
-        // Do not let SeqSer's "inline first key after dash" hint leak into the variant's inner value.
-        // Without this, a struct/map value can start as `Variant: a: 1`.
-        self.state.pending_layout.pending_inline_map = false;
-        // If this variant is inside a block sequence element (`- Variant:`), ensure the nested
-        // value indents under the variant label rather than aligning with the list indentation.
-        // SeqSer stores the dash's indentation depth in `after_dash_depth`.
-        if let Some(d) = self.state.after_dash_depth.take() {
-            let nested_depth = checked_depth_add(d, 1)?;
-            let prev_map_depth = self.state.current_map_depth.replace(nested_depth);
-            let res = value.serialize(&mut *self);
-            self.state.current_map_depth = prev_map_depth;
-            res
-        } else {
-            value.serialize(&mut *self)
-        }
+        self.enter_collection()?;
+        let result = self.write_newtype_variant(variant, value);
+        self.leave_collection();
+        result
     }
 
     // -------- Collections --------
 
-    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq> {
+    fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq> {
+        self.enter_collection()?;
         let flow = self.take_flow_for_seq();
         if flow {
             self.write_scalar_prefix_if_anchor()?;
@@ -1257,6 +1649,7 @@ impl<'a, 'b, W: Write> Serializer for &'a mut YamlSerializer<'b, W> {
                 depth: depth_next,
                 flow: true,
                 first: true,
+                index: 0,
             })
         } else {
             // Block sequence. Decide indentation based on whether this is after a map key or after a list dash.
@@ -1267,7 +1660,7 @@ impl<'a, 'b, W: Write> Serializer for &'a mut YamlSerializer<'b, W> {
             // `write_anchor_for_complex_node` will handle emitting the anchor and newline.
             if self.state.pending_layout.pending_space_after_colon
                 && self.state.last_value_was_block
-                && self.anchors.pending_id.is_none()
+                && !self.has_pending_properties()
             {
                 self.state.pending_layout.pending_space_after_colon = false;
                 if !self.state.at_line_start {
@@ -1287,8 +1680,8 @@ impl<'a, 'b, W: Write> Serializer for &'a mut YamlSerializer<'b, W> {
                 && self.state.after_dash_depth.is_some()
                 && !self.state.pending_layout.pending_space_after_colon;
             // `inline_first` assumes we stay mid-line, but a pending anchor writes `&aN\n` first.
-            let anchor_broke_line = self.anchors.pending_id.is_some();
-            self.write_anchor_for_complex_node()?;
+            let anchor_broke_line = self.has_pending_properties();
+            self.write_anchor_for_collection(len)?;
             if inline_first {
                 if anchor_broke_line {
                     // Inlining now would drop the nested dashes to column 0, past the anchor.
@@ -1335,6 +1728,7 @@ impl<'a, 'b, W: Write> Serializer for &'a mut YamlSerializer<'b, W> {
                 depth: depth_next,
                 flow: false,
                 first: true,
+                index: 0,
             })
         }
     }
@@ -1367,8 +1761,9 @@ impl<'a, 'b, W: Write> Serializer for &'a mut YamlSerializer<'b, W> {
         variant: &'static str,
         _len: usize,
     ) -> Result<Self::SerializeTupleVariant> {
+        self.enter_collection()?;
         let was_inline_value = self.state.pending_layout.pending_space_after_colon;
-        let anchor_broke_line = self.anchors.pending_id.is_some();
+        let anchor_broke_line = self.has_pending_properties();
         let after_dash_depth = self.state.after_dash_depth;
         self.write_anchor_for_complex_node()?;
 
@@ -1393,6 +1788,7 @@ impl<'a, 'b, W: Write> Serializer for &'a mut YamlSerializer<'b, W> {
                 depth: depth_next,
                 flow: false,
                 first: true,
+                index: 0,
             });
         }
         // Otherwise (top-level or sequence context).
@@ -1420,10 +1816,12 @@ impl<'a, 'b, W: Write> Serializer for &'a mut YamlSerializer<'b, W> {
             depth: depth_next,
             flow: false,
             first: true,
+            index: 0,
         })
     }
 
     fn serialize_map(self, len: Option<usize>) -> Result<Self::SerializeMap> {
+        self.enter_collection()?;
         let flow = self.take_flow_for_map();
         if flow {
             self.write_scalar_prefix_if_anchor()?;
@@ -1447,9 +1845,7 @@ impl<'a, 'b, W: Write> Serializer for &'a mut YamlSerializer<'b, W> {
             // If we are a value following a block sibling, force a newline now.
             // However, if a complex-node anchor is pending, we must keep `key: &aN` inline;
             // `write_anchor_for_complex_node` will handle emitting the anchor and newline.
-            if was_inline_value
-                && self.state.last_value_was_block
-                && self.anchors.pending_id.is_none()
+            if was_inline_value && self.state.last_value_was_block && !self.has_pending_properties()
             {
                 self.state.pending_layout.pending_space_after_colon = false;
                 if !self.state.at_line_start {
@@ -1460,7 +1856,7 @@ impl<'a, 'b, W: Write> Serializer for &'a mut YamlSerializer<'b, W> {
                 self.state.last_value_was_block = false;
             }
 
-            self.write_anchor_for_complex_node()?;
+            self.write_anchor_for_collection(len)?;
             if inline_first {
                 // Suppress newline after a list dash for inline map first key.
                 self.state.pending_layout.pending_inline_map = false;
@@ -1532,8 +1928,9 @@ impl<'a, 'b, W: Write> Serializer for &'a mut YamlSerializer<'b, W> {
         variant: &'static str,
         _len: usize,
     ) -> Result<Self::SerializeStructVariant> {
+        self.enter_collection()?;
         let was_inline_value = self.state.pending_layout.pending_space_after_colon;
-        let anchor_broke_line = self.anchors.pending_id.is_some();
+        let anchor_broke_line = self.has_pending_properties();
         let after_dash_depth = self.state.after_dash_depth;
         self.write_anchor_for_complex_node()?;
 
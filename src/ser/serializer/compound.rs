@@ -7,6 +7,7 @@ use std::fmt::Write;
 use super::helpers::{BoolCapture, StrCapture, UsizeCapture, scalar_key_to_string};
 use super::{AnchorId, YamlSerializer};
 use crate::ser::options::CommentPosition;
+use crate::ser::quoting::Resolution;
 use crate::ser::{Error, Result};
 
 /// From the spec:
@@ -22,6 +23,17 @@ fn is_simple_key_text(text: &str) -> bool {
     text.len() <= SIMPLE_KEY_MAX_LEN || text.chars().count() <= SIMPLE_KEY_MAX_LEN
 }
 
+/// Key path segment for `key`: the string itself, or its YAML rendering for other scalars.
+fn path_segment<T: ?Sized + Serialize>(key: &T, resolution: Resolution) -> String {
+    let mut capture = StrCapture::default();
+    if key.serialize(&mut capture).is_ok()
+        && let Ok(text) = capture.finish()
+    {
+        return text;
+    }
+    scalar_key_to_string(key, resolution).unwrap_or_default()
+}
+
 // ------------------------------------------------------------
 // Seq / Tuple serializers
 // ------------------------------------------------------------
@@ -40,6 +52,8 @@ pub struct SeqSer<'a, 'b, W: Write> {
     pub(super) flow: bool,
     /// Whether the next element is the first (comma handling in flow style).
     pub(super) first: bool,
+    /// Index of the next element, used as its key path segment.
+    pub(super) index: usize,
 }
 
 impl<W: Write> SerializeTuple for SeqSer<'_, '_, W> {
@@ -60,6 +74,58 @@ impl<W: Write> SerializeSeq for SeqSer<'_, '_, W> {
     type Error = Error;
 
     fn serialize_element<T: ?Sized + Serialize>(&mut self, v: &T) -> Result<()> {
+        if self.ser.tracks_paths() {
+            self.ser.push_path(self.index.to_string());
+            let result = self.write_element(v);
+            self.ser.pop_path();
+            result
+        } else {
+            self.write_element(v)
+        }
+    }
+
+    fn end(self) -> Result<()> {
+        self.ser.leave_collection();
+        if self.flow {
+            self.ser.out.write_str("]")?;
+            if self.ser.state.in_flow == 0 {
+                self.ser.newline()?;
+            }
+        } else if self.first {
+            // Empty block-style sequence.
+            if self.ser.settings.empty_as_braces {
+                // If we were pending a space after a colon (map value position), write it now.
+                if self.ser.state.pending_layout.pending_space_after_colon {
+                    self.ser.out.write_str(" ")?;
+                    self.ser.state.pending_layout.pending_space_after_colon = false;
+                } else if self.ser.state.at_line_start {
+                    // If at line start, indent appropriately.
+                    self.ser.write_indent(self.depth)?;
+                }
+                self.ser.out.write_str("[]")?;
+                self.ser.newline()?;
+            } else {
+                // Preserve legacy behavior: just emit a newline (empty body).
+                // Clear map-value pending state so it does not leak into following elements.
+                self.ser.state.pending_layout.pending_space_after_colon = false;
+                self.ser.newline()?;
+            }
+        } else {
+            // Block collection finished and it was not empty.
+            self.ser.state.last_value_was_block = true;
+            // Clear any dash/inline hints so they cannot affect the next sibling value
+            // (e.g., a mapping field following a block sequence value).
+            self.ser.state.pending_layout.pending_inline_map = false;
+            self.ser.state.after_dash_depth = None;
+            self.ser.state.pending_layout.inline_map_after_dash = false;
+        }
+        Ok(())
+    }
+}
+
+impl<W: Write> SeqSer<'_, '_, W> {
+    /// Emit one element in flow or block style.
+    fn write_element<T: ?Sized + Serialize>(&mut self, v: &T) -> Result<()> {
         if self.flow {
             if !self.first {
                 self.ser.out.write_str(", ")?;
@@ -104,43 +170,7 @@ impl<W: Write> SerializeSeq for SeqSer<'_, '_, W> {
             v.serialize(&mut *self.ser)?;
         }
         self.first = false;
-        Ok(())
-    }
-
-    fn end(self) -> Result<()> {
-        if self.flow {
-            self.ser.out.write_str("]")?;
-            if self.ser.state.in_flow == 0 {
-                self.ser.newline()?;
-            }
-        } else if self.first {
-            // Empty block-style sequence.
-            if self.ser.settings.empty_as_braces {
-                // If we were pending a space after a colon (map value position), write it now.
-                if self.ser.state.pending_layout.pending_space_after_colon {
-                    self.ser.out.write_str(" ")?;
-                    self.ser.state.pending_layout.pending_space_after_colon = false;
-                } else if self.ser.state.at_line_start {
-                    // If at line start, indent appropriately.
-                    self.ser.write_indent(self.depth)?;
-                }
-                self.ser.out.write_str("[]")?;
-                self.ser.newline()?;
-            } else {
-                // Preserve legacy behavior: just emit a newline (empty body).
-                // Clear map-value pending state so it does not leak into following elements.
-                self.ser.state.pending_layout.pending_space_after_colon = false;
-                self.ser.newline()?;
-            }
-        } else {
-            // Block collection finished and it was not empty.
-            self.ser.state.last_value_was_block = true;
-            // Clear any dash/inline hints so they cannot affect the next sibling value
-            // (e.g., a mapping field following a block sequence value).
-            self.ser.state.pending_layout.pending_inline_map = false;
-            self.ser.state.after_dash_depth = None;
-            self.ser.state.pending_layout.inline_map_after_dash = false;
-        }
+        self.index += 1;
         Ok(())
     }
 }
@@ -306,6 +336,7 @@ impl<W: Write> SpecialTupleSer<'_, '_, W> {
                             }
                         } else {
                             // present == false: emit null and skip field #3
+                            self.ser.charge_node(4)?;
                             if self.ser.state.at_line_start {
                                 self.ser.write_indent(self.ser.state.depth)?;
                             }
@@ -408,6 +439,8 @@ pub struct MapSer<'a, 'b, W: Write> {
     entries_written: usize,
     /// Kind of key waiting for its corresponding value.
     pending_key: Option<MapKeyKind>,
+    /// Key path segment of the value expected next, when paths are tracked.
+    path_key: Option<String>,
 }
 
 /// Output layout selected when a mapping is created.
@@ -470,6 +503,7 @@ impl<'a, 'b, W: Write> MapSer<'a, 'b, W> {
             layout: MapLayout::Flow,
             entries_written: 0,
             pending_key: None,
+            path_key: None,
         }
     }
 
@@ -488,6 +522,7 @@ impl<'a, 'b, W: Write> MapSer<'a, 'b, W> {
             },
             entries_written: 0,
             pending_key: None,
+            path_key: None,
         }
     }
 
@@ -556,6 +591,44 @@ impl<'a, 'b, W: Write> MapSer<'a, 'b, W> {
         self.pending_key = Some(MapKeyKind::Complex);
         Ok(())
     }
+
+    /// Emit the value of the current entry.
+    fn write_value<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<()> {
+        let key_kind = self.pending_key.take();
+        if self.layout.is_flow() {
+            self.ser.with_in_flow(|s| value.serialize(s))?;
+        } else {
+            let saved_pending_inline_map = self.ser.state.pending_layout.pending_inline_map;
+            let saved_depth = self.ser.state.depth;
+            if key_kind == Some(MapKeyKind::Complex) {
+                if self.layout.align_after_dash() && self.ser.state.at_line_start {
+                    let base = self.depth.saturating_sub(1);
+                    self.ser.write_indent_spaces(base)?;
+                    self.ser.out.write_str("  ")?;
+                    self.ser.state.at_line_start = false;
+                } else {
+                    self.ser.write_indent(self.depth)?;
+                }
+                self.ser.out.write_str(":")?;
+                self.ser.state.pending_layout.pending_space_after_colon = true;
+                self.ser.state.pending_layout.pending_inline_map = true;
+                self.ser.state.at_line_start = false;
+                self.ser.state.depth = self.depth;
+            }
+            let prev_map_depth = self.ser.state.current_map_depth.replace(self.depth);
+            let result = value.serialize(&mut *self.ser);
+            self.ser.state.current_map_depth = prev_map_depth;
+            // Always restore the parent's pending_inline_map to avoid leaking inline hints
+            // across sibling values (e.g., after finishing a sequence value like `groups`).
+            self.ser.state.pending_layout.pending_inline_map = saved_pending_inline_map;
+            if key_kind == Some(MapKeyKind::Complex) {
+                self.ser.state.depth = saved_depth;
+            }
+            result?;
+        }
+        self.entries_written = self.entries_written.saturating_add(1);
+        Ok(())
+    }
 }
 
 impl<W: Write> SerializeMap for MapSer<'_, '_, W> {
@@ -563,11 +636,15 @@ impl<W: Write> SerializeMap for MapSer<'_, '_, W> {
     type Error = Error;
 
     fn serialize_key<T: ?Sized + Serialize>(&mut self, key: &T) -> Result<()> {
+        if self.ser.tracks_paths() {
+            self.path_key = Some(path_segment(key, self.ser.settings.resolution));
+        }
         if self.layout.is_flow() {
             if self.entries_written > 0 {
                 self.ser.out.write_str(", ")?;
             }
-            let text = scalar_key_to_string(key, self.ser.settings.yaml_12)?;
+            let text = scalar_key_to_string(key, self.ser.settings.resolution)?;
+            self.ser.charge_node(text.len())?;
             if is_simple_key_text(&text) {
                 self.ser.out.write_str(&text)?;
                 self.ser.out.write_str(": ")?;
@@ -598,11 +675,15 @@ impl<W: Write> SerializeMap for MapSer<'_, '_, W> {
             self.ser.state.pending_layout.pending_inline_map = false;
             self.ser.state.last_value_was_block = false;
 
-            match scalar_key_to_string(key, self.ser.settings.yaml_12) {
+            match scalar_key_to_string(key, self.ser.settings.resolution) {
                 Ok(text) if is_simple_key_text(&text) => {
+                    self.ser.charge_node(text.len())?;
                     self.write_simple_key(&text)?;
                 }
-                Ok(text) => self.write_explicit_scalar_key(&text)?,
+                Ok(text) => {
+                    self.ser.charge_node(text.len())?;
+                    self.write_explicit_scalar_key(&text)?;
+                }
                 Err(Error::Unexpected { msg }) if msg == "non-scalar key" => {
                     self.write_explicit_key(key)?;
                 }
@@ -613,43 +694,19 @@ impl<W: Write> SerializeMap for MapSer<'_, '_, W> {
     }
 
     fn serialize_value<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<()> {
-        let key_kind = self.pending_key.take();
-        if self.layout.is_flow() {
-            self.ser.with_in_flow(|s| value.serialize(s))?;
-        } else {
-            let saved_pending_inline_map = self.ser.state.pending_layout.pending_inline_map;
-            let saved_depth = self.ser.state.depth;
-            if key_kind == Some(MapKeyKind::Complex) {
-                if self.layout.align_after_dash() && self.ser.state.at_line_start {
-                    let base = self.depth.saturating_sub(1);
-                    self.ser.write_indent_spaces(base)?;
-                    self.ser.out.write_str("  ")?;
-                    self.ser.state.at_line_start = false;
-                } else {
-                    self.ser.write_indent(self.depth)?;
-                }
-                self.ser.out.write_str(":")?;
-                self.ser.state.pending_layout.pending_space_after_colon = true;
-                self.ser.state.pending_layout.pending_inline_map = true;
-                self.ser.state.at_line_start = false;
-                self.ser.state.depth = self.depth;
+        match self.path_key.take() {
+            Some(segment) => {
+                self.ser.push_path(segment);
+                let result = self.write_value(value);
+                self.ser.pop_path();
+                result
             }
-            let prev_map_depth = self.ser.state.current_map_depth.replace(self.depth);
-            let result = value.serialize(&mut *self.ser);
-            self.ser.state.current_map_depth = prev_map_depth;
-            // Always restore the parent's pending_inline_map to avoid leaking inline hints
-            // across sibling values (e.g., after finishing a sequence value like `groups`).
-            self.ser.state.pending_layout.pending_inline_map = saved_pending_inline_map;
-            if key_kind == Some(MapKeyKind::Complex) {
-                self.ser.state.depth = saved_depth;
-            }
-            result?;
+            None => self.write_value(value),
         }
-        self.entries_written = self.entries_written.saturating_add(1);
-        Ok(())
     }
 
     fn end(self) -> Result<()> {
+        self.ser.leave_collection();
         if self.layout.is_flow() {
             self.ser.out.write_str("}")?;
             if self.ser.state.in_flow == 0 {
@@ -725,7 +782,8 @@ impl<W: Write> SerializeStructVariant for StructVariantSer<'_, '_, W> {
         key: &'static str,
         value: &T,
     ) -> Result<()> {
-        let text = scalar_key_to_string(&key, self.ser.settings.yaml_12)?;
+        let text = scalar_key_to_string(&key, self.ser.settings.resolution)?;
+        self.ser.charge_node(text.len())?;
         self.ser.write_indent(self.depth)?;
         self.ser.out.write_str(&text)?;
         // Defer spacing/newline decision to the value serializer similarly to map entries.
@@ -734,11 +792,19 @@ impl<W: Write> SerializeStructVariant for StructVariantSer<'_, '_, W> {
         self.ser.state.at_line_start = false;
         // Ensure nested mappings/collections used as this field's value indent relative to this struct variant.
         let prev_map_depth = self.ser.state.current_map_depth.replace(self.depth);
+        let tracks_paths = self.ser.tracks_paths();
+        if tracks_paths {
+            self.ser.push_path(key.to_string());
+        }
         let result = value.serialize(&mut *self.ser);
+        if tracks_paths {
+            self.ser.pop_path();
+        }
         self.ser.state.current_map_depth = prev_map_depth;
         result
     }
     fn end(self) -> Result<()> {
+        self.ser.leave_collection();
         Ok(())
     }
 }
@@ -1,4 +1,6 @@
-use serde_core::ser::{self, Serialize, SerializeTupleStruct, Serializer};
+use serde_core::ser::{
+    self, Serialize, SerializeMap, SerializeSeq, SerializeTupleStruct, Serializer,
+};
 use std::cell::RefCell;
 use std::rc::Rc;
 use std::sync::{Arc, Mutex};
@@ -6,12 +8,13 @@ use std::sync::{Arc, Mutex};
 use crate::{
     ArcAnchor, ArcRecursion, ArcRecursive, ArcWeakAnchor, Commented, DoubleQuoted, FlowMap,
     FlowSeq, NullableTilde, RcAnchor, RcRecursion, RcRecursive, RcWeakAnchor, SingleQuoted,
-    SpaceAfter,
+    SpaceAfter, TaggedOmap, TaggedPairs, TaggedSet,
 };
 
 use super::{
     NAME_DOUBLE_QUOTED, NAME_FLOW_MAP, NAME_FLOW_SEQ, NAME_NULLABLE_TILDE, NAME_SINGLE_QUOTED,
-    NAME_SPACE_AFTER, NAME_TUPLE_ANCHOR, NAME_TUPLE_COMMENTED, NAME_TUPLE_WEAK,
+    NAME_SPACE_AFTER, NAME_TAGGED_OMAP, NAME_TAGGED_PAIRS, NAME_TAGGED_SET, NAME_TUPLE_ANCHOR,
+    NAME_TUPLE_COMMENTED, NAME_TUPLE_WEAK,
 };
 
 // ------------------------------------------------------------
@@ -197,6 +200,57 @@ impl<T: Serialize> Serialize for NullableTilde<T> {
     }
 }
 
+// YAML 1.1 collection tags: the payload is the plain YAML shape of the collection.
+struct SetEntries<'a, T>(&'a T);
+struct PairEntries<'a, K, V>(&'a [(K, V)]);
+struct SinglePair<'a, K, V>(&'a K, &'a V);
+
+impl<T> Serialize for SetEntries<'_, T>
+where
+    for<'a> &'a T: IntoIterator,
+    for<'a> <&'a T as IntoIterator>::Item: Serialize,
+{
+    fn serialize<S: Serializer>(&self, s: S) -> std::result::Result<S::Ok, S::Error> {
+        s.collect_map(self.0.into_iter().map(|element| (element, ())))
+    }
+}
+impl<K: Serialize, V: Serialize> Serialize for PairEntries<'_, K, V> {
+    fn serialize<S: Serializer>(&self, s: S) -> std::result::Result<S::Ok, S::Error> {
+        let mut seq = s.serialize_seq(Some(self.0.len()))?;
+        for (key, value) in self.0 {
+            seq.serialize_element(&SinglePair(key, value))?;
+        }
+        seq.end()
+    }
+}
+impl<K: Serialize, V: Serialize> Serialize for SinglePair<'_, K, V> {
+    fn serialize<S: Serializer>(&self, s: S) -> std::result::Result<S::Ok, S::Error> {
+        let mut map = s.serialize_map(Some(1))?;
+        map.serialize_entry(self.0, self.1)?;
+        map.end()
+    }
+}
+
+impl<T> Serialize for TaggedSet<T>
+where
+    for<'a> &'a T: IntoIterator,
+    for<'a> <&'a T as IntoIterator>::Item: Serialize,
+{
+    fn serialize<S: Serializer>(&self, s: S) -> std::result::Result<S::Ok, S::Error> {
+        s.serialize_newtype_struct(NAME_TAGGED_SET, &SetEntries(&self.0))
+    }
+}
+impl<K: Serialize, V: Serialize> Serialize for TaggedOmap<K, V> {
+    fn serialize<S: Serializer>(&self, s: S) -> std::result::Result<S::Ok, S::Error> {
+        s.serialize_newtype_struct(NAME_TAGGED_OMAP, &PairEntries(&self.0))
+    }
+}
+impl<K: Serialize, V: Serialize> Serialize for TaggedPairs<K, V> {
+    fn serialize<S: Serializer>(&self, s: S) -> std::result::Result<S::Ok, S::Error> {
+        s.serialize_newtype_struct(NAME_TAGGED_PAIRS, &PairEntries(&self.0))
+    }
+}
+
 impl<T: Serialize> Serialize for Commented<T> {
     fn serialize<S: Serializer>(&self, s: S) -> std::result::Result<S::Ok, S::Error> {
         // Represent as a special tuple-struct with two fields: (comment, value)
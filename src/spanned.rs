@@ -175,6 +175,28 @@ impl<T> Spanned<T> {
             defined,
         }
     }
+
+    /// Render `message` with a snippet at [`Spanned::referenced`].
+    ///
+    /// The source is looked up in `registry`, so values from included files are shown
+    /// in their own file together with the include site.
+    #[cfg(feature = "deserialize")]
+    #[must_use]
+    pub fn render_snippet(&self, registry: &crate::SourceRegistry, message: &str) -> String {
+        registry.render_snippet(&self.referenced, message)
+    }
+
+    /// Render `message` with a snippet at [`Spanned::defined`], such as the anchor an
+    /// alias refers to.
+    #[cfg(feature = "deserialize")]
+    #[must_use]
+    pub fn render_defined_snippet(
+        &self,
+        registry: &crate::SourceRegistry,
+        message: &str,
+    ) -> String {
+        registry.render_snippet(&self.defined, message)
+    }
 }
 
 impl<'de, T> Deserialize<'de> for Spanned<T>
@@ -92,6 +92,55 @@ pub struct SingleQuoted<T>(pub T);
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct SpaceAfter<T>(pub T);
 
+/// Emit a set as a YAML 1.1 `!!set`: a mapping of the elements to null.
+///
+/// Any collection whose references iterate over serializable elements can be wrapped,
+/// for example `BTreeSet`, `HashSet` or `Vec`. Deserialization is transparent: the
+/// inner collection is read from the `!!set` keys.
+///
+/// ```rust
+/// # #[cfg(feature = "serialize")]
+/// # {
+/// use std::collections::BTreeSet;
+/// use serde::Serialize;
+/// use serde_saphyr::TaggedSet;
+///
+/// #[derive(Serialize)]
+/// struct Config {
+///     features: TaggedSet<BTreeSet<&'static str>>,
+/// }
+///
+/// let cfg = Config { features: TaggedSet(BTreeSet::from(["tls", "gzip"])) };
+/// let yaml = serde_saphyr::to_string(&cfg).unwrap();
+/// assert_eq!(yaml, "features: !!set\n  gzip: null\n  tls: null\n");
+/// # }
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TaggedSet<T>(pub T);
+
+/// Emit ordered key/value pairs as a YAML 1.1 `!!omap`: a sequence of single-pair mappings.
+///
+/// Keys are expected to be unique; reading an `!!omap` with a repeated key fails with
+/// [`Error::DuplicateOmapKey`](crate::Error::DuplicateOmapKey). Use [`TaggedPairs`] when
+/// keys may repeat. Deserialization accepts both `!!omap` and `!!pairs`.
+///
+/// ```rust
+/// # #[cfg(feature = "serialize")]
+/// # {
+/// use serde_saphyr::TaggedOmap;
+///
+/// let steps = TaggedOmap(vec![("build", 1), ("test", 2)]);
+/// let yaml = serde_saphyr::to_string(&steps).unwrap();
+/// assert_eq!(yaml, "!!omap\n- build: 1\n- test: 2\n");
+/// # }
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TaggedOmap<K, V>(pub Vec<(K, V)>);
+
+/// Emit key/value pairs as a YAML 1.1 `!!pairs`: like [`TaggedOmap`], but keys may repeat.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TaggedPairs<K, V>(pub Vec<(K, V)>);
+
 /// Serialize `None` as YAML tilde (`~`) while otherwise behaving like `Option<T>`.
 ///
 /// `Some(value)` is serialized transparently as `value`. `None` is serialized
@@ -295,6 +344,24 @@ impl<'de, T: Deserialize<'de>> Deserialize<'de> for SpaceAfter<T> {
     }
 }
 
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for TaggedSet<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        T::deserialize(deserializer).map(TaggedSet)
+    }
+}
+
+impl<'de, K: Deserialize<'de>, V: Deserialize<'de>> Deserialize<'de> for TaggedOmap<K, V> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        Vec::deserialize(deserializer).map(TaggedOmap)
+    }
+}
+
+impl<'de, K: Deserialize<'de>, V: Deserialize<'de>> Deserialize<'de> for TaggedPairs<K, V> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        Vec::deserialize(deserializer).map(TaggedPairs)
+    }
+}
+
 impl<'de, T: Deserialize<'de>> Deserialize<'de> for NullableTilde<T> {
     fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
         Option::<T>::deserialize(deserializer).map(NullableTilde)
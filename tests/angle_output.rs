@@ -0,0 +1,125 @@
+#![cfg(all(feature = "serialize", feature = "deserialize", feature = "robotics"))]
+use core::f64::consts::{FRAC_PI_2, PI};
+
+use serde::{Deserialize, Serialize};
+use serde_saphyr::{AngleFormat, AngleStyle, FlowSeq, Radians};
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+struct Calibration {
+    yaw: Radians<f64>,
+    pitch: Radians<f32>,
+    offset: Radians<f64>,
+    joints: FlowSeq<Vec<Radians<f64>>>,
+}
+
+fn calibration() -> Calibration {
+    Calibration {
+        yaw: Radians(FRAC_PI_2),
+        pitch: Radians(-45f32.to_radians()),
+        offset: Radians(0.123),
+        joints: FlowSeq(vec![
+            Radians(0.0),
+            Radians(PI),
+            Radians(12.5f64.to_radians()),
+        ]),
+    }
+}
+
+#[track_caller]
+fn assert_round_trips(yaml: &str) {
+    let options = serde_saphyr::options! { angle_conversions: true };
+    let back: Calibration = serde_saphyr::from_str_with_options(yaml, options).unwrap();
+    let expected = calibration();
+    let pairs = [
+        (back.yaw.0, expected.yaw.0),
+        (f64::from(back.pitch.0), f64::from(expected.pitch.0)),
+        (back.offset.0, expected.offset.0),
+    ]
+    .into_iter()
+    .chain(
+        back.joints
+            .0
+            .iter()
+            .map(|j| j.0)
+            .zip(expected.joints.0.iter().map(|j| j.0)),
+    );
+    for (actual, expected) in pairs {
+        assert!(
+            (actual - expected).abs() < 1e-6,
+            "{actual} != {expected} in\n{yaml}"
+        );
+    }
+}
+
+#[test]
+fn nice_angles_are_written_with_deg_by_default() {
+    let yaml = serde_saphyr::to_string(&calibration()).unwrap();
+    assert_eq!(
+        yaml,
+        "yaw: deg(90)\npitch: deg(-45)\noffset: 0.123\njoints: [deg(0), deg(180), 0.2181661564992912]\n"
+    );
+    assert_round_trips(&yaml);
+}
+
+#[test]
+fn degrees_tag_style_with_one_decimal() {
+    let options = serde_saphyr::ser_options! {
+        angle_format: AngleFormat::new(AngleStyle::DegreesTag).with_precision(1),
+    };
+    let yaml = serde_saphyr::to_string_with_options(&calibration(), options).unwrap();
+    assert!(yaml.contains("yaw: !degrees 90\n"), "{yaml}");
+    assert!(yaml.contains("!degrees 12.5]"), "{yaml}");
+    assert!(yaml.contains("offset: 0.123\n"), "{yaml}");
+    assert_round_trips(&yaml);
+}
+
+#[test]
+fn a_coarse_tolerance_reads_back_the_rounded_value() {
+    let options = serde_saphyr::ser_options! {
+        angle_format: AngleFormat::default().with_tolerance(0.1),
+    };
+    let value = Radians(90.05f64.to_radians());
+    let yaml = serde_saphyr::to_string_with_options(&value, options).unwrap();
+    assert_eq!(yaml, "deg(90)\n");
+
+    let options = serde_saphyr::options! { angle_conversions: true };
+    let back: Radians<f64> = serde_saphyr::from_str_with_options(&yaml, options).unwrap();
+    assert_eq!(back, Radians(FRAC_PI_2));
+    assert_ne!(back, value);
+}
+
+#[test]
+fn f32_angles_are_compared_in_f32_precision() {
+    // Widened to f64, -45° in f32 is -45.0000014°, well outside the default tolerance.
+    let value = Radians(-45f32.to_radians());
+    assert_eq!(serde_saphyr::to_string(&value).unwrap(), "deg(-45)\n");
+    assert!((f64::from(value.0).to_degrees() + 45.0).abs() > 1e-6);
+}
+
+#[test]
+fn non_float_radians_do_not_affect_later_floats() {
+    let yaml = serde_saphyr::to_string(&(Radians(1), PI)).unwrap();
+    assert_eq!(yaml, "- 1\n- 3.141592653589793\n");
+}
+
+#[test]
+fn radians_style_and_plain_floats_are_unchanged() {
+    let options = serde_saphyr::ser_options! {
+        angle_format: AngleFormat::new(AngleStyle::Radians),
+    };
+    let yaml = serde_saphyr::to_string_with_options(&Radians(FRAC_PI_2), options).unwrap();
+    assert_eq!(yaml, "1.5707963267948966\n");
+    assert_eq!(
+        serde_saphyr::to_string(&FRAC_PI_2).unwrap(),
+        "1.5707963267948966\n"
+    );
+}
+
+#[test]
+fn invalid_angle_format_is_rejected() {
+    let options = serde_saphyr::ser_options! {
+        angle_format: AngleFormat::default().with_tolerance(f64::NAN),
+    };
+    let err = serde_saphyr::to_string_with_options(&Radians(PI), options).unwrap_err();
+    assert!(err.to_string().contains("tolerance"), "{err}");
+}
@@ -0,0 +1,170 @@
+#![cfg(feature = "deserialize")]
+use serde::Deserialize;
+use serde_saphyr::localizer::{ExternalMessage, ExternalMessageSource, Localizer};
+use serde_saphyr::{
+    DefaultMessageFormatter, Error, Location, ValidationContext, ValidationIssue, ValidationSource,
+    path_key,
+};
+use std::borrow::Cow;
+
+#[derive(Debug, Deserialize)]
+struct Port {
+    name: String,
+    number: u32,
+}
+
+#[derive(Debug, Deserialize)]
+struct Service {
+    min_replicas: u32,
+    max_replicas: u32,
+    ports: Vec<Port>,
+}
+
+fn check_service(service: &Service, ctx: &mut ValidationContext) {
+    if service.min_replicas > service.max_replicas {
+        ctx.report(
+            path_key!["max_replicas"],
+            "replica_range",
+            "max_replicas must not be below min_replicas",
+        );
+    }
+    for (i, port) in service.ports.iter().enumerate() {
+        if port.number == 0 {
+            ctx.push(
+                ValidationIssue::new(path_key!["ports", i, "number"], "port_range")
+                    .with_params(vec![("port".into(), port.name.clone())]),
+            );
+        }
+    }
+}
+
+const VALID: &str = "min_replicas: 1\nmax_replicas: 3\nports:\n  - name: http\n    number: 80\n";
+
+#[test]
+fn valid_document_passes() {
+    let service: Service = serde_saphyr::from_str_with_validator(VALID, check_service).unwrap();
+    assert_eq!(service.ports[0].number, 80);
+}
+
+#[test]
+fn issues_are_located_by_path() {
+    let yaml = "min_replicas: 5\nmax_replicas: 3\nports:\n  - name: http\n    number: 0\n";
+    let err = serde_saphyr::from_str_with_validator::<Service, _>(yaml, check_service).unwrap_err();
+
+    let Error::ValidationError { source, issues, .. } = err.without_snippet() else {
+        panic!("expected a validation error, got {err:?}");
+    };
+    assert_eq!(*source, ValidationSource::Custom);
+    assert_eq!(issues.len(), 2);
+    assert_eq!(issues[1].code, "port_range");
+    assert_eq!(err.location().unwrap().line(), 2);
+
+    let rendered = err.to_string();
+    assert!(
+        rendered.contains("max_replicas must not be below min_replicas"),
+        "{rendered}"
+    );
+    assert!(rendered.contains("port_range (port=http)"), "{rendered}");
+    assert!(rendered.contains("5 |     number: 0"), "{rendered}");
+}
+
+#[test]
+fn alias_reports_anchor_definition() {
+    #[derive(Debug, Deserialize)]
+    struct Pair {
+        first: String,
+        second: String,
+    }
+    let yaml = "first: &a x\nsecond: *a\n";
+    let err = serde_saphyr::from_str_with_validator(yaml, |p: &Pair, ctx| {
+        if p.second.len() < 2 {
+            ctx.report(path_key!["second"], "length", "too short");
+        }
+        assert!(ctx.has_issues());
+        assert_eq!(p.first, p.second);
+    })
+    .unwrap_err();
+
+    let locations = err.locations().unwrap();
+    assert_eq!(locations.reference_location.line(), 2);
+    assert_eq!(locations.defined_location.line(), 1);
+    assert!(err.to_string().contains("anchor"), "{err}");
+}
+
+#[test]
+fn multiple_documents_collect_all_issues() {
+    let yaml = "value: 1\n---\nvalue: -1\n---\nvalue: -2\n";
+    #[derive(Debug, Deserialize)]
+    struct Doc {
+        value: i32,
+    }
+    let err = serde_saphyr::from_multiple_with_options_and_validator(
+        yaml,
+        serde_saphyr::options! {},
+        |d: &Doc, ctx| {
+            if d.value < 0 {
+                ctx.report(path_key!["value"], "negative", "value must be positive");
+            }
+        },
+    )
+    .unwrap_err();
+    let Error::ValidationErrors { source, errors } = &err else {
+        panic!("expected aggregated errors, got {err:?}");
+    };
+    assert_eq!(*source, ValidationSource::Custom);
+    assert_eq!(errors.len(), 2);
+    assert_eq!(errors[1].location().unwrap().line(), 5);
+}
+
+#[test]
+fn reader_variants_run_the_hook() {
+    let service: Service = serde_saphyr::from_reader_with_options_and_validator(
+        VALID.as_bytes(),
+        serde_saphyr::options! {},
+        check_service,
+    )
+    .unwrap();
+    assert_eq!(service.max_replicas, 3);
+
+    let yaml = "min_replicas: 1\nmax_replicas: 1\nports: []\n---\nmin_replicas: 2\nmax_replicas: 1\nports: []\n";
+    let mut reader = yaml.as_bytes();
+    let results: Vec<Result<Service, Error>> = serde_saphyr::read_with_options_and_validator(
+        &mut reader,
+        serde_saphyr::options! {},
+        check_service,
+    )
+    .collect();
+    assert_eq!(results.len(), 2);
+    assert!(results[0].is_ok());
+    assert!(
+        results[1]
+            .as_ref()
+            .unwrap_err()
+            .to_string()
+            .contains("max_replicas")
+    );
+}
+
+#[test]
+fn localizer_can_override_custom_issues() {
+    struct Translate;
+    impl Localizer for Translate {
+        fn override_external_message<'a>(&self, msg: ExternalMessage<'a>) -> Option<Cow<'a, str>> {
+            match (msg.source, msg.code) {
+                (ExternalMessageSource::Custom, Some("replica_range")) => {
+                    Some(Cow::Borrowed("TRANSLATED_RANGE"))
+                }
+                _ => None,
+            }
+        }
+
+        fn attach_location<'a>(&self, base: Cow<'a, str>, _loc: Location) -> Cow<'a, str> {
+            base
+        }
+    }
+
+    let yaml = "min_replicas: 5\nmax_replicas: 3\nports: []\n";
+    let err = serde_saphyr::from_str_with_validator::<Service, _>(yaml, check_service).unwrap_err();
+    let rendered = err.render_with_formatter(&DefaultMessageFormatter.with_localizer(&Translate));
+    assert!(rendered.contains("TRANSLATED_RANGE"), "{rendered}");
+}
@@ -0,0 +1,125 @@
+#![cfg(all(feature = "serialize", feature = "deserialize"))]
+use std::collections::BTreeMap;
+use std::io::Read;
+use std::time::{Duration, Instant};
+
+use serde_saphyr::{CancellationToken, Error, Interruption};
+
+fn interruption(err: &Error) -> Option<(Interruption, usize)> {
+    match err.without_snippet() {
+        Error::Interrupted { reason, location } => Some((*reason, location.line() as usize)),
+        _ => None,
+    }
+}
+
+#[test]
+fn unexpired_deadline_and_live_token_do_not_interfere() {
+    let token = CancellationToken::new();
+    let options = serde_saphyr::options! {}
+        .with_timeout(Duration::from_secs(60))
+        .with_cancellation(&token);
+    let v: Vec<u8> = serde_saphyr::from_str_with_options("[1, 2, 3]", options).unwrap();
+    assert_eq!(v, [1, 2, 3]);
+    assert!(!token.is_cancelled());
+}
+
+#[test]
+fn passed_deadline_stops_parsing() {
+    let options = serde_saphyr::options! {}.with_deadline(Instant::now());
+    let err = serde_saphyr::from_str_with_options::<Vec<u8>>("[1, 2]", options).unwrap_err();
+    assert_eq!(
+        interruption(&err),
+        Some((Interruption::DeadlineExceeded, 1))
+    );
+}
+
+#[test]
+fn token_cancelled_from_another_thread() {
+    let token = CancellationToken::new();
+    let remote = token.clone();
+    std::thread::spawn(move || remote.cancel()).join().unwrap();
+    assert!(token.is_cancelled());
+
+    let options = serde_saphyr::options! {}.with_cancellation(&token);
+    let err = serde_saphyr::from_str_with_options::<Vec<u8>>("[1]", options).unwrap_err();
+    assert_eq!(interruption(&err), Some((Interruption::Cancelled, 1)));
+}
+
+/// Yields at most 16 bytes per read and cancels the token after `cancel_after` reads.
+struct SlowReader {
+    data: Vec<u8>,
+    pos: usize,
+    reads: usize,
+    cancel_after: usize,
+    token: CancellationToken,
+}
+
+impl Read for SlowReader {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.reads += 1;
+        if self.reads > self.cancel_after {
+            self.token.cancel();
+        }
+        let n = buf.len().min(16).min(self.data.len() - self.pos);
+        buf[..n].copy_from_slice(&self.data[self.pos..self.pos + n]);
+        self.pos += n;
+        Ok(n)
+    }
+}
+
+#[test]
+fn slow_reader_is_interrupted_where_parsing_got_to() {
+    let token = CancellationToken::new();
+    let reader = SlowReader {
+        data: (0..100)
+            .map(|i| format!("key{i}: {i}\n"))
+            .collect::<String>()
+            .into_bytes(),
+        pos: 0,
+        reads: 0,
+        cancel_after: 20,
+        token: token.clone(),
+    };
+    let options = serde_saphyr::options! {}.with_cancellation(&token);
+    let err = serde_saphyr::from_reader_with_options::<_, BTreeMap<String, u32>>(reader, options)
+        .unwrap_err();
+    let (reason, line) = interruption(&err).expect("interrupted");
+    assert_eq!(reason, Interruption::Cancelled);
+    assert!((2..100).contains(&line), "stopped at line {line}");
+}
+
+#[test]
+fn interrupted_error_renders_with_snippet() {
+    let options = serde_saphyr::options! {}.with_deadline(Instant::now());
+    let err = serde_saphyr::from_str_with_options::<Vec<u8>>("[1, 2]", options).unwrap_err();
+    let rendered = err.to_string();
+    assert!(rendered.contains("deadline exceeded"), "{rendered}");
+    assert!(rendered.contains("[1, 2]"), "{rendered}");
+}
+
+#[cfg(feature = "include")]
+#[test]
+fn include_resolver_running_past_cancellation_is_reported_at_include() {
+    let token = CancellationToken::new();
+    let resolver_token = token.clone();
+    let options = serde_saphyr::options! {}
+        .with_cancellation(&token)
+        .with_include_resolver(move |req| {
+            resolver_token.cancel();
+            Ok(serde_saphyr::ResolvedInclude::new(
+                req.spec,
+                req.spec,
+                serde_saphyr::InputSource::from_string("1\n".to_string()),
+            ))
+        });
+    let yaml = "a: 1\nb: !include slow.yaml\n";
+    let err =
+        serde_saphyr::from_str_with_options::<BTreeMap<String, u8>>(yaml, options).unwrap_err();
+    match err.without_snippet() {
+        Error::Interrupted { reason, location } => {
+            assert_eq!(*reason, Interruption::Cancelled);
+            assert_eq!(location.line(), 2);
+        }
+        other => panic!("unexpected error: {other:?}"),
+    }
+}
@@ -0,0 +1,194 @@
+#![cfg(feature = "deserialize")]
+
+use serde_saphyr::diff::{self, ChangeKind, Document};
+use serde_saphyr::path_key;
+
+fn summary(old: &str, new: &str) -> Vec<String> {
+    diff::diff(old, new)
+        .unwrap()
+        .iter()
+        .map(ToString::to_string)
+        .collect()
+}
+
+#[test]
+fn formatting_and_comments_are_ignored() {
+    let old = "name: demo\nports: [80, 443]\nenabled: true\n";
+    let new =
+        "# service\nname: \"demo\"   # quoted now\nports:\n  - 80\n  - 0x1bb\nenabled: true\n";
+    assert!(summary(old, new).is_empty());
+}
+
+#[test]
+fn added_removed_and_changed_keys() {
+    let old = "a: 1\nb:\n  c: x\n  d: [1, 2]\n";
+    let new = "a: 1\nb:\n  c: z\n  e: {f: null}\n";
+    assert_eq!(
+        summary(old, new),
+        [
+            "changed b.c: x -> z",
+            "added b.e: {f: null}",
+            "removed b.d: [1, 2]",
+        ]
+    );
+}
+
+#[test]
+fn changes_carry_locations_in_both_inputs() {
+    let old = "a: 1\nb:\n  c: x\n  d: 2\n";
+    let new = "b:\n  c: y\na: 1\n";
+    let changes = diff::diff(old, new).unwrap();
+
+    let changed = changes
+        .iter()
+        .find(|change| change.kind == ChangeKind::Changed)
+        .unwrap();
+    assert_eq!(changed.path, path_key!["b", "c"]);
+    let old_location = changed.old_location.unwrap();
+    let new_location = changed.new_location.unwrap();
+    assert_eq!((old_location.line(), old_location.column()), (3, 6));
+    assert_eq!((new_location.line(), new_location.column()), (2, 6));
+
+    // A removal points at the mapping it was removed from in the new input.
+    let removed = changes
+        .iter()
+        .find(|change| change.kind == ChangeKind::Removed)
+        .unwrap();
+    assert_eq!(removed.path.to_string(), "b.d");
+    assert_eq!(removed.old_location.unwrap().line(), 4);
+    assert_eq!(removed.new_location.unwrap().line(), 2);
+}
+
+#[test]
+fn reordered_keys() {
+    let old = "a: 1\nb: 2\nc: 3\n";
+    let new = "b: 2\nc: 3\na: 1\n";
+    let changes = diff::diff(old, new).unwrap();
+    assert_eq!(changes.len(), 1, "{changes:?}");
+    assert_eq!(
+        changes[0].kind,
+        ChangeKind::Reordered {
+            old_index: 0,
+            new_index: 2
+        }
+    );
+    assert_eq!(changes[0].to_string(), "reordered a: position 0 -> 2");
+    assert_eq!(changes[0].old_location.unwrap().line(), 1);
+    assert_eq!(changes[0].new_location.unwrap().line(), 3);
+}
+
+#[test]
+fn sequence_insertion_is_one_addition() {
+    let old = "items: [a, b, c]\n";
+    let new = "items: [a, x, b, c]\n";
+    assert_eq!(summary(old, new), ["added items[1]: x"]);
+}
+
+#[test]
+fn sequence_items_are_matched_by_value() {
+    let old =
+        "servers:\n  - {host: a, port: 80}\n  - {host: b, port: 81}\n  - {host: c, port: 82}\n";
+    let new =
+        "servers:\n  - {host: c, port: 82}\n  - {host: a, port: 80}\n  - {host: b, port: 90}\n";
+    assert_eq!(
+        summary(old, new),
+        [
+            "reordered servers[0]: position 2 -> 0",
+            "changed servers[2].port: 81 -> 90",
+        ]
+    );
+}
+
+#[test]
+fn items_that_keep_their_index_are_not_reordered() {
+    // `2` is still at index 1, so only the item that changed position is reported.
+    assert_eq!(
+        summary("l: [1, 2, 3]\n", "l: [3, 2, 1]\n"),
+        ["reordered l[0]: position 2 -> 0"]
+    );
+}
+
+#[test]
+fn large_reordered_mapping() {
+    let keys: Vec<String> = (0..20_000).map(|i| format!("k{i}: {i}\n")).collect();
+    let old = keys.concat();
+    let mut rotated = keys.clone();
+    rotated.rotate_left(1);
+    let changes = diff::diff(&old, &rotated.concat()).unwrap();
+    assert_eq!(changes.len(), 1, "{:?}", &changes[..changes.len().min(3)]);
+    assert_eq!(changes[0].to_string(), "reordered k0: position 0 -> 19999");
+}
+
+#[test]
+fn large_sequences_are_compared_by_index() {
+    let items: Vec<String> = (0..20_000).map(|i| format!("- {i}\n")).collect();
+    let old = items.concat();
+    let mut shifted = items.clone();
+    shifted[5] = "- changed\n".to_owned();
+    shifted.push("- last\n".to_owned());
+    assert_eq!(
+        summary(&old, &shifted.concat()),
+        ["changed [5]: 5 -> changed", "added [20000]: last"]
+    );
+}
+
+#[test]
+fn types_are_part_of_the_value() {
+    let old = "a: 1\nb: '1'\nc: 1.0\nd: ~\n";
+    let new = "a: '1'\nb: 1\nc: 1.0e0\nd: null\n";
+    assert_eq!(
+        summary(old, new),
+        ["changed a: 1 -> \"1\"", "changed b: \"1\" -> 1"]
+    );
+}
+
+#[test]
+fn aliases_and_merge_keys_are_resolved() {
+    let old = "base: &base {retries: 3, timeout: 5}\nservice:\n  <<: *base\n  timeout: 10\n";
+    let new = "base: {retries: 3, timeout: 5}\nservice: {timeout: 10, retries: 4}\n";
+    let changes = diff::diff(old, new).unwrap();
+    assert_eq!(changes.len(), 1, "{changes:?}");
+    assert_eq!(changes[0].to_string(), "changed service.retries: 3 -> 4");
+    assert_eq!(changes[0].new_location.unwrap().line(), 2);
+}
+
+#[test]
+fn root_changes() {
+    assert_eq!(summary("1\n", "[1]\n"), ["changed <root>: 1 -> [1]"]);
+}
+
+#[test]
+fn documents_can_be_compared_repeatedly() {
+    let base = "a: 1\n".parse::<Document>().unwrap();
+    let same = "{a: 1}".parse::<Document>().unwrap();
+    let other = "a: 2\n".parse::<Document>().unwrap();
+    assert!(base.diff(&same).is_empty());
+    assert_eq!(base.diff(&other).len(), 1);
+    assert_eq!(other.diff(&base)[0].to_string(), "changed a: 2 -> 1");
+}
+
+#[test]
+fn invalid_input_is_an_error() {
+    let err = diff::diff("a: 1\n", "a: [1\n").unwrap_err();
+    assert!(err.location().is_some(), "{err}");
+}
+
+#[cfg(feature = "include")]
+#[test]
+fn includes_are_resolved() {
+    let options = serde_saphyr::options! {}.with_include_resolver(|req| {
+        Ok(serde_saphyr::ResolvedInclude::new(
+            format!("/etc/app/{}", req.spec),
+            req.spec,
+            serde_saphyr::InputSource::from_string("port: 8080\n".to_string()),
+        ))
+    });
+    let changes = diff::diff_with_options(
+        "server: !include server.yaml\n",
+        "server:\n  port: 80\n",
+        options,
+    )
+    .unwrap();
+    assert_eq!(changes.len(), 1, "{changes:?}");
+    assert_eq!(changes[0].to_string(), "changed server.port: 8080 -> 80");
+}
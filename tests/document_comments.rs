@@ -0,0 +1,112 @@
+#![cfg(all(feature = "serialize", feature = "deserialize"))]
+use serde::{Deserialize, Serialize};
+use serde_saphyr::{DocumentWriter, SerializerOptions};
+use std::collections::BTreeMap;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Config {
+    name: String,
+    port: u16,
+}
+
+fn config() -> Config {
+    Config {
+        name: "api".into(),
+        port: 8080,
+    }
+}
+
+#[test]
+fn header_and_footer_surround_the_document() {
+    let options = serde_saphyr::ser_options! {
+        header_comment: Some("Generated by build.rs\nDo not edit.".into()),
+        footer_comment: Some("end of config".into()),
+    };
+    let yaml = serde_saphyr::to_string_with_options(&config(), options).unwrap();
+    assert_eq!(
+        yaml,
+        "# Generated by build.rs\n# Do not edit.\nname: api\nport: 8080\n# end of config\n"
+    );
+    let back: Config = serde_saphyr::from_str(&yaml).unwrap();
+    assert_eq!(back, config());
+}
+
+#[test]
+fn header_precedes_directives() {
+    let options = serde_saphyr::ser_options! {
+        yaml_12: true,
+        header_comment: Some("SPDX-License-Identifier: MIT".into()),
+    };
+    let yaml = serde_saphyr::to_string_with_options(&5, options).unwrap();
+    assert_eq!(yaml, "# SPDX-License-Identifier: MIT\n%YAML 1.2\n---\n5\n");
+    let back: u32 = serde_saphyr::from_str(&yaml).unwrap();
+    assert_eq!(back, 5);
+}
+
+#[test]
+fn blank_and_windows_lines_are_kept_as_comment_lines() {
+    let options = serde_saphyr::ser_options! {
+        header_comment: Some("first\r\n\r\nthird \rpart".into()),
+    };
+    let yaml = serde_saphyr::to_string_with_options(&true, options).unwrap();
+    assert_eq!(yaml, "# first\n#\n# third  part\ntrue\n");
+}
+
+#[test]
+fn footer_follows_flow_and_empty_values() {
+    let options = || {
+        serde_saphyr::ser_options! {
+            footer_comment: Some("done".into()),
+        }
+    };
+    let empty: BTreeMap<String, u32> = BTreeMap::new();
+    let yaml = serde_saphyr::to_string_with_options(&empty, options()).unwrap();
+    assert_eq!(yaml, "{}\n# done\n");
+
+    let flow = serde_saphyr::FlowSeq(vec![1, 2]);
+    let yaml = serde_saphyr::to_string_with_options(&flow, options()).unwrap();
+    assert_eq!(yaml, "[1, 2]\n# done\n");
+}
+
+#[test]
+fn multiple_documents_get_one_header_and_a_footer_each() {
+    let options = serde_saphyr::ser_options! {
+        header_comment: Some("stream header".into()),
+        footer_comment: Some("document end".into()),
+    };
+    let docs = vec![1, 2];
+    let yaml = serde_saphyr::to_string_multiple_with_options(&docs, options).unwrap();
+    assert_eq!(
+        yaml,
+        "# stream header\n1\n# document end\n---\n2\n# document end\n"
+    );
+    let back: Vec<u32> = serde_saphyr::from_multiple(&yaml).unwrap();
+    assert_eq!(back, docs);
+}
+
+#[test]
+fn document_writer_places_footer_before_end_marker() {
+    let options = serde_saphyr::ser_options! {
+        footer_comment: Some("checksum: none".into()),
+    };
+    let mut writer = DocumentWriter::new(String::new(), options)
+        .unwrap()
+        .with_document_end_markers(true);
+    writer.write(&config()).unwrap();
+    assert_eq!(
+        writer.finish().unwrap(),
+        "name: api\nport: 8080\n# checksum: none\n...\n"
+    );
+}
+
+#[test]
+fn io_writer_writes_comments() {
+    let options = serde_saphyr::ser_options! {
+        header_comment: Some("top".into()),
+        footer_comment: Some("bottom".into()),
+    };
+    let mut out = Vec::new();
+    serde_saphyr::to_io_writer_with_options(&mut out, &"value", options).unwrap();
+    assert_eq!(String::from_utf8(out).unwrap(), "# top\nvalue\n# bottom\n");
+    assert!(SerializerOptions::default().header_comment.is_none());
+}
@@ -0,0 +1,179 @@
+#![cfg(all(feature = "serialize", feature = "deserialize"))]
+use serde::{Deserialize, Serialize};
+use serde_saphyr::{DocumentWriter, RcAnchor, SerializerOptions};
+use std::io;
+use std::rc::Rc;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Entry {
+    id: u32,
+    msg: String,
+}
+
+fn entry(id: u32) -> Entry {
+    Entry {
+        id,
+        msg: format!("message {id}"),
+    }
+}
+
+#[test]
+fn separates_documents_like_to_string_multiple() {
+    let docs = vec![entry(1), entry(2), entry(3)];
+    let mut writer = DocumentWriter::new(String::new(), SerializerOptions::default()).unwrap();
+    for doc in &docs {
+        writer.write(doc).unwrap();
+    }
+    assert_eq!(writer.documents_written(), 3);
+    let yaml = writer.finish().unwrap();
+    assert_eq!(yaml, serde_saphyr::to_string_multiple(&docs).unwrap());
+
+    let back: Vec<Entry> = serde_saphyr::from_multiple(&yaml).unwrap();
+    assert_eq!(back, docs);
+}
+
+#[test]
+fn explicit_start_and_end_markers() {
+    let mut writer = DocumentWriter::new(String::new(), SerializerOptions::default())
+        .unwrap()
+        .with_explicit_start(true)
+        .with_document_end_markers(true);
+    writer.write(&entry(1)).unwrap();
+    writer.write(&entry(2)).unwrap();
+    let yaml = writer.finish().unwrap();
+    assert_eq!(
+        yaml,
+        "---\nid: 1\nmsg: message 1\n...\n---\nid: 2\nmsg: message 2\n...\n"
+    );
+    let back: Vec<Entry> = serde_saphyr::from_multiple(&yaml).unwrap();
+    assert_eq!(back, vec![entry(1), entry(2)]);
+}
+
+#[test]
+fn yaml_12_directive_per_document() {
+    let options = serde_saphyr::ser_options! { yaml_12: true };
+    let mut writer = DocumentWriter::new(String::new(), options).unwrap();
+    writer.write(&1).unwrap();
+    writer.write(&2).unwrap();
+    let yaml = writer.finish().unwrap();
+    assert_eq!(yaml, "%YAML 1.2\n---\n1\n...\n%YAML 1.2\n---\n2\n");
+    let back: Vec<u32> = serde_saphyr::from_multiple(&yaml).unwrap();
+    assert_eq!(back, vec![1, 2]);
+}
+
+#[test]
+fn tag_directives_are_emitted() {
+    let mut writer = DocumentWriter::new(String::new(), SerializerOptions::default())
+        .unwrap()
+        .with_tag_directive("!e!", "tag:example.com,2026:")
+        .unwrap();
+    writer.write(&"a").unwrap();
+    writer.write(&"b").unwrap();
+    let yaml = writer.finish().unwrap();
+    assert_eq!(
+        yaml,
+        "%TAG !e! tag:example.com,2026:\n---\na\n...\n%TAG !e! tag:example.com,2026:\n---\nb\n"
+    );
+    let back: Vec<String> = serde_saphyr::from_multiple(&yaml).unwrap();
+    assert_eq!(back, vec!["a", "b"]);
+}
+
+#[test]
+fn invalid_tag_directives_are_rejected() {
+    let new = || DocumentWriter::new(String::new(), SerializerOptions::default()).unwrap();
+    assert!(new().with_tag_directive("e", "tag:x").is_err());
+    assert!(new().with_tag_directive("!e", "tag:x").is_err());
+    assert!(new().with_tag_directive("!e.x!", "tag:x").is_err());
+    assert!(new().with_tag_directive("!e!", "").is_err());
+    assert!(new().with_tag_directive("!e!", "tag: x").is_err());
+    assert!(new().with_tag_directive("!!", "tag:x").is_ok());
+}
+
+#[test]
+fn anchor_numbering_restarts_unless_shared() {
+    #[derive(Serialize)]
+    struct Pair {
+        a: RcAnchor<Vec<u8>>,
+        b: RcAnchor<Vec<u8>>,
+    }
+    let pair = || {
+        let shared = Rc::new(vec![1]);
+        Pair {
+            a: RcAnchor(shared.clone()),
+            b: RcAnchor(shared),
+        }
+    };
+
+    let mut writer = DocumentWriter::new(String::new(), SerializerOptions::default()).unwrap();
+    writer.write(&pair()).unwrap();
+    writer.write(&pair()).unwrap();
+    let yaml = writer.finish().unwrap();
+    assert_eq!(yaml.matches("&a1").count(), 2, "{yaml}");
+
+    let mut writer = DocumentWriter::new(String::new(), SerializerOptions::default())
+        .unwrap()
+        .with_shared_anchor_numbering(true);
+    writer.write(&pair()).unwrap();
+    writer.write(&pair()).unwrap();
+    let yaml = writer.finish().unwrap();
+    assert!(yaml.contains("&a1") && yaml.contains("*a1"), "{yaml}");
+    assert!(yaml.contains("&a2") && yaml.contains("*a2"), "{yaml}");
+}
+
+#[test]
+fn shared_numbering_keeps_custom_anchor_names() {
+    let options = serde_saphyr::ser_options! {
+        anchor_generator: Some(|id| format!("node{id}")),
+    };
+    let mut writer = DocumentWriter::new(String::new(), options)
+        .unwrap()
+        .with_shared_anchor_numbering(true);
+    for _ in 0..2 {
+        let shared = Rc::new(5);
+        writer
+            .write(&vec![RcAnchor(shared.clone()), RcAnchor(shared)])
+            .unwrap();
+    }
+    let yaml = writer.finish().unwrap();
+    assert_eq!(yaml, "- &node1 5\n- *node1\n---\n- &node2 5\n- *node2\n");
+}
+
+#[test]
+fn writes_to_io_destination() {
+    let mut writer =
+        DocumentWriter::from_io_writer(Vec::<u8>::new(), SerializerOptions::default()).unwrap();
+    writer.write(&entry(7)).unwrap();
+    writer.write(&entry(8)).unwrap();
+    let bytes = writer.into_io_writer().unwrap();
+    assert_eq!(
+        String::from_utf8(bytes).unwrap(),
+        "id: 7\nmsg: message 7\n---\nid: 8\nmsg: message 8\n"
+    );
+}
+
+#[test]
+fn io_errors_are_reported() {
+    struct Broken;
+    impl io::Write for Broken {
+        fn write(&mut self, _buf: &[u8]) -> io::Result<usize> {
+            Err(io::Error::other("pipe closed"))
+        }
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let mut writer = DocumentWriter::from_io_writer(Broken, SerializerOptions::default()).unwrap();
+    let err = writer.write(&entry(1)).unwrap_err();
+    assert!(
+        matches!(&err, serde_saphyr::ser::Error::IO { error } if error.to_string() == "pipe closed"),
+        "{err:?}"
+    );
+}
+
+#[test]
+fn inconsistent_options_are_rejected() {
+    let indent_step = 0;
+    let options = serde_saphyr::ser_options! { indent_step: indent_step };
+    assert!(DocumentWriter::new(String::new(), options).is_err());
+}
@@ -0,0 +1,61 @@
+#![cfg(all(feature = "serialize", feature = "deserialize"))]
+
+use std::collections::HashMap;
+
+use serde_saphyr::{DefaultMessageFormatter, RenderOptions, SnippetMode};
+
+fn duplicate_key_error() -> serde_saphyr::Error {
+    serde_saphyr::from_str::<HashMap<String, u8>>("a: 1\na: 2\n").unwrap_err()
+}
+
+#[test]
+fn codes_are_off_by_default() {
+    let err = duplicate_key_error();
+    assert_eq!(err.code(), "SY0101");
+    assert!(!err.to_string().contains("SY0101"), "{err}");
+}
+
+#[test]
+fn plain_rendering_prefixes_the_code() {
+    let err = duplicate_key_error();
+    let mut options = RenderOptions::new(&DefaultMessageFormatter);
+    options.snippets = SnippetMode::Off;
+    options.error_codes = true;
+    assert_eq!(
+        err.render_with_options(options),
+        "[SY0101] duplicate mapping key: a, set DuplicateKeyPolicy in Options if acceptable \
+         at line 2, column 1"
+    );
+}
+
+#[test]
+fn snippet_rendering_shows_the_code_in_the_title() {
+    let err = duplicate_key_error();
+    let rendered = err.render_with_options(serde_saphyr::render_options! {
+        error_codes: true,
+    });
+    assert!(
+        rendered.starts_with("error[SY0101]: line 2 column 1: duplicate mapping key: a"),
+        "{rendered}"
+    );
+    assert_eq!(rendered.matches("SY0101").count(), 1, "{rendered}");
+}
+
+#[test]
+fn every_code_is_explained() {
+    for info in serde_saphyr::error_codes::ERROR_CODES {
+        let found = serde_saphyr::error_codes::explain(info.code).unwrap();
+        assert_eq!(found, info);
+        assert!(!info.summary.is_empty() && !info.explanation.is_empty());
+    }
+}
+
+#[cfg(feature = "miette")]
+#[test]
+fn miette_diagnostic_carries_the_code() {
+    let source = "a: 1\na: 2\n";
+    let err = serde_saphyr::from_str::<HashMap<String, u8>>(source).unwrap_err();
+    let report = serde_saphyr::miette::to_miette_report(&err, source, "input.yaml");
+    let code = report.code().map(|code| code.to_string());
+    assert_eq!(code.as_deref(), Some("SY0101"));
+}
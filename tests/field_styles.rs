@@ -0,0 +1,169 @@
+#![cfg(all(feature = "serialize", feature = "deserialize"))]
+use serde::{Deserialize, Serialize};
+use serde_saphyr::ser::field_styles::FieldStyle;
+use std::collections::BTreeMap;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Metadata {
+    name: String,
+    labels: BTreeMap<String, String>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Port {
+    port: u16,
+    description: String,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Spec {
+    ports: Vec<u16>,
+    services: Vec<Port>,
+    script: String,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Manifest {
+    metadata: Metadata,
+    spec: Spec,
+    description: String,
+}
+
+fn manifest() -> Manifest {
+    Manifest {
+        metadata: Metadata {
+            name: "web".into(),
+            labels: BTreeMap::from([
+                ("app".into(), "web".into()),
+                ("tier".into(), "front".into()),
+            ]),
+        },
+        spec: Spec {
+            ports: vec![80, 443],
+            services: vec![Port {
+                port: 80,
+                description: "http".into(),
+            }],
+            script: "run".into(),
+        },
+        description: "demo".into(),
+    }
+}
+
+fn styles(entries: &[(&str, FieldStyle)]) -> Vec<(String, FieldStyle)> {
+    entries
+        .iter()
+        .map(|(path, style)| ((*path).to_string(), *style))
+        .collect()
+}
+
+#[test]
+fn styles_apply_by_path() {
+    let options = serde_saphyr::ser_options! {
+        field_styles: styles(&[
+            ("spec.ports", FieldStyle::Flow),
+            ("metadata.labels", FieldStyle::Flow),
+            ("metadata.name", FieldStyle::DoubleQuoted),
+            ("spec.script", FieldStyle::Literal),
+        ]),
+    };
+    let yaml = serde_saphyr::to_string_with_options(&manifest(), options).unwrap();
+    assert_eq!(
+        yaml,
+        "metadata:\n  name: \"web\"\n  labels: {app: web, tier: front}\nspec:\n  ports: [80, 443]\n  services:\n  - port: 80\n    description: http\n  script: |-\n    run\ndescription: demo\n"
+    );
+    let back: Manifest = serde_saphyr::from_str(&yaml).unwrap();
+    assert_eq!(back, manifest());
+}
+
+#[test]
+fn double_star_matches_any_depth() {
+    let options = serde_saphyr::ser_options! {
+        field_styles: styles(&[("**.description", FieldStyle::SingleQuoted)]),
+    };
+    let yaml = serde_saphyr::to_string_with_options(&manifest(), options).unwrap();
+    assert!(yaml.contains("    description: 'http'\n"), "{yaml}");
+    assert!(yaml.contains("\ndescription: 'demo'\n"), "{yaml}");
+}
+
+#[test]
+fn single_star_matches_sequence_indices() {
+    let options = serde_saphyr::ser_options! {
+        field_styles: styles(&[("spec.services.*", FieldStyle::Flow)]),
+    };
+    let yaml = serde_saphyr::to_string_with_options(&manifest(), options).unwrap();
+    assert!(
+        yaml.contains("  services:\n  - {port: 80, description: http}\n"),
+        "{yaml}"
+    );
+}
+
+#[test]
+fn first_matching_pattern_wins() {
+    let options = serde_saphyr::ser_options! {
+        field_styles: styles(&[
+            ("description", FieldStyle::DoubleQuoted),
+            ("**.description", FieldStyle::SingleQuoted),
+        ]),
+    };
+    let yaml = serde_saphyr::to_string_with_options(&manifest(), options).unwrap();
+    assert!(yaml.contains("\ndescription: \"demo\"\n"), "{yaml}");
+    assert!(yaml.contains("description: 'http'\n"), "{yaml}");
+}
+
+#[test]
+fn styles_that_do_not_fit_the_value_are_ignored() {
+    let options = serde_saphyr::ser_options! {
+        field_styles: styles(&[
+            ("metadata", FieldStyle::Literal),
+            ("spec.ports.*", FieldStyle::DoubleQuoted),
+            ("spec.script", FieldStyle::Flow),
+        ]),
+    };
+    let yaml = serde_saphyr::to_string_with_options(&manifest(), options).unwrap();
+    assert_eq!(
+        yaml,
+        serde_saphyr::to_string(&manifest()).unwrap(),
+        "styles leaked into nested values"
+    );
+}
+
+#[test]
+fn single_quoted_falls_back_when_escaping_is_needed() {
+    let mut map = BTreeMap::new();
+    map.insert("text", "tab\there");
+    let options = serde_saphyr::ser_options! {
+        field_styles: styles(&[("text", FieldStyle::SingleQuoted)]),
+    };
+    let yaml = serde_saphyr::to_string_with_options(&map, options).unwrap();
+    assert_eq!(yaml, "text: \"tab\\there\"\n");
+}
+
+#[test]
+fn wrappers_take_precedence() {
+    #[derive(Serialize)]
+    struct Doc {
+        note: serde_saphyr::LitString,
+    }
+    let options = serde_saphyr::ser_options! {
+        field_styles: styles(&[("note", FieldStyle::DoubleQuoted)]),
+    };
+    let doc = Doc {
+        note: serde_saphyr::LitString("a\nb".into()),
+    };
+    let yaml = serde_saphyr::to_string_with_options(&doc, options).unwrap();
+    assert_eq!(yaml, "note: |-\n  a\n  b\n");
+}
+
+#[test]
+fn invalid_patterns_are_rejected() {
+    for pattern in ["", "spec..ports", "spec."] {
+        let options = serde_saphyr::ser_options! {
+            field_styles: styles(&[(pattern, FieldStyle::Flow)]),
+        };
+        assert!(
+            serde_saphyr::to_string_with_options(&1, options).is_err(),
+            "{pattern:?}"
+        );
+    }
+}
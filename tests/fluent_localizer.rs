@@ -0,0 +1,104 @@
+#![cfg(all(feature = "serialize", feature = "deserialize"))]
+#![cfg(feature = "fluent")]
+
+use std::collections::BTreeMap;
+
+use serde::Deserialize;
+use serde_saphyr::fluent_localizer::{CatalogError, bundled_catalog};
+use serde_saphyr::{FluentLocalizer, RenderOptions, SnippetMode, Warnings};
+
+#[allow(dead_code)]
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Service {
+    name: String,
+    replicas: u32,
+}
+
+fn without_snippet(err: &serde_saphyr::Error, localizer: &FluentLocalizer) -> String {
+    let mut options = RenderOptions::new(localizer);
+    options.snippets = SnippetMode::Off;
+    err.render_with_options(options)
+}
+
+#[test]
+fn unknown_field_with_suggestion_in_german() {
+    let localizer = FluentLocalizer::bundled("de").unwrap();
+    let err = serde_saphyr::from_str::<Service>("name: web\nreplica: 2\n").unwrap_err();
+    let msg = without_snippet(&err, &localizer);
+    assert_eq!(
+        msg,
+        "unbekanntes Feld `replica`, erwartet eines von name, replicas; \
+         meinten Sie `replicas`? in Zeile 2, Spalte 1"
+    );
+}
+
+#[test]
+fn snippets_use_translated_locations() {
+    let localizer = FluentLocalizer::bundled("fr").unwrap();
+    let err = serde_saphyr::from_str::<Service>("name: web\nreplicas: many\n").unwrap_err();
+    let msg = err.render_with_formatter(&localizer);
+    assert!(msg.contains("ligne 2 colonne 11"), "{msg}");
+    assert!(!msg.contains("line"), "{msg}");
+}
+
+#[test]
+fn budget_breaches_are_translated() {
+    let localizer = FluentLocalizer::bundled("ja").unwrap();
+    let options = serde_saphyr::options! {
+        budget: serde_saphyr::budget! { max_depth: 2 },
+    };
+    let err =
+        serde_saphyr::from_str_with_options::<serde_json::Value>("a: {b: {c: [1]}}\n", options)
+            .unwrap_err();
+    let msg = without_snippet(&err, &localizer);
+    assert!(
+        msg.starts_with(
+            "YAML ドキュメントが大きすぎるか複雑すぎます: 入れ子が深すぎます（3 階層）"
+        ),
+        "{msg}"
+    );
+}
+
+#[test]
+fn warnings_are_translated() {
+    let localizer = FluentLocalizer::bundled("es").unwrap();
+    let warnings = Warnings::new();
+    let _: BTreeMap<String, bool> = serde_saphyr::from_str_with_options(
+        "enabled: on\n",
+        serde_saphyr::options! {}.with_warnings(&warnings),
+    )
+    .unwrap();
+    let rendered: Vec<String> = warnings
+        .take()
+        .iter()
+        .map(|warning| warning.render_with_options(RenderOptions::new(&localizer)))
+        .collect();
+    assert_eq!(
+        rendered,
+        [
+            "`on` se leyó como el booleano true; YAML 1.2 lo lee como cadena, escriba `true` \
+          en su lugar en la línea 1, columna 10"
+        ]
+    );
+}
+
+#[test]
+fn user_catalogs_extend_bundled_ones() {
+    let template = bundled_catalog("en").unwrap();
+    assert!(template.contains("error-eof = "));
+
+    let localizer =
+        FluentLocalizer::from_ftl("nl", "error-eof = onverwacht einde van het bestand").unwrap();
+    let err = serde_saphyr::from_str::<String>("").unwrap_err();
+    assert_eq!(
+        err.render_with_formatter(&localizer),
+        "onverwacht einde van het bestand at line 1, column 1"
+    );
+
+    let err = FluentLocalizer::bundled("de")
+        .unwrap()
+        .with_ftl("error-eof = {")
+        .unwrap_err();
+    assert!(matches!(err, CatalogError::Syntax(_)), "{err:?}");
+}
@@ -0,0 +1,158 @@
+#![cfg(feature = "deserialize")]
+
+use serde_saphyr::lint::{self, LintConfig, LintLevel, QuoteRequirement, QuoteType};
+
+fn issues(source: &str, config: &LintConfig) -> Vec<(&'static str, u64, u64)> {
+    lint::check(source, config)
+        .unwrap()
+        .into_iter()
+        .map(|issue| (issue.rule, issue.location.line(), issue.location.column()))
+        .collect()
+}
+
+#[test]
+fn clean_file_has_no_issues_with_default_config() {
+    let yaml = "---\n# settings\nname: demo\nenabled: true\nports:\n  - 80\n  - 443\n";
+    assert!(issues(yaml, &LintConfig::default()).is_empty());
+}
+
+#[test]
+fn default_config_matches_yamllint_default() {
+    let config = LintConfig::default();
+    let yaml = format!("enabled: on\nlong: {}\n", "x ".repeat(50));
+    assert_eq!(
+        issues(&yaml, &config),
+        [
+            ("document-start", 1, 1),
+            ("truthy", 1, 10),
+            ("line-length", 2, 81),
+            ("trailing-spaces", 2, 106),
+        ]
+    );
+}
+
+#[test]
+fn line_length_allows_long_urls() {
+    let config = LintConfig::from_yaml("rules:\n  line-length:\n    max: 20\n").unwrap();
+    let yaml = "---\nurl:\n  https://example.com/a/very/long/path\n# https://example.com/a/very/long/path\n";
+    assert!(issues(yaml, &config).is_empty());
+
+    let config = LintConfig::from_yaml(
+        "rules:\n  line-length:\n    max: 20\n    allow-non-breakable-words: false\n",
+    )
+    .unwrap();
+    assert_eq!(
+        issues(yaml, &config),
+        [("line-length", 3, 21), ("line-length", 4, 21)]
+    );
+}
+
+#[test]
+fn truthy_respects_allowed_values_and_check_keys() {
+    let config = LintConfig::from_yaml(
+        "rules:\n  truthy:\n    allowed-values: [\"true\", \"false\", \"on\"]\n    check-keys: false\n",
+    )
+    .unwrap();
+    let yaml = "---\non: yes\nno: on\nquoted: \"yes\"\ntagged: !!str off\n";
+    assert_eq!(issues(yaml, &config), [("truthy", 2, 5)]);
+}
+
+#[test]
+fn truthy_ignores_single_letter_keys_and_values() {
+    let yaml = "---\nx: 1\ny: 2\nn: 3\nY: N\n";
+    assert!(issues(yaml, &LintConfig::default()).is_empty());
+}
+
+#[test]
+fn empty_values_in_block_and_flow_mappings() {
+    let config = LintConfig::from_yaml("rules:\n  empty-values: enable\n").unwrap();
+    let yaml = "---\na:\nb: {c: }\nd: ~\n";
+    let found: Vec<_> = lint::check(yaml, &config)
+        .unwrap()
+        .into_iter()
+        .filter(|issue| issue.rule == "empty-values")
+        .map(|issue| issue.message)
+        .collect();
+    assert_eq!(
+        found,
+        [
+            "empty value in block mapping",
+            "empty value in flow mapping"
+        ]
+    );
+}
+
+#[test]
+fn quoted_strings_rule() {
+    let config =
+        LintConfig::from_yaml("rules:\n  quoted-strings:\n    quote-type: single\n").unwrap();
+    let rule = config.quoted_strings.as_ref().unwrap();
+    assert_eq!(rule.quote_type, QuoteType::Single);
+    assert_eq!(rule.required, QuoteRequirement::Always);
+
+    let yaml = "---\na: plain\nb: 'single'\nc: \"double\"\nd: 12\ne: |\n  block\n";
+    assert_eq!(
+        issues(yaml, &config),
+        [("quoted-strings", 2, 4), ("quoted-strings", 4, 4)]
+    );
+
+    let config =
+        LintConfig::from_yaml("rules:\n  quoted-strings:\n    required: only-when-needed\n")
+            .unwrap();
+    let yaml = "---\na: plain\nb: 'quoted'\nc: 'yes'\nd: '# hash'\n";
+    assert_eq!(issues(yaml, &config), [("quoted-strings", 3, 4)]);
+}
+
+#[test]
+fn document_start_can_be_forbidden() {
+    let config = LintConfig::from_yaml("rules:\n  document-start:\n    present: false\n").unwrap();
+    assert_eq!(issues("---\na: 1\n", &config), [("document-start", 1, 1)]);
+    assert!(issues("a: 1\n", &config).is_empty());
+}
+
+#[test]
+fn levels_and_disabling_from_config() {
+    let config = LintConfig::from_yaml(
+        "rules:\n  trailing-spaces:\n    level: warning\n  document-start: disable\n",
+    )
+    .unwrap();
+    let found = lint::check("a: 1 \n", &config).unwrap();
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].rule, "trailing-spaces");
+    assert_eq!(found[0].level, LintLevel::Warning);
+}
+
+#[test]
+fn invalid_config_is_reported_with_location() {
+    let err = LintConfig::from_yaml("rules:\n  line-length:\n    max: long\n").unwrap_err();
+    let rendered = err.to_string();
+    assert!(rendered.contains("rule `line-length`"), "{rendered}");
+    assert!(rendered.contains("expected a line length"), "{rendered}");
+
+    let err = LintConfig::from_yaml("rules:\n  truthy:\n    levl: error\n").unwrap_err();
+    assert!(err.to_string().contains("unknown option `levl`"), "{err}");
+}
+
+#[test]
+fn syntax_errors_fail_the_check() {
+    let err = lint::check("a: [1\n", &LintConfig::default()).unwrap_err();
+    assert!(err.location().is_some(), "{err}");
+}
+
+#[test]
+fn issue_renders_with_source_snippet() {
+    let source = "---\nkey: value \n";
+    let found = lint::check(source, &LintConfig::default()).unwrap();
+    let rendered =
+        found[0].render_with_source(source, "config.yaml", serde_saphyr::render_options! {});
+    assert!(rendered.starts_with("error:"), "{rendered}");
+    assert!(rendered.contains("config.yaml:2:11"), "{rendered}");
+    assert!(
+        rendered.contains("trailing spaces (trailing-spaces)"),
+        "{rendered}"
+    );
+    assert_eq!(
+        found[0].to_string(),
+        "trailing spaces (trailing-spaces) at line 2, column 11"
+    );
+}
@@ -0,0 +1,214 @@
+#![cfg(feature = "lsp")]
+
+use std::collections::HashMap;
+
+use serde::Deserialize;
+use serde_json::{Value, json};
+use serde_saphyr::lsp::{Server, Severity};
+use serde_saphyr::path_key;
+
+#[allow(dead_code)]
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Robot {
+    name: String,
+    joints: Vec<String>,
+    speed: u8,
+}
+
+fn server() -> Server {
+    Server::new().register_validated::<Robot, _>("**/robots/*.yaml", |robot, ctx| {
+        if robot.joints.is_empty() {
+            ctx.report(path_key!["joints"], "joints", "a robot needs joints");
+        }
+    })
+}
+
+fn frame(message: &Value) -> String {
+    let body = message.to_string();
+    format!("Content-Length: {}\r\n\r\n{body}", body.len())
+}
+
+fn parse_frames(mut output: &str) -> Vec<Value> {
+    let mut messages = Vec::new();
+    while let Some((header, rest)) = output.split_once("\r\n\r\n") {
+        let length: usize = header
+            .strip_prefix("Content-Length: ")
+            .unwrap()
+            .parse()
+            .unwrap();
+        messages.push(serde_json::from_str(&rest[..length]).unwrap());
+        output = &rest[length..];
+    }
+    messages
+}
+
+fn run_session(messages: &[Value]) -> Vec<Value> {
+    let input: String = messages.iter().map(frame).collect();
+    let mut output = Vec::new();
+    server().run(input.as_bytes(), &mut output).unwrap();
+    parse_frames(std::str::from_utf8(&output).unwrap())
+}
+
+#[test]
+fn type_errors_are_reported_with_ranges_and_codes() {
+    let yaml = "name: arm\njoints: [shoulder]\nspeed: 300\n";
+    let diagnostics = server().diagnostics("file:///srv/robots/arm.yaml", yaml);
+
+    assert_eq!(diagnostics.len(), 1, "{diagnostics:?}");
+    let diagnostic = &diagnostics[0];
+    assert_eq!(diagnostic.severity, Severity::Error);
+    assert_eq!(diagnostic.code, Some("SY0301"));
+    assert_eq!(diagnostic.range.start.line, 2);
+    assert_eq!(diagnostic.range.start.character, 7);
+    assert_eq!(diagnostic.range.end.character, 10);
+}
+
+#[test]
+fn unknown_fields_suggest_the_expected_name() {
+    let yaml = "name: arm\njoints: [shoulder]\nsped: 3\n";
+    let diagnostics = server().diagnostics("file:///srv/robots/arm.yaml", yaml);
+
+    assert_eq!(diagnostics.len(), 1, "{diagnostics:?}");
+    assert_eq!(diagnostics[0].code, Some("SY0404"));
+    assert!(diagnostics[0].message.contains("speed"), "{diagnostics:?}");
+    assert_eq!(diagnostics[0].range.start.line, 2);
+}
+
+#[test]
+fn validation_issues_point_at_the_value() {
+    let yaml = "name: arm\njoints: []\nspeed: 3\n";
+    let diagnostics = server().diagnostics("file:///srv/robots/arm.yaml", yaml);
+
+    assert_eq!(diagnostics.len(), 1, "{diagnostics:?}");
+    assert!(
+        diagnostics[0].message.contains("a robot needs joints"),
+        "{diagnostics:?}"
+    );
+    assert_eq!(diagnostics[0].range.start.line, 1);
+}
+
+#[test]
+fn anchored_values_relate_to_their_definition() {
+    let server =
+        Server::new().register_validated::<HashMap<String, u32>, _>("*.yaml", |values, ctx| {
+            if values["speed"] > 100 {
+                ctx.report(path_key!["speed"], "max", "too fast");
+            }
+        });
+    let diagnostics = server.diagnostics("file:///x.yaml", "base: &b 300\nspeed: *b\n");
+
+    assert_eq!(diagnostics.len(), 1, "{diagnostics:?}");
+    assert_eq!(diagnostics[0].range.start.line, 1);
+    assert_eq!(diagnostics[0].related.len(), 1, "{diagnostics:?}");
+    assert_eq!(diagnostics[0].related[0].0.start.line, 0);
+}
+
+#[test]
+fn warnings_are_published_with_warning_severity() {
+    let diagnostics = Server::new()
+        .register::<HashMap<String, bool>>("*.yaml")
+        .diagnostics("file:///x.yaml", "enabled: yes\n");
+    assert_eq!(diagnostics.len(), 1, "{diagnostics:?}");
+    assert_eq!(diagnostics[0].severity, Severity::Warning);
+    assert_eq!(diagnostics[0].code, None);
+}
+
+#[test]
+fn unregistered_files_are_ignored() {
+    let server = server();
+    assert!(!server.handles("file:///srv/other/arm.yaml"));
+    assert!(
+        server
+            .diagnostics("file:///srv/other/arm.yaml", "speed: 300\n")
+            .is_empty()
+    );
+}
+
+#[test]
+fn session_publishes_diagnostics_as_the_user_types() {
+    let uri = "file:///srv/robots/my%20arm.yaml";
+    let responses = run_session(&[
+        json!({ "jsonrpc": "2.0", "id": 1, "method": "initialize", "params": {} }),
+        json!({ "jsonrpc": "2.0", "method": "initialized", "params": {} }),
+        json!({
+            "jsonrpc": "2.0",
+            "method": "textDocument/didOpen",
+            "params": { "textDocument": {
+                "uri": uri, "languageId": "yaml", "version": 1,
+                "text": "name: arm\njoints: [a\n",
+            }},
+        }),
+        json!({
+            "jsonrpc": "2.0",
+            "method": "textDocument/didChange",
+            "params": {
+                "textDocument": { "uri": uri, "version": 2 },
+                "contentChanges": [{ "text": "name: arm\njoints: [a]\nspeed: 1\n" }],
+            },
+        }),
+        json!({
+            "jsonrpc": "2.0",
+            "method": "textDocument/didOpen",
+            "params": { "textDocument": {
+                "uri": "file:///srv/notes.yaml", "languageId": "yaml", "version": 1,
+                "text": "[",
+            }},
+        }),
+        json!({ "jsonrpc": "2.0", "id": 2, "method": "textDocument/hover", "params": {} }),
+        json!({
+            "jsonrpc": "2.0",
+            "method": "textDocument/didClose",
+            "params": { "textDocument": { "uri": uri } },
+        }),
+        json!({ "jsonrpc": "2.0", "id": 3, "method": "shutdown" }),
+        json!({ "jsonrpc": "2.0", "method": "exit" }),
+    ]);
+
+    assert_eq!(responses.len(), 6, "{responses:#?}");
+    assert_eq!(responses[0]["id"], 1);
+    assert_eq!(
+        responses[0]["result"]["capabilities"]["textDocumentSync"]["change"],
+        1
+    );
+
+    assert_eq!(responses[1]["method"], "textDocument/publishDiagnostics");
+    assert_eq!(responses[1]["params"]["uri"], uri);
+    let diagnostics = responses[1]["params"]["diagnostics"].as_array().unwrap();
+    assert_eq!(diagnostics.len(), 1, "{diagnostics:#?}");
+    assert_eq!(diagnostics[0]["severity"], 1);
+    assert_eq!(diagnostics[0]["source"], "serde-saphyr");
+
+    assert_eq!(responses[2]["params"]["diagnostics"], json!([]));
+    assert_eq!(responses[3]["id"], 2);
+    assert_eq!(responses[3]["error"]["code"], -32601);
+    assert_eq!(responses[4]["params"]["diagnostics"], json!([]));
+    assert_eq!(
+        responses[5],
+        json!({ "jsonrpc": "2.0", "id": 3, "result": null })
+    );
+}
+
+#[cfg(feature = "include")]
+#[test]
+fn errors_in_included_files_point_at_the_include() {
+    let options = serde_saphyr::options! {}.with_include_resolver(|req| {
+        Ok(serde_saphyr::ResolvedInclude::new(
+            format!("/etc/app/{}", req.spec),
+            req.spec,
+            serde_saphyr::InputSource::from_string("# server\nport: 99999\n".to_string()),
+        ))
+    });
+    let server = Server::new()
+        .with_options(options)
+        .register::<HashMap<String, HashMap<String, u16>>>("*.yaml");
+    let diagnostics =
+        server.diagnostics("file:///x.yaml", "# config\nserver: !include server.yaml\n");
+
+    assert_eq!(diagnostics.len(), 1, "{diagnostics:?}");
+    assert_eq!(diagnostics[0].range.start.line, 1);
+    assert!(
+        diagnostics[0].message.starts_with("server.yaml: "),
+        "{diagnostics:?}"
+    );
+}
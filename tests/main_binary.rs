@@ -178,3 +178,169 @@ fn include_option_rejects_option_as_missing_path() {
         "stderr: {stderr}"
     );
 }
+
+#[test]
+fn plain_mode_shows_error_code() {
+    let mut tmp = tempfile::NamedTempFile::new().expect("create temp file");
+    writeln!(tmp, "a: 1\na: 2").unwrap();
+    let path = tmp.path().to_str().unwrap();
+
+    let (_stdout, stderr, code) = run_binary(&["--plain", path]);
+    assert_eq!(code, 3, "stderr: {stderr}");
+    assert!(
+        stderr.contains("error[SY0101]: line 2 column 1"),
+        "stderr: {stderr}"
+    );
+}
+
+#[test]
+fn explain_prints_long_description() {
+    let (stdout, _stderr, code) = run_binary(&["explain", "sy0101"]);
+    assert_eq!(code, 0);
+    assert!(
+        stdout.starts_with("SY0101 DuplicateMappingKey: duplicate key in a mapping\n\n"),
+        "stdout: {stdout}"
+    );
+    assert!(stdout.contains("Fix:"), "stdout: {stdout}");
+}
+
+#[test]
+fn explain_without_code_lists_all_codes() {
+    let (stdout, _stderr, code) = run_binary(&["explain"]);
+    assert_eq!(code, 0);
+    assert_eq!(
+        stdout.lines().count(),
+        serde_saphyr::error_codes::ERROR_CODES.len()
+    );
+    assert!(
+        stdout.contains("SY0201 Eof: unexpected end of input\n"),
+        "stdout: {stdout}"
+    );
+}
+
+#[test]
+fn explain_unknown_code_exits_one() {
+    let (_stdout, stderr, code) = run_binary(&["explain", "SY0000"]);
+    assert_eq!(code, 1);
+    assert!(
+        stderr.contains("Unknown error code: SY0000"),
+        "stderr: {stderr}"
+    );
+}
+
+#[test]
+fn lint_reports_issues_and_exits_three_on_errors() {
+    let mut tmp = tempfile::NamedTempFile::new().expect("create temp file");
+    write!(tmp, "---\nenabled: yes \n").unwrap();
+    let path = tmp.path().to_str().unwrap();
+
+    let (stdout, stderr, code) = run_binary(&["lint", path]);
+    assert_eq!(code, 3, "stderr: {stderr}");
+    assert!(stdout.contains("(truthy)"), "stdout: {stdout}");
+    assert!(stdout.contains("(trailing-spaces)"), "stdout: {stdout}");
+    assert!(
+        stdout.contains(": 2 issue(s), 1 error(s)"),
+        "stdout: {stdout}"
+    );
+}
+
+#[test]
+fn lint_with_config_file() {
+    let mut config = tempfile::NamedTempFile::new().expect("create temp file");
+    write!(config, "rules:\n  trailing-spaces: disable\n").unwrap();
+    let mut tmp = tempfile::NamedTempFile::new().expect("create temp file");
+    write!(tmp, "---\nenabled: yes \n").unwrap();
+    let config = config.path().to_str().unwrap();
+    let path = tmp.path().to_str().unwrap();
+
+    let (stdout, stderr, code) = run_binary(&["lint", "--config", config, path]);
+    assert_eq!(code, 0, "stderr: {stderr}");
+    assert!(
+        stdout.contains(": 1 issue(s), 0 error(s)"),
+        "stdout: {stdout}"
+    );
+}
+
+#[test]
+fn lint_with_invalid_config_exits_two() {
+    let mut config = tempfile::NamedTempFile::new().expect("create temp file");
+    write!(config, "rules:\n  trailing-space: disable\n").unwrap();
+    let mut tmp = tempfile::NamedTempFile::new().expect("create temp file");
+    write!(tmp, "---\na: 1\n").unwrap();
+    let config = config.path().to_str().unwrap();
+    let path = tmp.path().to_str().unwrap();
+
+    let (_stdout, stderr, code) = run_binary(&["lint", "--config", config, path]);
+    assert_eq!(code, 2);
+    assert!(
+        stderr.contains("did you mean `trailing-spaces`?"),
+        "stderr: {stderr}"
+    );
+}
+
+#[test]
+fn lint_invalid_yaml_exits_three() {
+    let mut tmp = tempfile::NamedTempFile::new().expect("create temp file");
+    writeln!(tmp, "a: [1").unwrap();
+    let path = tmp.path().to_str().unwrap();
+
+    let (stdout, stderr, code) = run_binary(&["lint", path]);
+    assert_eq!(code, 3);
+    assert!(stdout.is_empty(), "stdout: {stdout}");
+    assert!(stderr.contains("invalid:"), "stderr: {stderr}");
+}
+
+#[test]
+fn diff_reports_changes_with_locations() {
+    let mut old = tempfile::NamedTempFile::new().expect("create temp file");
+    write!(old, "name: demo\nport: 80\n").unwrap();
+    let mut new = tempfile::NamedTempFile::new().expect("create temp file");
+    write!(new, "# reformatted\nname: \"demo\"\nport: 8080\n").unwrap();
+    let old = old.path().to_str().unwrap();
+    let new = new.path().to_str().unwrap();
+
+    let (stdout, stderr, code) = run_binary(&["diff", old, new]);
+    assert_eq!(code, 0, "stderr: {stderr}");
+    assert_eq!(
+        stdout,
+        format!("changed port: 80 -> 8080\n  {old}:2:7 -> {new}:3:7\n")
+    );
+}
+
+#[test]
+fn diff_of_equal_documents() {
+    let mut old = tempfile::NamedTempFile::new().expect("create temp file");
+    writeln!(old, "a: [1, 2]").unwrap();
+    let mut new = tempfile::NamedTempFile::new().expect("create temp file");
+    write!(new, "a:\n  - 1\n  - 2\n").unwrap();
+    let old = old.path().to_str().unwrap();
+    let new = new.path().to_str().unwrap();
+
+    let (stdout, stderr, code) = run_binary(&["diff", old, new]);
+    assert_eq!(code, 0, "stderr: {stderr}");
+    assert_eq!(stdout, "No differences\n");
+}
+
+#[test]
+fn diff_invalid_yaml_names_the_file() {
+    let mut old = tempfile::NamedTempFile::new().expect("create temp file");
+    writeln!(old, "a: 1").unwrap();
+    let mut new = tempfile::NamedTempFile::new().expect("create temp file");
+    writeln!(new, "a: [1").unwrap();
+    let old = old.path().to_str().unwrap();
+    let new = new.path().to_str().unwrap();
+
+    let (_stdout, stderr, code) = run_binary(&["diff", old, new]);
+    assert_eq!(code, 3);
+    assert!(
+        stderr.starts_with(&format!("{new} invalid:")),
+        "stderr: {stderr}"
+    );
+}
+
+#[test]
+fn diff_needs_two_files() {
+    let (_stdout, stderr, code) = run_binary(&["diff", "only.yaml"]);
+    assert_eq!(code, 1);
+    assert!(stderr.contains("Usage:"), "stderr: {stderr}");
+}
@@ -0,0 +1,154 @@
+#![cfg(all(feature = "serialize", feature = "deserialize"))]
+use std::collections::BTreeMap;
+
+use indoc::indoc;
+use serde::Deserialize;
+use serde_saphyr::{Error, OwnedRawYaml, RawYaml};
+
+#[derive(Debug, Deserialize)]
+struct Plugin<'a> {
+    kind: String,
+    #[serde(borrow)]
+    config: RawYaml<'a>,
+    enabled: bool,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Http {
+    port: u16,
+    routes: Vec<String>,
+}
+
+#[test]
+fn captures_block_mapping_and_parses_later() {
+    let yaml = indoc! {"
+        kind: http
+        config:
+          port: 8080
+          routes:
+            - /a
+            - /b
+        # trailing comment
+        enabled: true
+    "};
+    let plugin: Plugin = serde_saphyr::from_str(yaml).unwrap();
+    assert_eq!(plugin.kind, "http");
+    assert!(plugin.enabled);
+    assert_eq!(
+        plugin.config.get(),
+        "port: 8080\n  routes:\n    - /a\n    - /b"
+    );
+    assert_eq!(plugin.config.location().line(), 3);
+    assert_eq!(plugin.config.location().column(), 3);
+
+    let http: Http = plugin.config.parse().unwrap();
+    assert_eq!(
+        http,
+        Http {
+            port: 8080,
+            routes: vec!["/a".into(), "/b".into()],
+        }
+    );
+}
+
+#[derive(Debug, Deserialize)]
+struct Holder<'a> {
+    #[serde(borrow)]
+    v: RawYaml<'a>,
+}
+
+#[test]
+fn captures_exact_source_of_each_node_shape() {
+    for (yaml, text) in [
+        ("v: 0x1F # hex\n", "0x1F"),
+        ("v: 'single' \n", "'single'"),
+        ("v: plain\n  continued\n", "plain\n  continued"),
+        ("v: [1, {x: 2}]\n", "[1, {x: 2}]"),
+        ("v:\n- a\n- - b\n", "- a\n- - b"),
+        ("v:\n- [a]\n- b\n", "- [a]\n- b"),
+        ("v: |\n  text\n  more\n", "|\n  text\n  more\n"),
+        ("v: >- # folded\n  text\n", ">- # folded\n  text\n"),
+        ("v: &a {k: 1}\n", "{k: 1}"),
+    ] {
+        let holder: Holder = serde_saphyr::from_str(yaml).unwrap();
+        assert_eq!(holder.v.get(), text, "{yaml:?}");
+        assert_eq!(
+            holder.v.location().span().byte_len(),
+            Some(text.len() as u64)
+        );
+    }
+}
+
+#[test]
+fn reparsing_keeps_scalar_semantics() {
+    let holder: Holder = serde_saphyr::from_str("v: >-\n  one\n  two\n").unwrap();
+    assert_eq!(holder.v.parse::<String>().unwrap(), "one two");
+
+    let holder: Holder = serde_saphyr::from_str("v:\n  - 1\n  - 2\n").unwrap();
+    assert_eq!(holder.v.parse::<Vec<u8>>().unwrap(), [1, 2]);
+}
+
+#[test]
+fn deferred_errors_point_into_original_document() {
+    let yaml = "kind: http\nconfig:\n  port: high\n  routes: []\nenabled: false\n";
+    let plugin: Plugin = serde_saphyr::from_str(yaml).unwrap();
+    let err = plugin.config.parse::<Http>().unwrap_err();
+    let location = err.location().unwrap();
+    assert_eq!((location.line(), location.column()), (3, 9));
+}
+
+#[test]
+fn owned_form_works_with_deserialize_owned() {
+    #[derive(Deserialize)]
+    struct Steps {
+        steps: Vec<OwnedRawYaml>,
+    }
+
+    fn load<T: serde::de::DeserializeOwned>(yaml: &str) -> T {
+        serde_saphyr::from_str(yaml).unwrap()
+    }
+
+    let steps: Steps = load("steps:\n  - run: build\n  - [a, b]\n");
+    assert_eq!(steps.steps[0].get(), "run: build");
+    assert_eq!(steps.steps[1].to_string(), "[a, b]");
+    let map: BTreeMap<String, String> = steps.steps[0].parse().unwrap();
+    assert_eq!(map["run"], "build");
+    assert_eq!(steps.steps[1].as_raw().location().line(), 3);
+}
+
+#[test]
+fn reader_input_is_rejected() {
+    let err = serde_saphyr::from_reader::<_, Steps>("v: 1\n".as_bytes()).unwrap_err();
+    assert!(matches!(
+        err.without_snippet(),
+        Error::RawYamlUnavailable { location } if location.line() == 1
+    ));
+
+    #[derive(Debug, Deserialize)]
+    struct Steps {
+        #[allow(dead_code)]
+        v: OwnedRawYaml,
+    }
+}
+
+#[cfg(feature = "include")]
+#[test]
+fn included_nodes_are_rejected() {
+    let options = serde_saphyr::options! {}.with_include_resolver(|req| {
+        Ok(serde_saphyr::ResolvedInclude::new(
+            req.spec,
+            req.spec,
+            serde_saphyr::InputSource::from_string("port: 1\n".to_string()),
+        ))
+    });
+    let err = serde_saphyr::from_str_with_options::<Holder>("v: !include child.yaml\n", options)
+        .unwrap_err();
+    assert!(matches!(
+        err.without_snippet(),
+        Error::RawYamlUnavailable { .. }
+    ));
+
+    let holder: Holder =
+        serde_saphyr::from_str_with_options("v: [1]\n", serde_saphyr::options! {}).unwrap();
+    assert_eq!(holder.v.get(), "[1]");
+}
@@ -0,0 +1,116 @@
+#![cfg(all(feature = "serialize", feature = "deserialize"))]
+
+use std::collections::HashMap;
+
+use serde_saphyr::RenderTarget;
+
+fn invalid_value_error() -> serde_saphyr::Error {
+    serde_saphyr::from_str::<HashMap<String, u8>>("a: 1\nb: <x>\n").unwrap_err()
+}
+
+#[test]
+fn plain_is_the_default_target() {
+    let err = invalid_value_error();
+    let rendered = err.render_with_options(serde_saphyr::render_options! {});
+    assert!(!rendered.contains('\x1b'), "{rendered:?}");
+    assert_eq!(rendered, err.to_string());
+}
+
+#[test]
+fn ansi_target_colors_snippets() {
+    let err = invalid_value_error();
+    let rendered = err.render_with_options(serde_saphyr::render_options! {
+        target: RenderTarget::Ansi,
+    });
+
+    let no_color = std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+    assert_eq!(rendered.contains("\x1b["), !no_color, "{rendered:?}");
+    assert!(rendered.contains("b: <x>"), "{rendered:?}");
+}
+
+#[test]
+fn html_target_escapes_source_and_highlights_spans() {
+    let err = invalid_value_error();
+    let rendered = err.render_with_options(serde_saphyr::render_options! {
+        target: RenderTarget::Html,
+    });
+
+    assert!(
+        rendered.starts_with(
+            "<pre class=\"serde-saphyr-diagnostic\"><span class=\"error\">error</span>"
+        ),
+        "{rendered}"
+    );
+    assert!(rendered.ends_with("</pre>"), "{rendered}");
+    assert!(rendered.contains("b: &lt;x&gt;"), "{rendered}");
+    assert!(rendered.contains("&lt;input&gt;:2:4"), "{rendered}");
+    assert!(
+        rendered.contains("<span class=\"error\">^</span>"),
+        "{rendered}"
+    );
+    assert!(!rendered.contains('\x1b'), "{rendered:?}");
+    assert_eq!(
+        rendered.matches("<span").count(),
+        rendered.matches("</span>").count()
+    );
+}
+
+#[test]
+fn html_target_without_snippet_escapes_message() {
+    let err = invalid_value_error();
+    let rendered = err.render_with_options(serde_saphyr::render_options! {
+        target: RenderTarget::Html,
+        snippets: serde_saphyr::SnippetMode::Off,
+    });
+    assert_eq!(
+        rendered,
+        "<pre class=\"serde-saphyr-diagnostic\">invalid u8 at line 2, column 4</pre>"
+    );
+}
+
+#[test]
+fn html_target_keeps_escape_sequences_in_keys_out_of_markup() {
+    let yaml = "\"\\e[31mX\\e[0m<b>m\": 1\n\"\\e[31mX\\e[0m<b>m\": 2\n";
+    let err = serde_saphyr::from_str::<HashMap<String, u8>>(yaml).unwrap_err();
+
+    for snippets in [
+        serde_saphyr::SnippetMode::Auto,
+        serde_saphyr::SnippetMode::Off,
+    ] {
+        let rendered = err.render_with_options(serde_saphyr::render_options! {
+            target: RenderTarget::Html,
+            snippets: snippets,
+        });
+        assert!(!rendered.contains('\x1b'), "{rendered:?}");
+        assert!(!rendered.contains("<b>"), "{rendered}");
+        assert!(!rendered.contains("\">X</span>"), "{rendered}");
+        assert!(rendered.contains("␛[31mX␛[0m&lt;b&gt;m"), "{rendered}");
+        assert_eq!(
+            rendered.matches("<span").count(),
+            rendered.matches("</span>").count(),
+            "{rendered}"
+        );
+    }
+}
+
+#[test]
+fn warnings_render_as_html() {
+    let yaml = "enabled: yes\n";
+    let warnings = serde_saphyr::Warnings::new();
+    let options = serde_saphyr::options! {}.with_warnings(&warnings);
+    let _: HashMap<String, bool> = serde_saphyr::from_str_with_options(yaml, options).unwrap();
+
+    let warning = warnings.take().into_iter().next().expect("one warning");
+    let rendered = warning.render_with_source(
+        yaml,
+        serde_saphyr::render_options! {
+            target: RenderTarget::Html,
+        },
+    );
+    assert!(
+        rendered.starts_with(
+            "<pre class=\"serde-saphyr-diagnostic\"><span class=\"warning\">warning</span>"
+        ),
+        "{rendered}"
+    );
+}
@@ -119,4 +119,61 @@ angle_from_sexagesimal_rad: !radians 8:32:53.2
         assert!((v.angle_from_sexagesimal_2 - expected_rad).abs() < 1e-12);
         assert!((v.angle_from_sexagesimal_rad - expected_rad).abs() < 1e-12);
     }
+
+    #[derive(Debug, Deserialize)]
+    struct Wheel {
+        radius: f64,
+        update_period: f64,
+        rate: f32,
+        timeout: f64,
+    }
+
+    #[test]
+    fn robotics_units_end_to_end() {
+        let yaml = r#"
+radius: mm(40) + 5mm
+update_period: ms(20)
+rate: 50Hz
+timeout: 1/Hz(2)
+"#;
+        let options = serde_saphyr::options! {
+            unit_conversions: true,
+        };
+        let v: Wheel = from_str_with_options(yaml, options).expect("parse robotics YAML");
+        assert!((v.radius - 0.045).abs() < 1e-12);
+        assert!((v.update_period - 0.02).abs() < 1e-12);
+        assert!((v.rate - 50.0).abs() < 1e-6);
+        assert!((v.timeout - 0.5).abs() < 1e-12);
+    }
+
+    #[test]
+    fn robotics_unit_kind_error_points_at_scalar() {
+        let yaml = "radius: 0.1
+update_period: mm(20) + ms(1)
+rate: 1
+timeout: 1
+";
+        let options = serde_saphyr::options! {
+            unit_conversions: true,
+        };
+        let err = from_str_with_options::<Wheel>(yaml, options).unwrap_err();
+        let location = err.location().expect("location");
+        assert_eq!((location.line(), location.column()), (2, 16));
+        assert!(
+            err.to_string().contains("cannot add a length and a time"),
+            "{err}"
+        );
+
+        let options = serde_saphyr::options! {
+            angle_conversions: true,
+        };
+        assert!(
+            from_str_with_options::<Wheel>(
+                "radius: 12mm
+",
+                options
+            )
+            .is_err()
+        );
+    }
 }
@@ -0,0 +1,113 @@
+#![cfg(all(feature = "serialize", feature = "deserialize"))]
+#![cfg(feature = "properties")]
+
+use std::collections::HashMap;
+
+use serde::Deserialize;
+use serde_saphyr::{Error, Options, RosSubstitutions, from_str_with_options};
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Node {
+    name: String,
+    params: String,
+    rate: f64,
+}
+
+fn options() -> Options {
+    let substitutions = RosSubstitutions::new()
+        .with_args(HashMap::from([
+            ("robot".to_string(), "r2d2".to_string()),
+            ("token".to_string(), "zz-secret".to_string()),
+        ]))
+        .with_env_resolver(|name| (name == "ROS_DOMAIN_ID").then(|| "42".to_string()))
+        .with_package_share_resolver(|package| Some(format!("/opt/ros/share/{package}")));
+    serde_saphyr::options! {}.with_ros_substitutions(substitutions)
+}
+
+#[test]
+fn substitutions_expand_in_plain_scalars() {
+    let yaml = "\
+name: $(arg robot)_$(optenv ROBOT_SUFFIX sim)
+params: $(find-pkg-share $(arg robot))/config/params.yaml
+rate: $(env ROS_DOMAIN_ID)
+";
+    let node: Node = from_str_with_options(yaml, options()).unwrap();
+    assert_eq!(
+        node,
+        Node {
+            name: "r2d2_sim".to_string(),
+            params: "/opt/ros/share/r2d2/config/params.yaml".to_string(),
+            rate: 42.0,
+        }
+    );
+}
+
+#[test]
+fn quoted_scalars_keys_and_disabled_substitutions_stay_verbatim() {
+    let map: HashMap<String, String> =
+        from_str_with_options("$(arg robot): '$(arg robot)'\n", options()).unwrap();
+    assert_eq!(map["$(arg robot)"], "$(arg robot)");
+
+    let value: String = serde_saphyr::from_str("$(arg robot)").unwrap();
+    assert_eq!(value, "$(arg robot)");
+}
+
+#[test]
+fn properties_and_substitutions_combine() {
+    let options =
+        options().with_properties(HashMap::from([("PACKAGE".to_string(), "nav2".to_string())]));
+    let value: String =
+        from_str_with_options("$(find-pkg-share ${PACKAGE})/$(arg robot)", options).unwrap();
+    assert_eq!(value, "/opt/ros/share/nav2/r2d2");
+}
+
+#[test]
+fn failed_substitutions_report_source_text_and_location() {
+    let err =
+        from_str_with_options::<Node>("name: x\nparams: $(arg missing)\n", options()).unwrap_err();
+    match err.without_snippet() {
+        Error::UnresolvedSubstitution {
+            substitution,
+            location,
+        } => {
+            assert_eq!(substitution, "$(arg missing)");
+            assert_eq!(location.line(), 2);
+        }
+        other => panic!("unexpected error: {other:?}"),
+    }
+
+    let err = from_str_with_options::<String>("$(find r2d2)", options()).unwrap_err();
+    assert!(
+        matches!(err.without_snippet(), Error::InvalidSubstitution { .. }),
+        "{err:?}"
+    );
+}
+
+#[test]
+fn resolved_values_do_not_leak_into_errors() {
+    let err = from_str_with_options::<Node>("name: x\nparams: y\nrate: $(arg token)\n", options())
+        .unwrap_err();
+    let msg = err.to_string();
+    assert!(!msg.contains("zz-secret"), "secret leaked: {msg}");
+    assert!(msg.contains("$(arg token)"), "{msg}");
+
+    #[derive(Debug, Deserialize)]
+    #[allow(dead_code)]
+    enum Mode {
+        Fast,
+    }
+    let err = from_str_with_options::<Mode>("$(arg token)", options()).unwrap_err();
+    let msg = err.to_string();
+    assert!(!msg.contains("zz-secret"), "secret leaked: {msg}");
+}
+
+#[cfg(feature = "robotics")]
+#[test]
+fn eval_uses_the_robotics_expression_evaluator() {
+    let value: f64 = from_str_with_options(
+        "$(eval $(env ROS_DOMAIN_ID) / 2 + deg(180) / pi)",
+        options(),
+    )
+    .unwrap();
+    assert_eq!(value, 22.0);
+}
@@ -0,0 +1,138 @@
+#![cfg(all(feature = "serialize", feature = "deserialize", feature = "robotics"))]
+use core::f64::consts::{FRAC_1_SQRT_2, FRAC_PI_2, PI};
+use std::collections::HashMap;
+
+use serde::Deserialize;
+use serde_saphyr::{Quaternion, Rotation, RotationFormat};
+
+#[derive(Debug, Deserialize)]
+struct Poses {
+    rotations: Vec<Rotation>,
+}
+
+#[track_caller]
+fn assert_yaw_90(rotation: &Rotation) {
+    let q = rotation.quaternion();
+    let expected = [0.0, 0.0, FRAC_1_SQRT_2, FRAC_1_SQRT_2];
+    for (a, e) in [q.x, q.y, q.z, q.w].into_iter().zip(expected) {
+        assert!((a - e).abs() < 1e-12, "{q:?}");
+    }
+}
+
+#[test]
+fn every_form_yields_the_same_normalized_rotation() {
+    let yaml = r#"
+rotations:
+  - rpy(0, 0, deg(90))
+  - axis_angle(0, 0, 5, pi/2)
+  - quat(0, 0, 2, 2)
+  - [0, 0, 1, 1]
+  - [0, 0, 1.5707963267948966]
+  - !degrees [0, 0, 90]
+  - {x: 0, y: 0, z: 3, w: 3}
+  - {roll: 0, pitch: 0, yaw: deg(90)}
+  - {axis: [0, 0, 1], angle: deg(90)}
+"#;
+    let options = serde_saphyr::options! {
+        angle_conversions: true,
+    };
+    let poses: Poses = serde_saphyr::from_str_with_options(yaml, options).unwrap();
+    assert_eq!(poses.rotations.len(), 9);
+    for rotation in &poses.rotations {
+        assert_yaw_90(rotation);
+    }
+}
+
+#[test]
+fn degrees_tag_applies_to_sequence_items_only_with_angle_conversions() {
+    let options = serde_saphyr::options! {
+        angle_conversions: true,
+    };
+    let v: Vec<f64> =
+        serde_saphyr::from_str_with_options("!degrees [90, 180]", options.clone()).unwrap();
+    assert!((v[0] - FRAC_PI_2).abs() < 1e-12);
+
+    // Nested containers do not inherit the tag.
+    let v: Vec<Vec<f64>> = serde_saphyr::from_str_with_options("!degrees [[90]]", options).unwrap();
+    assert_eq!(v, [[90.0]]);
+
+    let v: Vec<f64> = serde_saphyr::from_str("!degrees [90]").unwrap();
+    assert_eq!(v, [90.0]);
+}
+
+#[test]
+fn collection_angle_tags_convert_plain_float_targets() {
+    let options = serde_saphyr::options! {
+        angle_conversions: true,
+    };
+    let v: Vec<f64> =
+        serde_saphyr::from_str_with_options("!degrees [90, !radians 1.5, 180]", options.clone())
+            .unwrap();
+    assert!((v[0] - FRAC_PI_2).abs() < 1e-12);
+    assert_eq!(v[1], 1.5);
+    assert!((v[2] - PI).abs() < 1e-12);
+
+    let m: HashMap<String, f64> =
+        serde_saphyr::from_str_with_options("!degrees {yaw: 90}", options.clone()).unwrap();
+    assert!((m["yaw"] - FRAC_PI_2).abs() < 1e-12);
+
+    let v: Vec<f64> = serde_saphyr::from_str_with_options("!radians [1.5]", options).unwrap();
+    assert_eq!(v, [1.5]);
+}
+
+#[test]
+fn degrees_tag_applies_to_mapping_values() {
+    let options = serde_saphyr::options! {
+        angle_conversions: true,
+    };
+    let r: Rotation =
+        serde_saphyr::from_str_with_options("!degrees {yaw: 90}", options.clone()).unwrap();
+    assert_yaw_90(&r);
+
+    // The axis is a direction, not an angle, so only `angle` is converted.
+    let r: Rotation =
+        serde_saphyr::from_str_with_options("!degrees {axis: [0, 0, 1], angle: 90}", options)
+            .unwrap();
+    assert_yaw_90(&r);
+}
+
+#[test]
+fn invalid_rotations_are_rejected() {
+    for yaml in [
+        "rpy(1, 2)",
+        "quat(0, 0, 0, 0)",
+        "[1, 2]",
+        "[1, 2, 3, 4, 5]",
+        "{x: 1, roll: 2}",
+        "{x: 0, y: 0, z: 0, w: 0}",
+        "{axis: [0, 0, 0], angle: 1}",
+    ] {
+        assert!(
+            serde_saphyr::from_str::<Quaternion>(yaml).is_err(),
+            "{yaml} should fail"
+        );
+    }
+
+    let err = serde_saphyr::from_str::<Poses>("rotations:\n  - rpy(0, 0, furlong)\n").unwrap_err();
+    assert!(err.to_string().contains("furlong"), "{err}");
+    assert_eq!(err.location().map(|l| l.line()), Some(2));
+}
+
+#[test]
+fn serializes_in_the_configured_format() {
+    let rotation = Rotation::from_rpy(0.0, 0.0, FRAC_PI_2);
+    let cases = [
+        (RotationFormat::Quaternion, "z: 0.7071067811865475"),
+        (RotationFormat::QuaternionSeq, "- 0.7071067811865476"),
+        (RotationFormat::Rpy, "- 1.57079632679489"),
+        (RotationFormat::RpyDegrees, "rpy(deg(0), deg(0), deg(90))"),
+    ];
+    for (format, expected) in cases {
+        let yaml = serde_saphyr::to_string(&rotation.with_format(format)).unwrap();
+        assert!(yaml.contains(expected), "{format:?}: {yaml}");
+
+        let back: Rotation = serde_saphyr::from_str(&yaml).unwrap();
+        assert_yaw_90(&back);
+        assert_eq!(back.format(), RotationFormat::default());
+    }
+}
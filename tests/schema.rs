@@ -0,0 +1,204 @@
+#![cfg(all(feature = "serialize", feature = "deserialize"))]
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use serde_saphyr::{Error, Schema, from_str_with_options};
+
+const SCALARS: &str = "\
+a: ~
+b: Null
+c: yes
+d: TRUE
+e: 0x1F
+f: 0b101
+g: 1:30
+h: 1_000
+i: .inf
+j: 012
+k: -1.5e3
+";
+
+fn load_any(yaml: &str, schema: Schema) -> Value {
+    let options = serde_saphyr::options! {
+        schema: Some(schema),
+        reject_non_finite_typeless_float: false,
+    };
+    from_str_with_options(yaml, options).unwrap()
+}
+
+#[test]
+fn failsafe_reads_every_plain_scalar_as_string() {
+    let value = load_any(SCALARS, Schema::Failsafe);
+    assert_eq!(value["a"], json!("~"));
+    assert_eq!(value["d"], json!("TRUE"));
+    assert_eq!(value["k"], json!("-1.5e3"));
+}
+
+#[test]
+fn json_schema_accepts_only_json_spellings() {
+    let value = load_any(
+        "a: null\nb: true\nc: -12\nd: 1.5e3\nrest: ~\n",
+        Schema::Json,
+    );
+    assert_eq!(
+        value,
+        json!({"a": null, "b": true, "c": -12, "d": 1500.0, "rest": "~"})
+    );
+
+    let value = load_any(SCALARS, Schema::Json);
+    for key in ["b", "c", "d", "e", "f", "g", "h", "i", "j"] {
+        assert!(value[key].is_string(), "{key}: {}", value[key]);
+    }
+}
+
+#[test]
+fn core_schema_resolves_core_forms() {
+    let value = load_any(SCALARS, Schema::Core);
+    assert_eq!(
+        value,
+        json!({
+            "a": null, "b": null, "c": "yes", "d": true, "e": 31, "f": "0b101",
+            "g": "1:30", "h": "1_000", "i": ".inf", "j": 12, "k": -1500.0,
+        })
+    );
+}
+
+#[test]
+fn yaml11_schema_resolves_pyyaml_forms() {
+    let value = load_any(SCALARS, Schema::Yaml11);
+    assert_eq!(
+        value,
+        json!({
+            "a": null, "b": null, "c": true, "d": true, "e": 31, "f": 5,
+            "g": 90, "h": 1000, "i": ".inf", "j": 10, "k": "-1.5e3",
+        })
+    );
+    assert_eq!(load_any("t: 1:30.5\n", Schema::Yaml11)["t"], json!(90.5));
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Typed {
+    flag: bool,
+    mode: u32,
+    ratio: f64,
+    label: String,
+    note: Option<String>,
+}
+
+#[test]
+fn typed_targets_use_the_schema_spellings() {
+    let yaml = "flag: Y\nmode: 0755\nratio: 1\nlabel: '~'\nnote: Null\n";
+    let options = serde_saphyr::options! { schema: Some(Schema::Yaml11) };
+    let typed: Typed = from_str_with_options(yaml, options).unwrap();
+    assert_eq!(
+        typed,
+        Typed {
+            flag: true,
+            mode: 0o755,
+            ratio: 1.0,
+            label: "~".to_owned(),
+            note: None,
+        }
+    );
+
+    let options = serde_saphyr::options! { schema: Some(Schema::Yaml11) };
+    let err = from_str_with_options::<String>("~", options).unwrap_err();
+    assert!(matches!(
+        err.without_snippet(),
+        Error::NullIntoString { .. }
+    ));
+}
+
+#[test]
+fn json_schema_rejects_core_spellings_for_typed_targets() {
+    let options = || serde_saphyr::options! { schema: Some(Schema::Json) };
+    let err = from_str_with_options::<bool>("True", options()).unwrap_err();
+    assert!(matches!(
+        err.without_snippet(),
+        Error::InvalidScalar { ty: "boolean", .. }
+    ));
+    assert!(from_str_with_options::<u8>("0x1F", options()).is_err());
+    assert!(from_str_with_options::<f64>(".inf", options()).is_err());
+
+    // `~` is a string under the JSON schema.
+    let label: String = from_str_with_options("~", options()).unwrap();
+    assert_eq!(label, "~");
+}
+
+#[test]
+fn yaml_11_directive_selects_yaml11_schema() {
+    let core = || serde_saphyr::options! { schema: Some(Schema::Core) };
+    let yaml = "%YAML 1.1\n---\nflag: on\nmode: 0755\n";
+    let value: Value = from_str_with_options(yaml, core()).unwrap();
+    assert_eq!(value, json!({"flag": true, "mode": 493}));
+
+    // Only for that document.
+    let yaml = "%YAML 1.1\n---\ntimeout: 1:30\n...\n---\ntimeout: 1:30\n";
+    let values: Vec<Value> = serde_saphyr::from_multiple_with_options(yaml, core()).unwrap();
+    assert_eq!(
+        values,
+        vec![json!({"timeout": 90}), json!({"timeout": "1:30"})]
+    );
+
+    // An explicit JSON schema wins over the directive.
+    let options = serde_saphyr::options! { schema: Some(Schema::Json) };
+    let value: Value = from_str_with_options("%YAML 1.1\n---\nflag: on\n", options).unwrap();
+    assert_eq!(value, json!({"flag": "on"}));
+}
+
+#[test]
+fn yaml_11_directive_without_schema_selects_yaml11_schema() {
+    let yaml = "%YAML 1.1\n---\na: 0755\nb: 1:30\n";
+    let value: Value = serde_saphyr::from_str(yaml).unwrap();
+    assert_eq!(value, json!({"a": 493, "b": 90}));
+
+    let mode: u32 = serde_saphyr::from_str("%YAML 1.1\n---\n0755\n").unwrap();
+    assert_eq!(mode, 0o755);
+
+    // Without the directive the default resolution is unchanged.
+    let value: Value = serde_saphyr::from_str("a: 0755\nb: 1:30\n").unwrap();
+    assert_eq!(value, json!({"a": 755.0, "b": "1:30"}));
+}
+
+#[test]
+fn no_schema_follows_the_selected_schema() {
+    let options = || serde_saphyr::options! { no_schema: true, schema: Some(Schema::Core) };
+    let label: String = from_str_with_options("yes", options()).unwrap();
+    assert_eq!(label, "yes");
+    let err = from_str_with_options::<String>("0o17", options()).unwrap_err();
+    assert!(matches!(
+        err.without_snippet(),
+        Error::QuotingRequired { .. }
+    ));
+}
+
+#[derive(Serialize)]
+struct Labels {
+    duration: &'static str,
+    answer: &'static str,
+}
+
+#[test]
+fn serializer_quotes_what_the_schema_would_resolve() {
+    let labels = Labels {
+        duration: "1:30",
+        answer: "yes",
+    };
+
+    let options = serde_saphyr::ser_options! { schema: Some(Schema::Yaml11) };
+    let yaml = serde_saphyr::to_string_with_options(&labels, options).unwrap();
+    assert_eq!(yaml, "duration: \"1:30\"\nanswer: \"yes\"\n");
+
+    let options = serde_saphyr::ser_options! { schema: Some(Schema::Core) };
+    let yaml = serde_saphyr::to_string_with_options(&labels, options).unwrap();
+    assert_eq!(yaml, "duration: 1:30\nanswer: yes\n");
+}
+
+#[test]
+fn yaml11_schema_conflicts_with_yaml_12_output() {
+    let options = serde_saphyr::ser_options! { schema: Some(Schema::Yaml11), yaml_12: true };
+    let err = serde_saphyr::to_string_with_options(&1, options).unwrap_err();
+    assert!(matches!(
+        err,
+        serde_saphyr::SerializeError::InvalidOptions(_)
+    ));
+}
@@ -0,0 +1,185 @@
+#![cfg(feature = "serialize")]
+use serde::Serialize;
+use serde_saphyr::ser::Error;
+use serde_saphyr::ser::budget::BudgetBreach;
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::rc::Rc;
+
+#[derive(Serialize)]
+struct Node {
+    name: String,
+    children: Vec<Node>,
+}
+
+fn chain(depth: usize) -> Node {
+    let mut node = Node {
+        name: "leaf".into(),
+        children: vec![],
+    };
+    for _ in 0..depth {
+        node = Node {
+            name: "inner".into(),
+            children: vec![node],
+        };
+    }
+    node
+}
+
+#[test]
+fn no_budget_by_default() {
+    let values: Vec<u32> = (0..10_000).collect();
+    let yaml = serde_saphyr::to_string(&values).unwrap();
+    assert!(yaml.ends_with("- 9999\n"));
+}
+
+#[test]
+fn default_budget_accepts_ordinary_values() {
+    let options = serde_saphyr::ser_options! {
+        budget: serde_saphyr::ser_budget! {},
+    };
+    let yaml = serde_saphyr::to_string_with_options(&chain(10), options).unwrap();
+    assert!(yaml.contains("name: leaf"));
+}
+
+#[test]
+fn node_limit_is_enforced() {
+    let options = serde_saphyr::ser_options! {
+        budget: serde_saphyr::ser_budget! { max_nodes: 10 },
+    };
+    let values: Vec<u32> = (0..20).collect();
+    let err = serde_saphyr::to_string_with_options(&values, options).unwrap_err();
+    assert!(
+        matches!(
+            err,
+            Error::Budget {
+                breach: BudgetBreach::Nodes { nodes: 11 }
+            }
+        ),
+        "{err:?}"
+    );
+}
+
+#[test]
+fn node_limit_counts_keys() {
+    let mut map = BTreeMap::new();
+    for i in 0..3 {
+        map.insert(format!("k{i}"), i);
+    }
+    // One mapping plus three keys and three values.
+    let exact = serde_saphyr::ser_options! {
+        budget: serde_saphyr::ser_budget! { max_nodes: 7 },
+    };
+    assert!(serde_saphyr::to_string_with_options(&map, exact).is_ok());
+    let short = serde_saphyr::ser_options! {
+        budget: serde_saphyr::ser_budget! { max_nodes: 6 },
+    };
+    assert!(serde_saphyr::to_string_with_options(&map, short).is_err());
+}
+
+#[test]
+fn depth_limit_is_enforced() {
+    let options = serde_saphyr::ser_options! {
+        budget: serde_saphyr::ser_budget! { max_depth: 8 },
+    };
+    let err = serde_saphyr::to_string_with_options(&chain(8), options).unwrap_err();
+    assert!(
+        matches!(
+            err,
+            Error::Budget {
+                breach: BudgetBreach::Depth { depth: 9 }
+            }
+        ),
+        "{err:?}"
+    );
+
+    let options = serde_saphyr::ser_options! {
+        budget: serde_saphyr::ser_budget! { max_depth: 8 },
+    };
+    // chain(3) nests struct -> seq -> struct ... for a total of 7 levels.
+    assert!(serde_saphyr::to_string_with_options(&chain(3), options).is_ok());
+}
+
+#[test]
+fn depth_limit_applies_to_flow_collections() {
+    let nested = serde_saphyr::FlowSeq(vec![vec![vec![1]]]);
+    let options = serde_saphyr::ser_options! {
+        budget: serde_saphyr::ser_budget! { max_depth: 2 },
+    };
+    let err = serde_saphyr::to_string_with_options(&nested, options).unwrap_err();
+    assert!(matches!(
+        err,
+        Error::Budget {
+            breach: BudgetBreach::Depth { .. }
+        }
+    ));
+}
+
+#[test]
+fn depth_limit_stops_reference_cycles() {
+    #[derive(Serialize)]
+    struct Cyclic {
+        next: Option<Rc<RefCell<Cyclic>>>,
+    }
+
+    let node = Rc::new(RefCell::new(Cyclic { next: None }));
+    node.borrow_mut().next = Some(node.clone());
+    let options = serde_saphyr::ser_options! {
+        budget: serde_saphyr::ser_budget! {},
+    };
+    let err = serde_saphyr::to_string_with_options(&*node.borrow(), options);
+    // Break the cycle so the test does not leak.
+    node.borrow_mut().next = None;
+    assert!(matches!(
+        err,
+        Err(Error::Budget {
+            breach: BudgetBreach::Depth { depth: 65 }
+        })
+    ));
+}
+
+#[test]
+fn output_bytes_limit_is_enforced() {
+    let options = serde_saphyr::ser_options! {
+        budget: serde_saphyr::ser_budget! { max_output_bytes: 64 },
+    };
+    let long = "x".repeat(100);
+    let err = serde_saphyr::to_string_with_options(&vec![long], options).unwrap_err();
+    assert!(matches!(
+        err,
+        Error::Budget {
+            breach: BudgetBreach::OutputBytes { .. }
+        }
+    ));
+
+    let options = serde_saphyr::ser_options! {
+        budget: serde_saphyr::ser_budget! { max_output_bytes: 64 },
+    };
+    let mut out = String::new();
+    let values: Vec<u32> = (0..1_000).collect();
+    let err = serde_saphyr::to_fmt_writer_with_options(&mut out, &values, options).unwrap_err();
+    assert!(matches!(err, Error::Budget { .. }));
+    // The limit is checked per node, so at most one line of indicators may follow it.
+    assert!(out.len() < 64 + 8, "output grew to {} bytes", out.len());
+}
+
+#[test]
+fn budget_applies_per_document() {
+    let options = serde_saphyr::ser_options! {
+        budget: serde_saphyr::ser_budget! { max_nodes: 3 },
+    };
+    let docs = vec![vec![1, 2], vec![3, 4]];
+    let yaml = serde_saphyr::to_string_multiple_with_options(&docs, options).unwrap();
+    assert_eq!(yaml, "- 1\n- 2\n---\n- 3\n- 4\n");
+}
+
+#[test]
+fn budget_error_display() {
+    let err = Error::Budget {
+        breach: BudgetBreach::Nodes { nodes: 5 },
+    };
+    assert_eq!(
+        err.to_string(),
+        "serializer budget breached: node count limit exceeded (5)"
+    );
+}
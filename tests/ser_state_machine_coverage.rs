@@ -110,7 +110,7 @@ struct AnchoredEmptyMap {
 }
 
 #[test]
-fn anchored_empty_map_after_an_indented_dash_uses_aligned_braces() {
+fn anchored_empty_map_after_an_indented_dash_keeps_braces_inline() {
     let value = AnchoredEmptyMap {
         items: vec![RcAnchor::wrapping(BTreeMap::new())],
     };
@@ -120,11 +120,7 @@ fn anchored_empty_map_after_an_indented_dash_uses_aligned_braces() {
 
     let yaml = to_string_with_options(&value, options).unwrap();
 
-    assert!(yaml.contains("- &a1\n"), "missing anchor:\n{yaml}");
-    assert!(
-        yaml.contains("    {}\n"),
-        "empty map was not aligned:\n{yaml}"
-    );
+    assert_eq!(yaml, "items:\n  - &a1 {}\n");
 }
 
 #[derive(Serialize)]
@@ -218,6 +218,32 @@ fn arc_anchor_shared_in_map_has_repeated_anchor_and_values() {
     );
 }
 
+#[test]
+fn anchored_empty_collections_stay_inline_and_round_trip() {
+    #[derive(Debug, Deserialize, Serialize)]
+    struct Doc {
+        a: RcAnchor<Vec<u8>>,
+        b: RcAnchor<Vec<u8>>,
+        m: RcAnchor<BTreeMap<String, u8>>,
+        n: RcAnchor<BTreeMap<String, u8>>,
+    }
+    let list = Rc::new(Vec::new());
+    let map = Rc::new(BTreeMap::new());
+    let doc = Doc {
+        a: RcAnchor(list.clone()),
+        b: RcAnchor(list),
+        m: RcAnchor(map.clone()),
+        n: RcAnchor(map),
+    };
+
+    let yaml = to_string(&doc).expect("serialize anchored empty collections");
+    assert_eq!(yaml, "a: &a1 []\nb: *a1\nm: &a2 {}\n\"n\": *a2\n");
+
+    let back: Doc = from_str(&yaml).expect("read anchored empty collections");
+    assert!(back.a.is_empty() && back.m.is_empty());
+    assert!(Rc::ptr_eq(&back.a.0, &back.b.0));
+}
+
 #[test]
 fn rc_weak_anchor_present_serializes_under_anchor() {
     let strong = Rc::new(Node {
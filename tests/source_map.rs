@@ -0,0 +1,114 @@
+#![cfg(feature = "deserialize")]
+use serde::Deserialize;
+use serde_saphyr::path_key;
+use serde_saphyr::path_map::PathKey;
+use std::collections::BTreeMap;
+
+#[derive(Debug, Deserialize)]
+struct Server {
+    host: String,
+    port: u16,
+}
+
+#[derive(Debug, Deserialize)]
+struct Config {
+    name: String,
+    servers: Vec<Server>,
+    #[serde(default)]
+    labels: BTreeMap<String, String>,
+}
+
+#[test]
+fn records_nested_struct_sequence_and_map_paths() {
+    let yaml = "name: demo\nservers:\n  - host: a\n    port: 80\n  - host: b\n    port: 0\nlabels:\n  tier: web\n";
+    let (config, map) = serde_saphyr::from_str_with_source_map::<Config>(yaml).unwrap();
+    assert_eq!(config.name, "demo");
+    assert_eq!(config.servers[1].host, "b");
+    assert_eq!(config.labels["tier"], "web");
+
+    let line = |path: &PathKey| map.get(path).unwrap().reference_location.line();
+    assert_eq!(line(&path_key!["name"]), 1);
+    assert_eq!(line(&path_key!["servers"]), 3);
+    assert_eq!(line(&path_key!["servers", 0, "host"]), 3);
+    assert_eq!(line(&path_key!["servers", 1, "port"]), 6);
+    assert_eq!(line(&path_key!["labels", "tier"]), 8);
+    assert_eq!(
+        map.get(&path_key!["servers", 1, "host"])
+            .unwrap()
+            .reference_location
+            .column(),
+        11
+    );
+    assert!(map.get(&path_key!["servers", 2]).is_none());
+    assert!(map.get(&path_key!["missing"]).is_none());
+    assert!(!map.is_empty());
+    assert_eq!(map.len(), map.iter().count());
+}
+
+#[test]
+fn alias_reports_reference_and_definition() {
+    #[derive(Deserialize)]
+    struct Pair {
+        base: Server,
+        copy: Server,
+    }
+    let yaml = "base: &s\n  host: a\n  port: 1\ncopy: *s\n";
+    let (pair, map) = serde_saphyr::from_str_with_source_map::<Pair>(yaml).unwrap();
+    assert_eq!(pair.copy.port, pair.base.port);
+
+    let copy = map.get(&path_key!["copy", "port"]).unwrap();
+    assert_eq!(copy.reference_location.line(), 4);
+    assert_eq!(copy.defined_location.line(), 3);
+}
+
+#[test]
+fn uses_yaml_key_spelling() {
+    #[derive(Deserialize)]
+    #[serde(rename_all = "kebab-case")]
+    struct Limits {
+        max_connections: u32,
+    }
+    let (limits, map) =
+        serde_saphyr::from_str_with_source_map::<Limits>("max-connections: 10\n").unwrap();
+    assert_eq!(limits.max_connections, 10);
+    assert!(map.get(&path_key!["max-connections"]).is_some());
+    assert!(map.get(&path_key!["max_connections"]).is_none());
+}
+
+#[test]
+fn path_key_macro_matches_builder() {
+    assert_eq!(path_key![], PathKey::new());
+    assert_eq!(
+        path_key!["a", 1, String::from("b")],
+        PathKey::new().join_key("a").join_index(1).join_key("b")
+    );
+}
+
+#[test]
+fn options_variant_applies_options() {
+    let options = serde_saphyr::options! {
+        budget: serde_saphyr::budget! { max_nodes: 2 },
+    };
+    let err =
+        serde_saphyr::from_str_with_options_and_source_map::<Vec<u32>>("[1, 2, 3, 4]", options)
+            .unwrap_err();
+    assert!(err.to_string().contains("budget"), "{err}");
+
+    let (value, map) = serde_saphyr::from_str_with_options_and_source_map::<Vec<u32>>(
+        "[1, 2]",
+        serde_saphyr::options! {},
+    )
+    .unwrap();
+    assert_eq!(value, vec![1, 2]);
+    assert_eq!(
+        map.get(&path_key![1]).unwrap().reference_location.column(),
+        5
+    );
+}
+
+#[test]
+fn errors_are_returned_without_map() {
+    let err = serde_saphyr::from_str_with_source_map::<Config>("name: [1]\n").unwrap_err();
+    assert!(err.location().is_some());
+    assert!(serde_saphyr::from_str_with_source_map::<u8>("1\n---\n2\n").is_err());
+}
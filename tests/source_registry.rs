@@ -0,0 +1,103 @@
+#![cfg(all(feature = "serialize", feature = "deserialize"))]
+use serde::Deserialize;
+use serde_saphyr::{SourceRegistry, Spanned};
+
+#[derive(Debug, Deserialize)]
+struct Root {
+    name: Spanned<String>,
+}
+
+#[test]
+fn registry_records_root_input() {
+    let registry = SourceRegistry::new();
+    let options = serde_saphyr::options! {}.with_source_registry(&registry);
+    let root: Root = serde_saphyr::from_str_with_options("name: demo\n", options).unwrap();
+
+    assert_eq!(registry.len(), 1);
+    let source = registry.source_of(&root.name.referenced).unwrap();
+    assert_eq!(source.name(), "<input>");
+    assert_eq!(source.text(), Some("name: demo\n"));
+    assert_eq!(source.resolved_id(), None);
+    assert_eq!(source.parent(), None);
+
+    let rendered = root.name.render_snippet(&registry, "name is taken");
+    assert!(rendered.contains("name is taken"), "{rendered}");
+    assert!(rendered.contains("1 | name: demo"), "{rendered}");
+}
+
+#[test]
+fn registry_is_cleared_per_parse_and_reader_text_is_not_kept() {
+    let registry = SourceRegistry::new();
+    let options = serde_saphyr::options! {}.with_source_registry(&registry);
+    let _: Root = serde_saphyr::from_str_with_options("name: a\n", options.clone()).unwrap();
+    let root: Root =
+        serde_saphyr::from_reader_with_options("name: b\n".as_bytes(), options).unwrap();
+
+    assert_eq!(registry.len(), 1);
+    assert_eq!(registry.sources()[0].text(), None);
+    let rendered = root.name.render_snippet(&registry, "unknown name");
+    assert_eq!(rendered, "<input>:1:7: unknown name");
+}
+
+#[test]
+fn defined_snippet_points_at_the_anchor() {
+    #[derive(Deserialize)]
+    struct Pair {
+        #[allow(dead_code)]
+        a: String,
+        b: Spanned<String>,
+    }
+
+    let registry = SourceRegistry::new();
+    let options = serde_saphyr::options! {}.with_source_registry(&registry);
+    let pair: Pair = serde_saphyr::from_str_with_options("a: &x one\nb: *x\n", options).unwrap();
+
+    let used = pair.b.render_snippet(&registry, "used here");
+    assert!(used.contains("<input>:2:4"), "{used}");
+    let defined = pair.b.render_defined_snippet(&registry, "defined here");
+    assert!(defined.contains("<input>:1:7"), "{defined}");
+}
+
+#[cfg(feature = "include")]
+#[test]
+fn included_locations_are_attributed_to_their_file() {
+    #[derive(Debug, Deserialize)]
+    struct Service {
+        server: Server,
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct Server {
+        port: Spanned<u16>,
+    }
+
+    let registry = SourceRegistry::new();
+    let options = serde_saphyr::options! {}
+        .with_source_registry(&registry)
+        .with_include_resolver(|req| {
+            Ok(serde_saphyr::ResolvedInclude::new(
+                format!("/etc/app/{}", req.spec),
+                req.spec,
+                serde_saphyr::InputSource::from_string("# server\nport: 80\n".to_string()),
+            ))
+        });
+    let service: Service =
+        serde_saphyr::from_str_with_options("server: !include server.yaml\n", options).unwrap();
+
+    assert_eq!(registry.len(), 2);
+    let source = registry.source_of(&service.server.port.referenced).unwrap();
+    assert_eq!(source.name(), "server.yaml");
+    assert_eq!(source.resolved_id(), Some("/etc/app/server.yaml"));
+    assert_eq!(source.text(), Some("# server\nport: 80\n"));
+    let parent = registry.get(source.parent().unwrap()).unwrap();
+    assert_eq!(parent.name(), "<input>");
+    assert_eq!(source.include_location().line(), 1);
+
+    let rendered = service
+        .server
+        .port
+        .render_snippet(&registry, "privileged port");
+    assert!(rendered.contains("--> server.yaml:2:7"), "{rendered}");
+    assert!(rendered.contains("2 | port: 80"), "{rendered}");
+    assert!(rendered.contains("--> <input>:1:18"), "{rendered}");
+}
@@ -0,0 +1,89 @@
+#![cfg(feature = "deserialize")]
+use serde::Deserialize;
+use serde_saphyr::localizer::Localizer;
+use serde_saphyr::{DefaultMessageFormatter, Error, UserMessageFormatter};
+
+#[allow(dead_code)]
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Deployment {
+    name: String,
+    #[serde(default)]
+    max_replicas: u32,
+    #[serde(default)]
+    strategy: Strategy,
+}
+
+#[derive(Debug, Default, Deserialize)]
+enum Strategy {
+    #[default]
+    Recreate,
+    RollingUpdate,
+}
+
+fn load_err(yaml: &str) -> Error {
+    serde_saphyr::from_str::<Deployment>(yaml).unwrap_err()
+}
+
+#[test]
+fn unknown_field_suggests_closest_name() {
+    let err = load_err("nmae: web\n");
+    assert_eq!(err.suggestion(), Some("name"));
+
+    let msg = err.render_with_formatter(&DefaultMessageFormatter);
+    assert!(
+        msg.contains("unknown field `nmae`, expected one of name, max_replicas, strategy; did you mean `name`?"),
+        "{msg}"
+    );
+}
+
+#[test]
+fn suggestion_ignores_case_and_separators() {
+    let err = load_err("name: web\nmaxReplicas: 3\n");
+    assert_eq!(err.suggestion(), Some("max_replicas"));
+
+    let err = load_err("name: web\nstrategy: rolling-update\n");
+    assert_eq!(err.suggestion(), Some("RollingUpdate"));
+    assert!(err.to_string().contains("did you mean `RollingUpdate`?"));
+}
+
+#[test]
+fn unrelated_names_get_no_suggestion() {
+    let err = load_err("name: web\nimage: nginx\n");
+    assert_eq!(err.suggestion(), None);
+    assert!(!err.to_string().contains("did you mean"));
+
+    let err = load_err("name: [\n");
+    assert_eq!(err.suggestion(), None);
+}
+
+#[test]
+fn user_formatter_keeps_the_suggestion() {
+    let err = load_err("name: web\nstrategy: Recreat\n");
+    let msg = err.render_with_formatter(&UserMessageFormatter);
+    assert!(msg.contains("did you mean `Recreate`?"), "{msg}");
+}
+
+#[test]
+fn localizer_controls_suggestion_wording() {
+    struct German;
+    impl Localizer for German {
+        fn did_you_mean(&self, suggestion: &str) -> String {
+            format!("meinten Sie `{suggestion}`?")
+        }
+    }
+
+    let err = load_err("nmae: web\n");
+    let msg = err.render_with_formatter(&DefaultMessageFormatter.with_localizer(&German));
+    assert!(msg.contains("; meinten Sie `name`?"), "{msg}");
+}
+
+#[cfg(feature = "miette")]
+#[test]
+fn miette_report_shows_suggestion_as_help() {
+    let yaml = "nmae: web\n";
+    let err = load_err(yaml);
+    let report = serde_saphyr::miette::to_miette_report(&err, yaml, "deploy.yaml");
+    let help = report.help().map(|help| help.to_string());
+    assert_eq!(help.as_deref(), Some("did you mean `name`?"));
+}
@@ -0,0 +1,168 @@
+#![cfg(all(feature = "serialize", feature = "deserialize"))]
+use serde::{Deserialize, Serialize};
+use serde_saphyr::{SerializerOptions, TagDirective};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+enum Color {
+    Red,
+    Green,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Blob {
+    #[serde(with = "serde_bytes")]
+    data: Vec<u8>,
+}
+
+fn k8s() -> TagDirective {
+    TagDirective::new("!k8s!", "tag:example.com,2026:")
+}
+
+#[test]
+fn directives_are_written_before_the_document() {
+    let options = serde_saphyr::ser_options! { tag_directives: vec![k8s()] };
+    let yaml = serde_saphyr::to_string_with_options(&vec![1, 2], options).unwrap();
+    assert_eq!(yaml, "%TAG !k8s! tag:example.com,2026:\n---\n- 1\n- 2\n");
+
+    let options = serde_saphyr::ser_options! { yaml_12: true, tag_directives: vec![k8s()] };
+    let yaml = serde_saphyr::to_string_with_options(&7, options).unwrap();
+    assert_eq!(
+        yaml,
+        "%YAML 1.2\n%TAG !k8s! tag:example.com,2026:\n---\n7\n"
+    );
+}
+
+#[test]
+fn enum_tags_use_registered_handle() {
+    let options = serde_saphyr::ser_options! {
+        tagged_enums: true,
+        tag_directives: vec![k8s()],
+        enum_tag_prefix: Some("tag:example.com,2026:".to_string()),
+    };
+    let yaml = serde_saphyr::to_string_with_options(&vec![Color::Red], options).unwrap();
+    assert_eq!(
+        yaml,
+        "%TAG !k8s! tag:example.com,2026:\n---\n- !k8s!Color Red\n"
+    );
+    let back: Vec<Color> = serde_saphyr::from_str(&yaml).unwrap();
+    assert_eq!(back, vec![Color::Red]);
+}
+
+#[test]
+fn longest_matching_prefix_wins() {
+    let options = serde_saphyr::ser_options! {
+        tagged_enums: true,
+        tag_directives: vec![
+            k8s(),
+            TagDirective::new("!apps!", "tag:example.com,2026:apps/"),
+        ],
+        enum_tag_prefix: Some("tag:example.com,2026:apps/".to_string()),
+    };
+    let yaml = serde_saphyr::to_string_with_options(&Color::Green, options).unwrap();
+    assert!(yaml.ends_with("---\n!apps!Color Green\n"), "{yaml}");
+}
+
+#[test]
+fn unregistered_enum_prefix_is_written_verbatim() {
+    let options = serde_saphyr::ser_options! {
+        tagged_enums: true,
+        enum_tag_prefix: Some("tag:example.com,2026:".to_string()),
+    };
+    let yaml = serde_saphyr::to_string_with_options(&Color::Red, options).unwrap();
+    assert_eq!(yaml, "!<tag:example.com,2026:Color> Red\n");
+    let back: Color = serde_saphyr::from_str(&yaml).unwrap();
+    assert_eq!(back, Color::Red);
+}
+
+#[test]
+fn default_enum_tags_keep_secondary_handle() {
+    let options = serde_saphyr::ser_options! {
+        tagged_enums: true,
+        tag_directives: vec![k8s()],
+    };
+    let yaml = serde_saphyr::to_string_with_options(&Color::Red, options).unwrap();
+    assert!(yaml.ends_with("---\n!!Color Red\n"), "{yaml}");
+}
+
+#[test]
+fn binary_uses_handle_for_yaml_namespace() {
+    let blob = Blob {
+        data: b"hi".to_vec(),
+    };
+    let options = serde_saphyr::ser_options! {
+        tag_directives: vec![TagDirective::new("!y!", "tag:yaml.org,2002:")],
+    };
+    let yaml = serde_saphyr::to_string_with_options(&blob, options).unwrap();
+    assert!(yaml.ends_with("---\ndata: !y!binary aGk=\n"), "{yaml}");
+    let back: Blob = serde_saphyr::from_str(&yaml).unwrap();
+    assert_eq!(back, blob);
+
+    // Redefining `!!` moves the YAML namespace out of the shorthand.
+    let options = serde_saphyr::ser_options! {
+        tag_directives: vec![TagDirective::new("!!", "tag:example.com,2026:")],
+    };
+    let yaml = serde_saphyr::to_string_with_options(&blob, options).unwrap();
+    assert!(
+        yaml.ends_with("data: !<tag:yaml.org,2002:binary> aGk=\n"),
+        "{yaml}"
+    );
+}
+
+#[test]
+fn user_defined_handles_select_enum_variants() {
+    #[derive(Debug, PartialEq, Deserialize)]
+    enum Workload {
+        Deployment { replicas: u32 },
+        Job(String),
+    }
+
+    let yaml = "%TAG !k8s! tag:example.com,2026:\n---\n- !k8s!Deployment { replicas: 3 }\n- !k8s!Job nightly\n";
+    let workloads: Vec<Workload> = serde_saphyr::from_str(yaml).unwrap();
+    assert_eq!(
+        workloads,
+        vec![
+            Workload::Deployment { replicas: 3 },
+            Workload::Job("nightly".into())
+        ]
+    );
+}
+
+#[test]
+fn any_directive_prefix_selects_enum_variants() {
+    #[derive(Debug, PartialEq, Deserialize)]
+    enum Workload {
+        Deployment(u32),
+        Job { name: String },
+    }
+
+    let yaml =
+        "%TAG !k! https://example.com/ns/\n---\n- !k!Deployment 1\n- !k!Job {name: nightly}\n";
+    let workloads: Vec<Workload> = serde_saphyr::from_str(yaml).unwrap();
+    assert_eq!(
+        workloads,
+        vec![
+            Workload::Deployment(1),
+            Workload::Job {
+                name: "nightly".into()
+            }
+        ]
+    );
+
+    let yaml = "%TAG !k! !my-\n---\n!k!Deployment 2\n";
+    let workload: Workload = serde_saphyr::from_str(yaml).unwrap();
+    assert_eq!(workload, Workload::Deployment(2));
+}
+
+#[test]
+fn invalid_directives_are_rejected() {
+    let bad = [
+        vec![TagDirective::new("k8s", "tag:x")],
+        vec![TagDirective::new("!k8s!", "")],
+        vec![k8s(), k8s()],
+    ];
+    for tag_directives in bad {
+        let options = serde_saphyr::ser_options! { tag_directives: tag_directives };
+        assert!(serde_saphyr::to_string_with_options(&1, options).is_err());
+    }
+    assert!(SerializerOptions::default().tag_directives.is_empty());
+}
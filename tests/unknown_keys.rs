@@ -0,0 +1,113 @@
+#![cfg(feature = "deserialize")]
+use serde::Deserialize;
+use serde_saphyr::{Error, UnknownKeyPolicy, ValidationSource, Warnings, path_key};
+
+#[allow(dead_code)]
+#[derive(Debug, Deserialize)]
+struct Port {
+    name: String,
+    number: u16,
+}
+
+#[allow(dead_code)]
+#[derive(Debug, Deserialize)]
+struct Service {
+    name: String,
+    #[serde(default)]
+    replicas: Option<u32>,
+    ports: Vec<Port>,
+}
+
+const TYPOS: &str = "\
+name: web
+replica: 3
+ports:
+  - name: http
+    number: 80
+    protocl: tcp
+";
+
+fn deny() -> serde_saphyr::Options {
+    serde_saphyr::options! { unknown_keys: UnknownKeyPolicy::Error }
+}
+
+fn unwrap_validation(err: &Error) -> &Error {
+    match err {
+        Error::WithSnippet { error, .. } => error,
+        other => other,
+    }
+}
+
+#[test]
+fn unknown_keys_are_ignored_by_default() {
+    let service: Service = serde_saphyr::from_str(TYPOS).unwrap();
+    assert_eq!(service.replicas, None);
+}
+
+#[test]
+fn unknown_keys_policy_error_collects_every_key() {
+    let err = serde_saphyr::from_str_with_options::<Service>(TYPOS, deny()).unwrap_err();
+
+    let Error::ValidationError { source, issues, .. } = unwrap_validation(&err) else {
+        panic!("expected a validation error, got {err:?}");
+    };
+    assert_eq!(*source, ValidationSource::UnknownKeys);
+    assert_eq!(issues.len(), 2);
+    assert_eq!(issues[0].path, path_key!["replica"]);
+    assert_eq!(issues[0].code, "unknown_key");
+    assert_eq!(issues[1].path, path_key!["ports", 0, "protocl"]);
+    assert_eq!(
+        issues[1].params,
+        vec![("key".to_owned(), "protocl".to_owned())]
+    );
+
+    let location = err.location().unwrap();
+    assert_eq!((location.line(), location.column()), (2, 1));
+}
+
+#[test]
+fn unknown_keys_error_renders_each_key_with_its_path() {
+    let err = serde_saphyr::from_str_with_options::<Service>(TYPOS, deny()).unwrap_err();
+    let rendered = err.to_string();
+    assert!(rendered.contains("unknown key for `replica`"), "{rendered}");
+    assert!(rendered.contains("ports[0].protocl"), "{rendered}");
+    assert!(rendered.contains("    protocl: tcp"), "{rendered}");
+}
+
+#[test]
+fn nested_content_of_unknown_keys_is_not_reported_separately() {
+    let yaml = "name: web\nports: []\nextra:\n  a: 1\n  b: [1, 2]\n";
+    let err = serde_saphyr::from_str_with_options::<Service>(yaml, deny()).unwrap_err();
+    let Error::ValidationError { issues, .. } = unwrap_validation(&err) else {
+        panic!("expected a validation error, got {err:?}");
+    };
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0].path, path_key!["extra"]);
+}
+
+#[test]
+fn known_keys_and_maps_pass_under_policy_error() {
+    let yaml = "name: web\nreplicas: 2\nports:\n  - name: http\n    number: 80\n";
+    let service: Service = serde_saphyr::from_str_with_options(yaml, deny()).unwrap();
+    assert_eq!(service.replicas, Some(2));
+
+    // Map targets consume every key.
+    let map: std::collections::BTreeMap<String, u32> =
+        serde_saphyr::from_str_with_options("a: 1\nb: 2\n", deny()).unwrap();
+    assert_eq!(map.len(), 2);
+}
+
+#[test]
+fn unknown_keys_policy_works_with_warnings_and_multiple_documents() {
+    let warnings = Warnings::new();
+    let options = deny().with_warnings(&warnings);
+    let yaml = "name: a\nports: []\n---\nname: b\nports: []\nextra: 1\n";
+    let err = serde_saphyr::from_multiple_with_options::<Service>(yaml, options).unwrap_err();
+
+    let Error::ValidationError { issues, .. } = unwrap_validation(&err) else {
+        panic!("expected a validation error, got {err:?}");
+    };
+    assert_eq!(issues[0].path, path_key!["extra"]);
+    assert_eq!(err.location().unwrap().line(), 6);
+    assert_eq!(warnings.len(), 1);
+}
@@ -0,0 +1,235 @@
+#![cfg(feature = "deserialize")]
+use serde::Deserialize;
+use serde_saphyr::{
+    DefaultMessageFormatter, DuplicateKeyPolicy, Error, MessageFormatter, RenderOptions, Warning,
+    WarningKind, Warnings, path_key,
+};
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+
+#[allow(dead_code)]
+#[derive(Debug, Deserialize)]
+struct Service {
+    enabled: bool,
+    mode: String,
+    replicas: u32,
+}
+
+fn load<T: for<'de> Deserialize<'de>>(yaml: &str, options: serde_saphyr::Options) -> Vec<Warning> {
+    let warnings = Warnings::new();
+    let _: T = serde_saphyr::from_str_with_options(yaml, options.with_warnings(&warnings))
+        .expect("document should load");
+    warnings.take()
+}
+
+#[test]
+fn yaml11_booleans_are_reported_with_path_and_location() {
+    let yaml = "enabled: on\nmode: fast\nreplicas: 2\n";
+    let found = load::<Service>(yaml, serde_saphyr::options! {});
+
+    assert_eq!(found.len(), 1, "{found:?}");
+    assert_eq!(
+        found[0].kind,
+        WarningKind::Yaml11Boolean {
+            value: "on".to_owned(),
+            interpreted_as: true,
+        }
+    );
+    assert_eq!(found[0].path, path_key!["enabled"]);
+    assert_eq!(found[0].location.line(), 1);
+    assert_eq!(found[0].location.column(), 10);
+}
+
+#[test]
+fn true_and_false_are_not_reported() {
+    let found = load::<Service>(
+        "enabled: true\nmode: fast\nreplicas: 2\n",
+        serde_saphyr::options! {},
+    );
+    assert!(found.is_empty(), "{found:?}");
+}
+
+#[test]
+fn legacy_octal_is_reported_both_ways() {
+    let yaml = "enabled: true\nmode: 0644\nreplicas: 010\n";
+    let found = load::<Service>(yaml, serde_saphyr::options! { legacy_octal_numbers: true });
+    assert_eq!(found.len(), 2, "{found:?}");
+    assert_eq!(
+        found[0].kind,
+        WarningKind::LegacyOctal {
+            value: "0644".to_owned(),
+            read_as_octal: false,
+        }
+    );
+    assert_eq!(found[0].path, path_key!["mode"]);
+    assert_eq!(
+        found[1].kind,
+        WarningKind::LegacyOctal {
+            value: "010".to_owned(),
+            read_as_octal: true,
+        }
+    );
+    assert_eq!(found[1].path, path_key!["replicas"]);
+
+    // Quoted strings and numbers that are not octal-looking stay quiet.
+    let found = load::<Service>(
+        "enabled: true\nmode: '0644'\nreplicas: 0\n",
+        serde_saphyr::options! {},
+    );
+    assert!(found.is_empty(), "{found:?}");
+}
+
+#[test]
+fn legacy_octal_under_core_schema_is_not_reported_as_octal() {
+    let yaml = "enabled: true\nmode: fast\nreplicas: 012\n";
+    let options = serde_saphyr::options! { schema: Some(serde_saphyr::Schema::Core) };
+    let found = load::<Service>(yaml, options);
+    assert_eq!(found.len(), 1, "{found:?}");
+    assert_eq!(
+        found[0].kind,
+        WarningKind::LegacyOctal {
+            value: "012".to_owned(),
+            read_as_octal: false,
+        }
+    );
+    assert!(!found[0].to_string().contains("0o12"), "{}", found[0]);
+}
+
+#[test]
+fn ignored_keys_are_reported_once_with_nested_path() {
+    #[allow(dead_code)]
+    #[derive(Debug, Deserialize)]
+    struct Root {
+        services: Vec<Service>,
+    }
+
+    let yaml = "\
+services:
+  - enabled: true
+    mode: fast
+    replicas: 1
+    extra:
+      flag: yes
+      mode: 0755
+";
+    let found = load::<Root>(yaml, serde_saphyr::options! {});
+
+    assert_eq!(found.len(), 1, "{found:?}");
+    assert_eq!(
+        found[0].kind,
+        WarningKind::IgnoredKey {
+            key: "extra".to_owned()
+        }
+    );
+    assert_eq!(found[0].path, path_key!["services", 0, "extra"]);
+    assert_eq!(found[0].location.line(), 5);
+    assert_eq!(found[0].location.column(), 5);
+}
+
+#[test]
+fn last_wins_duplicates_are_reported() {
+    let yaml = "a: 1\nb: 2\na: 3\n";
+    let found = load::<BTreeMap<String, u32>>(
+        yaml,
+        serde_saphyr::options! { duplicate_keys: DuplicateKeyPolicy::LastWins },
+    );
+    assert_eq!(found.len(), 1, "{found:?}");
+    assert_eq!(
+        found[0].kind,
+        WarningKind::DuplicateKeyOverride {
+            key: Some("a".to_owned())
+        }
+    );
+    assert_eq!(found[0].location.line(), 3);
+}
+
+#[test]
+fn overridden_merge_keys_are_reported_at_the_merge() {
+    let yaml = "\
+base: &base
+  mode: slow
+  replicas: 1
+service:
+  <<: *base
+  enabled: true
+  mode: fast
+";
+    #[allow(dead_code)]
+    #[derive(Debug, Deserialize)]
+    struct Root {
+        base: BTreeMap<String, String>,
+        service: Service,
+    }
+
+    let found = load::<Root>(yaml, serde_saphyr::options! {});
+    assert_eq!(found.len(), 1, "{found:?}");
+    assert_eq!(
+        found[0].kind,
+        WarningKind::MergeOverride {
+            key: Some("mode".to_owned())
+        }
+    );
+    assert_eq!(found[0].path, path_key!["service"]);
+    assert_eq!(found[0].location.line(), 5);
+}
+
+#[test]
+fn nothing_is_collected_without_a_collector() {
+    let warnings = Warnings::new();
+    let options = serde_saphyr::options! {}.with_warnings(&warnings);
+    let _: Service =
+        serde_saphyr::from_str_with_options("enabled: y\nmode: a\nreplicas: 1\n", options).unwrap();
+
+    let service: Service =
+        serde_saphyr::from_str("enabled: yes\nmode: 0644\nreplicas: 2\nother: 1\n").unwrap();
+    assert!(service.enabled);
+    assert_eq!(warnings.len(), 1);
+}
+
+#[test]
+fn warnings_accumulate_across_loads_until_taken() {
+    let warnings = Warnings::new();
+    for _ in 0..2 {
+        let options = serde_saphyr::options! {}.with_warnings(&warnings);
+        let _: Service =
+            serde_saphyr::from_str_with_options("enabled: no\nmode: a\nreplicas: 1\n", options)
+                .unwrap();
+    }
+    assert_eq!(warnings.len(), 2);
+    assert_eq!(warnings.take().len(), 2);
+    assert!(warnings.is_empty());
+}
+
+#[test]
+fn warnings_render_with_snippet_and_custom_formatter() {
+    let yaml = "enabled: yes\nmode: fast\nreplicas: 2\n";
+    let found = load::<Service>(yaml, serde_saphyr::options! {});
+    let warning = &found[0];
+
+    let plain = warning.to_string();
+    assert!(
+        plain.contains("`yes` was read as the boolean true"),
+        "{plain}"
+    );
+    assert!(plain.contains("line 1, column 10"), "{plain}");
+
+    let rendered = warning.render_with_source(yaml, RenderOptions::default());
+    assert!(rendered.contains("warning"), "{rendered}");
+    assert!(rendered.contains("enabled: yes"), "{rendered}");
+
+    struct Custom;
+    impl MessageFormatter for Custom {
+        fn format_message<'a>(&self, err: &'a Error) -> Cow<'a, str> {
+            DefaultMessageFormatter.format_message(err)
+        }
+
+        fn format_warning<'a>(&self, warning: &'a Warning) -> Cow<'a, str> {
+            match warning.kind {
+                WarningKind::Yaml11Boolean { .. } => Cow::Borrowed("use true or false"),
+                _ => Cow::Owned(warning.default_message()),
+            }
+        }
+    }
+    let custom = warning.render_with_options(RenderOptions::new(&Custom));
+    assert!(custom.starts_with("use true or false"), "{custom}");
+}
@@ -0,0 +1,170 @@
+#![cfg(all(feature = "serialize", feature = "deserialize"))]
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value, json};
+use serde_saphyr::{Error, TaggedOmap, TaggedPairs, TaggedSet};
+
+#[test]
+fn set_reads_into_set_types() {
+    let yaml = "--- !!set\n? red\n? green\n? blue\n";
+    let set: BTreeSet<String> = serde_saphyr::from_str(yaml).unwrap();
+    assert_eq!(
+        set,
+        BTreeSet::from(["blue".into(), "green".into(), "red".into()])
+    );
+
+    let set: HashSet<u8> = serde_saphyr::from_str("!!set {1, 2, 3}").unwrap();
+    assert_eq!(set, HashSet::from([1, 2, 3]));
+
+    let set: Vec<String> = serde_saphyr::from_str("!!set {a: ~, b: null}").unwrap();
+    assert_eq!(set, ["a", "b"]);
+}
+
+#[test]
+fn set_rejects_values() {
+    let err = serde_saphyr::from_str::<BTreeSet<String>>("!!set\n? a\nb: 1\n").unwrap_err();
+    assert!(matches!(
+        err.without_snippet(),
+        Error::SetValueNotNull { location } if location.line() == 3
+    ));
+
+    let err = serde_saphyr::from_str::<BTreeSet<String>>("!!set {a: [1]}").unwrap_err();
+    assert!(matches!(
+        err.without_snippet(),
+        Error::SetValueNotNull { .. }
+    ));
+}
+
+const OMAP: &str = "\
+!!omap
+- zebra: 1
+- apple: 2
+- mango: 3
+";
+
+#[test]
+fn omap_reads_into_ordered_maps_and_pairs() {
+    let map: Map<String, Value> = serde_saphyr::from_str(OMAP).unwrap();
+    assert_eq!(map.keys().collect::<Vec<_>>(), ["zebra", "apple", "mango"]);
+
+    let pairs: Vec<(String, u32)> = serde_saphyr::from_str(OMAP).unwrap();
+    assert_eq!(
+        pairs,
+        [
+            ("zebra".into(), 1),
+            ("apple".into(), 2),
+            ("mango".into(), 3)
+        ]
+    );
+
+    let sorted: BTreeMap<String, u32> = serde_saphyr::from_str(OMAP).unwrap();
+    assert_eq!(sorted["mango"], 3);
+
+    // Untyped targets keep the list-of-mappings shape.
+    let value: Value = serde_saphyr::from_str(OMAP).unwrap();
+    assert_eq!(value, json!([{"zebra": 1}, {"apple": 2}, {"mango": 3}]));
+}
+
+#[test]
+fn omap_rejects_duplicate_keys_but_pairs_allow_them() {
+    let yaml = "!!omap\n- a: 1\n- b: 2\n- a: 3\n";
+    let err = serde_saphyr::from_str::<Vec<(String, u32)>>(yaml).unwrap_err();
+    assert!(matches!(
+        err.without_snippet(),
+        Error::DuplicateOmapKey { key: Some(key), location } if key == "a" && location.line() == 4
+    ));
+
+    let yaml = "!!pairs\n- a: 1\n- b: 2\n- a: 3\n";
+    let pairs: Vec<(String, u32)> = serde_saphyr::from_str(yaml).unwrap();
+    assert_eq!(pairs, [("a".into(), 1), ("b".into(), 2), ("a".into(), 3)]);
+}
+
+#[test]
+fn pairs_entries_must_be_single_pair_mappings() {
+    for yaml in [
+        "!!pairs\n- a: 1\n  b: 2\n",
+        "!!pairs\n- {}\n",
+        "!!omap\n- [a, 1]\n",
+    ] {
+        let err = serde_saphyr::from_str::<Vec<(String, u32)>>(yaml).unwrap_err();
+        assert!(
+            matches!(err.without_snippet(), Error::InvalidPairsEntry { .. }),
+            "{yaml}: {err}"
+        );
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, PartialEq)]
+struct Palette {
+    tags: TaggedSet<BTreeSet<String>>,
+    steps: TaggedOmap<String, u32>,
+    log: TaggedPairs<String, String>,
+}
+
+#[test]
+fn wrappers_emit_tags_and_round_trip() {
+    let palette = Palette {
+        tags: TaggedSet(BTreeSet::from(["dark".into(), "warm".into()])),
+        steps: TaggedOmap(vec![("b".into(), 2), ("a".into(), 1)]),
+        log: TaggedPairs(vec![
+            ("start".into(), "ok".into()),
+            ("start".into(), "again".into()),
+        ]),
+    };
+
+    let yaml = serde_saphyr::to_string(&palette).unwrap();
+    assert_eq!(
+        yaml,
+        "\
+tags: !!set
+  dark: null
+  warm: null
+steps: !!omap
+- b: 2
+- a: 1
+log: !!pairs
+- start: ok
+- start: again
+"
+    );
+
+    let back: Palette = serde_saphyr::from_str(&yaml).unwrap();
+    assert_eq!(back, palette);
+}
+
+#[test]
+fn wrappers_emit_tags_in_flow_and_top_level_positions() {
+    let omap = TaggedOmap(vec![("x".to_owned(), 1)]);
+    assert_eq!(serde_saphyr::to_string(&omap).unwrap(), "!!omap\n- x: 1\n");
+
+    let set = serde_saphyr::FlowMap(TaggedSet(vec!["a", "b"]));
+    assert_eq!(
+        serde_saphyr::to_string(&set).unwrap(),
+        "!!set {a: null, b: null}\n"
+    );
+}
+
+#[test]
+fn empty_wrappers_keep_their_tag_inline_and_round_trip() {
+    let palette = Palette {
+        tags: TaggedSet(BTreeSet::new()),
+        steps: TaggedOmap(Vec::new()),
+        log: TaggedPairs(Vec::new()),
+    };
+
+    let yaml = serde_saphyr::to_string(&palette).unwrap();
+    assert_eq!(yaml, "tags: !!set {}\nsteps: !!omap []\nlog: !!pairs []\n");
+
+    let back: Palette = serde_saphyr::from_str(&yaml).unwrap();
+    assert_eq!(back, palette);
+
+    let top = TaggedOmap::<String, u32>(Vec::new());
+    assert_eq!(serde_saphyr::to_string(&top).unwrap(), "!!omap []\n");
+
+    let items = vec![TaggedSet(Vec::<u8>::new()), TaggedSet(vec![1])];
+    let yaml = serde_saphyr::to_string(&items).unwrap();
+    assert_eq!(yaml, "- !!set {}\n- !!set\n  1: null\n");
+    let back: Vec<Vec<u8>> = serde_saphyr::from_str(&yaml).unwrap();
+    assert_eq!(back, [vec![], vec![1]]);
+}